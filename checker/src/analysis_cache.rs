@@ -0,0 +1,166 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// A cache of the findings of the functions that have been analyzed, which lets a later analysis
+// skip the functions whose MIR has not changed since, and report their cached findings instead.
+// This is meant for analyzing a crate again and again, e.g. in watch mode or in a pre-commit hook,
+// where most functions are the same as in the previous analysis.
+
+use std::fs;
+use std::fs::File;
+use std::ops::Range;
+use std::path::Path;
+
+use fs2::FileExt;
+use log::*;
+use serde::{Deserialize, Serialize};
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::TyCtxt;
+
+use crate::report::{fingerprint_hash, Finding, FINGERPRINT_OFFSET_BASIS};
+
+/// The findings of an analyzed function, together with the hash of the MIR they were found in.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CachedFunction {
+    pub crate_name: String,
+    /// The summary key of the function, which identifies it across compilations.
+    pub function: String,
+    pub mir_hash: String,
+    /// The findings that were reported while the function was analyzed.
+    pub findings: Vec<Finding>,
+}
+
+/// The cached functions of the crates that were analyzed with the cache file given by
+/// --analysis-cache. The file is a JSON array of the cached functions.
+#[derive(Debug, Default)]
+pub struct AnalysisCache {
+    functions: Vec<CachedFunction>,
+    /// The functions of the crate being analyzed, which replace those of the crate in the file,
+    /// each with the range of the findings of the crate that were reported for it.
+    analyzed: Vec<(CachedFunction, Range<usize>)>,
+}
+
+impl AnalysisCache {
+    /// Loads the cached functions of the file at the given path. A cache file that does not exist
+    /// yet is empty, and one that cannot be parsed, e.g. one written by an older version of the
+    /// tool, is discarded, since the functions can always be analyzed again.
+    pub fn load(cache_path: Option<&str>) -> AnalysisCache {
+        let Some(cache_path) = cache_path else {
+            return AnalysisCache::default();
+        };
+        let Ok(cache_json) = fs::read_to_string(cache_path) else {
+            return AnalysisCache::default();
+        };
+        match serde_json::from_str::<Vec<CachedFunction>>(&cache_json) {
+            Ok(functions) => {
+                info!("loaded {} cached functions", functions.len());
+                AnalysisCache {
+                    functions,
+                    analyzed: Vec::new(),
+                }
+            }
+            Err(e) => {
+                warn!("discarding the analysis cache {cache_path}: {e}");
+                AnalysisCache::default()
+            }
+        }
+    }
+
+    /// Returns the cached findings of the function, if it was analyzed with the same MIR.
+    pub fn findings_for(
+        &self,
+        crate_name: &str,
+        function: &str,
+        mir_hash: &str,
+    ) -> Option<&[Finding]> {
+        self.functions
+            .iter()
+            .find(|cached| {
+                cached.crate_name == crate_name
+                    && cached.function == function
+                    && cached.mir_hash == mir_hash
+            })
+            .map(|cached| cached.findings.as_slice())
+    }
+
+    /// Records that the given range of the findings of the crate being analyzed was reported for
+    /// the function, so that they are written to the cache file. The findings are only taken when
+    /// the file is written, once they are complete, e.g. with their reach paths.
+    pub fn insert(
+        &mut self,
+        crate_name: &str,
+        function: &str,
+        mir_hash: String,
+        findings: Range<usize>,
+    ) {
+        let cached_function = CachedFunction {
+            crate_name: crate_name.to_string(),
+            function: function.to_string(),
+            mir_hash,
+            findings: Vec::new(),
+        };
+        self.analyzed.push((cached_function, findings));
+    }
+
+    /// Replaces the cached functions of the given crate in the cache file at cache_path with the
+    /// functions recorded by insert, given the findings of the crate. The crates of a workspace
+    /// may share the cache file, so it is re-read under a lock, like a baseline.
+    pub fn write(
+        &self,
+        cache_path: &str,
+        crate_name: &str,
+        findings: &[Finding],
+    ) -> Result<(), String> {
+        let lock_file = File::create(format!("{cache_path}.lock"))
+            .map_err(|e| format!("could not lock the analysis cache {cache_path}: {e}"))?;
+        // Use the fs2 methods explicitly, newer toolchains have inherent File locking methods
+        FileExt::lock_exclusive(&lock_file)
+            .map_err(|e| format!("could not lock the analysis cache {cache_path}: {e}"))?;
+        let existing_path = Some(cache_path).filter(|path| Path::new(path).exists());
+        let mut functions: Vec<CachedFunction> = AnalysisCache::load(existing_path)
+            .functions
+            .into_iter()
+            .filter(|cached| cached.crate_name != crate_name)
+            .collect();
+        functions.extend(self.analyzed.iter().map(|(cached, range)| CachedFunction {
+            findings: findings[range.clone()].to_vec(),
+            ..cached.clone()
+        }));
+        let result = serde_json::to_string_pretty(&functions)
+            .map_err(|e| format!("could not serialize the analysis cache {cache_path}: {e}"))
+            .and_then(|cache_json| {
+                fs::write(cache_path, cache_json)
+                    .map_err(|e| format!("could not write the analysis cache {cache_path}: {e}"))
+            });
+        FileExt::unlock(&lock_file)
+            .map_err(|e| format!("could not unlock the analysis cache {cache_path}: {e}"))?;
+        result
+    }
+}
+
+/// Hashes the MIR of the function, including its promoted constants, together with the position
+/// of the function in its file. The cached findings of the function refer to positions in the file,
+/// so they are only reused while the function has not moved either. The hash does not cover the
+/// functions that it calls or is called by, nor the options of the analysis, so the cache is meant
+/// for analyzing the same crate again with the same options.
+pub fn mir_hash(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    let mut mir_text = Vec::new();
+    if let Err(e) = rustc_middle::mir::write_mir_pretty(tcx, Some(def_id), &mut mir_text) {
+        warn!("could not render the MIR of {def_id:?}: {e}");
+    }
+    let span = tcx.def_span(def_id).source_callsite();
+    let source_map = tcx.sess.source_map();
+    let position = format!(
+        "{}..{}",
+        source_map.lookup_byte_offset(span.lo()).pos.0,
+        source_map.lookup_byte_offset(span.hi()).pos.0
+    );
+    let mut hash = FINGERPRINT_OFFSET_BASIS;
+    for part in [position.as_bytes(), &mir_text] {
+        hash = fingerprint_hash(hash, part);
+    }
+    format!("{hash:016x}")
+}
@@ -0,0 +1,402 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// Recognizes the MIR that the #[program] macro of Anchor expands to, so that the checkers apply to
+// the instruction handlers of an Anchor program. The macro generates a dispatch function in
+// ``__private::__global`` for every handler, which deserializes and validates the accounts of the
+// instruction with ``try_accounts`` and passes them to the handler in a ``Context``. The handlers
+// are analyzed in place of the generated code, and the fields of the accounts of their Context are
+// mapped to the accounts that the checkers reason about, e.g. a ``Signer`` field is an account
+// whose signer flag Anchor has checked before the handler is entered. The constraints of the
+// #[account(..)] attributes of the fields are checks that Anchor makes as well, e.g. ``mut`` checks
+// that the account is writable.
+
+use std::collections::HashMap;
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir;
+use rustc_middle::ty::{AdtDef, Ty, TyCtxt, TyKind};
+use rustc_target::abi::FieldIdx;
+
+// The module that the #[program] macro generates the dispatch functions of the handlers in
+const DISPATCH_MODULE: &str = "__private::__global::";
+
+// The functions of Anchor that transfer lamports or tokens by invoking another program
+const CPI_TRANSFERS: [&str; 3] = [
+    "anchor_lang::system_program::transfer",
+    "anchor_spl::token::transfer",
+    "anchor_spl::token::transfer_checked",
+];
+
+// What Anchor validates about an account of a Context before the handler is entered
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AnchorAccount {
+    // A ``Signer``, whose signer flag is checked
+    Signer,
+    // An ``Account``, ``AccountLoader``, ``Program`` or the like, whose owner or key is checked
+    Checked,
+    // An ``AccountInfo`` or ``UncheckedAccount``, of which nothing is checked
+    Unchecked,
+}
+
+impl AnchorAccount {
+    /// Returns what Anchor checks about an account of the given type, if it is one of the account
+    /// types of Anchor. A boxed account, e.g. ``Box<Account<'info, Vault>>``, is the account.
+    fn of<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<AnchorAccount> {
+        let TyKind::Adt(def, args) = ty.peel_refs().kind() else {
+            return None;
+        };
+        if def.is_box() {
+            return Self::of(tcx, args.type_at(0));
+        }
+        let path = tcx.def_path_str(def.did());
+        let name = path.rsplit("::").next().unwrap_or_default();
+        match name {
+            "Signer" => Some(AnchorAccount::Signer),
+            "Account" | "AccountLoader" | "InterfaceAccount" | "Program" | "Interface"
+            | "Sysvar" | "SystemAccount" => Some(AnchorAccount::Checked),
+            "AccountInfo" | "UncheckedAccount" => Some(AnchorAccount::Unchecked),
+            _ => None,
+        }
+    }
+}
+
+// The checks that the constraints of the #[account(..)] attribute of a field of the accounts
+// struct make before the handler is entered, e.g. ``#[account(mut, has_one = authority)]``
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AnchorConstraints {
+    // The account has signed the transaction, by the signer constraint
+    pub signer: bool,
+    // The account is writable, by the mut, init or init_if_needed constraint
+    pub writable: bool,
+    // The owner or the key of the account is checked, by the owner, address, seeds, init or
+    // init_if_needed constraint of the account, or by the has_one constraint of another account
+    // of the struct, which compares the key with one stored in the data of that account
+    pub validated: bool,
+}
+
+impl AnchorConstraints {
+    /// Returns the constraints that the #[account(..)] attributes make on each field of the
+    /// accounts struct. An attribute is recognized by its name, so that ``#[account(..)]`` is one
+    /// whatever the path it is written with, and its arguments are read from its source text, since
+    /// a constraint such as ``mut`` is not a meta item.
+    fn of_fields(tcx: TyCtxt<'_>, accounts: AdtDef<'_>) -> HashMap<FieldIdx, AnchorConstraints> {
+        let fields = &accounts.non_enum_variant().fields;
+        let mut constraints: HashMap<FieldIdx, AnchorConstraints> = HashMap::new();
+        for (field, field_def) in fields.iter_enumerated() {
+            for attr in tcx.get_attrs_unchecked(field_def.did) {
+                if attr.is_doc_comment()
+                    || attr.path().last().map(|s| s.as_str()) != Some("account")
+                {
+                    continue;
+                }
+                let Ok(source) = tcx.sess.source_map().span_to_snippet(attr.span) else {
+                    continue;
+                };
+                for (key, value) in constraint_args(&source) {
+                    let field_constraints = constraints.entry(field).or_default();
+                    match key {
+                        "signer" => field_constraints.signer = true,
+                        "mut" => field_constraints.writable = true,
+                        "init" | "init_if_needed" => {
+                            field_constraints.writable = true;
+                            field_constraints.validated = true;
+                        }
+                        "owner" | "address" | "seeds" => field_constraints.validated = true,
+                        "has_one" => {
+                            let target = fields
+                                .iter_enumerated()
+                                .find(|(_, target)| Some(target.name.as_str()) == value);
+                            if let Some((target, _)) = target {
+                                constraints.entry(target).or_default().validated = true;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        constraints
+    }
+}
+
+/// Returns the constraints in the source text of an #[account(..)] attribute, each as its key and
+/// the value it is set to, if any, e.g. ``("has_one", Some("authority"))`` for
+/// ``has_one = authority @ ErrorCode::Unauthorized``. The constraints are separated by the commas
+/// that are not nested in brackets, e.g. not by those of ``seeds = [b"vault", user.key().as_ref()]``.
+fn constraint_args(source: &str) -> Vec<(&str, Option<&str>)> {
+    let (Some(start), Some(end)) = (source.find('('), source.rfind(')')) else {
+        return Vec::new();
+    };
+    if end <= start {
+        return Vec::new();
+    }
+    let args = &source[start + 1..end];
+    let mut constraints = Vec::new();
+    let mut depth = 0usize;
+    let mut arg_start = 0;
+    for (index, c) in args
+        .char_indices()
+        .chain(std::iter::once((args.len(), ',')))
+    {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                let arg = args[arg_start..index].trim();
+                arg_start = index + 1;
+                if arg.is_empty() {
+                    continue;
+                }
+                let constraint = match arg.split_once('=') {
+                    Some((key, value)) => {
+                        let value = value.split('@').next().unwrap_or_default().trim();
+                        (key.trim(), Some(value))
+                    }
+                    None => (arg, None),
+                };
+                constraints.push(constraint);
+            }
+            _ => {}
+        }
+    }
+    constraints
+}
+
+// An account of the Context of a handler
+#[derive(Clone, Copy, Debug)]
+pub struct AnchorField {
+    // The first local that holds the account, which stands for the account in the checkers
+    pub account: mir::Local,
+    // The type of the account
+    pub kind: AnchorAccount,
+    // The constraints of the account
+    pub constraints: AnchorConstraints,
+}
+
+impl AnchorField {
+    /// Returns true if Anchor checks that the account has signed the transaction, since it is a
+    /// Signer or has the signer constraint.
+    pub fn is_signed(&self) -> bool {
+        self.kind == AnchorAccount::Signer || self.constraints.signer
+    }
+
+    /// Returns true if Anchor checks that the account is writable.
+    pub fn is_writable(&self) -> bool {
+        self.constraints.writable
+    }
+
+    /// Returns true if Anchor checks neither the owner nor the key of the account, so that the
+    /// data of the account may be forged like that of an account passed to a plain program.
+    pub fn is_unchecked(&self) -> bool {
+        self.kind == AnchorAccount::Unchecked && !self.constraints.validated
+    }
+}
+
+/// Returns true if the function is one of the dispatch functions that the #[program] macro
+/// generates, e.g. ``my_program::__private::__global::withdraw``.
+pub fn is_dispatch(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    tcx.def_path_str(def_id).contains(DISPATCH_MODULE)
+}
+
+/// Returns the handler that the dispatch function passes the Context to, i.e. the function of the
+/// crate that it calls with a Context as its first argument.
+pub fn handler_of(tcx: TyCtxt<'_>, dispatch: DefId) -> Option<DefId> {
+    if !tcx.is_mir_available(dispatch) {
+        return None;
+    }
+    let mir = tcx.optimized_mir(dispatch);
+    mir.basic_blocks.iter().find_map(|block| {
+        let mir::TerminatorKind::Call { func, .. } = &block.terminator().kind else {
+            return None;
+        };
+        let (callee, _) = func.const_fn_def()?;
+        if !callee.is_local() || is_dispatch(tcx, callee) {
+            return None;
+        }
+        let fn_ty = tcx.type_of(callee).skip_binder();
+        if !fn_ty.is_fn() {
+            return None;
+        }
+        let fn_sig = fn_ty.fn_sig(tcx).skip_binder();
+        let first_input = fn_sig.inputs().first()?;
+        context_accounts(tcx, *first_input).map(|_| callee)
+    })
+}
+
+/// Returns true if a call of the function with the given path transfers lamports or tokens by
+/// invoking another program, which may call back into the program.
+pub fn is_cpi_transfer(callee_path: &str) -> bool {
+    CPI_TRANSFERS.contains(&callee_path)
+}
+
+/// Returns the struct of the accounts of the type if it is a ``Context``, i.e. the last type
+/// argument of ``Context<'a, 'b, 'c, 'info, T>``.
+fn context_accounts<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<AdtDef<'tcx>> {
+    let TyKind::Adt(def, args) = ty.kind() else {
+        return None;
+    };
+    let path = tcx.def_path_str(def.did());
+    if !path.starts_with("anchor_lang::") || !path.ends_with("::Context") {
+        return None;
+    }
+    match args.types().last()?.kind() {
+        TyKind::Adt(accounts, _) if accounts.is_struct() => Some(*accounts),
+        _ => None,
+    }
+}
+
+// The accounts of the Context of a handler that the locals of the handler hold or refer to
+#[derive(Default)]
+pub struct AnchorContext<'tcx> {
+    // The struct of the accounts of the Context, None if the body is not a handler
+    accounts: Option<AdtDef<'tcx>>,
+    // The field of the accounts struct that each local holds or refers to
+    locals: HashMap<mir::Local, FieldIdx>,
+    // The constraints of the fields of the accounts struct
+    constraints: HashMap<FieldIdx, AnchorConstraints>,
+    // The accounts of the fields that a local holds
+    fields: HashMap<FieldIdx, AnchorField>,
+}
+
+impl<'tcx> AnchorContext<'tcx> {
+    /// Finds the accounts of the Context argument of the body, if it is the body of a handler, and
+    /// follows them through the copies, borrows and derefs of the body, e.g. from
+    /// ``&ctx.accounts.user`` to the AccountInfo that ``to_account_info`` makes of it.
+    pub fn new(tcx: TyCtxt<'tcx>, mir: &mir::Body<'tcx>) -> AnchorContext<'tcx> {
+        let mut context = AnchorContext::default();
+        let Some(accounts) = mir
+            .args_iter()
+            .find_map(|local| context_accounts(tcx, mir.local_decls[local].ty))
+        else {
+            return context;
+        };
+        context.accounts = Some(accounts);
+        context.constraints = AnchorConstraints::of_fields(tcx, accounts);
+        // Every pass but the last maps another local, so the passes are bounded by the locals
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in mir.basic_blocks.iter() {
+                for statement in &block.statements {
+                    let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind else {
+                        continue;
+                    };
+                    let (mir::Rvalue::Use(mir::Operand::Copy(read) | mir::Operand::Move(read))
+                    | mir::Rvalue::Ref(_, _, read)
+                    | mir::Rvalue::CopyForDeref(read)) = rvalue
+                    else {
+                        continue;
+                    };
+                    if place.projection.is_empty() && !context.locals.contains_key(&place.local) {
+                        if let Some(field) = context.field_of(tcx, mir, read) {
+                            changed |= context.insert(tcx, mir, place.local, field);
+                        }
+                    }
+                }
+                let mir::TerminatorKind::Call {
+                    func,
+                    args,
+                    destination,
+                    ..
+                } = &block.terminator().kind
+                else {
+                    continue;
+                };
+                let callee_name = func
+                    .const_fn_def()
+                    .map(|(def_id, _)| tcx.def_path_str(def_id))
+                    .unwrap_or_default();
+                let is_conversion = ["::deref", "::deref_mut", "::to_account_info", "::as_ref"]
+                    .iter()
+                    .any(|suffix| callee_name.ends_with(suffix));
+                let account = args.first().and_then(|arg| arg.node.place());
+                if is_conversion
+                    && destination.projection.is_empty()
+                    && !context.locals.contains_key(&destination.local)
+                {
+                    if let Some(field) =
+                        account.and_then(|account| context.locals.get(&account.local))
+                    {
+                        changed |= context.insert(tcx, mir, destination.local, *field);
+                    }
+                }
+            }
+        }
+        context
+    }
+
+    /// Returns the account of the Context that the place is, refers to or selects a field of,
+    /// e.g. ``(*ctx.accounts).user.key``, along with what Anchor checks about the account.
+    pub fn account_of(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        mir: &mir::Body<'tcx>,
+        place: &mir::Place<'tcx>,
+    ) -> Option<AnchorField> {
+        if let Some(field) = self.locals.get(&place.local) {
+            return self.fields.get(field).copied();
+        }
+        let accounts = self.accounts?;
+        place
+            .iter_projections()
+            .find_map(|(base, elem)| match elem {
+                mir::ProjectionElem::Field(field, _) => {
+                    match base.ty(&mir.local_decls, tcx).ty.kind() {
+                        TyKind::Adt(def, _) if *def == accounts => self.fields.get(&field).copied(),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+    }
+
+    /// Returns the field of the accounts that the place selects or refers to, e.g. the field of
+    /// ``(*_3).0`` if _3 refers to the accounts, or of ``*_5`` if _5 refers to the field.
+    fn field_of(
+        &self,
+        tcx: TyCtxt<'tcx>,
+        mir: &mir::Body<'tcx>,
+        place: &mir::Place<'tcx>,
+    ) -> Option<FieldIdx> {
+        if let Some(field) = self.locals.get(&place.local) {
+            let only_derefs = place
+                .projection
+                .iter()
+                .all(|elem| matches!(elem, mir::ProjectionElem::Deref));
+            return only_derefs.then_some(*field);
+        }
+        let (base, mir::ProjectionElem::Field(field, _)) = place.as_ref().last_projection()? else {
+            return None;
+        };
+        match base.ty(&mir.local_decls, tcx).ty.kind() {
+            TyKind::Adt(def, _) if Some(*def) == self.accounts => Some(field),
+            _ => None,
+        }
+    }
+
+    /// Maps the local to the field. The first local that holds a field of an account type stands
+    /// for the account of the field. Returns true if the local was not mapped yet.
+    fn insert(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        mir: &mir::Body<'tcx>,
+        local: mir::Local,
+        field: FieldIdx,
+    ) -> bool {
+        if !self.fields.contains_key(&field) {
+            let Some(kind) = AnchorAccount::of(tcx, mir.local_decls[local].ty) else {
+                return false;
+            };
+            let anchor_field = AnchorField {
+                account: local,
+                kind,
+                constraints: self.constraints.get(&field).copied().unwrap_or_default(),
+            };
+            self.fields.insert(field, anchor_field);
+        }
+        self.locals.insert(local, field).is_none()
+    }
+}
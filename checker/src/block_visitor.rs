@@ -11,6 +11,7 @@ use std::rc::Rc;
 use log_derive::*;
 
 use mirai_annotations::*;
+use rustc_hir::def::DefKind;
 use rustc_hir::def_id::DefId;
 use rustc_index::{Idx, IndexVec};
 use rustc_middle::mir;
@@ -35,6 +36,7 @@ use crate::environment::Environment;
 use crate::expression::{Expression, ExpressionType};
 use crate::k_limits;
 use crate::known_names::KnownNames;
+use crate::mir_walker::ResolvedCallee;
 use crate::options::DiagLevel;
 use crate::path::{Path, PathEnum, PathSelector};
 use crate::path::{PathOrFunction, PathRefinement, PathRoot};
@@ -44,7 +46,7 @@ use crate::tag_domain::Tag;
 use crate::type_visitor::TypeVisitor;
 use crate::utils;
 use crate::{abstract_value, known_names};
-use crate::contract_errors::BlockStatement;
+use crate::contract_errors;
 
 /// Holds the state for the basic block visitor
 pub struct BlockVisitor<'block, 'analysis, 'compilation, 'tcx> {
@@ -89,7 +91,7 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
 
         if !self.bv.check_for_errors {
             while location.statement_index < terminator_index {
-                self.visit_statement(bb, location, &statements[location.statement_index]);
+                self.visit_statement(location, &statements[location.statement_index]);
                 check_for_early_return!(self.bv);
                 location.statement_index += 1;
             }
@@ -104,7 +106,7 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
             ref kind,
         }) = *terminator
         {
-            self.visit_terminator(bb, location, kind, *source_info);
+            self.visit_terminator(location, kind, *source_info);
         }
 
         info!("Current body visitor {:?}", self.bv);
@@ -112,15 +114,9 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
 
     /// Calls a specialized visitor for each kind of statement.
     #[logfn_inputs(DEBUG)]
-    fn visit_statement(&mut self, bb: mir::BasicBlock, location: mir::Location, statement: &mir::Statement<'tcx>) {
+    fn visit_statement(&mut self, location: mir::Location, statement: &mir::Statement<'tcx>) {
         debug!("env {:?}", self.bv.current_environment);
         self.bv.current_location = location;
-        self.bv
-            .reentrancy_checker
-            .block_statements
-            .entry(bb)
-            .or_insert(Vec::new())
-            .push(BlockStatement::Statement(statement.clone()));
 
         info!("Statement {:?}", statement);
 
@@ -155,7 +151,844 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
     #[logfn_inputs(TRACE)]
     fn visit_assign(&mut self, place: &mir::Place<'tcx>, rvalue: &mir::Rvalue<'tcx>) {
         info!("Place {:?}, rvalue {:?}", place, rvalue);
-        self.bv.reentrancy_checker.current_assign_destination = Some(*place);
+        self.bv
+            .saturating_arithmetic_checker
+            .local_sources
+            .record(place, rvalue);
+        self.bv.slice_panic_checker.local_sources.record(place, rvalue);
+        self.bv
+            .unchecked_first_byte_checker
+            .local_sources
+            .record(place, rvalue);
+        self.bv.unverified_pda_checker.local_sources.record(place, rvalue);
+        self.bv
+            .distinct_accounts_checker
+            .local_sources
+            .record(place, rvalue);
+        self.bv
+            .over_privileged_account_checker
+            .local_sources
+            .record(place, rvalue);
+        self.bv
+            .ephemeral_state_checker
+            .local_sources
+            .record(place, rvalue);
+        self.bv
+            .non_persistent_state_checker
+            .local_sources
+            .record(place, rvalue);
+        self.bv
+            .unvalidated_loop_account_checker
+            .local_sources
+            .record(place, rvalue);
+        self.bv
+            .lamports_double_borrow_checker
+            .local_sources
+            .record(place, rvalue);
+        self.bv
+            .unbounded_account_write_checker
+            .local_sources
+            .record(place, rvalue);
+        self.bv.clock_payout_checker.record(place, rvalue);
+        // Narrowing casts of the amounts read from the instruction data or a balance are here
+        self.record_instruction_data_amounts();
+        if let mir::Rvalue::Cast(mir::CastKind::IntToInt, operand, target_ty) = rvalue {
+            let source_ty = operand.ty(self.bv.mir, self.bv.tcx);
+            let is_narrowing = matches!(
+                (Self::integer_bit_width(source_ty), Self::integer_bit_width(*target_ty)),
+                (Some(source_width), Some(target_width)) if target_width < source_width
+            );
+            let checker = &self.bv.truncating_cast_checker;
+            let read_span = operand.place().and_then(|cast| checker.amount_of(&cast));
+            if let (true, Some(read_span)) = (is_narrowing, read_span) {
+                self.bv.truncating_cast_checker.narrowing_casts.push((
+                    self.bv.current_span,
+                    read_span,
+                    source_ty.to_string(),
+                    target_ty.to_string(),
+                ));
+            }
+        }
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place))
+        | mir::Rvalue::Ref(_, _, read_place)
+        | mir::Rvalue::CopyForDeref(read_place) = rvalue
+        {
+            if matches!(self.field_name(read_place).as_deref(), Some("lamports" | "amount"))
+                && place.projection.is_empty()
+            {
+                self.bv
+                    .truncating_cast_checker
+                    .amounts
+                    .entry(place.local)
+                    .or_insert(self.bv.current_span);
+            }
+        }
+        self.bv.truncating_cast_checker.record(place, rvalue);
+        // The lamports of created accounts are computed from the rent here, or are constants
+        self.bv.rent_exemption_checker.record(place, rvalue);
+        // The bytes that the bump seeds of created program derived addresses are made of
+        self.bv.pda_bump_checker.local_sources.record(place, rvalue);
+        self.bv.pda_bump_checker.local_sources.record_cast(place, rvalue);
+        if let mir::Rvalue::Aggregate(box mir::AggregateKind::Array(_), operands) = rvalue {
+            if place.projection.is_empty() {
+                let elements = operands
+                    .iter()
+                    .map(|operand| operand.place().map(|element| element.local))
+                    .collect();
+                self.bv.pda_bump_checker.arrays.insert(place.local, elements);
+            }
+        }
+        // The arrays of seeds and the lengths of the constant seeds are here
+        self.bv.seed_limits_checker.local_sources.record(place, rvalue);
+        self.bv
+            .seed_limits_checker
+            .local_sources
+            .record_cast(place, rvalue);
+        if place.projection.is_empty() {
+            match rvalue {
+                mir::Rvalue::Use(mir::Operand::Constant(constant))
+                | mir::Rvalue::Cast(_, mir::Operand::Constant(constant), _) => {
+                    if let TyKind::Ref(_, ty, _) = constant.ty().kind() {
+                        if let TyKind::Array(_, length) = ty.kind() {
+                            if let Some(length) = length.try_to_target_usize(self.bv.tcx) {
+                                self.bv
+                                    .seed_limits_checker
+                                    .constant_seed_lengths
+                                    .insert(place.local, length);
+                            }
+                        }
+                    }
+                }
+                mir::Rvalue::Aggregate(box mir::AggregateKind::Array(_), operands) => {
+                    let elements = operands
+                        .iter()
+                        .map(|operand| operand.place().map(|element| element.local))
+                        .collect();
+                    self.bv
+                        .seed_limits_checker
+                        .arrays
+                        .insert(place.local, elements);
+                }
+                _ => {}
+            }
+        }
+        // Reading the signer flag, the owner or the key of a loop account validates the account
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place))
+        | mir::Rvalue::Ref(_, _, read_place)
+        | mir::Rvalue::CopyForDeref(read_place) = rvalue
+        {
+            if matches!(
+                self.field_name(read_place).as_deref(),
+                Some("is_signer" | "owner" | "key")
+            ) {
+                let checker = &self.bv.unvalidated_loop_account_checker;
+                if let Some(item) = checker.loop_item_of(read_place.local) {
+                    self.bv
+                        .unvalidated_loop_account_checker
+                        .validations
+                        .push((self.bv.current_location, item));
+                }
+            }
+        }
+        // The accounts of an Anchor Context are told apart by their field, not by the Context, and
+        // are checked by the constraints of their field
+        let is_anchor_account =
+            place.projection.is_empty() && self.record_anchor_account(place.local);
+        if !is_anchor_account {
+            self.bv
+                .missing_signer_checker
+                .local_sources
+                .record(place, rvalue);
+        }
+        // Reading the signer flag of an account allows the lamports of the account to be mutated
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place))
+        | mir::Rvalue::CopyForDeref(read_place) = rvalue
+        {
+            if self.field_name(read_place).as_deref() == Some("is_signer") {
+                self.bv.missing_signer_checker.signer_checks.push((
+                    self.bv.current_location,
+                    read_place.local,
+                    self.bv.current_span,
+                ));
+            }
+        }
+        // Reading the writable flag of an account allows the account to be mutated
+        if !is_anchor_account {
+            self.bv.is_writable_checker.local_sources.record(place, rvalue);
+        }
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place))
+        | mir::Rvalue::CopyForDeref(read_place) = rvalue
+        {
+            if self.field_name(read_place).as_deref() == Some("is_writable") {
+                self.bv.is_writable_checker.writable_checks.push((
+                    self.bv.current_location,
+                    read_place.local,
+                    self.bv.current_span,
+                ));
+            }
+        }
+        // Reading the owner or the data of an account whose owner is to be checked is here
+        if !is_anchor_account {
+            self.bv.owner_check_checker.local_sources.record(place, rvalue);
+        }
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place))
+        | mir::Rvalue::Ref(_, _, read_place)
+        | mir::Rvalue::CopyForDeref(read_place) = rvalue
+        {
+            let field_name = self.field_name(read_place);
+            // The owner or the data of an AccountInfo field of an Anchor Context is of its field
+            let account_place = self
+                .bv
+                .anchor_context
+                .account_of(self.bv.tcx, self.bv.mir, read_place)
+                .map_or(*read_place, |field| mir::Place::from(field.account));
+            let is_owner_field = field_name.as_deref() == Some("owner");
+            self.bv.owner_check_checker.record_owner(
+                place,
+                if is_owner_field { &account_place } else { read_place },
+                is_owner_field,
+            );
+            if field_name.as_deref() == Some("data") {
+                self.bv.owner_check_checker.data_reads.push((
+                    self.bv.current_location,
+                    account_place.local,
+                    self.bv.current_span,
+                ));
+            }
+        }
+        if let mir::Rvalue::BinaryOp(mir::BinOp::Eq | mir::BinOp::Ne, box (left, right)) = rvalue {
+            self.bv.owner_check_checker.compare(
+                self.bv.current_location,
+                left.place().map(|left| left.local),
+                right.place().map(|right| right.local),
+            );
+        }
+        // Reading the data of an account, deriving the bytes or the length from it and comparing
+        // them with a length or a discriminator is here
+        let data_field_account = match rvalue {
+            mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place))
+            | mir::Rvalue::Ref(_, _, read_place)
+            | mir::Rvalue::CopyForDeref(read_place)
+                if self.field_name(read_place).as_deref() == Some("data") =>
+            {
+                Some(read_place.local)
+            }
+            _ => None,
+        };
+        let (location, span) = (self.bv.current_location, self.bv.current_span);
+        let checker = &mut self.bv.unchecked_deserialize_checker;
+        match data_field_account {
+            Some(account) if place.projection.is_empty() => {
+                checker.data_reads.insert(place.local, (account, span));
+            }
+            _ => {
+                checker.local_sources.record(place, rvalue);
+                checker.local_sources.record_cast(place, rvalue);
+            }
+        }
+        if let mir::Rvalue::Len(slice)
+        | mir::Rvalue::UnaryOp(
+            mir::UnOp::PtrMetadata,
+            mir::Operand::Copy(slice) | mir::Operand::Move(slice),
+        ) = rvalue
+        {
+            if place.projection.is_empty() {
+                checker.local_sources.record_derived(place.local, slice.local);
+            }
+        }
+        if let mir::Rvalue::BinaryOp(
+            mir::BinOp::Eq
+            | mir::BinOp::Ne
+            | mir::BinOp::Lt
+            | mir::BinOp::Le
+            | mir::BinOp::Gt
+            | mir::BinOp::Ge,
+            box (left, right),
+        ) = rvalue
+        {
+            for compared in [left, right].iter().filter_map(|operand| operand.place()) {
+                checker.comparisons.push((location, compared.local));
+            }
+        }
+        // Reading the key of an account, comparing it and building an instruction with a program id
+        self.bv.arbitrary_cpi_checker.local_sources.record(place, rvalue);
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place))
+        | mir::Rvalue::Ref(_, _, read_place)
+        | mir::Rvalue::CopyForDeref(read_place) = rvalue
+        {
+            let is_key_field = self.is_key_read(read_place);
+            self.bv
+                .arbitrary_cpi_checker
+                .record_key(place, read_place, is_key_field);
+        }
+        // Reading the key of an account, which may look up state, and the signer flag of an account
+        self.bv.account_substitution_checker.local_sources.record(place, rvalue);
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place))
+        | mir::Rvalue::Ref(_, _, read_place)
+        | mir::Rvalue::CopyForDeref(read_place) = rvalue
+        {
+            let is_key_field = self.is_key_read(read_place);
+            self.bv
+                .account_substitution_checker
+                .record_key(place, read_place, is_key_field);
+            if self.field_name(read_place).as_deref() == Some("is_signer") {
+                self.bv.account_substitution_checker.signer_checks.push((
+                    self.bv.current_location,
+                    read_place.local,
+                    self.bv.current_span,
+                ));
+            }
+        }
+        match rvalue {
+            mir::Rvalue::BinaryOp(mir::BinOp::Eq | mir::BinOp::Ne, box (left, right)) => {
+                self.bv.arbitrary_cpi_checker.compare(
+                    self.bv.current_location,
+                    left.place().map(|left| left.local),
+                    right.place().map(|right| right.local),
+                );
+            }
+            mir::Rvalue::Aggregate(box mir::AggregateKind::Adt(def_id, ..), operands)
+                if place.projection.is_empty() =>
+            {
+                let adt_def = self.bv.tcx.adt_def(*def_id);
+                if adt_def.is_struct() {
+                    let fields = &adt_def.non_enum_variant().fields;
+                    let program_id = operands.iter_enumerated().find_map(|(field_index, operand)| {
+                        (fields[field_index].name.as_str() == "program_id")
+                            .then(|| operand.place())
+                            .flatten()
+                    });
+                    if let Some(program_id) = program_id {
+                        self.bv
+                            .arbitrary_cpi_checker
+                            .instruction_program_ids
+                            .insert(place.local, program_id.local);
+                    }
+                }
+            }
+            _ => {}
+        }
+        // Reading the signer flag or the owner of an account validates the account
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place))
+        | mir::Rvalue::Ref(_, _, read_place)
+        | mir::Rvalue::CopyForDeref(read_place) = rvalue
+        {
+            if matches!(self.field_name(read_place).as_deref(), Some("is_signer" | "owner")) {
+                self.bv.unguarded_transfer_helper_checker.check_for_account_validation = true;
+            }
+        }
+        // Reading the is_writable flag of an account assumes that the account is writable
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place)) =
+            rvalue
+        {
+            if self.field_name(read_place).as_deref() == Some("is_writable") {
+                self.bv
+                    .over_privileged_account_checker
+                    .writable_checks
+                    .entry(read_place.local)
+                    .or_insert(self.bv.current_span);
+            }
+        }
+        self.bv
+            .unsafe_data_cast_checker
+            .local_sources
+            .record(place, rvalue);
+        // Casting a pointer into a byte buffer to a pointer to another type reinterprets the bytes
+        if let mir::Rvalue::Cast(_, operand, cast_ty) = rvalue {
+            if let TyKind::RawPtr(pointee_ty, _) = cast_ty.kind() {
+                let operand_ty = self.get_operand_rustc_type(operand);
+                let checker = &self.bv.unsafe_data_cast_checker;
+                let is_byte_pointer = operand.place().is_some_and(|pointer| {
+                    checker
+                        .byte_pointers
+                        .contains(&checker.local_sources.source_of(pointer.local))
+                });
+                if self.is_reinterpreted_type(*pointee_ty)
+                    && (is_byte_pointer || self.is_byte_data(operand_ty))
+                {
+                    self.bv
+                        .unsafe_data_cast_checker
+                        .unsafe_casts
+                        .insert(self.bv.current_location, self.bv.current_span);
+                }
+            }
+        }
+        self.bv
+            .lamports_overwrite_checker
+            .local_sources
+            .record(place, rvalue);
+        // Assigning a constant through a reference to the lamports of an account overwrites them
+        if let Some(account) = self.lamports_account(place) {
+            if let mir::Rvalue::Use(mir::Operand::Constant(constant)) = rvalue {
+                let is_zero = constant
+                    .const_
+                    .try_to_scalar_int()
+                    .is_some_and(|value| value.is_null());
+                self.bv.lamports_overwrite_checker.overwrites.insert(
+                    self.bv.current_location,
+                    (self.bv.current_span, account, is_zero),
+                );
+                if is_zero {
+                    self.bv.close_authority_checker.lamport_zeroings.insert(
+                        self.bv.current_location,
+                        (self.bv.current_span, account),
+                    );
+                }
+            }
+        }
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place)) =
+            rvalue
+        {
+            if let Some(account) = self.lamports_account(read_place) {
+                self.bv.lamports_overwrite_checker.balance_reads.insert(account);
+                self.bv.close_authority_checker.balance_reads.insert(account);
+            }
+        }
+        // Assigning a constant to the lamports of an account debits it of whatever it held, and
+        // the copies of the lamports are followed to the subtractions from them
+        if let Some(account) = self.signer_lamports_account(place) {
+            if matches!(rvalue, mir::Rvalue::Use(mir::Operand::Constant(..))) {
+                self.bv.missing_signer_checker.debited_accounts.insert(account);
+            }
+        }
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place)) =
+            rvalue
+        {
+            if let Some(account) = self.signer_lamports_account(read_place) {
+                if place.projection.is_empty() {
+                    self.bv.missing_signer_checker.balances.insert(place.local, account);
+                }
+            }
+        }
+        self.bv
+            .token_decimals_checker
+            .local_sources
+            .record(place, rvalue);
+        // Token amounts are read from the amount field of a token account
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place))
+        | mir::Rvalue::Ref(_, _, read_place)
+        | mir::Rvalue::CopyForDeref(read_place) = rvalue
+        {
+            if let Some(field @ ("mint" | "decimals")) = self.field_name(read_place).as_deref() {
+                let checker = &mut self.bv.token_decimals_checker;
+                let account = checker.local_sources.source_of(read_place.local);
+                checker.mint_reads.insert(account);
+                if field == "decimals" && place.projection.is_empty() {
+                    checker.decimals.insert(place.local, account);
+                }
+            }
+            if place.projection.is_empty() {
+                if let Some(account) = self.token_amount_account(read_place) {
+                    self.bv
+                        .token_decimals_checker
+                        .amounts
+                        .insert(place.local, account);
+                }
+            }
+        }
+        if let mir::Rvalue::BinaryOp(bin_op, box (left_operand, right_operand)) = rvalue {
+            let is_amount_operation = matches!(
+                bin_op,
+                mir::BinOp::Lt
+                    | mir::BinOp::Le
+                    | mir::BinOp::Gt
+                    | mir::BinOp::Ge
+                    | mir::BinOp::Eq
+                    | mir::BinOp::Ne
+                    | mir::BinOp::Add
+                    | mir::BinOp::AddWithOverflow
+                    | mir::BinOp::Sub
+                    | mir::BinOp::SubWithOverflow
+            );
+            if is_amount_operation {
+                let left_account =
+                    left_operand.place().and_then(|left| self.token_amount_account(&left));
+                let right_account =
+                    right_operand.place().and_then(|right| self.token_amount_account(&right));
+                self.bv.token_decimals_checker.record_operation(
+                    self.bv.current_location,
+                    self.bv.current_span,
+                    left_account,
+                    right_account,
+                );
+            }
+            // Subtracting from the lamports of an account debits the account
+            if matches!(bin_op, mir::BinOp::Sub | mir::BinOp::SubWithOverflow) {
+                if let Some(account) =
+                    left_operand.place().and_then(|left| self.lamports_value_account(&left))
+                {
+                    self.bv.missing_signer_checker.debited_accounts.insert(account);
+                }
+            }
+            // The decimals of two mints are compared to check that their amounts are comparable
+            if matches!(bin_op, mir::BinOp::Eq | mir::BinOp::Ne) {
+                let left_mint = left_operand.place().and_then(|left| self.decimals_account(&left));
+                let right_mint =
+                    right_operand.place().and_then(|right| self.decimals_account(&right));
+                if let (Some(left_mint), Some(right_mint)) = (left_mint, right_mint) {
+                    if left_mint != right_mint {
+                        self.bv.token_decimals_checker.check_for_decimals_comparison = true;
+                    }
+                }
+            }
+        }
+        self.bv
+            .unvalidated_count_checker
+            .local_sources
+            .record(place, rvalue);
+        // Returning Ok is a success, even if an error has been logged before
+        if place.local == mir::RETURN_PLACE && place.projection.is_empty() {
+            if let mir::Rvalue::Aggregate(box mir::AggregateKind::Adt(def_id, variant, ..), _) =
+                rvalue
+            {
+                let adt_def = self.bv.tcx.adt_def(*def_id);
+                if self.bv.tcx.item_name(*def_id).as_str() == "Result"
+                    && adt_def.variant(*variant).name.as_str() == "Ok"
+                {
+                    self.bv
+                        .ok_after_error_log_checker
+                        .ok_returns
+                        .push(self.bv.current_location);
+                }
+            }
+        }
+        // A byte read from the instruction data is a count, as are the values computed from it
+        if let mir::Rvalue::Use(mir::Operand::Copy(read_place) | mir::Operand::Move(read_place)) =
+            rvalue
+        {
+            let is_element = read_place
+                .projection
+                .iter()
+                .any(|elem| matches!(elem, mir::ProjectionElem::Index(_)));
+            if is_element && place.projection.is_empty() {
+                self.record_tainted_slices();
+                let source = self
+                    .bv
+                    .unvalidated_count_checker
+                    .local_sources
+                    .source_of(read_place.local);
+                if self.bv.slice_panic_checker.tainted_slices.contains(&source) {
+                    self.bv
+                        .unvalidated_count_checker
+                        .counts
+                        .insert(place.local, place.local);
+                }
+            }
+        }
+        let count_operands: Vec<&mir::Operand<'tcx>> = match rvalue {
+            mir::Rvalue::Cast(_, operand, _) => vec![operand],
+            mir::Rvalue::BinaryOp(bin_op, box (left_operand, right_operand))
+                if matches!(
+                    bin_op,
+                    mir::BinOp::Add
+                        | mir::BinOp::AddWithOverflow
+                        | mir::BinOp::Mul
+                        | mir::BinOp::MulWithOverflow
+                ) =>
+            {
+                vec![left_operand, right_operand]
+            }
+            _ => vec![],
+        };
+        let checker = &self.bv.unvalidated_count_checker;
+        let count = count_operands
+            .iter()
+            .filter_map(|operand| operand.place())
+            .find_map(|operand| checker.count_of(operand.local));
+        if let Some(count) = count {
+            if place.projection.is_empty() {
+                self.bv
+                    .unvalidated_count_checker
+                    .counts
+                    .insert(place.local, count);
+            }
+        }
+        // Arithmetic on a u64 or i64 balance, e.g. ``*entry += amount``, is here, and is an update
+        // of the balance once its result is stored back to it
+        self.bv
+            .integer_overflow_checker
+            .local_sources
+            .record(place, rvalue);
+        if let mir::Rvalue::BinaryOp(bin_op, box (left_operand, right_operand)) = rvalue {
+            let message = contract_errors::IntegerOverflowChecker::overflow_message(*bin_op);
+            let is_balance_type = matches!(
+                left_operand.ty(self.bv.mir, self.bv.tcx).kind(),
+                TyKind::Uint(UintTy::U64) | TyKind::Int(IntTy::I64)
+            );
+            // A copy of the balance, e.g. of ``*entry`` in ``*entry = *entry + amount``, is the
+            // balance as well
+            let balance = left_operand.place().and_then(|left| {
+                let checker = &self.bv.integer_overflow_checker;
+                let source = checker.local_sources.source_of(left.local);
+                if left.projection.is_empty() && checker.balance_refs.contains(&source) {
+                    return Some(source);
+                }
+                self.balance_of(&left)
+            });
+            if let (Some(_), true, Some(balance)) = (message, is_balance_type, balance) {
+                let checker = &self.bv.integer_overflow_checker;
+                let amount = right_operand
+                    .place()
+                    .map(|right| checker.local_sources.source_of(right.local));
+                let operation =
+                    (self.bv.current_location, self.bv.current_span, *bin_op, balance, amount);
+                if self.balance_of(place) == Some(balance) {
+                    self.bv.integer_overflow_checker.balance_updates.push(operation);
+                } else if place.projection.is_empty() {
+                    self.bv
+                        .integer_overflow_checker
+                        .operation_results
+                        .insert(place.local, operation);
+                }
+                // The amount is chosen by the caller if it is read from the instruction data
+                let is_tainted = right_operand
+                    .place()
+                    .is_some_and(|right| self.bv.taint_analysis.is_tainted(right));
+                if is_tainted {
+                    self.bv
+                        .integer_overflow_checker
+                        .tainted_operations
+                        .insert(self.bv.current_span);
+                }
+            }
+        }
+        // The result of an operation on a balance, e.g. ``_5.0`` of a checked addition in a debug
+        // build, that is stored back to the balance updates it
+        if let mir::Rvalue::Use(mir::Operand::Copy(result) | mir::Operand::Move(result)) = rvalue {
+            let balance = self.balance_of(place);
+            let operation = self
+                .bv
+                .integer_overflow_checker
+                .operation_results
+                .get(&result.local)
+                .copied();
+            if let (Some(balance), Some(operation)) = (balance, operation) {
+                if operation.3 == balance {
+                    self.bv.integer_overflow_checker.balance_updates.push(operation);
+                }
+            }
+        }
+        // A comparison of a balance with an amount may guard the subtraction of the amount
+        if let mir::Rvalue::BinaryOp(
+            mir::BinOp::Lt | mir::BinOp::Le | mir::BinOp::Gt | mir::BinOp::Ge,
+            box (left_operand, right_operand),
+        ) = rvalue
+        {
+            let checker = &self.bv.integer_overflow_checker;
+            let [left, right] = [left_operand, right_operand].map(|operand| {
+                operand.place().map(|operand| match self.balance_of(&operand) {
+                    Some(balance) => balance,
+                    None => checker.local_sources.source_of(operand.local),
+                })
+            });
+            self.bv
+                .integer_overflow_checker
+                .comparisons
+                .push((self.bv.current_location, left, right));
+        }
+        // Mutating a map, directly or through a helper, borrows it mutably
+        if let mir::Rvalue::Ref(_, mir::BorrowKind::Mut { .. }, borrowed) = rvalue {
+            self.bv
+                .non_persistent_state_checker
+                .mutable_borrows
+                .insert(borrowed.local);
+        }
+        // Comparing a count with a length validates the count
+        if let mir::Rvalue::BinaryOp(bin_op, box (left_operand, right_operand)) = rvalue {
+            if matches!(
+                bin_op,
+                mir::BinOp::Lt | mir::BinOp::Le | mir::BinOp::Gt | mir::BinOp::Ge
+            ) {
+                let checker = &self.bv.unvalidated_count_checker;
+                let compared: Vec<mir::Place<'tcx>> = [left_operand, right_operand]
+                    .iter()
+                    .filter_map(|operand| operand.place())
+                    .collect();
+                let count = compared.iter().find_map(|operand| checker.count_of(operand.local));
+                let has_length = compared.iter().any(|operand| checker.is_length(operand.local));
+                if let (Some(count), true) = (count, has_length) {
+                    self.bv.unvalidated_count_checker.validated_counts.insert(count);
+                }
+            }
+            if matches!(bin_op, mir::BinOp::Sub | mir::BinOp::SubWithOverflow)
+                && left_operand
+                    .place()
+                    .is_some_and(|left| self.bv.unvalidated_count_checker.is_length(left.local))
+                && place.projection.is_empty()
+            {
+                self.bv.unvalidated_count_checker.lengths.insert(place.local);
+            }
+        }
+        if let mir::Rvalue::Len(_) | mir::Rvalue::UnaryOp(mir::UnOp::PtrMetadata, _) = rvalue {
+            if place.projection.is_empty() {
+                self.bv.unvalidated_count_checker.lengths.insert(place.local);
+            }
+        }
+        // A range that is bounded by a count is what a loop over the items of the count iterates
+        if let mir::Rvalue::Aggregate(box mir::AggregateKind::Adt(def_id, ..), operands) = rvalue {
+            if self.bv.tcx.item_name(*def_id).as_str() == "Range" {
+                let checker = &self.bv.unvalidated_count_checker;
+                let count = operands
+                    .iter()
+                    .filter_map(|operand| operand.place())
+                    .find_map(|operand| checker.count_of(operand.local));
+                if let Some(count) = count {
+                    self.bv
+                        .unvalidated_count_checker
+                        .count_loops
+                        .push((self.bv.current_span, count));
+                }
+            }
+        }
+        self.bv.manual_rent_checker.local_sources.record(place, rvalue);
+        // Values computed from a data length by a cast or an addition are data lengths as well,
+        // while multiplying a data length computes the rent by hand
+        let data_length_operands: Vec<&mir::Operand<'tcx>> = match rvalue {
+            mir::Rvalue::Cast(_, operand, _) => vec![operand],
+            mir::Rvalue::BinaryOp(_, box (left_operand, right_operand)) => {
+                vec![left_operand, right_operand]
+            }
+            _ => vec![],
+        };
+        let is_data_length_operation = data_length_operands
+            .iter()
+            .filter_map(|operand| operand.place())
+            .any(|operand| self.bv.manual_rent_checker.is_data_length(operand.local));
+        if is_data_length_operation {
+            let is_multiplication = matches!(
+                rvalue,
+                mir::Rvalue::BinaryOp(mir::BinOp::Mul | mir::BinOp::MulWithOverflow, _)
+            );
+            if is_multiplication {
+                self.bv
+                    .manual_rent_checker
+                    .manual_computations
+                    .insert(self.bv.current_location, self.bv.current_span);
+            }
+            self.bv.manual_rent_checker.data_lengths.insert(place.local);
+        }
+        self.bv
+            .swallowed_cpi_error_checker
+            .local_sources
+            .record(place, rvalue);
+        self.bv
+            .unchecked_cpi_result_checker
+            .visit_assign(place, rvalue);
+        // The result of an invocation is matched by branching on its discriminant
+        if let mir::Rvalue::Discriminant(enum_place) = rvalue {
+            if place.projection.is_empty() {
+                self.bv
+                    .swallowed_cpi_error_checker
+                    .discriminants
+                    .insert(place.local, enum_place.local);
+            }
+        }
+        self.bv
+            .lamports_authorization_checker
+            .local_sources
+            .record(place, rvalue);
+        // The lamports of an account are compared, e.g. to decide whether an action is allowed
+        if let mir::Rvalue::BinaryOp(bin_op, box (left_operand, right_operand)) = rvalue {
+            let is_comparison = matches!(
+                bin_op,
+                mir::BinOp::Lt
+                    | mir::BinOp::Le
+                    | mir::BinOp::Gt
+                    | mir::BinOp::Ge
+                    | mir::BinOp::Eq
+                    | mir::BinOp::Ne
+            );
+            let checker = &self.bv.lamports_authorization_checker;
+            let is_lamports_comparison = [left_operand, right_operand]
+                .iter()
+                .filter_map(|operand| operand.place())
+                .any(|operand| checker.is_lamport_balance(operand.local));
+            if is_comparison && is_lamports_comparison && place.projection.is_empty() {
+                self.bv
+                    .lamports_authorization_checker
+                    .lamport_comparisons
+                    .insert(place.local, self.bv.current_span);
+            }
+        }
+        if let mir::Rvalue::BinaryOp(bin_op, box (left_operand, right_operand)) = rvalue {
+            let is_ordering = matches!(
+                bin_op,
+                mir::BinOp::Lt | mir::BinOp::Le | mir::BinOp::Gt | mir::BinOp::Ge
+            );
+            let is_equality = matches!(bin_op, mir::BinOp::Eq | mir::BinOp::Ne);
+            // Comparing a derived address verifies the program derived address it is compared with
+            let checker = &self.bv.unverified_pda_checker;
+            if is_equality
+                && [left_operand, right_operand]
+                    .iter()
+                    .filter_map(|operand| operand.place())
+                    .any(|operand| checker.is_derived_key(operand.local))
+            {
+                self.bv.unverified_pda_checker.check_for_key_comparison = true;
+            }
+            if is_equality {
+                if let (Some(left_place), Some(right_place)) =
+                    (left_operand.place(), right_operand.place())
+                {
+                    self.bv
+                        .distinct_accounts_checker
+                        .compared_pairs
+                        .insert((left_place.local, right_place.local));
+                }
+            }
+            for operand in [left_operand, right_operand] {
+                if let Some(compared_place) = operand.place() {
+                    if is_ordering || is_equality {
+                        self.bv
+                            .unchecked_first_byte_checker
+                            .guards
+                            .push((self.bv.current_location, compared_place.local));
+                    }
+                    if is_ordering {
+                        self.bv
+                            .saturating_arithmetic_checker
+                            .compared_locals
+                            .insert(compared_place.local);
+                        self.bv
+                            .unbounded_account_write_checker
+                            .compared_locals
+                            .insert(compared_place.local);
+                    }
+                    if is_ordering || is_equality {
+                        self.bv
+                            .slice_panic_checker
+                            .compared_locals
+                            .insert(compared_place.local);
+                    }
+                }
+            }
+        }
+        // The length of a slice is loaded either directly or via the pointer metadata
+        if let mir::Rvalue::Len(slice)
+        | mir::Rvalue::UnaryOp(
+            mir::UnOp::PtrMetadata,
+            mir::Operand::Copy(slice) | mir::Operand::Move(slice),
+        ) = rvalue
+        {
+            if place.projection.is_empty() {
+                self.bv
+                    .slice_panic_checker
+                    .slice_lengths
+                    .insert(place.local, slice.local);
+                self.bv
+                    .unchecked_first_byte_checker
+                    .slice_lengths
+                    .insert(place.local, slice.local);
+            }
+        }
+        if let mir::Rvalue::Use(mir::Operand::Constant(constant)) = rvalue {
+            if place.projection.is_empty() && self.is_zero_constant(constant) {
+                self.bv
+                    .unchecked_first_byte_checker
+                    .zero_locals
+                    .insert(place.local);
+            }
+        }
         let mut path = self.visit_lh_place(place);
         match &path.value {
             PathEnum::PhantomData => {
@@ -296,7 +1129,6 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
     #[logfn_inputs(DEBUG)]
     fn visit_terminator(
         &mut self,
-        bb: mir::BasicBlock,
         location: mir::Location,
         kind: &mir::TerminatorKind<'tcx>,
         source_info: mir::SourceInfo,
@@ -306,12 +1138,6 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
         self.bv.current_span = source_info.span;
 
         info!("Kind {:?}", kind);
-        self.bv
-            .reentrancy_checker
-            .block_statements
-            .entry(bb)
-            .or_insert(Vec::new())
-            .push(BlockStatement::TerminatorKind(kind.clone()));
 
         match kind {
             mir::TerminatorKind::Goto { target } => self.visit_goto(*target),
@@ -336,13 +1162,12 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
                 unwind,
                 call_source: _,
                 fn_span,
-            } => self.visit_call(bb, func, args, *destination, *target, *unwind, fn_span),
+            } => self.visit_call(func, args, *destination, *target, *unwind, fn_span),
             mir::TerminatorKind::TailCall {
                 func,
                 args,
                 fn_span,
             } => self.visit_call(
-                bb,
                 func,
                 args,
                 mir::Place::return_place(),
@@ -386,6 +1211,39 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
         switch_ty: Ty<'tcx>,
         targets: &rustc_middle::mir::SwitchTargets,
     ) {
+        // Branching on a comparison with the lamports of an account authorizes by the lamports
+        if let Some(discr_place) = discr.place() {
+            let checker = &self.bv.lamports_authorization_checker;
+            let comparison = checker.local_sources.source_of(discr_place.local);
+            if let Some(span) = checker.lamport_comparisons.get(&comparison).copied() {
+                self.bv
+                    .lamports_authorization_checker
+                    .authorizations
+                    .insert(self.bv.current_location, span);
+            }
+        }
+        // Branching on a value that comes from the input decides on the panics of an arm
+        if let Some(discr_place) = discr.place() {
+            if self.bv.taint_analysis.is_tainted(discr_place) {
+                self.bv.panic_reachability_checker.tainted_branches.push((
+                    self.bv.current_location,
+                    discr_place.local,
+                    self.bv.current_span,
+                ));
+            }
+        }
+        // Branching on the discriminant of an invocation result, where the Err arm does nothing
+        if let Some(discr_place) = discr.place() {
+            let checker = &self.bv.swallowed_cpi_error_checker;
+            if let Some(span) = checker.invocation_of(discr_place.local) {
+                if self.is_error_ignored(targets) {
+                    self.bv
+                        .swallowed_cpi_error_checker
+                        .swallowed_errors
+                        .insert(self.bv.current_location, span);
+                }
+            }
+        }
         let mut default_exit_condition = self.bv.current_environment.entry_condition.clone();
         let discr = self.visit_operand(discr);
 
@@ -514,6 +1372,9 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
         // meaning.
         _replace: bool,
     ) {
+        if place.projection.is_empty() {
+            self.bv.lamports_double_borrow_checker.release(place.local);
+        }
         let tcx = self.bv.cv.tcx;
         let place_path = self.get_path_for_place(place);
         let path = place_path.canonicalize(&self.bv.current_environment);
@@ -590,128 +1451,1021 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
             }
         }
 
-        // Propagate the entry condition to the successor blocks.
-        self.bv
-            .current_environment
-            .exit_conditions
-            .insert_mut(target, self.bv.current_environment.entry_condition.clone());
-        if let mir::UnwindAction::Cleanup(unwind_target) = unwind {
-            self.bv.current_environment.exit_conditions.insert_mut(
-                unwind_target,
-                self.bv.current_environment.entry_condition.clone(),
-            );
+        // Propagate the entry condition to the successor blocks.
+        self.bv
+            .current_environment
+            .exit_conditions
+            .insert_mut(target, self.bv.current_environment.entry_condition.clone());
+        if let mir::UnwindAction::Cleanup(unwind_target) = unwind {
+            self.bv.current_environment.exit_conditions.insert_mut(
+                unwind_target,
+                self.bv.current_environment.entry_condition.clone(),
+            );
+        }
+    }
+
+    /// Block ends with the call of a function.
+    ///
+    /// #Arguments
+    /// * `func` - The function that’s being called
+    /// * `args` - Arguments the function is called with.
+    /// These are owned by the callee, which is free to modify them.
+    /// This allows the memory occupied by "by-value" arguments to be
+    /// reused across function calls without duplicating the contents.
+    /// * `destination` - Destination for the return value.
+    /// * `unwind` - Work to be done if the call unwinds.
+    /// * `call_source` - Where this call came from in HIR/THIR.
+    /// operator. True for overloaded function call.
+    #[allow(clippy::too_many_arguments)]
+    #[logfn_inputs(TRACE)]
+    fn visit_call(
+        &mut self,
+        func: &mir::Operand<'tcx>,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        destination: mir::Place<'tcx>,
+        target: Option<mir::BasicBlock>,
+        unwind: mir::UnwindAction,
+        fn_span: &rustc_span::Span,
+    ) {
+        // This offset is used to distinguish any local variables that leak out from the called function
+        // from local variables of the callee function.
+        // This situation arises when a structured value stored in a local variable is assigned to
+        // a field reachable from a mutable parameter.
+        // We assume that no program that does not make MIRAI run out of memory will have more than
+        // a million local variables.
+        self.bv.fresh_variable_offset += 1_000_000;
+
+        info!("Hi call");
+        info!("source location {:?}", fn_span);
+        info!("call stack {:?}", self.bv.active_calls_map);
+        info!("visit_call {:?}, args {:?}", func, args);
+        info!(
+            "self.generic_argument_map {:?}",
+            self.type_visitor().generic_argument_map
+        );
+        info!(
+            "actual_argument_types {:?}",
+            self.type_visitor().actual_argument_types
+        );
+        info!("destination {:?}", destination);
+        trace!("env {:?}", self.bv.current_environment);
+
+        let func_to_call = self.visit_operand(func);
+        let func_ref = self.get_func_ref(&func_to_call);
+        let func_ref_to_call = if let Some(fr) = func_ref {
+            fr
+        } else {
+            if self.might_be_reachable().unwrap_or(true)
+                && self
+                    .bv
+                    .already_reported_errors_for_call_to
+                    .insert(func_to_call)
+            {
+                self.report_missing_summary();
+            }
+            return;
+        };
+        let argument_type_key = func_ref_to_call.argument_type_key.clone();
+
+        let callee_def_id = func_ref_to_call
+            .def_id
+            .expect("callee obtained via operand should have def id");
+        
+        let callee_name =  utils::summary_key_str(self.bv.tcx, callee_def_id);
+        // The checkers of the Checker trait see the call with the callee resolved here
+        self.bv.resolved_callees.insert(
+            self.bv.current_location,
+            ResolvedCallee {
+                def_id: callee_def_id,
+                name: callee_name.clone(),
+                argument_type_key: argument_type_key.clone(),
+            },
+        );
+        // Values read from the clock and the payouts they decide are here
+        let is_clock_type =
+            callee_name.contains("Clock") || argument_type_key.contains("clock_Clock");
+        if is_clock_type
+            && (callee_name.ends_with(".get") || callee_name.contains("from_account_info"))
+        {
+            self.bv
+                .clock_payout_checker
+                .read_clock(destination.local, self.bv.current_span);
+        }
+        let checker = &self.bv.clock_payout_checker;
+        let has_clock_arg = args
+            .iter()
+            .filter_map(|arg| arg.node.place())
+            .any(|arg| checker.is_clock_value(&arg));
+        if has_clock_arg {
+            let is_payout = callee_name.contains("try_borrow_mut_lamports")
+                || callee_name.ends_with(".transfer");
+            if is_payout {
+                self.bv
+                    .clock_payout_checker
+                    .clock_payouts
+                    .push(self.bv.current_span);
+            }
+            if destination.projection.is_empty() {
+                self.bv
+                    .clock_payout_checker
+                    .clock_values
+                    .insert(destination.local);
+            }
+        }
+
+        // The amounts flow through the calls, other than the length of the instruction data, and
+        // the balances are read by the calls of lamports and the borrows of the lamports
+        self.record_instruction_data_amounts();
+        let checker = &self.bv.truncating_cast_checker;
+        let read_span = args
+            .iter()
+            .filter_map(|arg| arg.node.place())
+            .find_map(|arg| checker.amount_of(&arg))
+            .filter(|_| !callee_name.ends_with(".len"));
+        let is_balance_read = callee_name.ends_with(".lamports")
+            || callee_name.contains("try_borrow_lamports")
+            || callee_name.contains("try_borrow_mut_lamports");
+        let read_span = read_span.or(is_balance_read.then_some(self.bv.current_span));
+        if let (Some(read_span), true) = (read_span, destination.projection.is_empty()) {
+            self.bv
+                .truncating_cast_checker
+                .amounts
+                .entry(destination.local)
+                .or_insert(read_span);
+        }
+
+        // Accounts are created here, with lamports that should come from Rent::minimum_balance,
+        // which flow through the calls that compute with them, e.g. checked_add and unwrap
+        let checker = &self.bv.rent_exemption_checker;
+        let has_rent_balance_arg = args
+            .iter()
+            .filter_map(|arg| arg.node.place())
+            .any(|arg| checker.rent_balances.contains(&arg.local));
+        if (has_rent_balance_arg || callee_name.ends_with(".minimum_balance"))
+            && destination.projection.is_empty()
+        {
+            self.bv.rent_exemption_checker.rent_balances.insert(destination.local);
+        }
+        let lamports_index = if callee_name.ends_with("system_instruction.create_account") {
+            Some(2)
+        } else if callee_name.ends_with("system_instruction.create_account_with_seed") {
+            Some(4)
+        } else {
+            None
+        };
+        if let Some(lamports) = lamports_index.and_then(|index| args.get(index)) {
+            self.bv
+                .rent_exemption_checker
+                .create_account(&lamports.node, self.bv.current_span);
+        }
+
+        // Clock read from a passed account is here
+        if callee_name.contains("from_account_info")
+            && (callee_name.contains("Clock") || argument_type_key.contains("clock_Clock"))
+        {
+            self.bv.clock_account_read_checker.check_for_clock_account_read = true;
+            self.bv.clock_account_read_checker.clock_account_read_span = self.bv.current_span;
+        }
+
+        // Panicking slice operation on an untrusted length is here
+        if callee_name.contains("slice.implement") {
+            if let Some(slice) = args.first().and_then(|arg| arg.node.place()) {
+                self.record_tainted_slices();
+                if callee_name.ends_with(".len") && destination.projection.is_empty() {
+                    self.bv
+                        .slice_panic_checker
+                        .slice_lengths
+                        .insert(destination.local, slice.local);
+                    self.bv
+                        .unchecked_first_byte_checker
+                        .slice_lengths
+                        .insert(destination.local, slice.local);
+                }
+                // Testing for emptiness compares the length with zero
+                if callee_name.ends_with(".is_empty") && destination.projection.is_empty() {
+                    self.bv
+                        .slice_panic_checker
+                        .slice_lengths
+                        .insert(destination.local, slice.local);
+                    self.bv
+                        .slice_panic_checker
+                        .compared_locals
+                        .insert(destination.local);
+                    self.bv
+                        .unchecked_first_byte_checker
+                        .slice_lengths
+                        .insert(destination.local, slice.local);
+                    self.bv
+                        .unchecked_first_byte_checker
+                        .guards
+                        .push((self.bv.current_location, destination.local));
+                }
+                if (callee_name.contains(".split_at") && !callee_name.contains("_checked"))
+                    || callee_name.contains(".chunks_exact")
+                {
+                    self.bv
+                        .slice_panic_checker
+                        .panicking_calls
+                        .insert(self.bv.current_location, (self.bv.current_span, slice.local));
+                }
+            }
+        }
+
+        // Indexing by a range, converting a subslice and borrowing account data are here
+        if let Some(slice) = args.first().and_then(|arg| arg.node.place()) {
+            // Both the Index trait and its implementation for slices are in an index module
+            let is_index = (callee_name.ends_with(".index") || callee_name.ends_with(".index_mut"))
+                && callee_name.contains(".index.");
+            let is_unwrap = callee_name.ends_with(".unwrap") || callee_name.ends_with(".expect");
+            if is_index {
+                self.record_tainted_slices();
+                self.bv
+                    .slice_panic_checker
+                    .panicking_calls
+                    .insert(self.bv.current_location, (self.bv.current_span, slice.local));
+            }
+            if is_unwrap {
+                let checker = &self.bv.slice_panic_checker;
+                let source = checker.local_sources.source_of(slice.local);
+                if let Some(converted) = checker.conversions.get(&source).copied() {
+                    self.bv
+                        .slice_panic_checker
+                        .unwrapped_conversions
+                        .insert(self.bv.current_location, (self.bv.current_span, converted));
+                } else if self.is_option_or_result(slice.local) {
+                    self.bv.panic_reachability_checker.unwraps.push((
+                        self.bv.current_location,
+                        slice.local,
+                        self.bv.current_span,
+                    ));
+                }
+            }
+            if destination.projection.is_empty() {
+                let checker = &mut self.bv.slice_panic_checker;
+                // The data of an account is controlled by the caller like the instruction data
+                if callee_name.contains("try_borrow_data")
+                    || callee_name.contains("try_borrow_mut_data")
+                {
+                    checker.account_data.insert(destination.local);
+                }
+                if callee_name.contains("try_into") {
+                    checker.conversions.insert(destination.local, slice.local);
+                }
+                // A subslice, or the slice that a borrow of account data results in, is derived
+                let is_derivation = callee_name.ends_with(".branch")
+                    || callee_name.ends_with(".deref")
+                    || callee_name.ends_with(".deref_mut")
+                    || is_index
+                    || (is_unwrap && !checker.conversions.contains_key(&slice.local));
+                if is_derivation {
+                    checker.local_sources.record_derived(destination.local, slice.local);
+                }
+            }
+        }
+
+        // Explicit panics, which are decided by the input if a branch on it leads to them
+        if callee_name.contains("panicking.") {
+            self.bv
+                .panic_reachability_checker
+                .panics
+                .push((self.bv.current_location, self.bv.current_span));
+        }
+
+        // Panic in the entrypoint handler is here, the helpers it calls are analyzed separately
+        if self.bv.entrypoint_panic_checker.check_for_entrypoint_handler
+            && (callee_name.ends_with(".unwrap")
+                || callee_name.ends_with(".expect")
+                || callee_name.contains("panicking."))
+        {
+            self.bv
+                .entrypoint_panic_checker
+                .panicking_calls
+                .insert(self.bv.current_location, self.bv.current_span);
+        }
+
+        // Overwriting the lamports of an account is here, the references to the lamports are
+        // obtained by borrowing them and are then passed through ? and deref_mut
+        if callee_name.contains("try_borrow_mut_lamports")
+            || callee_name.contains("try_borrow_lamports")
+        {
+            if let Some(account) = args.first().and_then(|arg| arg.node.place()) {
+                let checker = &self.bv.lamports_overwrite_checker;
+                let account = checker.local_sources.source_of(account.local);
+                self.bv
+                    .lamports_overwrite_checker
+                    .lamport_refs
+                    .insert(destination.local, account);
+            }
+        }
+        if callee_name.ends_with(".lamports") {
+            if let Some(account) = args.first().and_then(|arg| arg.node.place()) {
+                let checker = &self.bv.lamports_overwrite_checker;
+                let account = checker.local_sources.source_of(account.local);
+                self.bv.lamports_overwrite_checker.balance_reads.insert(account);
+                self.bv.close_authority_checker.balance_reads.insert(account);
+            }
+        }
+        // Zeroing account data keeps a closed account from being revived with its data
+        if callee_name.ends_with(".fill") || callee_name.contains("sol_memset") {
+            self.bv.close_authority_checker.check_for_data_zeroing = true;
+        }
+        if callee_name.ends_with(".deref")
+            || callee_name.ends_with(".deref_mut")
+            || callee_name.ends_with(".branch")
+            || callee_name.ends_with(".unwrap")
+            || callee_name.ends_with(".expect")
+        {
+            let account = args
+                .first()
+                .and_then(|arg| arg.node.place())
+                .and_then(|lamports| {
+                    self.bv.lamports_overwrite_checker.account_of(lamports.local)
+                });
+            if let Some(account) = account {
+                self.bv
+                    .lamports_overwrite_checker
+                    .lamport_refs
+                    .insert(destination.local, account);
+            }
+            let account = args
+                .first()
+                .and_then(|arg| arg.node.place())
+                .and_then(|lamports| self.bv.missing_signer_checker.account_of(lamports.local));
+            if let Some(account) = account {
+                self.bv
+                    .missing_signer_checker
+                    .lamport_refs
+                    .insert(destination.local, account);
+            }
+        }
+        // The values of a map, e.g. the result of balances.get_mut(key), and what they unwrap to
+        let is_map_value = contract_errors::is_map_method(&callee_name)
+            && [".get_mut", ".index_mut", ".or_insert", ".or_default", ".or_insert_with"]
+                .iter()
+                .any(|lookup| callee_name.ends_with(lookup));
+        let is_unwrapped_map_value = [".ok_or", ".ok_or_else", ".branch", ".unwrap", ".expect"]
+            .iter()
+            .any(|unwrapping| callee_name.ends_with(unwrapping))
+            && args.first().and_then(|arg| arg.node.place()).is_some_and(|value| {
+                let checker = &self.bv.integer_overflow_checker;
+                checker
+                    .balance_refs
+                    .contains(&checker.local_sources.source_of(value.local))
+            });
+        if is_map_value || is_unwrapped_map_value {
+            self.bv
+                .integer_overflow_checker
+                .balance_refs
+                .insert(destination.local);
+        }
+        // The balance is read when it is passed by value, e.g. to checked_add
+        for arg in args {
+            let account = arg.node.place().and_then(|place| self.lamports_account(&place));
+            if let Some(account) = account {
+                self.bv.lamports_overwrite_checker.balance_reads.insert(account);
+            }
+        }
+        // The lamports of an account are debited by the methods that subtract from them as well
+        let is_subtraction = [
+            ".checked_sub", ".saturating_sub", ".wrapping_sub", ".overflowing_sub",
+        ]
+        .iter()
+        .any(|method| callee_name.ends_with(method));
+        if is_subtraction {
+            let account = args
+                .first()
+                .and_then(|arg| arg.node.place())
+                .and_then(|lamports| self.lamports_value_account(&lamports));
+            if let Some(account) = account {
+                self.bv.missing_signer_checker.debited_accounts.insert(account);
+            }
+        }
+
+        // Token amounts of different accounts are compared or combined by their methods as well
+        let is_amount_method = [
+            ".lt", ".le", ".gt", ".ge", ".partial_cmp", ".cmp", ".checked_add", ".checked_sub",
+            ".saturating_add", ".saturating_sub",
+        ]
+        .iter()
+        .any(|method| callee_name.ends_with(method));
+        if is_amount_method {
+            let left_account = args
+                .first()
+                .and_then(|arg| arg.node.place())
+                .and_then(|left| self.token_amount_account(&left));
+            let right_account = args
+                .get(1)
+                .and_then(|arg| arg.node.place())
+                .and_then(|right| self.token_amount_account(&right));
+            self.bv.token_decimals_checker.record_operation(
+                self.bv.current_location,
+                self.bv.current_span,
+                left_account,
+                right_account,
+            );
+        }
+
+        // Authorization by the lamports of an account is here, the lamports are read by lamports()
+        // and compared by an operator or by one of the comparison methods
+        if callee_name.ends_with(".lamports") {
+            self.bv
+                .lamports_authorization_checker
+                .lamport_balances
+                .insert(destination.local);
+        }
+        if callee_name.contains("try_borrow_mut_lamports") || callee_name.ends_with(".transfer") {
+            self.bv.lamports_authorization_checker.check_for_lamport_transfer = true;
+        }
+        // Transfers of a helper that is shared by several callers are recorded here
+        if callee_name.contains("try_borrow_mut_lamports") || callee_name.ends_with(".transfer") {
+            self.bv
+                .unguarded_transfer_helper_checker
+                .transfers
+                .insert(self.bv.current_location, self.bv.current_span);
+        }
+        let is_comparison_method = [".lt", ".le", ".gt", ".ge", ".eq", ".ne"]
+            .iter()
+            .any(|method| callee_name.ends_with(method));
+        if is_comparison_method {
+            let checker = &self.bv.lamports_authorization_checker;
+            let is_lamports_comparison = args
+                .iter()
+                .filter_map(|arg| arg.node.place())
+                .any(|arg| checker.is_lamport_balance(arg.local));
+            if is_lamports_comparison {
+                self.bv
+                    .lamports_authorization_checker
+                    .lamport_comparisons
+                    .insert(destination.local, self.bv.current_span);
+            }
+        }
+
+        // Manual rent computation is here, the data length is read by data_len() and passed
+        // through arithmetic methods and ? before it is multiplied
+        if callee_name.ends_with(".data_len") {
+            self.bv.manual_rent_checker.data_lengths.insert(destination.local);
+        }
+        if callee_name.ends_with(".minimum_balance") {
+            self.bv.manual_rent_checker.check_for_minimum_balance = true;
+        }
+        let is_data_length_argument = args
+            .first()
+            .and_then(|arg| arg.node.place())
+            .is_some_and(|arg| self.bv.manual_rent_checker.is_data_length(arg.local));
+        if is_data_length_argument {
+            let is_multiplication = [".checked_mul", ".saturating_mul", ".wrapping_mul"]
+                .iter()
+                .any(|method| callee_name.ends_with(method));
+            if is_multiplication {
+                self.bv
+                    .manual_rent_checker
+                    .manual_computations
+                    .insert(self.bv.current_location, self.bv.current_span);
+            }
+            let is_propagation = [
+                ".checked_add", ".saturating_add", ".wrapping_add", ".ok_or", ".unwrap",
+                ".expect", ".branch", ".into", ".try_into",
+            ]
+            .iter()
+            .any(|method| callee_name.ends_with(method));
+            if is_multiplication || is_propagation {
+                self.bv.manual_rent_checker.data_lengths.insert(destination.local);
+            }
+        }
+
+        // Cross-program invocations are here, their results are matched by a later branch
+        if callee_name.ends_with(".invoke") || callee_name.ends_with(".invoke_signed") {
+            self.bv
+                .swallowed_cpi_error_checker
+                .cpi_results
+                .insert(destination.local, self.bv.current_span);
+        }
+        // The result of an invocation, unless it is read later, e.g. by ? or a match, is unchecked
+        let callee_path = self.bv.tcx.def_path_str(callee_def_id);
+        if contract_errors::UncheckedCpiResultChecker::is_invocation(&callee_path)
+            && destination.projection.is_empty()
+        {
+            self.bv
+                .unchecked_cpi_result_checker
+                .cpi_results
+                .insert(destination.local, self.bv.current_span);
+        }
+        self.bv.unchecked_cpi_result_checker.visit_call_args(args);
+
+        // Logged errors are here, the message is taken from the source of the call, e.g. of msg!
+        if callee_name.contains("log") || callee_name.contains("_print") {
+            let source_map = self.bv.tcx.sess.source_map();
+            let is_error_log = source_map
+                .span_to_snippet(self.bv.current_span.source_callsite())
+                .is_ok_and(|snippet| {
+                    contract_errors::OkAfterErrorLogChecker::is_error_message(&snippet)
+                });
+            if is_error_log {
+                self.bv
+                    .ok_after_error_log_checker
+                    .error_logs
+                    .insert(self.bv.current_location, self.bv.current_span);
+            }
+        }
+
+        // Lengths that a count of the instruction data is validated against are here
+        if callee_name.ends_with(".len") && destination.projection.is_empty() {
+            self.bv
+                .unvalidated_count_checker
+                .lengths
+                .insert(destination.local);
+        }
+
+        // Invocations signed by program derived addresses and the re-derivations are here
+        if callee_name.ends_with(".invoke_signed") {
+            self.bv
+                .unverified_pda_checker
+                .signed_invocations
+                .push(self.bv.current_span);
+        }
+        if callee_name.ends_with(".find_program_address")
+            || callee_name.ends_with(".create_program_address")
+        {
+            self.bv
+                .unverified_pda_checker
+                .derived_keys
+                .insert(destination.local);
+        }
+        let checker = &self.bv.unverified_pda_checker;
+        let has_derived_arg = args
+            .iter()
+            .filter_map(|arg| arg.node.place())
+            .any(|arg| checker.is_derived_key(arg.local));
+        if has_derived_arg {
+            if callee_name.ends_with(".eq") || callee_name.ends_with(".ne") {
+                self.bv.unverified_pda_checker.check_for_key_comparison = true;
+                // The key that is compared with the derived address is of an account of the program
+                let checker = &self.bv.unverified_pda_checker;
+                let compared_keys: Vec<mir::Local> = args
+                    .iter()
+                    .filter_map(|arg| arg.node.place())
+                    .filter(|arg| !checker.is_derived_key(arg.local))
+                    .map(|arg| arg.local)
+                    .collect();
+                for key in compared_keys {
+                    self.bv.is_writable_checker.record_signed_account(key);
+                }
+            }
+            let is_propagation = [".unwrap", ".expect", ".map_err", ".branch", ".ok_or"]
+                .iter()
+                .any(|method| callee_name.ends_with(method));
+            if is_propagation {
+                self.bv
+                    .unverified_pda_checker
+                    .derived_keys
+                    .insert(destination.local);
+            }
+        }
+
+        // Writes into account data, the data they write and its length are here
+        if callee_name.ends_with(".try_borrow_mut_data") {
+            self.bv
+                .unbounded_account_write_checker
+                .account_data
+                .insert(destination.local);
+        }
+        if let Some(first_arg) = args.first().and_then(|arg| arg.node.place()) {
+            let is_derivation = contract_errors::DERIVING_METHODS
+                .iter()
+                .any(|method| callee_name.ends_with(method));
+            if is_derivation {
+                self.bv
+                    .unbounded_account_write_checker
+                    .local_sources
+                    .record_derived(destination.local, first_arg.local);
+            }
+            if callee_name.ends_with(".len") {
+                self.bv
+                    .unbounded_account_write_checker
+                    .lengths
+                    .insert(destination.local, first_arg.local);
+            }
+            let source = args.get(1).and_then(|arg| arg.node.place());
+            let is_write = callee_name.ends_with(".copy_from_slice")
+                || callee_name.ends_with(".extend_from_slice");
+            if let (true, Some(source)) = (is_write, source) {
+                self.bv.unbounded_account_write_checker.writes.push((
+                    self.bv.current_span,
+                    first_arg.local,
+                    source.local,
+                ));
+            }
+        }
+
+        // Derivations of program derived addresses and signed invocations pass seeds
+        if callee_name.ends_with(".find_program_address")
+            || callee_name.ends_with(".create_program_address")
+        {
+            if let Some(seeds) = args.first().and_then(|arg| arg.node.place()) {
+                self.bv
+                    .seed_limits_checker
+                    .derivations
+                    .push((self.bv.current_span, seeds.local));
+            }
+        }
+        // A byte taken out of the instruction data is derived from it, e.g. by ``first()?``
+        let is_byte_access = [
+            ".first", ".get", ".ok_or", ".branch", ".unwrap", ".expect", ".copied",
+        ]
+        .iter()
+        .any(|suffix| callee_name.ends_with(suffix));
+        if let Some(first_arg) = args.first().and_then(|arg| arg.node.place()) {
+            if is_byte_access && destination.projection.is_empty() {
+                self.bv
+                    .pda_bump_checker
+                    .local_sources
+                    .record_derived(destination.local, first_arg.local);
+            }
+        }
+        if callee_name.ends_with(".create_program_address") {
+            if let Some(seeds) = args.first().and_then(|arg| arg.node.place()) {
+                self.record_untrusted_args();
+                self.bv
+                    .pda_bump_checker
+                    .creations
+                    .push((self.bv.current_span, seeds.local));
+            }
+        }
+        if callee_name.ends_with(".invoke_signed") {
+            if let Some(signers_seeds) = args.get(2).and_then(|arg| arg.node.place()) {
+                self.bv
+                    .seed_limits_checker
+                    .signed_invocations
+                    .push((self.bv.current_span, signers_seeds.local));
+            }
+        }
+
+        // Accounts passed to the instruction, comparisons of their owners and reads of their data
+        if destination.projection.is_empty() {
+            self.record_anchor_account(destination.local);
+        }
+        if callee_name.contains("next_account_info") {
+            self.bv
+                .owner_check_checker
+                .next_accounts
+                .insert(destination.local);
+        }
+        if let Some(first_arg) = args.first().and_then(|arg| arg.node.place()) {
+            if callee_name.ends_with(".branch") || callee_name.ends_with(".unwrap") {
+                self.bv
+                    .owner_check_checker
+                    .local_sources
+                    .record_derived(destination.local, first_arg.local);
+            }
+            if callee_name.ends_with(".try_borrow_data") {
+                self.bv.owner_check_checker.data_reads.push((
+                    self.bv.current_location,
+                    first_arg.local,
+                    self.bv.current_span,
+                ));
+            }
+        }
+        if callee_name.ends_with(".eq") || callee_name.ends_with(".ne") {
+            let compared: Vec<Option<mir::Local>> = args
+                .iter()
+                .map(|arg| arg.node.place().map(|place| place.local))
+                .collect();
+            if let [left, right] = compared[..] {
+                self.bv
+                    .owner_check_checker
+                    .compare(self.bv.current_location, left, right);
+            }
+        }
+
+        // Reads of the data of an account, the values derived from it, their comparisons and
+        // their deserializations are here
+        let (location, span) = (self.bv.current_location, self.bv.current_span);
+        let checker = &mut self.bv.unchecked_deserialize_checker;
+        if let Some(first_arg) = args.first().and_then(|arg| arg.node.place()) {
+            let is_data_borrow = callee_name.ends_with(".try_borrow_data")
+                || callee_name.ends_with(".try_borrow_mut_data");
+            if is_data_borrow && destination.projection.is_empty() {
+                checker.data_reads.insert(destination.local, (first_arg.local, span));
+            } else if contract_errors::UncheckedDeserializeChecker::is_derivation(&callee_name) {
+                checker
+                    .local_sources
+                    .record_derived(destination.local, first_arg.local);
+            }
+        }
+        let arg_locals: Vec<mir::Local> = args
+            .iter()
+            .filter_map(|arg| arg.node.place())
+            .map(|place| place.local)
+            .collect();
+        if contract_errors::UncheckedDeserializeChecker::is_deserializer(&callee_name) {
+            for bytes in &arg_locals {
+                checker.deserializations.push((location, *bytes, span));
+            }
+        }
+        let is_comparison = [".eq", ".ne", ".lt", ".le", ".gt", ".ge"]
+            .iter()
+            .any(|method| callee_name.ends_with(method));
+        if is_comparison {
+            for compared in arg_locals {
+                checker.comparisons.push((location, compared));
+            }
+        }
+
+        // Invocations of a program whose id is taken from a passed account are here
+        let checker = &mut self.bv.arbitrary_cpi_checker;
+        if callee_name.contains("next_account_info") {
+            checker.next_accounts.insert(destination.local);
+        }
+        if let Some(first_arg) = args.first().and_then(|arg| arg.node.place()) {
+            if callee_name.ends_with(".branch") || callee_name.ends_with(".unwrap") {
+                checker
+                    .local_sources
+                    .record_derived(destination.local, first_arg.local);
+            }
+            if callee_name.ends_with(".invoke") || callee_name.ends_with(".invoke_signed") {
+                checker
+                    .invocations
+                    .push((self.bv.current_location, first_arg.local, self.bv.current_span));
+            }
+        }
+        if callee_name.ends_with(".eq") || callee_name.ends_with(".ne") {
+            let compared: Vec<Option<mir::Local>> = args
+                .iter()
+                .map(|arg| arg.node.place().map(|place| place.local))
+                .collect();
+            if let [left, right] = compared[..] {
+                checker.compare(self.bv.current_location, left, right);
+            }
+        }
+        // A builder of an instruction takes its program id first, e.g. token_program_id
+        let tcx = self.bv.tcx;
+        let takes_program_id = matches!(tcx.def_kind(callee_def_id), DefKind::Fn | DefKind::AssocFn)
+            && tcx
+                .fn_arg_names(callee_def_id)
+                .first()
+                .is_some_and(|name| name.as_str().ends_with("program_id"));
+        if takes_program_id && destination.projection.is_empty() {
+            if let Some(program_id) = args.first().and_then(|arg| arg.node.place()) {
+                self.bv
+                    .arbitrary_cpi_checker
+                    .instruction_program_ids
+                    .insert(destination.local, program_id.local);
+            }
+        }
+
+        // Mutations of lamports and the helpers that check a signer, e.g. assert_signer(user)?
+        if let Some(account) = args.first().and_then(|arg| arg.node.place()) {
+            if callee_name.contains("try_borrow_mut_lamports") {
+                self.bv.missing_signer_checker.lamport_mutations.push((
+                    self.bv.current_location,
+                    account.local,
+                    self.bv.current_span,
+                ));
+                self.bv
+                    .missing_signer_checker
+                    .lamport_refs
+                    .insert(destination.local, account.local);
+            } else if callee_name.contains("signer") {
+                self.bv.missing_signer_checker.signer_checks.push((
+                    self.bv.current_location,
+                    account.local,
+                    self.bv.current_span,
+                ));
+                self.bv.account_substitution_checker.signer_checks.push((
+                    self.bv.current_location,
+                    account.local,
+                    self.bv.current_span,
+                ));
+            }
+        }
+
+        // Lookups of a map by a key, e.g. balances.get_mut(user_account.key)
+        let lookups = [".get", ".get_mut", ".entry", ".insert", ".remove", ".contains_key", ".index"];
+        let is_lookup = lookups.iter().any(|method| callee_name.ends_with(method));
+        if contract_errors::is_map_method(&callee_name) && is_lookup {
+            if let Some(key) = args.get(1).and_then(|arg| arg.node.place()) {
+                self.bv.account_substitution_checker.key_lookups.push((
+                    self.bv.current_location,
+                    key.local,
+                    self.bv.current_span,
+                ));
+            }
+        }
+
+        // Mutations of data or lamports of accounts that have a writable flag, and the helpers that
+        // check the flag, e.g. assert_writable(vault)?
+        if let Some(account) = args.first() {
+            let account_ty = account.node.ty(self.bv.mir, self.bv.tcx);
+            if let (Some(account_place), true) =
+                (account.node.place(), Self::has_field(account_ty, "is_writable"))
+            {
+                let access = (self.bv.current_location, account_place.local, self.bv.current_span);
+                if callee_name.contains("try_borrow_mut_data")
+                    || callee_name.contains("try_borrow_mut_lamports")
+                {
+                    self.bv.is_writable_checker.mutations.push(access);
+                } else if callee_name.contains("writable") {
+                    self.bv.is_writable_checker.writable_checks.push(access);
+                }
+            }
+        }
+
+        // Borrows of lamports are here, the guard of a borrow is passed on by ``?``
+        if callee_name.ends_with(".try_borrow_mut_lamports") {
+            if let Some(account) = args.first().and_then(|arg| arg.node.place()) {
+                self.bv.lamports_double_borrow_checker.borrow(
+                    account.local,
+                    destination.local,
+                    self.bv.current_span,
+                );
+            }
+        }
+        if callee_name.ends_with(".branch") || callee_name.ends_with(".unwrap") {
+            if let Some(result) = args.first().and_then(|arg| arg.node.place()) {
+                self.bv
+                    .lamports_double_borrow_checker
+                    .propagate(result.local, destination.local);
+            }
+        }
+
+        // Mutations of the accounts of a loop are here, the accounts are the items of an iterator
+        if callee_name.ends_with(".next") {
+            self.bv
+                .unvalidated_loop_account_checker
+                .loop_items
+                .insert(destination.local);
+        }
+        if callee_name.contains("try_borrow_mut_lamports")
+            || callee_name.contains("try_borrow_mut_data")
+        {
+            let checker = &self.bv.unvalidated_loop_account_checker;
+            let item = args
+                .first()
+                .and_then(|arg| arg.node.place())
+                .and_then(|account| checker.loop_item_of(account.local));
+            if let Some(item) = item {
+                self.bv
+                    .unvalidated_loop_account_checker
+                    .mutations
+                    .insert(self.bv.current_location, (self.bv.current_span, item));
+            }
+        }
+
+        // Missing inequality check between a debited and a credited account is here
+        if callee_name.contains("try_borrow_mut_lamports") {
+            if let Some(account) = args.first().and_then(|arg| arg.node.place()) {
+                self.bv.distinct_accounts_checker.lamport_borrows.insert(
+                    self.bv.current_location,
+                    (self.bv.current_span, account.local),
+                );
+            }
+        }
+        if callee_name.contains("try_borrow_mut_data") {
+            if let Some(account) = args.first().and_then(|arg| arg.node.place()) {
+                self.bv.distinct_accounts_checker.data_borrows.insert(
+                    self.bv.current_location,
+                    (self.bv.current_span, account.local),
+                );
+            }
+        }
+        // Account keys are compared via PartialEq, e.g. vault.key != recipient.key
+        if callee_name.ends_with(".eq") || callee_name.ends_with(".ne") {
+            let left = args.first().and_then(|arg| arg.node.place());
+            let right = args.get(1).and_then(|arg| arg.node.place());
+            if let (Some(left), Some(right)) = (left, right) {
+                self.bv
+                    .distinct_accounts_checker
+                    .compared_pairs
+                    .insert((left.local, right.local));
+            }
         }
-    }
-
-    /// Block ends with the call of a function.
-    ///
-    /// #Arguments
-    /// * `func` - The function that’s being called
-    /// * `args` - Arguments the function is called with.
-    /// These are owned by the callee, which is free to modify them.
-    /// This allows the memory occupied by "by-value" arguments to be
-    /// reused across function calls without duplicating the contents.
-    /// * `destination` - Destination for the return value.
-    /// * `unwind` - Work to be done if the call unwinds.
-    /// * `call_source` - Where this call came from in HIR/THIR.
-    /// operator. True for overloaded function call.
-    #[allow(clippy::too_many_arguments)]
-    #[logfn_inputs(TRACE)]
-    fn visit_call(
-        &mut self,
-        bb: mir::BasicBlock,
-        func: &mir::Operand<'tcx>,
-        args: &[Spanned<mir::Operand<'tcx>>],
-        destination: mir::Place<'tcx>,
-        target: Option<mir::BasicBlock>,
-        unwind: mir::UnwindAction,
-        fn_span: &rustc_span::Span,
-    ) {
-        // This offset is used to distinguish any local variables that leak out from the called function
-        // from local variables of the callee function.
-        // This situation arises when a structured value stored in a local variable is assigned to
-        // a field reachable from a mutable parameter.
-        // We assume that no program that does not make MIRAI run out of memory will have more than
-        // a million local variables.
-        self.bv.fresh_variable_offset += 1_000_000;
-
-        info!("Hi call");
-        info!("source location {:?}", fn_span);
-        info!("call stack {:?}", self.bv.active_calls_map);
-        info!("visit_call {:?}, args {:?}", func, args);
-        info!(
-            "self.generic_argument_map {:?}",
-            self.type_visitor().generic_argument_map
-        );
-        info!(
-            "actual_argument_types {:?}",
-            self.type_visitor().actual_argument_types
-        );
-        info!("destination {:?}", destination);
-        trace!("env {:?}", self.bv.current_environment);
 
-        let func_to_call = self.visit_operand(func);
-        let func_ref = self.get_func_ref(&func_to_call);
-        let func_ref_to_call = if let Some(fr) = func_ref {
-            fr
-        } else {
-            if self.might_be_reachable().unwrap_or(true)
-                && self
-                    .bv
-                    .already_reported_errors_for_call_to
-                    .insert(func_to_call)
-            {
-                self.report_missing_summary();
+        // Logging whole account data or instruction data is here, msg! formats its arguments first
+        let is_log = callee_name.contains("sol_log");
+        if is_log {
+            self.bv.logged_data_checker.check_for_log = true;
+        }
+        if is_log || (callee_name.contains("core.fmt.rt") && callee_name.contains(".new_")) {
+            let logs_byte_buffer = args.iter().any(|arg| {
+                let arg_ty = self.get_operand_rustc_type(&arg.node);
+                self.is_byte_buffer(arg_ty)
+            });
+            if logs_byte_buffer {
+                self.bv
+                    .logged_data_checker
+                    .logged_buffers
+                    .insert(self.bv.current_location, self.bv.current_span);
             }
-            return;
-        };
-        // Time manipulation is here 
-        let argument_type_key = func_ref_to_call.argument_type_key.clone();
-        if argument_type_key.contains("__solana_clock_Clock") {
-            self.bv.time_manipulation_checker.check_for_clock_lib = true;
-            self.bv.time_manipulation_checker.time_manipulation_span = self.bv.current_span;
         }
 
-        let callee_def_id = func_ref_to_call
-            .def_id
-            .expect("callee obtained via operand should have def id");
-        
-        let callee_name =  utils::summary_key_str(self.bv.tcx, callee_def_id);
-        // Numerical precision error is here
-        if callee_name.contains(".round") {
-            self.bv.numerical_precision_checker.check_for_round_func = true;
-            self.bv.numerical_precision_checker.numerical_precision_error_span = self.bv.current_span;
+        // Logging state computed from maps that are not persisted is here
+        let is_map = contract_errors::is_map_method(&callee_name);
+        let is_constructor = callee_name.ends_with(".new")
+            || callee_name.ends_with(".default")
+            || callee_name.ends_with(".with_capacity");
+        if is_map && is_constructor && destination.projection.is_empty() {
+            self.bv
+                .ephemeral_state_checker
+                .ephemeral_collections
+                .insert(destination.local);
+        }
+        if is_log {
+            self.bv.ephemeral_state_checker.check_for_log = true;
         }
 
-        // Bad randomness is here
-        if callee_name.contains("fastrand") 
-        || callee_name.contains("oorandom.implement_oorandom") 
-        || callee_name.contains("nanorand.rand")
+        // Maps that are mutated but never persisted to account data are here
+        if is_map && is_constructor && destination.projection.is_empty() {
+            self.bv
+                .non_persistent_state_checker
+                .collections
+                .insert(destination.local, self.bv.current_span);
+        }
+        if callee_name.contains("try_borrow_mut_data")
+            || callee_name.ends_with(".serialize")
+            || callee_name.ends_with(".pack")
+            || callee_name.ends_with(".pack_into_slice")
         {
-            self.bv.bad_randomness_checker.check_for_rand_lib = true;
-            self.bv.bad_randomness_checker.bad_randomness_span = self.bv.current_span;
+            self.bv.non_persistent_state_checker.check_for_persist = true;
+        }
+        if callee_name.contains("core.fmt.rt") && callee_name.contains(".new_") {
+            if let Some(value) = args.first().and_then(|arg| arg.node.place()) {
+                self.bv
+                    .ephemeral_state_checker
+                    .formatted_values
+                    .insert(self.bv.current_location, (self.bv.current_span, value.local));
+            }
+        } else if destination.projection.is_empty() {
+            let argument_locals = args
+                .iter()
+                .filter_map(|arg| arg.node.place().map(|place| place.local))
+                .collect();
+            self.bv
+                .ephemeral_state_checker
+                .call_results
+                .insert(destination.local, argument_locals);
         }
 
-        // Reentrancy is here
-        if callee_name.contains("try_borrow_mut_lamports") {
-            self.bv.reentrancy_checker.function_lamport_transfer.entry(bb).or_insert(callee_name.clone());
+        // Unsafe reinterpretation of account data is here
+        if (callee_name.ends_with(".as_ptr") || callee_name.ends_with(".as_mut_ptr"))
+            && destination.projection.is_empty()
+        {
+            let is_byte_buffer = args.first().is_some_and(|arg| {
+                let arg_ty = self.get_operand_rustc_type(&arg.node);
+                self.is_byte_data(arg_ty)
+            });
+            if is_byte_buffer {
+                self.bv
+                    .unsafe_data_cast_checker
+                    .byte_pointers
+                    .insert(destination.local);
+            }
         }
-        if callee_name.contains("std.collections.hash.map") {
-            self.bv.reentrancy_checker.check_for_balance_variable = true;
-            self.bv.reentrancy_checker.temporary_variable_for_balance = Some(destination);
-            self.bv.reentrancy_checker.starting_reentrancy_span = self.bv.current_span.lo();
+        if callee_name.ends_with(".transmute") || callee_name.ends_with(".transmute_copy") {
+            let destination_ty = destination.ty(self.bv.mir, self.bv.tcx).ty;
+            let transmutes_byte_data = args.iter().any(|arg| {
+                let arg_ty = self.get_operand_rustc_type(&arg.node);
+                self.is_byte_data(arg_ty)
+            });
+            if transmutes_byte_data && self.is_reinterpreted_type(destination_ty) {
+                self.bv
+                    .unsafe_data_cast_checker
+                    .unsafe_casts
+                    .insert(self.bv.current_location, self.bv.current_span);
+            }
         }
-        if self.bv.reentrancy_checker.check_for_balance_variable {
+
+        // Writable accounts that are only read are here
+        if callee_name.contains("borrow_mut")
+            || callee_name.ends_with(".realloc")
+            || callee_name.ends_with(".assign")
+        {
             for arg in args {
-                let operand = arg.node.clone();
-                if let mir::Operand::Copy(place) | mir::Operand::Move(place) = operand {
-                    if self.bv.reentrancy_checker.temporary_variable_for_balance == Some(place) {
-                        self.bv.reentrancy_checker.temporary_variable_for_balance = Some(destination);
-                    }
+                if let Some(account) = arg.node.place() {
+                    self.bv
+                        .over_privileged_account_checker
+                        .mutated_accounts
+                        .insert(account.local);
                 }
             }
         }
-        
+        if callee_name.contains("program.invoke") {
+            self.bv.over_privileged_account_checker.check_for_invoke = true;
+        }
+
+        // Saturating arithmetic masking an error is here, balances and amounts are held in u64
+        if callee_name.contains("implement_u64.saturating_sub")
+            || callee_name.contains("implement_u64.saturating_add")
+        {
+            let argument_locals = args
+                .iter()
+                .filter_map(|arg| arg.node.place().map(|place| place.local))
+                .collect();
+            self.bv
+                .saturating_arithmetic_checker
+                .saturating_operations
+                .insert(self.bv.current_location, (self.bv.current_span, argument_locals));
+        }
+
         let generic_args = self
             .bv
             .cv
@@ -795,7 +2549,7 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
         let current_location = self.bv.current_location;
         self.bv
             .block_to_call
-            .insert(current_location, callee_def_id);
+            .insert(current_location, (callee_def_id, callee_generic_arguments));
 
         let tcx = self.bv.tcx;
         let mut call_visitor = CallVisitor::new(
@@ -1556,6 +3310,372 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
         }
     }
 
+    /// Returns true if the constant is an integer zero.
+    fn is_zero_constant(&self, constant: &mir::ConstOperand<'tcx>) -> bool {
+        constant
+            .const_
+            .try_to_scalar_int()
+            .is_some_and(|value| value.is_null())
+    }
+
+    /// Records the arguments of the function body that are references to byte slices, such as the
+    /// instruction data, since their lengths are controlled by the caller.
+    fn record_tainted_slices(&mut self) {
+        let mir = self.bv.mir;
+        for local in mir.args_iter() {
+            if let TyKind::Ref(_, ty, _) = mir.local_decls[local].ty.kind() {
+                if let TyKind::Slice(elem_ty) = ty.kind() {
+                    if matches!(elem_ty.kind(), TyKind::Uint(UintTy::U8)) {
+                        self.bv.slice_panic_checker.tainted_slices.insert(local);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records the arguments of the function body that are byte slices, such as the instruction
+    /// data, as the sources of amounts, each read at the declaration of the argument.
+    fn record_instruction_data_amounts(&mut self) {
+        let mir = self.bv.mir;
+        for local in mir.args_iter() {
+            if let TyKind::Ref(_, ty, _) = mir.local_decls[local].ty.kind() {
+                if let TyKind::Slice(elem_ty) = ty.kind() {
+                    if matches!(elem_ty.kind(), TyKind::Uint(UintTy::U8)) {
+                        self.bv
+                            .truncating_cast_checker
+                            .amounts
+                            .entry(local)
+                            .or_insert(mir.local_decls[local].source_info.span);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Record the arguments of the function that are bytes or byte slices, and so supplied by the
+    /// caller, as the possible sources of a bump seed.
+    fn record_untrusted_args(&mut self) {
+        let mir = self.bv.mir;
+        for local in mir.args_iter() {
+            let ty = match mir.local_decls[local].ty.kind() {
+                TyKind::Ref(_, ty, _) => match ty.kind() {
+                    TyKind::Slice(elem_ty) => *elem_ty,
+                    _ => *ty,
+                },
+                _ => mir.local_decls[local].ty,
+            };
+            if matches!(ty.kind(), TyKind::Uint(UintTy::U8)) {
+                self.bv.pda_bump_checker.untrusted_args.insert(local);
+            }
+        }
+    }
+
+    /// Returns true if the targets of a branch on the discriminant of a Result continue with the
+    /// code that follows the Ok arm when the result is an Err, doing nothing in between but
+    /// logging, jumping and dropping. An Err arm that assigns a return value returns the error.
+    fn is_error_ignored(&self, targets: &rustc_middle::mir::SwitchTargets) -> bool {
+        let Some(ok_bb) = targets.iter().find(|(value, _)| *value == 0).map(|(_, bb)| bb) else {
+            return false;
+        };
+        let err_bb = targets
+            .iter()
+            .find(|(value, _)| *value == 1)
+            .map_or(targets.otherwise(), |(_, bb)| bb);
+        let basic_blocks = &self.bv.mir.basic_blocks;
+        // A function without a return value assigns the unit value in any arm that it ends with
+        let returns_unit = self.bv.mir.local_decls[mir::RETURN_PLACE].ty.is_unit();
+        // The blocks that the Ok arm reaches, including the code after the branch
+        let mut ok_blocks: HashSet<mir::BasicBlock> = HashSet::new();
+        let mut pending = vec![ok_bb];
+        while let Some(bb) = pending.pop() {
+            if ok_blocks.insert(bb) {
+                pending.extend(basic_blocks[bb].terminator().successors());
+            }
+        }
+        let mut bb = err_bb;
+        let mut visited: HashSet<mir::BasicBlock> = HashSet::new();
+        while visited.insert(bb) {
+            if ok_blocks.contains(&bb) {
+                return true;
+            }
+            let block = &basic_blocks[bb];
+            let assigns_return_value = block.statements.iter().any(|statement| {
+                matches!(
+                    &statement.kind,
+                    mir::StatementKind::Assign(box (place, _)) if place.local == mir::RETURN_PLACE
+                )
+            });
+            if assigns_return_value && !returns_unit {
+                return false;
+            }
+            bb = match &block.terminator().kind {
+                mir::TerminatorKind::Goto { target } | mir::TerminatorKind::Drop { target, .. } => {
+                    *target
+                }
+                mir::TerminatorKind::Call {
+                    func,
+                    target: Some(target),
+                    ..
+                } => {
+                    let is_logging = func.const_fn_def().is_some_and(|(def_id, _)| {
+                        let name = utils::summary_key_str(self.bv.tcx, def_id);
+                        name.contains("log") || name.contains("_print") || name.contains("fmt")
+                    });
+                    if !is_logging {
+                        return false;
+                    }
+                    *target
+                }
+                _ => return false,
+            };
+        }
+        false
+    }
+
+    /// Returns true if the local holds an Option or a Result, as unwrapped by unwrap and expect.
+    fn is_option_or_result(&self, local: mir::Local) -> bool {
+        let tcx = self.bv.tcx;
+        self.bv.mir.local_decls[local]
+            .ty
+            .ty_adt_def()
+            .is_some_and(|adt| {
+                tcx.is_diagnostic_item(rustc_span::sym::Option, adt.did())
+                    || tcx.is_diagnostic_item(rustc_span::sym::Result, adt.did())
+            })
+    }
+
+    /// Returns the number of bits of an integer type, where usize and isize have the 64 bits of
+    /// the targets of the smart contracts, or None if the type is not an integer type.
+    fn integer_bit_width(ty: Ty<'tcx>) -> Option<u64> {
+        match ty.kind() {
+            TyKind::Uint(uint_ty) => Some(uint_ty.bit_width().unwrap_or(64)),
+            TyKind::Int(int_ty) => Some(int_ty.bit_width().unwrap_or(64)),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the struct field that the place selects last, if any.
+    fn field_name(&self, place: &mir::Place<'tcx>) -> Option<String> {
+        let (base, mir::ProjectionElem::Field(field_index, _)) = place.as_ref().last_projection()?
+        else {
+            return None;
+        };
+        let base_ty = base.ty(&self.bv.mir.local_decls, self.bv.tcx).ty;
+        if let TyKind::Adt(def, _) = base_ty.kind() {
+            if def.is_struct() {
+                return Some(def.non_enum_variant().fields[field_index].name.to_string());
+            }
+        }
+        None
+    }
+
+    /// Records that the local holds or refers to an account of the Context of an Anchor handler,
+    /// if it does, e.g. the AccountInfo of ``ctx.accounts.user``. The signer, writable and owner
+    /// checkers then resolve the local to the account of its field, and take the checks that
+    /// Anchor makes of the account to be satisfied, e.g. the signer check of a Signer or of an
+    /// account with the signer constraint. An AccountInfo or UncheckedAccount without a constraint
+    /// on its owner or key is passed to the instruction unchecked. Returns true if the local
+    /// holds an account of the Context.
+    fn record_anchor_account(&mut self, local: mir::Local) -> bool {
+        let place = mir::Place::from(local);
+        let anchor_context = &self.bv.anchor_context;
+        let Some(field) = anchor_context.account_of(self.bv.tcx, self.bv.mir, &place) else {
+            return false;
+        };
+        let account = field.account;
+        if local != account {
+            self.bv
+                .missing_signer_checker
+                .local_sources
+                .record_derived(local, account);
+            self.bv
+                .is_writable_checker
+                .local_sources
+                .record_derived(local, account);
+            self.bv
+                .owner_check_checker
+                .local_sources
+                .record_derived(local, account);
+        }
+        if field.is_signed() {
+            self.bv.missing_signer_checker.signed_accounts.insert(account);
+        }
+        if field.is_writable() {
+            self.bv.is_writable_checker.writable_accounts.insert(account);
+        }
+        if field.is_unchecked() {
+            self.bv.owner_check_checker.next_accounts.insert(account);
+        }
+        true
+    }
+
+    /// Returns true if the type, or the type that it refers to, is a struct with a field of the
+    /// given name, e.g. the is_writable field of AccountInfo.
+    fn has_field(ty: Ty<'tcx>, name: &str) -> bool {
+        if let TyKind::Adt(def, _) = ty.peel_refs().kind() {
+            if def.is_struct() {
+                return def.non_enum_variant().fields.iter().any(|f| f.name.as_str() == name);
+            }
+        }
+        false
+    }
+
+    /// Returns true if the place reads the key field of a struct, e.g. ``*account.key``, where the
+    /// key is a reference to the key as in AccountInfo.
+    fn is_key_read(&self, place: &mir::Place<'tcx>) -> bool {
+        let mut place_ref = place.as_ref();
+        while let Some((base, mir::ProjectionElem::Deref)) = place_ref.last_projection() {
+            place_ref = base;
+        }
+        let Some((base, mir::ProjectionElem::Field(field_index, _))) = place_ref.last_projection()
+        else {
+            return false;
+        };
+        let base_ty = base.ty(&self.bv.mir.local_decls, self.bv.tcx).ty;
+        if let TyKind::Adt(def, _) = base_ty.kind() {
+            if def.is_struct() {
+                return def.non_enum_variant().fields[field_index].name.as_str() == "key";
+            }
+        }
+        false
+    }
+
+    /// Returns the token account whose amount the place holds or refers to, e.g. the account
+    /// of ``source.amount``.
+    fn token_amount_account(&self, place: &mir::Place<'tcx>) -> Option<mir::Local> {
+        let checker = &self.bv.token_decimals_checker;
+        if self.field_name(place).as_deref() == Some("amount") {
+            return Some(checker.local_sources.source_of(place.local));
+        }
+        if !place
+            .projection
+            .iter()
+            .all(|elem| matches!(elem, mir::ProjectionElem::Deref))
+        {
+            return None;
+        }
+        checker.amounts.get(&place.local).copied()
+    }
+
+    /// Returns the mint whose decimals the place holds, e.g. the account of ``mint.decimals``.
+    fn decimals_account(&self, place: &mir::Place<'tcx>) -> Option<mir::Local> {
+        let checker = &self.bv.token_decimals_checker;
+        if self.field_name(place).as_deref() == Some("decimals") {
+            return Some(checker.local_sources.source_of(place.local));
+        }
+        if !place.projection.is_empty() {
+            return None;
+        }
+        checker.decimals.get(&place.local).copied()
+    }
+
+    /// Returns the account whose lamports the place holds or refers to, e.g. the account of
+    /// ``**lamports`` or of a copy of it, as the missing signer checker resolves the account.
+    fn lamports_value_account(&self, place: &mir::Place<'tcx>) -> Option<mir::Local> {
+        if place.projection.is_empty() {
+            return self.bv.missing_signer_checker.balances.get(&place.local).copied();
+        }
+        self.signer_lamports_account(place)
+    }
+
+    /// Like lamports_account, but with the account as the missing signer checker resolves it,
+    /// which tells the accounts of an Anchor Context apart.
+    fn signer_lamports_account(&self, place: &mir::Place<'tcx>) -> Option<mir::Local> {
+        if place.projection.is_empty()
+            || !place
+                .projection
+                .iter()
+                .all(|elem| matches!(elem, mir::ProjectionElem::Deref))
+        {
+            return None;
+        }
+        self.bv.missing_signer_checker.account_of(place.local)
+    }
+
+    /// Returns the balance that the place refers to, if the place dereferences a reference to the
+    /// value of a map or to the lamports of an account, e.g. ``*entry`` or ``**lamports``.
+    fn balance_of(&self, place: &mir::Place<'tcx>) -> Option<mir::Local> {
+        let checker = &self.bv.integer_overflow_checker;
+        if let Some(balance) = checker.balance_of(place) {
+            return Some(balance);
+        }
+        self.lamports_account(place)
+            .map(|_| checker.local_sources.source_of(place.local))
+    }
+
+    /// Returns the account whose lamports the place refers to, if the place dereferences a
+    /// reference to the lamports of an account, e.g. ``**lamports``.
+    fn lamports_account(&self, place: &mir::Place<'tcx>) -> Option<mir::Local> {
+        if place.projection.is_empty()
+            || !place
+                .projection
+                .iter()
+                .all(|elem| matches!(elem, mir::ProjectionElem::Deref))
+        {
+            return None;
+        }
+        self.bv.lamports_overwrite_checker.account_of(place.local)
+    }
+
+    /// Returns true if the type is, or refers to, a whole slice of bytes, such as the instruction
+    /// data or the data of an account, possibly wrapped in a cell or a smart pointer.
+    fn is_byte_buffer(&self, ty: Ty<'tcx>) -> bool {
+        let mut ty = ty;
+        loop {
+            match ty.kind() {
+                TyKind::Ref(_, referenced_ty, _) => ty = *referenced_ty,
+                // Wrappers such as Rc, RefCell, Ref and Vec hold the buffer as their first type argument
+                TyKind::Adt(def, args) if !def.did().is_local() => match args.types().next() {
+                    Some(wrapped_ty) => ty = wrapped_ty,
+                    None => return false,
+                },
+                TyKind::Slice(elem_ty) => {
+                    if matches!(elem_ty.kind(), TyKind::Uint(UintTy::U8)) {
+                        return true;
+                    }
+                    // Logging functions such as sol_log_data take a slice of buffers
+                    ty = *elem_ty;
+                }
+                _ => return false,
+            }
+        }
+    }
+
+    /// Returns true if the type is, or points to, a byte buffer or a byte array, which is the form
+    /// that account data takes before it is deserialized.
+    fn is_byte_data(&self, ty: Ty<'tcx>) -> bool {
+        if self.is_byte_buffer(ty) {
+            return true;
+        }
+        let mut ty = ty;
+        while let TyKind::Ref(_, pointee_ty, _) | TyKind::RawPtr(pointee_ty, _) = ty.kind() {
+            ty = *pointee_ty;
+        }
+        match ty.kind() {
+            TyKind::Array(elem_ty, _) | TyKind::Slice(elem_ty) => {
+                matches!(elem_ty.kind(), TyKind::Uint(UintTy::U8))
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true if bytes that are viewed as the type, or as the type it points to, are
+    /// reinterpreted. Bytes remain bytes, and a transparent wrapper has the layout of its field.
+    fn is_reinterpreted_type(&self, ty: Ty<'tcx>) -> bool {
+        if self.is_byte_data(ty) {
+            return false;
+        }
+        let mut ty = ty;
+        while let TyKind::Ref(_, pointee_ty, _) | TyKind::RawPtr(pointee_ty, _) = ty.kind() {
+            ty = *pointee_ty;
+        }
+        match ty.kind() {
+            TyKind::Uint(UintTy::U8) => false,
+            TyKind::Adt(def, _) => !def.repr().transparent(),
+            _ => true,
+        }
+    }
+
     /// Jump to the target if the condition has the expected value,
     /// otherwise panic with a message and a cleanup target.
     #[logfn_inputs(TRACE)]
@@ -1567,6 +3687,37 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
         target: mir::BasicBlock,
         unwind: mir::UnwindAction,
     ) {
+        // Indexing a slice is bounds checked against its length
+        if let mir::AssertKind::BoundsCheck { len, index } = msg {
+            if let Some(length) = len.place() {
+                self.record_tainted_slices();
+                self.bv.slice_panic_checker.indexing_operations.insert(
+                    self.bv.current_location,
+                    (self.bv.current_span, length.local),
+                );
+                let tainted_slices = self.bv.slice_panic_checker.tainted_slices.clone();
+                let checker = &mut self.bv.unchecked_first_byte_checker;
+                checker.instruction_data.extend(tainted_slices);
+                checker.bounds_check_lengths.insert(length.local);
+                self.bv
+                    .unchecked_deserialize_checker
+                    .bounds_check_lengths
+                    .insert(length.local);
+                let is_first_byte = match index {
+                    mir::Operand::Constant(constant) => self.is_zero_constant(constant),
+                    mir::Operand::Copy(index) | mir::Operand::Move(index) => {
+                        let checker = &self.bv.unchecked_first_byte_checker;
+                        checker.zero_locals.contains(&checker.local_sources.source_of(index.local))
+                    }
+                };
+                if is_first_byte {
+                    self.bv.unchecked_first_byte_checker.first_byte_accesses.insert(
+                        self.bv.current_location,
+                        (self.bv.current_span, length.local),
+                    );
+                }
+            }
+        }
         // Propagate the entry condition to the successor blocks, conjoined with cond (or !cond).
         let cond_val = self.visit_operand(cond);
         let not_cond_val = cond_val.logical_not();
@@ -1859,20 +4010,9 @@ impl<'block, 'analysis, 'compilation, 'tcx> BlockVisitor<'block, 'analysis, 'com
     fn visit_use(&mut self, path: Rc<Path>, operand: &mir::Operand<'tcx>) {
         match operand {
             mir::Operand::Copy(place) => {
-                if self.bv.reentrancy_checker.check_for_balance_variable && self.bv.reentrancy_checker.temporary_variable_for_balance == Some(*place) {
-                    self.bv.reentrancy_checker.temporary_variable_for_balance = self.bv.reentrancy_checker.current_assign_destination;
-                }
                 self.visit_used_copy(path, place);
             }
             mir::Operand::Move(place) => {
-                if self.bv.reentrancy_checker.check_for_balance_variable {
-                    if let Some(temporary_place) = self.bv.reentrancy_checker.temporary_variable_for_balance {
-                        if *place == temporary_place || place.local == temporary_place.local {
-                            self.bv.reentrancy_checker.temporary_variable_for_balance = self.bv.reentrancy_checker.current_assign_destination;
-                            self.bv.reentrancy_checker.check_for_balance_variable = false;
-                        }
-                    }
-                }
                 self.visit_used_move(path, place);
             }
             mir::Operand::Constant(constant) => {
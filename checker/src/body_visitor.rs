@@ -14,28 +14,47 @@ use rpds::HashTrieMap;
 
 use mirai_annotations::*;
 use rustc_errors::Diag;
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_middle::mir;
 use rustc_middle::ty::{AdtDef, Const, GenericArgsRef, Ty, TyCtxt, TyKind, UintTy};
 
 use crate::abstract_value::{self, AbstractValue, AbstractValueTrait, BOTTOM};
+use crate::anchor::AnchorContext;
 use crate::block_visitor::BlockVisitor;
 use crate::call_visitor::CallVisitor;
 use crate::constant_domain::ConstantDomain;
-use crate::contract_errors::{BadrandomnessChecker, NumericalPrecisionErrorChecker, ReentrancyChecker, TimeManipulationChecker};
+use crate::contract_errors::{
+    checker_confidence, registered_checkers, AccountSubstitutionChecker, ArbitraryCpiChecker,
+    Checker, ClockAccountReadChecker, ClockPayoutChecker, CloseAuthorityChecker,
+    DistinctAccountsChecker, EntrypointPanicChecker, EphemeralStateChecker, IntegerOverflowChecker,
+    IsWritableChecker, LamportsAuthorizationChecker, LamportsDoubleBorrowChecker,
+    LamportsOverwriteChecker, LoggedDataChecker, ManualRentChecker, MissingSignerChecker,
+    NonPersistentStateChecker, OkAfterErrorLogChecker, OverPrivilegedAccountChecker,
+    OwnerCheckChecker, PanicReachabilityChecker, PdaBumpChecker, RentExemptionChecker,
+    SaturatingArithmeticChecker, SeedLimitsChecker, SlicePanicChecker, StackUsageChecker,
+    SwallowedCpiErrorChecker, TokenDecimalsChecker, TruncatingCastChecker,
+    UnboundedAccountWriteChecker, UncheckedCpiResultChecker, UncheckedDeserializeChecker,
+    UncheckedFirstByteChecker, UnguardedTransferHelperChecker, UnsafeDataCastChecker,
+    UnvalidatedCountChecker, UnvalidatedLoopAccountChecker, UnverifiedPdaChecker,
+};
 use crate::crate_visitor::CrateVisitor;
 use crate::environment::Environment;
 use crate::expression::{Expression, ExpressionType, LayoutSource};
 use crate::fixed_point_visitor::FixedPointVisitor;
+use crate::mir_walker::ResolvedCallee;
 use crate::options::DiagLevel;
 use crate::path::{Path, PathEnum, PathSelector};
 use crate::path::{PathRefinement, PathRoot};
+use crate::report::{
+    Confidence, Finding, ReentrancyEvidence, Severity, SuppressedFinding, SuppressionSource,
+};
 #[cfg(not(feature = "z3"))]
 use crate::smt_solver::SolverStub;
 use crate::smt_solver::{SmtResult, SmtSolver};
 use crate::summaries;
 use crate::summaries::{Precondition, Summary};
 use crate::tag_domain::Tag;
+use crate::taint_analysis::TaintAnalysis;
 use crate::type_visitor::{self, TypeCache, TypeVisitor};
 #[cfg(feature = "z3")]
 use crate::z3_solver::Z3Solver;
@@ -81,14 +100,55 @@ pub struct BodyVisitor<'analysis, 'compilation, 'tcx> {
     pub smt_solver: SolverStub,
     #[cfg(feature = "z3")]
     pub smt_solver: Z3Solver,
-    pub block_to_call: HashMap<mir::Location, DefId>,
+    pub block_to_call: HashMap<mir::Location, (DefId, GenericArgsRef<'tcx>)>,
+    // The callees of the calls of the body as visit_call resolved them, see MirWalker
+    pub resolved_callees: HashMap<mir::Location, ResolvedCallee>,
     pub treat_as_foreign: bool,
     type_visitor: TypeVisitor<'tcx>,
     // Vulnerability detection for smart contracts
-    pub reentrancy_checker: ReentrancyChecker<'tcx>,
-    pub time_manipulation_checker: TimeManipulationChecker,
-    pub bad_randomness_checker: BadrandomnessChecker,
-    pub numerical_precision_checker: NumericalPrecisionErrorChecker
+    pub saturating_arithmetic_checker: SaturatingArithmeticChecker,
+    pub clock_account_read_checker: ClockAccountReadChecker,
+    pub slice_panic_checker: SlicePanicChecker,
+    pub distinct_accounts_checker: DistinctAccountsChecker,
+    pub logged_data_checker: LoggedDataChecker,
+    pub over_privileged_account_checker: OverPrivilegedAccountChecker,
+    pub ephemeral_state_checker: EphemeralStateChecker,
+    pub unsafe_data_cast_checker: UnsafeDataCastChecker,
+    pub lamports_overwrite_checker: LamportsOverwriteChecker,
+    pub token_decimals_checker: TokenDecimalsChecker,
+    pub entrypoint_panic_checker: EntrypointPanicChecker,
+    pub lamports_authorization_checker: LamportsAuthorizationChecker,
+    pub unguarded_transfer_helper_checker: UnguardedTransferHelperChecker,
+    pub manual_rent_checker: ManualRentChecker,
+    pub swallowed_cpi_error_checker: SwallowedCpiErrorChecker,
+    pub unvalidated_loop_account_checker: UnvalidatedLoopAccountChecker,
+    pub unchecked_first_byte_checker: UncheckedFirstByteChecker,
+    pub unverified_pda_checker: UnverifiedPdaChecker,
+    pub unvalidated_count_checker: UnvalidatedCountChecker,
+    pub ok_after_error_log_checker: OkAfterErrorLogChecker,
+    pub stack_usage_checker: StackUsageChecker,
+    pub lamports_double_borrow_checker: LamportsDoubleBorrowChecker,
+    pub seed_limits_checker: SeedLimitsChecker,
+    pub unbounded_account_write_checker: UnboundedAccountWriteChecker,
+    pub clock_payout_checker: ClockPayoutChecker,
+    pub missing_signer_checker: MissingSignerChecker,
+    pub owner_check_checker: OwnerCheckChecker,
+    pub integer_overflow_checker: IntegerOverflowChecker,
+    pub non_persistent_state_checker: NonPersistentStateChecker,
+    pub arbitrary_cpi_checker: ArbitraryCpiChecker,
+    pub pda_bump_checker: PdaBumpChecker,
+    pub close_authority_checker: CloseAuthorityChecker,
+    pub truncating_cast_checker: TruncatingCastChecker,
+    pub rent_exemption_checker: RentExemptionChecker,
+    pub is_writable_checker: IsWritableChecker,
+    pub account_substitution_checker: AccountSubstitutionChecker,
+    pub panic_reachability_checker: PanicReachabilityChecker,
+    pub unchecked_cpi_result_checker: UncheckedCpiResultChecker,
+    pub unchecked_deserialize_checker: UncheckedDeserializeChecker,
+    // The checkers that implement the Checker trait, which the MirWalker dispatches to
+    pub checkers: Vec<Box<dyn Checker<'tcx> + 'tcx>>,
+    pub taint_analysis: TaintAnalysis,
+    pub anchor_context: AnchorContext<'tcx>,
 }
 
 impl Debug for BodyVisitor<'_, '_, '_> {
@@ -127,6 +187,16 @@ impl<'analysis, 'compilation, 'tcx> BodyVisitor<'analysis, 'compilation, 'tcx> {
             tcx.instance_mir(instance)
         };
         crate_visitor.call_graph.add_root(def_id);
+        let caller_count = crate_visitor
+            .callers
+            .get(&def_id)
+            .map_or(0, |callers| callers.len());
+        // The handler of entrypoint! is called by the function that the macro declares
+        let is_entrypoint_handler = crate_visitor.callers.get(&def_id).is_some_and(|callers| {
+            callers
+                .iter()
+                .any(|caller| utils::is_solana_entrypoint(tcx, *caller))
+        });
         BodyVisitor {
             cv: crate_visitor,
             tcx,
@@ -156,12 +226,51 @@ impl<'analysis, 'compilation, 'tcx> BodyVisitor<'analysis, 'compilation, 'tcx> {
             fresh_variable_offset: 0,
             smt_solver: Self::get_solver(),
             block_to_call: HashMap::default(),
+            resolved_callees: HashMap::default(),
             treat_as_foreign: false,
             type_visitor: TypeVisitor::new(def_id, mir, tcx, type_cache),
-            reentrancy_checker: ReentrancyChecker::new(),
-            time_manipulation_checker: TimeManipulationChecker::new(),
-            bad_randomness_checker: BadrandomnessChecker::new(),
-            numerical_precision_checker: NumericalPrecisionErrorChecker::new()
+            saturating_arithmetic_checker: SaturatingArithmeticChecker::new(),
+            clock_account_read_checker: ClockAccountReadChecker::new(),
+            slice_panic_checker: SlicePanicChecker::new(),
+            distinct_accounts_checker: DistinctAccountsChecker::new(),
+            logged_data_checker: LoggedDataChecker::new(),
+            over_privileged_account_checker: OverPrivilegedAccountChecker::new(),
+            ephemeral_state_checker: EphemeralStateChecker::new(),
+            unsafe_data_cast_checker: UnsafeDataCastChecker::new(),
+            lamports_overwrite_checker: LamportsOverwriteChecker::new(),
+            token_decimals_checker: TokenDecimalsChecker::new(),
+            entrypoint_panic_checker: EntrypointPanicChecker::new(is_entrypoint_handler),
+            lamports_authorization_checker: LamportsAuthorizationChecker::new(),
+            unguarded_transfer_helper_checker: UnguardedTransferHelperChecker::new(caller_count),
+            manual_rent_checker: ManualRentChecker::new(),
+            swallowed_cpi_error_checker: SwallowedCpiErrorChecker::new(),
+            unvalidated_loop_account_checker: UnvalidatedLoopAccountChecker::new(),
+            unchecked_first_byte_checker: UncheckedFirstByteChecker::new(),
+            unverified_pda_checker: UnverifiedPdaChecker::new(),
+            unvalidated_count_checker: UnvalidatedCountChecker::new(),
+            ok_after_error_log_checker: OkAfterErrorLogChecker::new(),
+            stack_usage_checker: StackUsageChecker::new(),
+            lamports_double_borrow_checker: LamportsDoubleBorrowChecker::new(),
+            seed_limits_checker: SeedLimitsChecker::new(),
+            unbounded_account_write_checker: UnboundedAccountWriteChecker::new(),
+            clock_payout_checker: ClockPayoutChecker::new(),
+            missing_signer_checker: MissingSignerChecker::new(),
+            owner_check_checker: OwnerCheckChecker::new(),
+            integer_overflow_checker: IntegerOverflowChecker::new(),
+            non_persistent_state_checker: NonPersistentStateChecker::new(),
+            arbitrary_cpi_checker: ArbitraryCpiChecker::new(),
+            pda_bump_checker: PdaBumpChecker::new(),
+            close_authority_checker: CloseAuthorityChecker::new(),
+            truncating_cast_checker: TruncatingCastChecker::new(),
+            rent_exemption_checker: RentExemptionChecker::new(),
+            is_writable_checker: IsWritableChecker::new(),
+            account_substitution_checker: AccountSubstitutionChecker::new(),
+            panic_reachability_checker: PanicReachabilityChecker::new(),
+            unchecked_cpi_result_checker: UncheckedCpiResultChecker::new(),
+            unchecked_deserialize_checker: UncheckedDeserializeChecker::new(),
+            checkers: registered_checkers(&crate_visitor.options),
+            taint_analysis: TaintAnalysis::new(tcx, mir),
+            anchor_context: AnchorContext::new(tcx, mir),
         }
     }
 
@@ -184,6 +293,7 @@ impl<'analysis, 'compilation, 'tcx> BodyVisitor<'analysis, 'compilation, 'tcx> {
         self.preconditions = Vec::new();
         self.fresh_variable_offset = 1000;
         self.block_to_call = HashMap::default();
+        self.resolved_callees = HashMap::default();
         self.type_visitor_mut().reset_visitor_state();
     }
 
@@ -307,10 +417,15 @@ impl<'analysis, 'compilation, 'tcx> BodyVisitor<'analysis, 'compilation, 'tcx> {
 
         // Compute dominance information for calls
         let dominators = self.mir.basic_blocks.dominators();
-        for (location1, callee_defid1) in self.block_to_call.iter() {
-            for (location2, callee_defid2) in self.block_to_call.iter() {
+        for (location1, (callee_defid1, callee_args1)) in self.block_to_call.iter() {
+            for (location2, (callee_defid2, callee_args2)) in self.block_to_call.iter() {
                 if location1 != location2 && location1.dominates(*location2, dominators) {
-                    self.cv.call_graph.add_dom(*callee_defid1, *callee_defid2);
+                    self.cv.call_graph.add_dom(
+                        *callee_defid1,
+                        *callee_args1,
+                        *callee_defid2,
+                        *callee_args2,
+                    );
                 }
             }
         }
@@ -344,14 +459,15 @@ impl<'analysis, 'compilation, 'tcx> BodyVisitor<'analysis, 'compilation, 'tcx> {
     /// Adds the given diagnostic builder to the buffer.
     /// Buffering diagnostics gives us the chance to sort them before printing them out,
     /// which is desirable for tools that compare the diagnostics from one run of MIRAI with another.
+    /// Returns false if the diagnostic was cancelled rather than buffered.
     #[logfn_inputs(TRACE)]
-    pub fn emit_diagnostic(&mut self, diagnostic_builder: Diag<'compilation, ()>) {
+    pub fn emit_diagnostic(&mut self, diagnostic_builder: Diag<'compilation, ()>) -> bool {
         if (self.treat_as_foreign || !self.def_id.is_local())
             && !matches!(self.cv.options.diag_level, DiagLevel::Paranoid)
         {
             // only give diagnostics in code that belongs to the crate being analyzed
             diagnostic_builder.cancel();
-            return;
+            return false;
         }
         // Do not emit diagnostics for code generated by derive macros since it is currently
         // unlikely that the end user of the diagnostic will be able to anything about it.
@@ -359,15 +475,167 @@ impl<'analysis, 'compilation, 'tcx> BodyVisitor<'analysis, 'compilation, 'tcx> {
             if span.in_derive_expansion() {
                 info!("derive macro has warning: {:?}", diagnostic_builder);
                 diagnostic_builder.cancel();
-                return;
+                return false;
             }
         }
         let call_depth = *self.active_calls_map.get(&self.def_id).unwrap_or(&0u64);
         if call_depth > 1 {
             diagnostic_builder.cancel();
-            return;
+            return false;
         }
         self.buffered_diagnostics.push(diagnostic_builder);
+        true
+    }
+
+    /// Emits a warning issued by the named smart contract checker and, unless the warning was
+    /// cancelled, records it as a finding of the crate being analyzed.
+    /// Findings suppressed by the user are not emitted, but kept aside if they are to be shown.
+    pub fn emit_contract_warning(
+        &mut self,
+        checker_name: &'static str,
+        severity: Severity,
+        span: rustc_span::Span,
+        warning_message: &'static str,
+    ) {
+        self.emit_contract_warning_with_related(checker_name, severity, span, warning_message, &[]);
+    }
+
+    /// Like emit_contract_warning, but the finding also refers to the given related locations,
+    /// each with what it has to do with the finding.
+    pub fn emit_contract_warning_with_related(
+        &mut self,
+        checker_name: &'static str,
+        severity: Severity,
+        span: rustc_span::Span,
+        warning_message: &'static str,
+        related: &[(rustc_span::Span, &str)],
+    ) {
+        self.emit_contract_warning_with_confidence(
+            checker_name,
+            severity,
+            checker_confidence(checker_name),
+            span,
+            warning_message,
+            related,
+        );
+    }
+
+    /// Like emit_contract_warning_with_related, but with the confidence of this particular finding
+    /// rather than the confidence of its checker.
+    /// Findings that are less confident than --min-confidence are neither emitted nor recorded.
+    pub fn emit_contract_warning_with_confidence(
+        &mut self,
+        checker_name: &'static str,
+        severity: Severity,
+        confidence: Confidence,
+        span: rustc_span::Span,
+        warning_message: &'static str,
+        related: &[(rustc_span::Span, &str)],
+    ) {
+        self.emit_contract_warning_with_evidence(
+            checker_name,
+            severity,
+            confidence,
+            span,
+            warning_message,
+            related,
+            None,
+        );
+    }
+
+    /// Like emit_contract_warning_with_confidence, but a reentrancy finding also records the
+    /// spans of the load, transfer and store that make up the reentrancy, if they are known.
+    #[allow(clippy::too_many_arguments)]
+    pub fn emit_contract_warning_with_evidence(
+        &mut self,
+        checker_name: &'static str,
+        severity: Severity,
+        confidence: Confidence,
+        span: rustc_span::Span,
+        warning_message: &'static str,
+        related: &[(rustc_span::Span, &str)],
+        evidence: Option<(rustc_span::Span, rustc_span::Span, rustc_span::Span)>,
+    ) {
+        // The smart contract checkers are only concerned with the code of the crate being analyzed
+        if self.treat_as_foreign || !self.def_id.is_local() {
+            return;
+        }
+        if !self.cv.options.is_checker_enabled(checker_name) {
+            return;
+        }
+        if self
+            .cv
+            .options
+            .min_confidence
+            .is_some_and(|min_confidence| confidence < min_confidence)
+        {
+            return;
+        }
+        // The severity given on the command line takes precedence over the one of the checker
+        let severity = self
+            .cv
+            .options
+            .rule_severities
+            .get(checker_name)
+            .copied()
+            .unwrap_or(severity);
+        let crate_name = self.tcx.crate_name(LOCAL_CRATE);
+        let source_map = self.cv.session.source_map();
+        let finding = Finding::new(
+            crate_name.as_str(),
+            checker_name,
+            severity,
+            source_map,
+            span,
+            warning_message,
+        )
+        .with_confidence(confidence)
+        .with_fingerprint(&self.tcx.def_path_str(self.def_id), source_map, span)
+        .with_help_uri(self.cv.options.rule_doc_url.as_deref())
+        .with_related(source_map, related)
+        .with_evidence(evidence.map(|(load, transfer, store)| {
+            ReentrancyEvidence::new(source_map, load, transfer, store)
+        }));
+        // A pattern that matches in several blocks of the body is only reported for its first block
+        let mut reported_findings = self.cv.findings.iter().chain(
+            self.cv
+                .suppressed_findings
+                .iter()
+                .map(|suppressed| &suppressed.finding),
+        );
+        if reported_findings.any(|reported| finding.is_duplicate_of(reported)) {
+            return;
+        }
+        if let Some(source) =
+            self.cv
+                .suppressions
+                .suppression_for(self.tcx, source_map, self.def_id, span, &finding)
+        {
+            // The findings that are already in the baseline stay in a baseline written from it
+            if source == SuppressionSource::Baseline && self.cv.options.write_baseline.is_some() {
+                self.cv.baseline_findings.push(finding.clone());
+            }
+            if self.cv.options.show_suppressed {
+                self.cv
+                    .suppressed_findings
+                    .push(SuppressedFinding { finding, source });
+            }
+            return;
+        }
+        let warning = self.cv.session.dcx().struct_span_warn(span, warning_message);
+        if self.emit_diagnostic(warning) {
+            // When the crate is analyzed again in watch mode, only the changed findings are printed
+            if self.cv.previous_findings.is_some() {
+                if let Some(warning) = self.buffered_diagnostics.pop() {
+                    warning.cancel();
+                }
+            }
+            if self.cv.options.write_baseline.is_some() {
+                self.cv.baseline_findings.push(finding.clone());
+            }
+            self.cv.findings.push(finding);
+            self.cv.finding_functions.push(self.def_id);
+        }
     }
 
     pub fn get_char_const_val(&mut self, val: u128) -> Rc<AbstractValue> {
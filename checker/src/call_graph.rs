@@ -9,9 +9,10 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::path::Path;
 
+use petgraph::algo::tarjan_scc;
 use petgraph::dot::{Config, Dot};
 use petgraph::graph::{DefaultIx, NodeIndex};
-use petgraph::visit::Bfs;
+use petgraph::visit::{Bfs, EdgeRef};
 use petgraph::{Direction, Graph};
 use regex::Regex;
 use serde::ser::{SerializeMap, Serializer};
@@ -19,9 +20,11 @@ use serde::{Deserialize, Serialize};
 
 use mirai_annotations::*;
 use rustc_hir::def_id::DefId;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{GenericArgKind, GenericArgsRef, Instance, TyCtxt};
 use rustc_span::Span;
 
+use crate::report::{Finding, Severity};
+
 // An unique identifier for a Rust type string.
 type TypeId = u32;
 
@@ -51,10 +54,20 @@ pub enum CallGraphReduction {
     /// Remove nodes that have no incoming or outgoing edges.
     /// See `CallGraph::filter_no_edges`.
     Clean,
+    /// Replace each strongly connected component of recursive functions
+    /// by a single node that is labeled with the names of its members.
+    /// See `CallGraph::collapse_sccs`.
+    CollapseSccs,
+    /// Remove nodes that cannot be reached from an entry point, which is
+    /// `main`, or the function declared by `entrypoint!` if there is no `main`,
+    /// unless `DatalogConfig.entry_points` lists other functions.
+    /// See `CallGraph::prune_unreachable`.
+    PruneUnreachable,
 }
 
 /// Configuration options for Datalog output
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct DatalogConfig {
     /// Specifies location for graph to be output as Datalog input relations.
     ddlog_output_path: Box<str>,
@@ -66,6 +79,11 @@ pub struct DatalogConfig {
     /// Datalog output backend to use.
     /// Currently, Differential Datalog and Soufflé are supported.
     datalog_backend: DatalogBackend,
+    /// The names of the functions from which the PruneUnreachable reduction
+    /// keeps the reachable nodes. If empty, `main` is the only entry point,
+    /// or the function declared by `entrypoint!` for a Solana program without `main`.
+    #[serde(default)]
+    entry_points: Vec<Box<str>>,
 }
 
 impl DatalogConfig {
@@ -74,12 +92,14 @@ impl DatalogConfig {
         type_map_output_path: Box<str>,
         type_relations_path: Option<Box<str>>,
         datalog_backend: DatalogBackend,
+        entry_points: Vec<Box<str>>,
     ) -> DatalogConfig {
         DatalogConfig {
             ddlog_output_path,
             type_map_output_path,
             type_relations_path,
             datalog_backend,
+            entry_points,
         }
     }
 
@@ -98,10 +118,15 @@ impl DatalogConfig {
     pub fn get_datalog_backend(&self) -> DatalogBackend {
         self.datalog_backend
     }
+
+    pub fn get_entry_points(&self) -> &[Box<str>] {
+        &self.entry_points
+    }
 }
 
 /// Configuration options for call graph generation.
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
 pub struct CallGraphConfig {
     /// Optionally specified location for graph to be output as
     /// (call-site, caller, callee) triples, along with supporting tables.
@@ -109,6 +134,9 @@ pub struct CallGraphConfig {
     /// Optionally specifies location for graph to be output in dot format
     /// (for Graphviz).
     dot_output_path: Option<Box<str>>,
+    /// Optionally specifies location for the entry points and leaves of the graph
+    /// to be output as JSON. If specified, they are also annotated in the dot output.
+    entry_points_output_path: Option<Box<str>>,
     /// A list of call graph reductions to apply sequentially
     /// to the call graph.
     reductions: Vec<CallGraphReduction>,
@@ -119,12 +147,17 @@ pub struct CallGraphConfig {
     datalog_config: Option<DatalogConfig>,
     /// If true, collect all call sites.
     pub include_calls_in_summaries: bool,
+    /// If true, collect the edges of the graph even if no graph output is configured,
+    /// since the findings refer to the paths of the graph.
+    #[serde(default)]
+    pub include_edges_for_findings: bool,
 }
 
 impl CallGraphConfig {
     pub fn new(
         call_sites_output_path: Option<Box<str>>,
         dot_output_path: Option<Box<str>>,
+        entry_points_output_path: Option<Box<str>>,
         reductions: Vec<CallGraphReduction>,
         included_crates: Vec<Box<str>>,
         datalog_config: Option<DatalogConfig>,
@@ -132,10 +165,12 @@ impl CallGraphConfig {
         CallGraphConfig {
             call_sites_output_path,
             dot_output_path,
+            entry_points_output_path,
             reductions,
             included_crates,
             datalog_config,
             include_calls_in_summaries: false,
+            include_edges_for_findings: false,
         }
     }
 
@@ -147,6 +182,10 @@ impl CallGraphConfig {
         self.dot_output_path.as_deref()
     }
 
+    pub fn get_entry_points_path(&self) -> Option<&str> {
+        self.entry_points_output_path.as_deref()
+    }
+
     pub fn get_ddlog_path(&self) -> Option<&str> {
         self.datalog_config
             .as_ref()
@@ -164,8 +203,22 @@ impl CallGraphConfig {
             .as_ref()
             .map(|config| config.get_datalog_backend())
     }
+
+    /// Strictly validates the call graph config in the JSON file at the given path.
+    /// Returns the error of parsing the config, e.g. for a key that the config does not have,
+    /// which serde reports along with the keys that it may have, or for an unknown enum value.
+    pub fn check_file(path_to_config: &Path) -> Result<(), String> {
+        let config_str = fs::read_to_string(path_to_config)
+            .map_err(|e| format!("could not read the config: {e}"))?;
+        serde_json::from_str::<CallGraphConfig>(&config_str)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
 }
 
+/// The name of the function that the `entrypoint!` macro of `solana_program` declares.
+const SOLANA_ENTRYPOINT_NAME: &str = "entrypoint";
+
 /// The type of a call graph node.
 #[derive(Debug, Clone, Eq, PartialEq)]
 enum NodeType {
@@ -178,29 +231,64 @@ enum NodeType {
 /// Nodes in the call graph are functions defined by the program
 /// that is being analyzed.
 #[derive(Debug, Clone)]
-struct CallGraphNode {
+struct CallGraphNode<'tcx> {
     /// The DefId of the function
     defid: DefId,
-    /// The name of the function (derived from its DefId).
+    /// The instance of the function, which has the arguments of a generic function.
+    instance: Instance<'tcx>,
+    /// The name of the function (derived from its instance).
     name: Box<str>,
     /// The type of the node.
     node_type: NodeType,
+    /// If the node is a collapsed strongly connected component, the ids
+    /// that its members had in the graph that was collapsed.
+    scc_members: Vec<u32>,
 }
 
-impl CallGraphNode {
-    pub fn new_croot(defid: DefId) -> CallGraphNode {
+impl<'tcx> CallGraphNode<'tcx> {
+    pub fn new_croot(instance: Instance<'tcx>) -> CallGraphNode<'tcx> {
         CallGraphNode {
-            defid,
-            name: CallGraphNode::format_name(defid),
+            defid: instance.def_id(),
+            instance,
+            name: CallGraphNode::format_instance_name(instance),
             node_type: NodeType::CRoot,
+            scc_members: Vec::new(),
         }
     }
 
-    pub fn new_root(defid: DefId) -> CallGraphNode {
+    pub fn new_root(instance: Instance<'tcx>) -> CallGraphNode<'tcx> {
         CallGraphNode {
-            defid,
-            name: CallGraphNode::format_name(defid),
+            defid: instance.def_id(),
+            instance,
+            name: CallGraphNode::format_instance_name(instance),
             node_type: NodeType::Root,
+            scc_members: Vec::new(),
+        }
+    }
+
+    /// A node for a strongly connected component of the graph, which takes the instance of its
+    /// first member and is named after all of its members.
+    /// It is a crate root if one of its members is.
+    pub fn new_scc(members: &[(NodeId, &CallGraphNode<'tcx>)]) -> CallGraphNode<'tcx> {
+        let node_type = if members.iter().any(|(_, node)| node.is_croot()) {
+            NodeType::CRoot
+        } else {
+            NodeType::Root
+        };
+        CallGraphNode {
+            defid: members[0].1.defid,
+            instance: members[0].1.instance,
+            name: members
+                .iter()
+                .map(|(_, node)| &*node.name)
+                .collect::<Vec<&str>>()
+                .join(", ")
+                .into_boxed_str(),
+            node_type,
+            scc_members: members
+                .iter()
+                .map(|(node_id, _)| node_id.index() as u32)
+                .collect(),
         }
     }
 
@@ -214,6 +302,23 @@ impl CallGraphNode {
         format!("{lhs}{rhs}").into_boxed_str()
     }
 
+    /// Extracts a function name from the instance of a function, followed by
+    /// the type and constant arguments of the instance, if it has any.
+    fn format_instance_name(instance: Instance<'tcx>) -> Box<str> {
+        let name = CallGraphNode::format_name(instance.def_id());
+        let arguments = instance
+            .args
+            .iter()
+            .filter(|arg| !matches!(arg.unpack(), GenericArgKind::Lifetime(_)))
+            .map(|arg| arg.to_string())
+            .collect::<Vec<String>>();
+        if arguments.is_empty() {
+            name
+        } else {
+            format!("{name}::<{}>", arguments.join(", ")).into_boxed_str()
+        }
+    }
+
     /// A node is excluded if its name does not include any
     /// one of the included crates' names.
     pub fn is_excluded(&self, included_crates: &[&str]) -> bool {
@@ -277,6 +382,10 @@ const COLLECTION_TYPES: &[&str] = &[
     "std::vec::Vec",
 ];
 
+/// The prefix of the type of an edge for a call through a vtable, which keeps the type of a virtual
+/// edge distinct from the type of a static edge that passes the same data.
+const VIRTUAL_EDGE_TYPE_PREFIX: &str = "virtual ";
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum SimpleType {
     Base(Box<str>),
@@ -329,13 +438,15 @@ pub struct CallGraph<'tcx> {
     /// an analysis root.
     call_sites: HashMap<rustc_span::Span, (DefId, DefId)>,
     /// The graph structure capturing calls between nodes
-    graph: Graph<CallGraphNode, CallGraphEdge>,
-    /// A map from DefId to node information
-    nodes: HashMap<DefId, NodeId>,
+    graph: Graph<CallGraphNode<'tcx>, CallGraphEdge>,
+    /// A map from the instance of a function to its node.
+    /// A generic function has a node for each of its concrete instances,
+    /// while any other function has a node for its instance without arguments.
+    nodes: HashMap<Instance<'tcx>, NodeId>,
     /// A map from type string to an EdgeType instance
     edge_types: HashMap<Box<str>, EdgeType>,
     /// Dominance information
-    dominance: HashMap<DefId, HashSet<DefId>>,
+    dominance: HashMap<Instance<'tcx>, HashSet<Instance<'tcx>>>,
 }
 
 impl<'tcx> CallGraph<'tcx> {
@@ -349,10 +460,10 @@ impl<'tcx> CallGraph<'tcx> {
             tcx,
             non_local_defs: HashSet::new(),
             call_sites: HashMap::new(),
-            graph: Graph::<CallGraphNode, CallGraphEdge>::new(),
-            nodes: HashMap::<DefId, NodeId>::new(),
+            graph: Graph::<CallGraphNode<'tcx>, CallGraphEdge>::new(),
+            nodes: HashMap::<Instance<'tcx>, NodeId>::new(),
             edge_types: HashMap::<Box<str>, EdgeType>::new(),
-            dominance: HashMap::<DefId, HashSet<DefId>>::new(),
+            dominance: HashMap::<Instance<'tcx>, HashSet<Instance<'tcx>>>::new(),
         }
     }
 
@@ -369,12 +480,14 @@ impl<'tcx> CallGraph<'tcx> {
     }
 
     pub fn needs_edges(&self) -> bool {
-        self.config.dot_output_path.is_some() || self.config.datalog_config.is_some()
+        self.config.dot_output_path.is_some()
+            || self.config.datalog_config.is_some()
+            || self.config.include_edges_for_findings
     }
 
     /// Produce an updated call graph structure that preserves all the
     /// fields except `graph`, which is replaced.
-    fn update(&self, graph: Graph<CallGraphNode, CallGraphEdge>) -> CallGraph<'tcx> {
+    fn update(&self, graph: Graph<CallGraphNode<'tcx>, CallGraphEdge>) -> CallGraph<'tcx> {
         CallGraph {
             config: self.config.clone(),
             tcx: self.tcx,
@@ -387,10 +500,33 @@ impl<'tcx> CallGraph<'tcx> {
         }
     }
 
+    /// The instance of `defid` that identifies its node, given the `generic_args` of a call or of
+    /// the body that is analyzed. Only a generic function that is instantiated with concrete
+    /// arguments has a node for the arguments, so that each of its instances gets its own node.
+    fn get_instance(
+        &self,
+        defid: DefId,
+        generic_args: Option<GenericArgsRef<'tcx>>,
+    ) -> Instance<'tcx> {
+        match generic_args {
+            Some(args)
+                if self
+                    .tcx
+                    .generics_of(defid)
+                    .requires_monomorphization(self.tcx)
+                    && crate::utils::are_concrete(args) =>
+            {
+                Instance::new(defid, args)
+            }
+            _ => Instance::new(defid, self.tcx.mk_args(&[])),
+        }
+    }
+
     /// Add a new crate root node to the call graph.
     pub fn add_croot(&mut self, defid: DefId) {
-        let croot = CallGraphNode::new_croot(defid);
-        match self.nodes.entry(defid) {
+        let instance = self.get_instance(defid, None);
+        let croot = CallGraphNode::new_croot(instance);
+        match self.nodes.entry(instance) {
             Entry::Occupied(node) => {
                 // Replace non-croot existing node
                 let node_id = node.get().to_owned();
@@ -406,9 +542,19 @@ impl<'tcx> CallGraph<'tcx> {
     }
 
     /// Add a new root node to the call graph.
+    /// A generic function only gets nodes for its instances, which are added with the edges
+    /// that call them.
     pub fn add_root(&mut self, defid: DefId) {
-        if let Entry::Vacant(e) = self.nodes.entry(defid) {
-            let croot = CallGraphNode::new_root(defid);
+        if self
+            .tcx
+            .generics_of(defid)
+            .requires_monomorphization(self.tcx)
+        {
+            return;
+        }
+        let instance = self.get_instance(defid, None);
+        if let Entry::Vacant(e) = self.nodes.entry(instance) {
+            let croot = CallGraphNode::new_root(instance);
             let node_id = self.graph.add_node(croot);
             e.insert(node_id);
         }
@@ -416,11 +562,11 @@ impl<'tcx> CallGraph<'tcx> {
 
     /// Helper function to get a node or insert a new
     /// root node if it does not exist in the map.
-    fn get_or_insert_node(&mut self, defid: DefId) -> NodeId {
-        match self.nodes.entry(defid) {
+    fn get_or_insert_node(&mut self, instance: Instance<'tcx>) -> NodeId {
+        match self.nodes.entry(instance) {
             Entry::Occupied(node) => node.get().to_owned(),
             Entry::Vacant(v) => {
-                let node_id = self.graph.add_node(CallGraphNode::new_root(defid));
+                let node_id = self.graph.add_node(CallGraphNode::new_root(instance));
                 *v.insert(node_id)
             }
         }
@@ -450,8 +596,20 @@ impl<'tcx> CallGraph<'tcx> {
 
     /// Add a dominance relationship to the call graph.
     /// Denotes that `defid1` is dominated by `defid2`.
-    pub fn add_dom(&mut self, defid1: DefId, defid2: DefId) {
-        self.dominance.entry(defid1).or_default().insert(defid2);
+    /// The generic arguments of the calls select the instances of generic functions.
+    pub fn add_dom(
+        &mut self,
+        defid1: DefId,
+        args1: GenericArgsRef<'tcx>,
+        defid2: DefId,
+        args2: GenericArgsRef<'tcx>,
+    ) {
+        let instance1 = self.get_instance(defid1, Some(args1));
+        let instance2 = self.get_instance(defid2, Some(args2));
+        self.dominance
+            .entry(instance1)
+            .or_default()
+            .insert(instance2);
     }
 
     /// Add a new EdgeType to the call graph's `edge_types`.
@@ -465,10 +623,19 @@ impl<'tcx> CallGraph<'tcx> {
 
     /// Add a new edge to the call graph.
     /// The edge is a call edge from `caller_id` to `callee_id` with type `edge_type_str`.
-    pub fn add_edge(&mut self, caller_id: DefId, callee_id: DefId, edge_type_str: Box<str>) {
+    /// The generic arguments of the caller and of the callee, if any, select the nodes of
+    /// their instances.
+    pub fn add_edge(
+        &mut self,
+        caller_id: DefId,
+        caller_args: Option<GenericArgsRef<'tcx>>,
+        callee_id: DefId,
+        callee_args: Option<GenericArgsRef<'tcx>>,
+        edge_type_str: Box<str>,
+    ) {
         let type_id = self.add_edge_type(edge_type_str);
-        let caller_node = self.get_or_insert_node(caller_id);
-        let callee_node = self.get_or_insert_node(callee_id);
+        let caller_node = self.get_or_insert_node(self.get_instance(caller_id, caller_args));
+        let callee_node = self.get_or_insert_node(self.get_instance(callee_id, callee_args));
         let mut existing_types = HashSet::<TypeId>::new();
         for edge in self.graph.edges_connecting(caller_node, callee_node) {
             existing_types.insert(edge.weight().type_id);
@@ -479,6 +646,25 @@ impl<'tcx> CallGraph<'tcx> {
         }
     }
 
+    /// Add a new edge to the call graph for a call through a vtable.
+    /// The edge is a call edge from `caller_id` to `callee_id`, one of the candidate implementations
+    /// of the virtual method, with type `edge_type_str` tagged as virtual.
+    pub fn add_virtual_edge(
+        &mut self,
+        caller_id: DefId,
+        caller_args: Option<GenericArgsRef<'tcx>>,
+        callee_id: DefId,
+        edge_type_str: &str,
+    ) {
+        self.add_edge(
+            caller_id,
+            caller_args,
+            callee_id,
+            None,
+            format!("{VIRTUAL_EDGE_TYPE_PREFIX}{edge_type_str}").into_boxed_str(),
+        );
+    }
+
     /// Find a node in the call graph given a `name` that may appear as
     /// a substring within the node's name. The first such node is returned, if any.
     fn get_node_by_name(&self, name: &str) -> Option<NodeId> {
@@ -505,6 +691,13 @@ impl<'tcx> CallGraph<'tcx> {
         None
     }
 
+    /// Find the node of an instance in the call graph, if any.
+    fn get_node_by_instance(&self, instance: Instance<'tcx>) -> Option<NodeId> {
+        self.graph
+            .node_indices()
+            .find(|node_id| self.graph[*node_id].instance == instance)
+    }
+
     /// Deduplicate edges by only including a single edge for for each
     /// (caller, callee) pair that has an edge.
     ///
@@ -573,6 +766,65 @@ impl<'tcx> CallGraph<'tcx> {
         }
     }
 
+    /// Filter out all nodes from the graph that cannot be reached from an entry point.
+    ///
+    /// The entry points are the functions named in `DatalogConfig.entry_points`. If none are
+    /// named, the entry point is `main`, or the function declared by `entrypoint!` if there is no
+    /// `main`. If no function matches an entry point, the graph is not changed.
+    fn prune_unreachable(&self) -> CallGraph<'tcx> {
+        let entry_points = match &self.config.datalog_config {
+            Some(datalog_config) if !datalog_config.get_entry_points().is_empty() => {
+                self.find_entry_points(datalog_config.get_entry_points())
+            }
+            _ => {
+                let main_entry_points = self.find_entry_points(&["main".into()]);
+                if main_entry_points.is_empty() {
+                    self.find_entry_points(&[SOLANA_ENTRYPOINT_NAME.into()])
+                } else {
+                    main_entry_points
+                }
+            }
+        };
+        let mut reachable = HashSet::<NodeId>::new();
+        for node_id in entry_points {
+            if !reachable.contains(&node_id) {
+                let mut bfs = Bfs::new(&self.graph, node_id);
+                while let Some(reachable_id) = bfs.next(&self.graph) {
+                    reachable.insert(reachable_id);
+                }
+            }
+        }
+        if reachable.is_empty() {
+            warn!("no entry point in the call graph, nothing is pruned");
+            return self.clone();
+        }
+        let graph = self.graph.filter_map(
+            |node_id, node| {
+                if reachable.contains(&node_id) {
+                    Some(node.to_owned())
+                } else {
+                    None
+                }
+            },
+            |_, edge| Some(edge.to_owned()),
+        );
+        self.update(graph)
+    }
+
+    /// Returns the nodes whose name is one of the entry points,
+    /// or ends with `::` followed by one of the entry points.
+    fn find_entry_points(&self, entry_points: &[Box<str>]) -> Vec<NodeId> {
+        self.graph
+            .node_indices()
+            .filter(|node_id| {
+                let name = &self.graph[*node_id].name;
+                entry_points.iter().any(|entry_point| {
+                    **name == **entry_point || name.ends_with(&format!("::{entry_point}"))
+                })
+            })
+            .collect()
+    }
+
     /// Helper function for folding excluded nodes.
     ///
     /// Computes the set of reachable nodes reachable
@@ -715,6 +967,56 @@ impl<'tcx> CallGraph<'tcx> {
         self.update(graph)
     }
 
+    /// Collapse each strongly connected component of the graph, i.e. a set of functions that are
+    /// mutually recursive or a single function that calls itself, into a single node.
+    ///
+    /// The edges between the members of a component are removed, while the edges that
+    /// enter or leave the component are connected to its node.
+    fn collapse_sccs(&self) -> CallGraph<'tcx> {
+        // The first member of the strongly connected component of each node that is in one
+        let mut first_members = HashMap::<NodeId, NodeId>::new();
+        for component in tarjan_scc(&self.graph) {
+            if component.len() > 1 || self.graph.contains_edge(component[0], component[0]) {
+                if let Some(first_member) = component.iter().min() {
+                    for node_id in component.iter() {
+                        first_members.insert(*node_id, *first_member);
+                    }
+                }
+            }
+        }
+        let mut graph = Graph::<CallGraphNode<'tcx>, CallGraphEdge>::new();
+        let mut new_node_ids = HashMap::<NodeId, NodeId>::new();
+        // The first member of a component has the smallest id, so its new node
+        // is added before the other members are visited.
+        for node_id in self.graph.node_indices() {
+            let new_node_id = match first_members.get(&node_id) {
+                Some(first_member) if *first_member != node_id => new_node_ids[first_member],
+                Some(_) => {
+                    let mut members = self
+                        .graph
+                        .node_indices()
+                        .filter(|member| first_members.get(member) == Some(&node_id))
+                        .map(|member| (member, &self.graph[member]))
+                        .collect::<Vec<(NodeId, &CallGraphNode<'tcx>)>>();
+                    members.sort_by_key(|(member, _)| *member);
+                    graph.add_node(CallGraphNode::new_scc(&members))
+                }
+                None => graph.add_node(self.graph[node_id].to_owned()),
+            };
+            new_node_ids.insert(node_id, new_node_id);
+        }
+        let mut edges = HashSet::<RawEdge>::new();
+        for edge in self.graph.edge_references() {
+            let start_id = new_node_ids[&edge.source()];
+            let end_id = new_node_ids[&edge.target()];
+            let type_id = edge.weight().type_id;
+            if start_id != end_id && edges.insert((start_id, end_id, type_id)) {
+                graph.add_edge(start_id, end_id, CallGraphEdge::new(type_id));
+            }
+        }
+        self.update(graph)
+    }
+
     /// Produce a representation of the graph that uses
     /// the (shorter) `node.name`, which is derived from the node's DefId,
     /// rather than the full DefId itself.
@@ -736,6 +1038,8 @@ impl<'tcx> CallGraph<'tcx> {
                 CallGraphReduction::Fold => graph.fold_excluded(),
                 CallGraphReduction::Deduplicate => graph.deduplicate_edges(),
                 CallGraphReduction::Clean => graph.filter_no_edges(),
+                CallGraphReduction::CollapseSccs => graph.collapse_sccs(),
+                CallGraphReduction::PruneUnreachable => graph.prune_unreachable(),
             })
     }
 
@@ -967,9 +1271,9 @@ impl<'tcx> CallGraph<'tcx> {
         // Output dominance relations
         self.graph.map(
             |node_id1, node| {
-                if let Some(nodes) = self.dominance.get(&node.defid) {
-                    for defid2 in nodes.iter() {
-                        if let Some(node_id2) = self.get_node_by_defid(*defid2) {
+                if let Some(instances) = self.dominance.get(&node.instance) {
+                    for instance2 in instances.iter() {
+                        if let Some(node_id2) = self.get_node_by_instance(*instance2) {
                             output.add_relation(DatalogRelation::new_dom(
                                 node_id1.index() as u32,
                                 node_id2.index() as u32,
@@ -980,22 +1284,34 @@ impl<'tcx> CallGraph<'tcx> {
             },
             |_, _| (),
         );
-        // Output edge and edge type relations
+        // Output the members of the collapsed strongly connected components
+        for node_id in self.graph.node_indices() {
+            for member in self.graph[node_id].scc_members.iter() {
+                output.add_relation(DatalogRelation::new_scc_member(
+                    node_id.index() as u32,
+                    *member,
+                ));
+            }
+        }
+        // Output edge and edge type relations.
+        // The reductions insert edges in the iteration order of hash sets, so the edges are
+        // numbered in the order of their endpoints and types, which keeps the output stable.
+        let mut edges = Vec::<(u32, u32, TypeId)>::new();
         self.graph.map(
             |_, _| (),
             |edge_id, edge| {
                 if let Some((start_id, end_id)) = self.graph.edge_endpoints(edge_id) {
-                    output.add_relation(DatalogRelation::new_edge(
-                        ctr,
-                        start_id.index() as u32,
-                        end_id.index() as u32,
-                    ));
-                    output.add_relation(DatalogRelation::new_edge_type(ctr, edge.type_id));
-                    used_types.insert(edge.type_id);
-                    ctr += 1;
+                    edges.push((start_id.index() as u32, end_id.index() as u32, edge.type_id));
                 }
             },
         );
+        edges.sort();
+        for (start_id, end_id, type_id) in edges {
+            output.add_relation(DatalogRelation::new_edge(ctr, start_id, end_id));
+            output.add_relation(DatalogRelation::new_edge_type(ctr, type_id));
+            used_types.insert(type_id);
+            ctr += 1;
+        }
         // Output type relations
         let mut index_to_type = HashMap::<TypeId, Box<str>>::new();
         for (_, edge_type) in self.edge_types.iter() {
@@ -1045,14 +1361,162 @@ impl<'tcx> CallGraph<'tcx> {
 
     /// Produce a dot file representation of the call graph
     /// for displaying with Graphviz.
-    fn to_dot(&self, dot_path: &Path) {
+    /// If annotate_entry_points is true, entry points are drawn as boxes
+    /// and leaves are drawn with a double border.
+    fn to_dot(&self, dot_path: &Path, annotate_entry_points: bool) {
+        let graph = self.shortened_node_names();
+        let output = if annotate_entry_points {
+            let node_attributes = |_: &Graph<&str, ()>, (node_id, _): (NodeId, &&str)| {
+                let mut attributes = String::new();
+                if self.is_entry_point(node_id) {
+                    attributes.push_str("shape = box ");
+                }
+                if self.is_leaf(node_id) {
+                    attributes.push_str("peripheries = 2 ");
+                }
+                attributes
+            };
+            format!(
+                "{:?}",
+                Dot::with_attr_getters(
+                    &graph,
+                    &[Config::EdgeNoLabel],
+                    &|_, _| String::new(),
+                    &node_attributes,
+                )
+            )
+        } else {
+            format!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]))
+        };
+        match fs::write(dot_path, output) {
+            Ok(_) => (),
+            Err(e) => panic!("Failed to write dot file output: {e:?}"),
+        };
+    }
+
+    /// Writes a dot file of the functions of the crate and the calls between them, annotated with
+    /// the findings of the functions as given by `findings`. A function with a finding is filled in
+    /// red if one of its findings is Critical and in orange otherwise, and lists the checkers of its
+    /// findings in its tooltip. Entry points are drawn as boxes.
+    pub fn to_dot_with_findings(
+        &self,
+        dot_path: &Path,
+        findings: &[(DefId, &Finding)],
+    ) -> std::io::Result<()> {
+        let deduplicated = self.deduplicate_edges();
+        let local_graph = deduplicated.update(deduplicated.graph.filter_map(
+            |_, node| node.defid.is_local().then(|| node.to_owned()),
+            |_, edge| Some(edge.to_owned()),
+        ));
+        // The most severe finding and the checkers of the findings of every function
+        let mut node_findings = HashMap::<NodeId, (Severity, Vec<&str>)>::new();
+        for (defid, finding) in findings {
+            let Some(node_id) = local_graph.get_node_by_defid(*defid) else {
+                continue;
+            };
+            let (severity, checkers) = node_findings
+                .entry(node_id)
+                .or_insert((finding.severity, Vec::new()));
+            *severity = (*severity).max(finding.severity);
+            if !checkers.contains(&finding.checker_name.as_str()) {
+                checkers.push(&finding.checker_name);
+            }
+        }
+        let graph = local_graph.shortened_node_names();
+        let node_attributes = |_: &Graph<&str, ()>, (node_id, _): (NodeId, &&str)| {
+            let mut attributes = String::new();
+            if local_graph.is_entry_point(node_id) {
+                attributes.push_str("shape = box ");
+            }
+            if let Some((severity, checkers)) = node_findings.get(&node_id) {
+                let color = if *severity == Severity::Critical {
+                    "red"
+                } else {
+                    "orange"
+                };
+                attributes.push_str(&format!(
+                    "style = filled fillcolor = {color} tooltip = \"{}\" ",
+                    checkers.join(", ")
+                ));
+            }
+            attributes
+        };
         let output = format!(
             "{:?}",
-            Dot::with_config(&self.shortened_node_names(), &[Config::EdgeNoLabel])
+            Dot::with_attr_getters(
+                &graph,
+                &[Config::EdgeNoLabel],
+                &|_, _| String::new(),
+                &node_attributes,
+            )
         );
-        match fs::write(dot_path, output) {
+        fs::write(dot_path, output)
+    }
+
+    /// A node is an entry point if no function of the graph calls it,
+    /// e.g. the function declared by `entrypoint!` or a public function of a library.
+    fn is_entry_point(&self, node_id: NodeId) -> bool {
+        self.graph
+            .neighbors_directed(node_id, Direction::Incoming)
+            .next()
+            .is_none()
+    }
+
+    /// A node is a leaf if it does not call any local function, e.g. a pure helper.
+    fn is_leaf(&self, node_id: NodeId) -> bool {
+        self.graph
+            .neighbors_directed(node_id, Direction::Outgoing)
+            .all(|callee| {
+                self.graph
+                    .node_weight(callee)
+                    .is_some_and(|node| self.non_local_defs.contains(&node.defid))
+            })
+    }
+
+    /// Returns the names of the functions on a shortest call path from an entry point of the graph
+    /// to the function defid, starting with the entry point and ending with the function itself.
+    /// The path is found by a breadth first search over the callers of the function, and is empty
+    /// if the function is not in the graph or is only reached by recursive calls.
+    pub fn reach_path(&self, defid: DefId) -> Vec<String> {
+        let Some(target) = self.get_node_by_defid(defid) else {
+            return Vec::new();
+        };
+        // The callee of each visited caller on the way back to the target
+        let mut callees = HashMap::<NodeId, NodeId>::new();
+        let mut visited = HashSet::from([target]);
+        let mut queue = VecDeque::from([target]);
+        while let Some(node_id) = queue.pop_front() {
+            if self.is_entry_point(node_id) {
+                let mut path = Vec::new();
+                let mut current = Some(node_id);
+                while let Some(path_node_id) = current {
+                    if let Some(node) = self.graph.node_weight(path_node_id) {
+                        path.push(node.name.to_string());
+                    }
+                    current = callees.get(&path_node_id).copied();
+                }
+                return path;
+            }
+            for caller in self.graph.neighbors_directed(node_id, Direction::Incoming) {
+                if visited.insert(caller) {
+                    callees.insert(caller, node_id);
+                    queue.push_back(caller);
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    /// Produce a JSON file with the names of the entry points and the leaves of the call graph.
+    fn to_entry_points(&self, entry_points_path: &Path) {
+        let entry_point_info = EntryPointOutput::new(self);
+        match serde_json::to_string_pretty(&entry_point_info)
+            .map_err(|e| e.to_string())
+            .and_then(|entry_point_output| {
+                fs::write(entry_points_path, entry_point_output).map_err(|e| e.to_string())
+            }) {
             Ok(_) => (),
-            Err(e) => panic!("Failed to write dot file output: {e:?}"),
+            Err(e) => panic!("Failed to write entry point output: {e}"),
         };
     }
 
@@ -1071,7 +1535,7 @@ impl<'tcx> CallGraph<'tcx> {
     /// Top-level output function.
     ///
     /// First applies a set of reductions to the call graph.
-    /// Then produces Datalog and / or dot file output of the call graph.
+    /// Then produces Datalog, dot file, call site and / or entry point output of the call graph.
     pub fn output(&self) {
         let call_graph = self.reduce_graph(self.clone(), &self.config.reductions);
         if let Some(datalog_config) = &self.config.datalog_config {
@@ -1083,11 +1547,17 @@ impl<'tcx> CallGraph<'tcx> {
             );
         }
         if let Some(dot_path) = &self.config.dot_output_path {
-            call_graph.to_dot(Path::new(dot_path.as_ref()));
+            call_graph.to_dot(
+                Path::new(dot_path.as_ref()),
+                self.config.entry_points_output_path.is_some(),
+            );
         }
         if let Some(call_path) = &self.config.call_sites_output_path {
             call_graph.to_call_sites(Path::new(call_path.as_ref()));
         }
+        if let Some(entry_points_path) = &self.config.entry_points_output_path {
+            call_graph.to_entry_points(Path::new(entry_points_path.as_ref()));
+        }
     }
 
     pub fn get_calls_for_def_ids(&self) -> HashMap<DefId, Vec<(Span, DefId)>> {
@@ -1136,7 +1606,7 @@ impl Serialize for TypeMapOutput {
 
 /// Represents the types of datalog input relations
 /// that are generated.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 enum RelationType {
     /// `Dom(n1, n2)`: `n1` dominates `n2`.
     Dom,
@@ -1148,6 +1618,9 @@ enum RelationType {
     EqType,
     /// `Member(type_id1, type_id2)`: The type `type_id2` is a member of `type_id1`.
     Member,
+    /// `SccMember(scc_id, node_id)`: The node `scc_id` is a collapsed strongly
+    /// connected component of which `node_id` was a member.
+    SccMember,
 }
 
 impl fmt::Display for RelationType {
@@ -1158,12 +1631,14 @@ impl fmt::Display for RelationType {
             RelationType::EdgeType => write!(f, "EdgeType"),
             RelationType::EqType => write!(f, "EqType"),
             RelationType::Member => write!(f, "Member"),
+            RelationType::SccMember => write!(f, "SccMember"),
         }
     }
 }
 
 /// Represents an atomic Datalog relation.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Relations are ordered by their name and then by the values of their operands.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct DatalogRelation {
     /// A relation has a name.
     name: RelationType,
@@ -1207,6 +1682,13 @@ impl DatalogRelation {
         }
     }
 
+    pub fn new_scc_member(scc_id: u32, node_id: u32) -> DatalogRelation {
+        DatalogRelation {
+            name: RelationType::SccMember,
+            operands: vec![scc_id, node_id],
+        }
+    }
+
     /// Format the relation for Differential Datalog
     fn to_differential_datalog(&self) -> String {
         format!(
@@ -1251,23 +1733,28 @@ impl DatalogOutput {
     /// Output `relations` formatted for the given Datalog backend.
     /// Optionally, `filter` the output to only include relations of a
     /// particular `RelationType`.
+    /// The relations are output in the order of their tuple values, e.g. `Edge(2,..)` comes
+    /// before `Edge(10,..)`, so that the output does not depend on the order of the hash set.
     fn output_relation_set(
         &self,
         relations: &HashSet<DatalogRelation>,
         filter: Option<RelationType>,
         backend: DatalogBackend,
     ) -> String {
+        let mut sorted_relations = relations
+            .iter()
+            .filter(|relation| filter.is_none_or(|x| x == relation.name))
+            .collect::<Vec<&DatalogRelation>>();
+        sorted_relations.sort();
         let mut relation_strings = Vec::<String>::new();
-        for relation in relations.iter() {
-            if filter.is_none_or(|x| x == relation.name) {
-                match backend {
-                    DatalogBackend::DifferentialDatalog => relation_strings
-                        .push(format!("insert {};", relation.to_differential_datalog())),
-                    DatalogBackend::Souffle => relation_strings.push(relation.to_souffle()),
-                };
-            }
+        for relation in sorted_relations {
+            match backend {
+                DatalogBackend::DifferentialDatalog => {
+                    relation_strings.push(format!("insert {};", relation.to_differential_datalog()))
+                }
+                DatalogBackend::Souffle => relation_strings.push(relation.to_souffle()),
+            };
         }
-        relation_strings.sort();
         relation_strings.join("\n")
     }
 
@@ -1329,10 +1816,53 @@ impl DatalogOutput {
                 Some(RelationType::Member),
                 DatalogBackend::Souffle,
             ),
+        )?;
+        fs::write(
+            path.join("SccMember.facts"),
+            self.output_relation_set(
+                &self.relations,
+                Some(RelationType::SccMember),
+                DatalogBackend::Souffle,
+            ),
         )
     }
 }
 
+/// Lists the functions of the call graph that are not called by any other function
+/// (the entry points) and the functions that do not call any local function (the leaves).
+#[derive(Serialize)]
+struct EntryPointOutput {
+    /// The names of the entry points, e.g. the function declared by `entrypoint!`.
+    entry_points: Vec<String>,
+    /// The names of the leaves, e.g. pure helper functions.
+    leaves: Vec<String>,
+}
+
+impl EntryPointOutput {
+    pub fn new(call_graph: &CallGraph) -> EntryPointOutput {
+        let mut entry_points = Vec::new();
+        let mut leaves = Vec::new();
+        for node_id in call_graph.graph.node_indices() {
+            let Some(node) = call_graph.graph.node_weight(node_id) else {
+                continue;
+            };
+            if call_graph.non_local_defs.contains(&node.defid) {
+                continue;
+            }
+            if call_graph.is_entry_point(node_id) {
+                entry_points.push(node.name.to_string());
+            }
+            if call_graph.is_leaf(node_id) {
+                leaves.push(node.name.to_string());
+            }
+        }
+        EntryPointOutput {
+            entry_points,
+            leaves,
+        }
+    }
+}
+
 /// Summarizes the call graph of the current crate. Calls to higher order external functions
 /// are treated as local calls (i.e. the calls they make are also included here) because
 /// their call graphs are imprecise unless specialized with call site information.
@@ -292,6 +292,32 @@ impl<'call, 'block, 'analysis, 'compilation, 'tcx>
         }
     }
 
+    /// If self.callee_def_id is a trait method that is called through a vtable, i.e. on a trait
+    /// object that try_to_devirtualize cannot resolve, returns the methods that implement it in the
+    /// impls of the trait in the analyzed crate, since any of them may be the target of the call.
+    #[logfn_inputs(TRACE)]
+    fn get_virtual_call_candidates(&self) -> Vec<DefId> {
+        let tcx = self.block_visitor.bv.tcx;
+        if !utils::is_trait_method(self.callee_def_id, tcx) {
+            return vec![];
+        }
+        let is_virtual = self
+            .callee_generic_arguments
+            .and_then(|gen_args| gen_args.types().next())
+            .is_some_and(|arg0_ty| matches!(arg0_ty.kind(), TyKind::Dynamic(..)));
+        if !is_virtual {
+            return vec![];
+        }
+        tcx.all_impls(tcx.parent(self.callee_def_id))
+            .filter(|impl_def_id| impl_def_id.is_local())
+            .filter_map(|impl_def_id| {
+                tcx.impl_item_implementor_ids(impl_def_id)
+                    .get(&self.callee_def_id)
+                    .copied()
+            })
+            .collect()
+    }
+
     /// Extract a list of function references from an environment of function constant arguments
     #[logfn_inputs(TRACE)]
     fn get_function_constant_signature(
@@ -316,21 +342,36 @@ impl<'call, 'block, 'analysis, 'compilation, 'tcx>
     pub fn get_function_summary(&mut self) -> Option<Summary> {
         self.try_to_devirtualize();
         if self.block_visitor.bv.cv.call_graph.needs_edges() {
+            let caller_generic_arguments = self.type_visitor().generic_arguments;
             if self.actual_argument_types.is_empty() {
                 self.block_visitor.bv.cv.call_graph.add_edge(
                     self.block_visitor.bv.def_id,
+                    caller_generic_arguments,
                     self.callee_def_id,
+                    self.callee_generic_arguments,
                     "".to_string().into_boxed_str(),
                 );
             } else {
                 for ty in self.actual_argument_types.iter() {
                     self.block_visitor.bv.cv.call_graph.add_edge(
                         self.block_visitor.bv.def_id,
+                        caller_generic_arguments,
                         self.callee_def_id,
+                        self.callee_generic_arguments,
                         ty.to_string().into_boxed_str(),
                     );
                 }
             }
+            for candidate_def_id in self.get_virtual_call_candidates() {
+                for ty in self.actual_argument_types.iter() {
+                    self.block_visitor.bv.cv.call_graph.add_virtual_edge(
+                        self.block_visitor.bv.def_id,
+                        caller_generic_arguments,
+                        candidate_def_id,
+                        &ty.to_string(),
+                    );
+                }
+            }
         }
         if let Some(func_ref) = &self.callee_func_ref.clone() {
             // If the actual arguments include any function constants, collect them together
@@ -4,12 +4,15 @@
 // LICENSE file in the root directory of this source tree.
 #![allow(clippy::borrowed_box)]
 
+use crate::analysis_cache::AnalysisCache;
 use crate::call_graph::CallGraph;
 use crate::constant_domain::ConstantValueCache;
 use crate::crate_visitor::CrateVisitor;
 use crate::known_names::KnownNamesCache;
 use crate::options::Options;
+use crate::report::{Finding, FindingDelta};
 use crate::summaries::SummaryCache;
+use crate::suppression::Suppressions;
 
 use crate::type_visitor::TypeCache;
 use crate::utils;
@@ -18,6 +21,7 @@ use log_derive::*;
 use rustc_driver::Compilation;
 use rustc_interface::interface;
 use rustc_middle::ty::TyCtxt;
+use rustc_span::FileName;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter, Result};
@@ -35,6 +39,16 @@ pub struct MiraiCallbacks {
     output_directory: PathBuf,
     /// True if this run is done via cargo test
     test_run: bool,
+    /// The findings of the previous analysis in watch mode.
+    previous_findings: Option<Vec<Finding>>,
+    /// The findings of the crate, kept after the analysis in watch mode.
+    findings: Vec<Finding>,
+    /// The changes to the findings since the previous analysis in watch mode.
+    finding_delta: Option<FindingDelta>,
+    /// The local source files of the crate, which are watched for changes in watch mode.
+    source_files: Vec<PathBuf>,
+    /// The functions that were analyzed, rather than reported from the analysis cache.
+    analyzed_functions: Vec<String>,
 }
 
 /// Constructors
@@ -45,6 +59,11 @@ impl MiraiCallbacks {
             file_name: String::new(),
             output_directory: PathBuf::default(),
             test_run: false,
+            previous_findings: None,
+            findings: Vec::new(),
+            finding_delta: None,
+            source_files: Vec::new(),
+            analyzed_functions: Vec::new(),
         }
     }
 
@@ -54,8 +73,43 @@ impl MiraiCallbacks {
             file_name: String::new(),
             output_directory: PathBuf::default(),
             test_run: true,
+            previous_findings: None,
+            findings: Vec::new(),
+            finding_delta: None,
+            source_files: Vec::new(),
+            analyzed_functions: Vec::new(),
         }
     }
+
+    /// Analyzes the crate again in watch mode, after an analysis that produced the given findings.
+    pub fn with_previous_findings(mut self, previous_findings: Vec<Finding>) -> MiraiCallbacks {
+        self.previous_findings = Some(previous_findings);
+        self
+    }
+}
+
+/// Results of the analysis that are used by watch mode
+impl MiraiCallbacks {
+    /// The findings of the analyzed crate.
+    pub fn findings(&self) -> &[Finding] {
+        &self.findings
+    }
+
+    /// The changes to the findings since the previous analysis, if the crate was analyzed again.
+    pub fn finding_delta(&self) -> Option<&FindingDelta> {
+        self.finding_delta.as_ref()
+    }
+
+    /// The local source files of the analyzed crate.
+    pub fn source_files(&self) -> &[PathBuf] {
+        &self.source_files
+    }
+
+    /// The functions of the crate that were analyzed with --analysis-cache, i.e. those whose MIR
+    /// had changed since the findings of the cache were recorded.
+    pub fn analyzed_functions(&self) -> &[String] {
+        &self.analyzed_functions
+    }
 }
 
 impl Debug for MiraiCallbacks {
@@ -150,19 +204,48 @@ impl MiraiCallbacks {
             "storing summaries for {} at {}/.summary_store.sled",
             self.file_name, summary_store_path
         );
+        if self.options.watch {
+            self.source_files = compiler
+                .sess
+                .source_map()
+                .files()
+                .iter()
+                .filter(|file| !file.is_imported())
+                .filter_map(|file| match &file.name {
+                    FileName::Real(name) => name.local_path().map(|path| path.to_path_buf()),
+                    _ => None,
+                })
+                .collect();
+        }
         let call_graph_config = self.options.call_graph_config.to_owned();
+        let suppressions = Suppressions::load(self.options.baseline.as_deref())
+            .unwrap_or_else(|e| compiler.sess.dcx().fatal(e))
+            .with_policy(&self.options.policy_suppressions);
+        let analysis_cache = AnalysisCache::load(self.options.analysis_cache.as_deref());
         let mut crate_visitor = CrateVisitor {
+            analysis_cache,
+            analyzed_functions: Vec::new(),
+            analysis_is_partial: false,
             buffered_diagnostics: Vec::new(),
+            baseline_findings: Vec::new(),
+            callers: HashMap::new(),
+            changed_files: None,
             constant_time_tag_cache: None,
             constant_time_tag_not_found: false,
             constant_value_cache: ConstantValueCache::default(),
             diagnostics_for: HashMap::new(),
             file_name: self.file_name.as_str(),
+            findings: Vec::new(),
+            finding_functions: Vec::new(),
             known_names_cache: KnownNamesCache::create_cache_from_language_items(),
             options: &std::mem::take(&mut self.options),
+            previous_findings: self.previous_findings.take(),
             session: &compiler.sess,
             generic_args_cache: HashMap::new(),
             summary_cache: SummaryCache::new(summary_store_path),
+            stale_baseline: Vec::new(),
+            suppressed_findings: Vec::new(),
+            suppressions,
             tcx,
             test_run: self.test_run,
             type_cache: Rc::new(RefCell::new(TypeCache::new())),
@@ -171,7 +254,29 @@ impl MiraiCallbacks {
         if crate_visitor.options.print_summaries {
             crate_visitor.call_graph.config.include_calls_in_summaries = true;
         }
+        if crate_visitor.options.needs_call_graph_edges() {
+            crate_visitor.call_graph.config.include_edges_for_findings = true;
+        }
         crate_visitor.analyze_some_bodies();
+        crate_visitor.annotate_reach_paths();
+        // The findings are kept before --fail-on can abort the compilation
+        if crate_visitor.options.watch {
+            self.findings = crate_visitor.findings.clone();
+            self.finding_delta = crate_visitor.print_finding_delta();
+        }
+        if crate_visitor.options.analysis_cache.is_some() {
+            self.analyzed_functions = crate_visitor.analyzed_functions.clone();
+        }
+        crate_visitor.print_suppressed_findings();
+        crate_visitor.check_baseline_drift();
+        crate_visitor.write_baseline();
+        crate_visitor.write_analysis_cache();
+        crate_visitor.write_report();
+        crate_visitor.write_sarif_report();
+        crate_visitor.write_html_report();
+        crate_visitor.write_output();
+        crate_visitor.write_callgraph_with_findings();
+        crate_visitor.check_fail_on();
         crate_visitor.call_graph.output();
         crate_visitor.print_summaries();
     }
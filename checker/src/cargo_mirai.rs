@@ -8,18 +8,27 @@
 // 1) It implicitly adds the options "--cfg mirai -Z always_encode_mir" to the rustc invocation.
 // 2) It calls mirai rather than rustc for all the targets of the current package.
 // 3) It runs cargo test --no-run for test targets.
+// At the root of a workspace, only the members below the contracts directory are analyzed,
+// see workspace.rs.
 
 use std::ffi::OsString;
-use std::ops::Index;
 use std::path::Path;
 use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use cargo_metadata::{Package, Target, TargetKind};
 
+mod workspace;
+
 const CARGO_MIRAI_HELP: &str = r#"Static analysis tool for Rust programs
 
 Usage:
-    cargo mirai
+    cargo mirai [--lib] [--contracts-dir <dir>] [<cargo options>] [-- <mirai options>]
+
+Options:
+    --contracts-dir <dir>    At the root of a workspace, only analyze the members below this
+                             directory, which is relative to the root. Defaults to `contracts`,
+                             and all members are analyzed if the directory does not exist.
 "#;
 
 pub fn main() {
@@ -78,19 +87,32 @@ fn call_cargo() {
         std::process::exit(1);
     };
 
+    // Identifies this run to the analyses of the crates, so that the reports that earlier runs
+    // stored in the --report-dir are discarded rather than merged into the report of this run
+    let run_id = format!(
+        "{}-{}",
+        std::process::id(),
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default()
+    );
+
     if let Some(root) = metadata.root_package() {
-        call_cargo_on_each_package_target(root);
+        call_cargo_on_each_package_target(root, &run_id);
         return;
     }
 
-    // There is no root, this must be a workspace, so call_cargo_on_each_package_target on each workspace member
-    for package_id in &metadata.workspace_members {
-        let package = metadata.index(package_id);
-        call_cargo_on_each_package_target(package);
+    // There is no root, this must be a workspace, so call_cargo_on_each_package_target on each
+    // contract among the workspace members
+    let contracts_dir =
+        workspace::contracts_dir(&metadata, get_arg_flag_value("--contracts-dir").as_deref());
+    for package in workspace::contract_members(&metadata, &contracts_dir) {
+        call_cargo_on_each_package_target(package, &run_id);
     }
 }
 
-fn call_cargo_on_each_package_target(package: &Package) {
+fn call_cargo_on_each_package_target(package: &Package, run_id: &str) {
     let lib_only = get_arg_flag_presence("--lib");
     for target in &package.targets {
         let kind = target
@@ -100,11 +122,11 @@ fn call_cargo_on_each_package_target(package: &Package) {
         if lib_only && !target.is_lib() {
             continue;
         }
-        call_cargo_on_target(target, kind);
+        call_cargo_on_target(target, kind, run_id);
     }
 }
 
-fn call_cargo_on_target(target: &Target, kind: &TargetKind) {
+fn call_cargo_on_target(target: &Target, kind: &TargetKind, run_id: &str) {
     // Build a cargo command for target
     let mut cmd =
         Command::new(std::env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo")));
@@ -136,7 +158,11 @@ fn call_cargo_on_target(target: &Target, kind: &TargetKind) {
         if arg == "--" {
             break;
         }
-        if arg == "--lib" {
+        if arg == "--lib" || arg.starts_with("--contracts-dir=") {
+            continue;
+        }
+        if arg == "--contracts-dir" {
+            args.next();
             continue;
         }
         cmd.arg(arg);
@@ -167,6 +193,9 @@ fn call_cargo_on_target(target: &Target, kind: &TargetKind) {
     // the RUSTC_WRAPPER setting.
     cmd.env("MIRAI_KIND", kind_str);
 
+    // Communicate the identity of this run to the calls to mirai, see write_crate_report.
+    cmd.env("MIRAI_RUN_ID", run_id);
+
     // Set the tool chain to be compatible with mirai
     if let Some(toolchain) = option_env!("RUSTUP_TOOLCHAIN") {
         cmd.env("RUSTUP_TOOLCHAIN", toolchain);
@@ -1,7 +1,18 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::rc::Rc;
+use rustc_hir::def_id::DefId;
 use rustc_middle::mir;
-use rustc_span::{BytePos, Span};
+use rustc_middle::ty::{TyCtxt, TyKind};
+use rustc_span::source_map::Spanned;
+use rustc_span::{BytePos, Span, SyntaxContext};
+
+use crate::anchor;
+use crate::guard_analysis::GuardAnalysis;
+use crate::mir_walker::ResolvedCallee;
+use crate::options::{GuardedReentrancy, Options, ReentrancyTransfers};
+use crate::report::{Confidence, Severity};
+use crate::taint_analysis::{TaintAnalysis, TaintSource};
+use crate::utils;
 
 #[derive(Debug, Clone)]
 pub enum BlockStatement<'tcx> {
@@ -9,12 +20,585 @@ pub enum BlockStatement<'tcx> {
     TerminatorKind(mir::TerminatorKind<'tcx>)
 }
 
+// The names of the checkers, as used in findings, suppressions and severity overrides
+pub const CHECKER_NAMES: [&str; 45] = [
+    "reentrancy",
+    "time_manipulation",
+    "bad_randomness",
+    "numerical_precision",
+    "saturating_arithmetic",
+    "clock_account_read",
+    "slice_panic",
+    "self_transfer",
+    "distinct_accounts",
+    "logged_data",
+    "over_privileged_account",
+    "ephemeral_state",
+    "unsafe_data_cast",
+    "lamports_overwrite",
+    "token_decimals",
+    "entrypoint_panic",
+    "lamports_authorization",
+    "unguarded_transfer_helper",
+    "manual_rent",
+    "swallowed_cpi_error",
+    "unvalidated_loop_account",
+    "unchecked_first_byte",
+    "unverified_pda",
+    "unvalidated_count",
+    "ok_after_error_log",
+    "stack_usage",
+    "lamports_double_borrow",
+    "seed_limits",
+    "unbounded_account_write",
+    "clock_payout",
+    "missing_signer",
+    "owner_check",
+    "integer_overflow",
+    "non_persistent_state",
+    "arbitrary_cpi",
+    "pda_bump",
+    "close_authority",
+    "division_before_multiplication",
+    "truncating_cast",
+    "rent_exemption",
+    "is_writable",
+    "account_substitution",
+    "panic_reachability",
+    "unchecked_cpi_result",
+    "unchecked_deserialize",
+];
+
+// The groups of checkers that only run if a rule policy enables them, the other checkers form the
+// default group
+pub const OPT_IN_CHECKER_GROUPS: [(&str, &[&str]); 1] = [("pedantic", &["manual_rent"])];
+
+// The name of the group of the checkers that run unless a rule policy disables them
+pub const DEFAULT_CHECKER_GROUP: &str = "default";
+
+/// Returns true if the checker only runs when a rule policy enables it.
+pub fn is_opt_in_checker(checker_name: &str) -> bool {
+    return OPT_IN_CHECKER_GROUPS
+        .iter()
+        .any(|(_, checker_names)| checker_names.contains(&checker_name));
+}
+
+/// Returns the names of the checkers of the group, or None if there is no such group.
+pub fn checkers_of_group(group_name: &str) -> Option<Vec<&'static str>> {
+    if group_name == DEFAULT_CHECKER_GROUP {
+        return Some(CHECKER_NAMES.into_iter().filter(|c| !is_opt_in_checker(c)).collect());
+    }
+    return OPT_IN_CHECKER_GROUPS
+        .iter()
+        .find(|(name, _)| *name == group_name)
+        .map(|(_, checker_names)| checker_names.to_vec());
+}
+
+// A short description of every checker, which describes the rule of the checker in the SARIF log
+pub const CHECKER_DESCRIPTIONS: [(&str, &str); 45] = [
+    ("reentrancy", "A balance is stored after the lamports are transferred"),
+    ("time_manipulation", "The clock, which validators can skew, decides a branch or a transfer"),
+    ("bad_randomness", "Random numbers come from a predictable source"),
+    ("numerical_precision", "Amounts are rounded in a way that loses precision"),
+    ("saturating_arithmetic", "Saturating arithmetic hides an overflow that should be an error"),
+    ("clock_account_read", "The clock is read from an account that is not validated"),
+    ("slice_panic", "A slice operation panics on a length that comes from the caller"),
+    ("self_transfer", "Lamports are transferred from an account to itself"),
+    ("distinct_accounts", "Two accounts that are both mutated are not checked to differ"),
+    ("logged_data", "Whole account or instruction data is logged"),
+    ("over_privileged_account", "An account is writable but only read"),
+    ("ephemeral_state", "State that is not persisted is logged as if it were"),
+    ("unsafe_data_cast", "Account data is reinterpreted as a type without validation"),
+    ("lamports_overwrite", "The balance of an account is overwritten instead of adjusted"),
+    ("token_decimals", "Token amounts with different decimals are compared"),
+    ("entrypoint_panic", "The entrypoint handler panics instead of returning an error"),
+    ("lamports_authorization", "The balance of an account is used to authorize an action"),
+    ("unguarded_transfer_helper", "A helper transfers lamports without validating its accounts"),
+    ("manual_rent", "The rent is computed by hand instead of by Rent::minimum_balance"),
+    ("swallowed_cpi_error", "The error of a cross-program invocation is ignored"),
+    ("unvalidated_loop_account", "A loop mutates accounts that are not validated"),
+    ("unchecked_first_byte", "The first byte of instruction data is read without a length check"),
+    ("unverified_pda", "A program derived address is used without re-deriving it"),
+    ("unvalidated_count", "A count from the instruction data is not checked against its length"),
+    ("ok_after_error_log", "Success is returned after an error is logged"),
+    ("stack_usage", "The locals of a function may overflow the stack"),
+    ("lamports_double_borrow", "The lamports of an account are borrowed twice"),
+    ("seed_limits", "A program derived address has too many or too long seeds"),
+    ("unbounded_account_write", "Caller data of any length is written into account data"),
+    ("clock_payout", "The clock decides who is paid or how much"),
+    ("missing_signer", "Lamports are mutated without a signer check of their account"),
+    ("owner_check", "Account data is read without checking the owner of the account"),
+    ("integer_overflow", "A balance is updated by arithmetic that may overflow"),
+    ("non_persistent_state", "State is mutated in a map that is never persisted to account data"),
+    ("arbitrary_cpi", "A program is invoked by an id taken from a passed account without a check"),
+    ("pda_bump", "A program derived address is created with a bump seed supplied by the caller"),
+    ("close_authority", "An account is closed by draining its lamports without zeroing its data"),
+    ("division_before_multiplication", "An integer quotient is multiplied, losing the remainder"),
+    ("truncating_cast", "An amount is cast to a narrower integer type, which truncates it"),
+    ("rent_exemption", "An account is created with lamports not computed by Rent::minimum_balance"),
+    ("is_writable", "An account is mutated without checking that it is writable"),
+    ("account_substitution", "State is looked up by the key of an account that is not the signer"),
+    ("panic_reachability", "Unwrapping or a panic is decided by the input of the caller"),
+    ("unchecked_cpi_result", "The result of a cross-program invocation is never checked"),
+    ("unchecked_deserialize", "Account data is deserialized without a length or type check"),
+];
+
+/// Returns how confident the checker is, by default, that its findings are real issues. A checker
+/// may be more or less confident of a particular finding, depending on how much of the data flow
+/// of the finding it has seen, see ReentrancyChecker::confidence.
+pub fn checker_confidence(checker_name: &str) -> Confidence {
+    return match checker_name {
+        // The MIR of the function shows the whole pattern, e.g. the balance that was loaded before
+        // the transfer is stored after it, or a caller value reaches the operation that fails on
+        // it, and there is no check in between that the checker does not know about
+        "reentrancy"
+        | "slice_panic"
+        | "unchecked_first_byte"
+        | "self_transfer"
+        | "lamports_double_borrow"
+        | "seed_limits"
+        | "pda_bump"
+        | "arbitrary_cpi"
+        | "division_before_multiplication"
+        | "truncating_cast" => Confidence::High,
+        // The pattern matches by name or by shape, without following the data flow, e.g. any
+        // call of round or any log of an account, so many of the findings are intended
+        "numerical_precision"
+        | "logged_data"
+        | "over_privileged_account"
+        | "ephemeral_state"
+        | "manual_rent" => Confidence::Low,
+        // The usage is estimated from the sizes of the locals, which the compiler may overlap
+        "stack_usage" => Confidence::Low,
+        // The data flow is followed within the function, but a validation may be made where the
+        // checker does not look, e.g. by the caller, by a helper or by the runtime
+        _ => Confidence::Medium,
+    };
+}
+
+/// Returns true if the path names the function, i.e. if it is the name or ends with ``::`` and
+/// the name, e.g. ``solana_program::program::invoke`` for ``invoke``.
+pub fn is_path_of(path: &str, name: &str) -> bool {
+    return path == name || path.strip_suffix(name).is_some_and(|prefix| prefix.ends_with("::"));
+}
+
+/// Returns true if the callee is a method of a map. The maps of std are re-exported from alloc and
+/// hashbrown, so the maps of no_std crates are recognized as well.
+pub fn is_map_method(callee_name: &str) -> bool {
+    return callee_name.contains("collections.hash.map")
+        || callee_name.contains("collections.btree.map")
+        || callee_name.contains("hashbrown.map");
+}
+
+// A finding of a registered checker, which the body visitor emits once the body is walked
+pub struct CheckerFinding {
+    // The name of the checker, see CHECKER_NAMES
+    pub checker_name: &'static str,
+    pub severity: Severity,
+    // How confident the checker is of this particular finding, see checker_confidence
+    pub confidence: Confidence,
+    // The span contains codes related to the finding
+    pub span: Span,
+    pub message: &'static str,
+    // The related locations of the finding, each with what it has to do with the finding
+    pub related: Vec<(Span, &'static str)>,
+    // The spans of the load, the transfer and the store of a reentrancy, if they are known
+    pub evidence: Option<(Span, Span, Span)>
+}
+
+impl CheckerFinding {
+    /// Returns a finding of the checker at the span, with the confidence of the checker and
+    /// without related locations or evidence.
+    pub fn new(
+        checker_name: &'static str,
+        severity: Severity,
+        span: Span,
+        message: &'static str,
+    ) -> CheckerFinding {
+        return CheckerFinding {
+            checker_name,
+            severity,
+            confidence: checker_confidence(checker_name),
+            span,
+            message,
+            related: Vec::new(),
+            evidence: None
+        };
+    }
+}
+
+// The body that the MirWalker walks, with what the checkers may look up about it besides its MIR
+pub struct CheckerContext<'a, 'tcx> {
+    pub tcx: TyCtxt<'tcx>,
+    pub mir: &'tcx mir::Body<'tcx>,
+    // The function of the body
+    pub def_id: DefId,
+    // The locals of the body that hold values of the instruction data or of the data of an account
+    pub taint_analysis: &'a TaintAnalysis,
+    // The checks that protect the operations of the body, e.g. a signer check of a transfer
+    pub guards: &'a GuardAnalysis<'tcx>,
+    // The span that the abstract interpretation of the body visited last
+    pub last_span: Span
+}
+
+impl<'tcx> CheckerContext<'_, 'tcx> {
+    /// Returns the name of the struct field that the place selects last, if any.
+    pub fn field_name(&self, place: &mir::Place<'tcx>) -> Option<String> {
+        let (base, mir::ProjectionElem::Field(field_index, _)) = place.as_ref().last_projection()?
+        else {
+            return None;
+        };
+        let base_ty = base.ty(&self.mir.local_decls, self.tcx).ty;
+        if let TyKind::Adt(def, _) = base_ty.kind() {
+            if def.is_struct() {
+                return Some(def.non_enum_variant().fields[field_index].name.to_string());
+            }
+        }
+        return None;
+    }
+}
+
+/// A checker that the MirWalker feeds with the statements and terminators of the analyzed body, and
+/// that reports its findings once the body has been walked. The walker visits each statement once
+/// and dispatches it to all the checkers of registered_checkers, so a new checker only has to
+/// implement this trait and be added there. The checkers that are not yet ported to the trait are
+/// fields of BodyVisitor that the abstract interpretation of the body feeds directly.
+pub trait Checker<'tcx> {
+    /// The name of the checker, as it appears in CHECKER_NAMES.
+    fn name(&self) -> &'static str;
+
+    /// Visit a statement of the body.
+    fn visit_statement(
+        &mut self,
+        _context: &CheckerContext<'_, 'tcx>,
+        _location: mir::Location,
+        _statement: &mir::Statement<'tcx>,
+    ) {
+    }
+
+    /// Visit the terminator of a block of the body. The callee of a call is given if the abstract
+    /// interpretation of the body resolved it, see call_of for its arguments.
+    fn visit_terminator(
+        &mut self,
+        _context: &CheckerContext<'_, 'tcx>,
+        _location: mir::Location,
+        _terminator: &mir::Terminator<'tcx>,
+        _callee: Option<&ResolvedCallee>,
+    ) {
+    }
+
+    /// Returns the findings of the body, after all of its statements and terminators are visited.
+    fn finish(&mut self, context: &CheckerContext<'_, 'tcx>) -> Vec<CheckerFinding>;
+}
+
+/// Returns a fresh instance of each checker that implements the Checker trait, for the body that
+/// is about to be walked.
+pub fn registered_checkers<'tcx>(options: &Options) -> Vec<Box<dyn Checker<'tcx> + 'tcx>> {
+    let mut reentrancy_checker = ReentrancyChecker::with_transfers(
+        options.reentrancy_transfers,
+        options.safe_transfers.clone(),
+    );
+    reentrancy_checker.guarded_reentrancy = options.guarded_reentrancy;
+    return vec![
+        Box::new(reentrancy_checker),
+        Box::new(TimeManipulationChecker::new()),
+        Box::new(BadrandomnessChecker::new()),
+        Box::new(NumericalPrecisionErrorChecker::new()),
+        Box::new(SelfTransferChecker::new()),
+    ];
+}
+
+/// Returns the arguments and the destination of the call of the terminator, if it is a call. A
+/// tail call returns into the return place of the body.
+pub fn call_of<'a, 'tcx>(
+    terminator: &'a mir::Terminator<'tcx>,
+) -> Option<(&'a [Spanned<mir::Operand<'tcx>>], mir::Place<'tcx>)> {
+    return match &terminator.kind {
+        mir::TerminatorKind::Call {
+            args, destination, ..
+        } => Some((&args[..], *destination)),
+        mir::TerminatorKind::TailCall { args, .. } => Some((&args[..], mir::Place::return_place())),
+        _ => None,
+    };
+}
+
+/// Returns the places that the rvalue uses, e.g. both operands of ``a + b``.
+fn used_places<'tcx>(rvalue: &mir::Rvalue<'tcx>) -> Vec<mir::Place<'tcx>> {
+    return match rvalue {
+        mir::Rvalue::Use(operand)
+        | mir::Rvalue::Cast(_, operand, _)
+        | mir::Rvalue::UnaryOp(_, operand) => operand.place().into_iter().collect(),
+        mir::Rvalue::BinaryOp(_, box (left, right)) => {
+            [left, right].iter().filter_map(|operand| operand.place()).collect()
+        }
+        mir::Rvalue::Ref(_, _, used) | mir::Rvalue::CopyForDeref(used) => vec![*used],
+        _ => Vec::new(),
+    };
+}
+
+// The local that each local is copied, moved or reborrowed from
+#[derive(Default)]
+pub struct LocalSources {
+    sources: HashMap<mir::Local, mir::Local>,
+}
+
+impl LocalSources {
+    /// Record the source of the assigned local if the rvalue is a plain copy, move or reborrow.
+    pub fn record(&mut self, place: &mir::Place<'_>, rvalue: &mir::Rvalue<'_>) {
+        if !place.projection.is_empty() {
+            return;
+        }
+        let source = match rvalue {
+            mir::Rvalue::Use(mir::Operand::Copy(source) | mir::Operand::Move(source))
+            | mir::Rvalue::Ref(_, _, source)
+            | mir::Rvalue::CopyForDeref(source) => source,
+            _ => return,
+        };
+        self.sources.insert(place.local, source.local);
+    }
+
+    /// Record the source of the assigned local if the rvalue is a cast, e.g. the unsizing of a
+    /// reference to an array into a reference to a slice.
+    pub fn record_cast(&mut self, place: &mir::Place<'_>, rvalue: &mir::Rvalue<'_>) {
+        if !place.projection.is_empty() {
+            return;
+        }
+        if let mir::Rvalue::Cast(_, mir::Operand::Copy(source) | mir::Operand::Move(source), _) =
+            rvalue
+        {
+            self.sources.insert(place.local, source.local);
+        }
+    }
+
+    /// Record that the value of the local is derived from the source, e.g. by a call of deref.
+    pub fn record_derived(&mut self, local: mir::Local, source: mir::Local) {
+        self.sources.insert(local, source);
+    }
+
+    /// Follow the recorded copies back to the local that the value originates from.
+    pub fn source_of(&self, local: mir::Local) -> mir::Local {
+        let mut source = local;
+        // The chain of copies is bounded by the number of assignments, loops can make it cyclic
+        for _ in 0..self.sources.len() {
+            match self.sources.get(&source) {
+                Some(next_source) => source = *next_source,
+                None => break,
+            }
+        }
+        return source;
+    }
+}
+
+// The depth up to which the helper functions called by a helper function are followed
+pub const MAX_HELPER_DEPTH: usize = 3;
+
+// What a helper function of the crate does with the lamports and with the balances it is given
+#[derive(Clone, Debug, Default)]
+pub struct HelperSummary {
+    // True if the helper, or a helper it calls, transfers lamports
+    pub transfers: bool,
+    // The indices of the arguments that the helper, or a helper it calls, stores through
+    pub stored_args: HashSet<usize>,
+}
+
+impl HelperSummary {
+    /// Summarizes the MIR body of the helper function, following the helpers it calls up to
+    /// the given depth. A call is a transfer if is_transfer returns true for the path of its
+    /// callee. Returns None if the helper is not of the crate or has no MIR.
+    pub fn of(
+        tcx: TyCtxt<'_>,
+        def_id: DefId,
+        depth: usize,
+        is_transfer: &dyn Fn(&str) -> bool,
+    ) -> Option<HelperSummary> {
+        if depth == 0 || !def_id.is_local() || !tcx.is_mir_available(def_id) {
+            return None;
+        }
+        let mir = tcx.optimized_mir(def_id);
+        let mut local_sources = LocalSources::default();
+        for block in mir.basic_blocks.iter() {
+            for statement in &block.statements {
+                if let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind {
+                    local_sources.record(place, rvalue);
+                }
+            }
+        }
+        // The index of the argument that a local is copied or reborrowed from
+        let arg_index = |local: mir::Local| {
+            let source = local_sources.source_of(local).as_usize();
+            return (1..=mir.arg_count).contains(&source).then(|| source - 1);
+        };
+        let mut summary = HelperSummary::default();
+        for block in mir.basic_blocks.iter() {
+            for statement in &block.statements {
+                // A store through an argument, e.g. ``*balance -= amount``
+                if let mir::StatementKind::Assign(box (place, _)) = &statement.kind {
+                    if place.is_indirect_first_projection() {
+                        summary.stored_args.extend(arg_index(place.local));
+                    }
+                }
+            }
+            let mir::TerminatorKind::Call { func, args, .. } = &block.terminator().kind else {
+                continue;
+            };
+            let Some((callee_def_id, _)) = func.const_fn_def() else {
+                continue;
+            };
+            if is_transfer(&tcx.def_path_str(callee_def_id)) {
+                summary.transfers = true;
+                continue;
+            }
+            let Some(callee_summary) = HelperSummary::of(tcx, callee_def_id, depth - 1, is_transfer)
+            else {
+                continue;
+            };
+            summary.transfers |= callee_summary.transfers;
+            for (index, arg) in args.iter().enumerate() {
+                if !callee_summary.stored_args.contains(&index) {
+                    continue;
+                }
+                if let Some(place) = arg.node.place() {
+                    summary.stored_args.extend(arg_index(place.local));
+                }
+            }
+        }
+        return Some(summary);
+    }
+}
+
+// What the handler of an instruction does with the state that the dispatch of the instructions
+// passes to it, e.g. the balances of the users
+#[derive(Clone, Debug, Default)]
+pub struct InstructionSummary {
+    // The span of the first transfer of the handler, or of the call of a helper that transfers
+    pub transfer_span: Option<Span>,
+    // The indices of the arguments whose state the handler stores after a transfer
+    pub stored_after_transfer: HashSet<usize>,
+    // The indices of the arguments whose state the handler reads
+    pub read_args: HashSet<usize>,
+}
+
+impl InstructionSummary {
+    /// Summarizes the MIR body of the handler, following the helpers it calls up to the given
+    /// depth. The state of an argument is what it refers to, as well as what the calls that it is
+    /// given to return, e.g. the balance that ``balances.get_mut(key)`` returns, except for the
+    /// lamports that a transfer borrows. Returns None if the handler is not of the crate or has no
+    /// MIR.
+    pub fn of(
+        tcx: TyCtxt<'_>,
+        def_id: DefId,
+        depth: usize,
+        is_transfer: &dyn Fn(&str) -> bool,
+    ) -> Option<InstructionSummary> {
+        if depth == 0 || !def_id.is_local() || !tcx.is_mir_available(def_id) {
+            return None;
+        }
+        let mir = tcx.optimized_mir(def_id);
+        let mut local_sources = LocalSources::default();
+        // The blocks that contain a transfer, directly or by calling a helper that transfers
+        let mut transfer_blocks: Vec<mir::BasicBlock> = Vec::new();
+        let mut summary = InstructionSummary::default();
+        for (bb, block) in mir.basic_blocks.iter_enumerated() {
+            for statement in &block.statements {
+                if let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind {
+                    local_sources.record(place, rvalue);
+                }
+            }
+            let mir::TerminatorKind::Call { func, args, destination, .. } = &block.terminator().kind
+            else {
+                continue;
+            };
+            let Some((callee_def_id, _)) = func.const_fn_def() else {
+                continue;
+            };
+            let transfers = is_transfer(&tcx.def_path_str(callee_def_id))
+                || HelperSummary::of(tcx, callee_def_id, depth - 1, is_transfer)
+                    .is_some_and(|callee_summary| callee_summary.transfers);
+            if transfers {
+                transfer_blocks.push(bb);
+                if summary.transfer_span.is_none() {
+                    summary.transfer_span = Some(block.terminator().source_info.span);
+                }
+            } else if let Some(receiver) = args.first().and_then(|arg| arg.node.place()) {
+                if destination.projection.is_empty() {
+                    local_sources.record_derived(destination.local, receiver.local);
+                }
+            }
+        }
+        // The index of the argument whose state a local refers to
+        let arg_index = |local: mir::Local| {
+            let source = local_sources.source_of(local).as_usize();
+            return (1..=mir.arg_count).contains(&source).then(|| source - 1);
+        };
+        // The blocks executed after a transfer, including those reached by a loop
+        let mut after_transfer: BTreeSet<mir::BasicBlock> = BTreeSet::new();
+        let mut worklist: Vec<mir::BasicBlock> = transfer_blocks
+            .iter()
+            .flat_map(|bb| mir.basic_blocks[*bb].terminator().successors())
+            .collect();
+        while let Some(bb) = worklist.pop() {
+            if after_transfer.insert(bb) {
+                worklist.extend(mir.basic_blocks[bb].terminator().successors());
+            }
+        }
+        for (bb, block) in mir.basic_blocks.iter_enumerated() {
+            for statement in &block.statements {
+                let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind else {
+                    continue;
+                };
+                // A store through the state of an argument, e.g. ``*balance -= amount``
+                if place.is_indirect_first_projection() && after_transfer.contains(&bb) {
+                    summary.stored_after_transfer.extend(arg_index(place.local));
+                }
+                if let mir::Rvalue::Use(operand) | mir::Rvalue::Cast(_, operand, _) = rvalue {
+                    if let Some(read_place) = operand.place() {
+                        summary.read_args.extend(arg_index(read_place.local));
+                    }
+                }
+                if let mir::Rvalue::Ref(_, _, read_place) | mir::Rvalue::CopyForDeref(read_place) =
+                    rvalue
+                {
+                    summary.read_args.extend(arg_index(read_place.local));
+                }
+            }
+            let mir::TerminatorKind::Call { func, args, .. } = &block.terminator().kind else {
+                continue;
+            };
+            for arg in args.iter() {
+                if let Some(arg_place) = arg.node.place() {
+                    summary.read_args.extend(arg_index(arg_place.local));
+                }
+            }
+            // A helper that stores the state after a transfer of its own stores it for the handler
+            let Some((callee_def_id, _)) = func.const_fn_def() else {
+                continue;
+            };
+            let Some(callee_summary) =
+                InstructionSummary::of(tcx, callee_def_id, depth - 1, is_transfer)
+            else {
+                continue;
+            };
+            for (index, arg) in args.iter().enumerate() {
+                if !callee_summary.stored_after_transfer.contains(&index) {
+                    continue;
+                }
+                if let Some(arg_place) = arg.node.place() {
+                    summary.stored_after_transfer.extend(arg_index(arg_place.local));
+                }
+            }
+        }
+        return Some(summary);
+    }
+}
+
 // Hold states for the reentrancy
 pub struct ReentrancyChecker<'tcx> {
-    // The block statements are belong to a function
-    pub block_statements: HashMap<mir::BasicBlock, Vec<BlockStatement<'tcx>>>,
-    // The function call transfers tokens in solana contract
-    pub function_lamport_transfer: HashMap<mir::BasicBlock, Rc<str>>,
+    // The block statements are belong to a function, ordered by their basic block
+    pub block_statements: BTreeMap<mir::BasicBlock, Vec<BlockStatement<'tcx>>>,
+    // The function call transfers tokens in solana contract, ordered by their basic block
+    pub function_lamport_transfer: BTreeMap<mir::BasicBlock, Rc<str>>,
+    // The spans of the calls that transfer tokens, keyed by their basic block
+    pub transfer_spans: BTreeMap<mir::BasicBlock, Span>,
     // The temporary variable holds the balance of an user in the solana contract
     pub temporary_variable_for_balance: Option<mir::Place<'tcx>>,
     // Check for detecting the variable holding the balance of an user in the solana contract
@@ -24,65 +608,479 @@ pub struct ReentrancyChecker<'tcx> {
     // The starting spans contain reentrancy codes
     pub starting_reentrancy_span: BytePos,
     // The ending spans contain reentrancy codes
-    pub ending_reentrancy_span: BytePos
+    pub ending_reentrancy_span: BytePos,
+    // The calls of helper functions that store through the balance, keyed by their basic block
+    pub helper_stores: BTreeMap<mir::BasicBlock, Span>,
+    // The blocks of the transfers that are made by helper functions rather than the function
+    pub helper_transfers: BTreeSet<mir::BasicBlock>,
+    // The summaries of the helper functions called by the function
+    pub helper_summaries: HashMap<DefId, HelperSummary>,
+    // The local that each local of the function is copied or reborrowed from
+    pub local_sources: LocalSources,
+    // The span of the call that loads the balance of the user, e.g. ``balances.get_mut(key)``
+    pub load_span: Option<Span>,
+    // The spans of the terminators of the blocks, which locate a store made by a terminator
+    pub terminator_spans: BTreeMap<mir::BasicBlock, Span>,
+    // Which calls are transfers besides the invocations of other programs
+    pub transfers: ReentrancyTransfers,
+    // The paths of the functions whose calls are never transfers, see is_transfer
+    pub safe_transfers: Vec<String>,
+    // The calls of the functions of the crate with the locals of their arguments, which are the
+    // handlers of the instructions if the function dispatches the instructions
+    pub handler_calls: Vec<(mir::Location, DefId, Vec<Option<mir::Local>>, Span)>,
+    // The blocks that branch on the instruction data, e.g. by ``match instruction_data.first()``
+    pub dispatch_branches: Vec<mir::BasicBlock>,
+    // The summaries of the handlers of the instructions that the function dispatches
+    pub instruction_summaries: HashMap<DefId, InstructionSummary>,
+    // The reentrancies across the instructions, each with the span of the call of the handler
+    // that transfers, the span of its transfer and the span of the call of the other handler
+    pub cross_instruction_spans: Vec<(Span, Span, Span)>,
+    // How a reentrancy of a function that is guarded by a reentrancy flag is reported
+    pub guarded_reentrancy: GuardedReentrancy
 }
 
 impl<'tcx> ReentrancyChecker<'tcx> {
     pub fn new() -> ReentrancyChecker<'tcx> {
         ReentrancyChecker {
-            block_statements: HashMap::default(),
-            function_lamport_transfer: HashMap::default(),
+            block_statements: BTreeMap::default(),
+            function_lamport_transfer: BTreeMap::default(),
+            transfer_spans: BTreeMap::default(),
             temporary_variable_for_balance: None,
             check_for_balance_variable: false,
             current_assign_destination: None,
             starting_reentrancy_span: BytePos(0),
-            ending_reentrancy_span: BytePos(0)
+            ending_reentrancy_span: BytePos(0),
+            helper_stores: BTreeMap::default(),
+            helper_transfers: BTreeSet::default(),
+            helper_summaries: HashMap::default(),
+            local_sources: LocalSources::default(),
+            load_span: None,
+            terminator_spans: BTreeMap::default(),
+            transfers: ReentrancyTransfers::default(),
+            safe_transfers: Vec::new(),
+            handler_calls: Vec::new(),
+            dispatch_branches: Vec::new(),
+            instruction_summaries: HashMap::default(),
+            cross_instruction_spans: Vec::new(),
+            guarded_reentrancy: GuardedReentrancy::default()
+        }
+    }
+
+    /// Returns a checker that treats the calls given by transfers as transfers, except for the
+    /// calls of the safe transfers.
+    pub fn with_transfers(
+        transfers: ReentrancyTransfers,
+        safe_transfers: Vec<String>,
+    ) -> ReentrancyChecker<'tcx> {
+        let mut checker = ReentrancyChecker::new();
+        checker.transfers = transfers;
+        checker.safe_transfers = safe_transfers;
+        return checker;
+    }
+
+    /// Records that the operand of the rvalue assigned to current_assign_destination is the
+    /// balance, if the operand copies or moves the temporary variable that holds the balance.
+    pub fn visit_balance_use(&mut self, operand: &mir::Operand<'tcx>) {
+        if !self.check_for_balance_variable {
+            return;
+        }
+        match operand {
+            mir::Operand::Copy(place) => {
+                if self.temporary_variable_for_balance == Some(*place) {
+                    self.temporary_variable_for_balance = self.current_assign_destination;
+                }
+            }
+            mir::Operand::Move(place) => {
+                if let Some(temporary_place) = self.temporary_variable_for_balance {
+                    if *place == temporary_place || place.local == temporary_place.local {
+                        self.temporary_variable_for_balance = self.current_assign_destination;
+                        self.check_for_balance_variable = false;
+                    }
+                }
+            }
+            mir::Operand::Constant(_) => {}
+        }
+    }
+
+    /// Records the call at bb, which is a ``TRANSFER`` instruction if it invokes another program,
+    /// and a ``LOAD`` instruction if it looks up the balance of an user in a map.
+    pub fn visit_reentrancy_call(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        location: mir::Location,
+        callee: &ResolvedCallee,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        destination: mir::Place<'tcx>,
+        span: Span,
+    ) {
+        let bb = location.block;
+        if self.is_transfer(&tcx.def_path_str(callee.def_id)) {
+            self.function_lamport_transfer.entry(bb).or_insert(callee.name.clone());
+            self.transfer_spans.entry(bb).or_insert(span);
+        }
+        // A helper of the crate may transfer the lamports or store the balance for the caller
+        if callee.def_id.is_local() {
+            self.visit_helper_call(tcx, bb, callee.def_id, args, span);
+            // The helper may be the handler of an instruction that the function dispatches
+            let arg_locals = args.iter().map(|arg| arg.node.place().map(|place| place.local));
+            self.handler_calls.push((location, callee.def_id, arg_locals.collect(), span));
+        }
+        if is_map_method(&callee.name) {
+            self.check_for_balance_variable = true;
+            self.temporary_variable_for_balance = Some(destination);
+            self.starting_reentrancy_span = span.lo();
+            self.load_span = Some(span);
+        }
+        if self.check_for_balance_variable {
+            for arg in args {
+                if let mir::Operand::Copy(place) | mir::Operand::Move(place) = arg.node {
+                    if self.temporary_variable_for_balance == Some(place) {
+                        self.temporary_variable_for_balance = Some(destination);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns true if a call of the function with the given path is a ``TRANSFER`` instruction.
+    /// An invocation of another program is, since the program may call back into the contract,
+    /// whether it is made with invoke or with a CPI helper of Anchor, and so is a borrow of the
+    /// lamports of an account, unless only invocations are transfers.
+    /// A call of a safe transfer, named by its path or by the end of its path, never is.
+    pub fn is_transfer(&self, callee_path: &str) -> bool {
+        if self.is_safe_transfer(callee_path) {
+            return false;
+        }
+        if is_path_of(callee_path, "invoke")
+            || is_path_of(callee_path, "invoke_signed")
+            || anchor::is_cpi_transfer(callee_path)
+        {
+            return true;
         }
+        return self.transfers == ReentrancyTransfers::Lamports
+            && callee_path.contains("try_borrow_mut_lamports");
+    }
+
+    /// Returns true if the function with the given path is one of the safe transfers.
+    pub fn is_safe_transfer(&self, callee_path: &str) -> bool {
+        return self
+            .safe_transfers
+            .iter()
+            .any(|safe_transfer| is_path_of(callee_path, safe_transfer));
+    }
+
+    /// Records the call at bb of a helper function of the crate. The call is a ``TRANSFER``
+    /// instruction if the helper transfers lamports, and a ``STORE`` instruction if the helper
+    /// stores through the balance that it is given, e.g. ``update_balance(balance, amount)``.
+    pub fn visit_helper_call(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        bb: mir::BasicBlock,
+        callee_def_id: DefId,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        span: Span,
+    ) {
+        let summary = match self.helper_summaries.get(&callee_def_id) {
+            Some(summary) => summary.clone(),
+            None => {
+                let is_transfer = |callee_path: &str| self.is_transfer(callee_path);
+                let summary = HelperSummary::of(tcx, callee_def_id, MAX_HELPER_DEPTH, &is_transfer)
+                    .unwrap_or_default();
+                self.helper_summaries.insert(callee_def_id, summary.clone());
+                summary
+            }
+        };
+        let callee_path = tcx.def_path_str(callee_def_id);
+        if summary.transfers && !self.is_safe_transfer(&callee_path) {
+            self.function_lamport_transfer.entry(bb).or_insert(Rc::from(callee_path));
+            self.transfer_spans.entry(bb).or_insert(span);
+            self.helper_transfers.insert(bb);
+        }
+        let Some(balance) = self.temporary_variable_for_balance else {
+            return;
+        };
+        let balance_source = self.local_sources.source_of(balance.local);
+        let stores_balance = args.iter().enumerate().any(|(index, arg)| {
+            summary.stored_args.contains(&index)
+                && arg.node.place().is_some_and(|place| {
+                    self.local_sources.source_of(place.local) == balance_source
+                })
+        });
+        if stores_balance {
+            self.helper_stores.entry(bb).or_insert(span);
+        }
+    }
+
+    /// Returns a checker for the given basic blocks of a function, whose lamports are transferred
+    /// by the terminators of the transfer blocks and whose user balance is held by balance_place.
+    /// This lets the checker run on hand-built MIR, without compiling a contract.
+    pub fn for_blocks(
+        blocks: Vec<(mir::BasicBlock, Vec<BlockStatement<'tcx>>)>,
+        transfer_blocks: Vec<mir::BasicBlock>,
+        balance_place: Option<mir::Place<'tcx>>,
+    ) -> ReentrancyChecker<'tcx> {
+        let mut checker = ReentrancyChecker::new();
+        checker.block_statements = blocks.into_iter().collect();
+        checker.function_lamport_transfer = transfer_blocks
+            .into_iter()
+            .map(|bb| (bb, Rc::from("try_borrow_mut_lamports")))
+            .collect();
+        checker.temporary_variable_for_balance = balance_place;
+        checker.check_for_balance_variable = balance_place.is_some();
+        return checker;
     }
 
     /// Check if the reentrancy happens. The reentrancy will possibly happens if the following executions
     /// happen. First, a ``LOAD`` instruction occurs. Second, the ``TRANSFER`` instruction occurs.
     /// Lastly, a ``STORE`` instruction executes, interacting with the same location accessed by
-    /// the former ``LOAD`` instruction.
+    /// the former ``LOAD`` instruction. The ``TRANSFER`` and ``STORE`` instructions may also be
+    /// made by the helper functions that the function calls.
     pub fn check(&self) -> bool {
         info!("Check for reentrancy");
-        let mut is_reentrancy = false;
-        if self.function_lamport_transfer.is_empty() {
-            return is_reentrancy;
-        }
-        if let Some((last_bb, _)) = self.function_lamport_transfer.iter().last() {
-            info!("Last function lamport {:?}", last_bb);
-            info!("Variable for balance {:?}", self.temporary_variable_for_balance);
-            for (bb, block_statements) in &self.block_statements {
-                if bb <= last_bb {
+        return self.reentrant_store_span().is_some();
+    }
+
+    /// Returns the span of the first ``STORE`` instruction made after the last ``TRANSFER``
+    /// instruction, if there is one, see check. A store of a block whose span is not known, as in
+    /// hand-built MIR, is located at the dummy span.
+    pub fn reentrant_store_span(&self) -> Option<Span> {
+        // The blocks are ordered, so the last transfer is the one of the highest block, and the
+        // blocks after it are visited in the same order on every run
+        let (last_bb, _) = self.function_lamport_transfer.last_key_value()?;
+        info!("Last function lamport {:?}", last_bb);
+        info!("Variable for balance {:?}", self.temporary_variable_for_balance);
+        // The blocks executed after the transfer, including those reached by a loop
+        let reachable_blocks = self.reachable_after(*last_bb);
+        for (bb, block_statements) in &self.block_statements {
+            if !reachable_blocks.contains(bb) {
+                continue;
+            }
+            info!("bb {:?} is reachable from last_bb {:?}", bb, last_bb);
+            for block_statement in block_statements {
+                // If the balance is assigned to a constant
+                if let BlockStatement::Statement(statement) = block_statement {
+                    if self.visit_reentrancy_statement(&statement.kind) {
+                        return Some(statement.source_info.span);
+                    }
+                }
+                // If the balance is related to arithmetic operations. E.g., balance -= amount
+                if let BlockStatement::TerminatorKind(kind) = block_statement {
+                    if self.visit_reentrancy_terminator(kind) {
+                        let span = self.terminator_spans.get(bb).copied();
+                        return Some(span.unwrap_or(rustc_span::DUMMY_SP));
+                    }
+                }
+            }
+        }
+        // If the balance is stored by a helper function called after the last transfer
+        return self
+            .helper_stores
+            .iter()
+            .find(|(bb, _)| reachable_blocks.contains(bb))
+            .map(|(_, span)| *span);
+    }
+
+    /// Check if the reentrancy happens across the instructions that the function dispatches. The
+    /// reentrancy will possibly happen if the handler of an instruction, called on an arm of a
+    /// branch on the instruction data, stores a state after a ``TRANSFER`` instruction, while the
+    /// handler called on another arm reads the same state. The transfer may reenter the program
+    /// with the other instruction, which then reads the state before it is stored, e.g. a balance
+    /// that a withdraw has not decreased yet.
+    pub fn check_across_instructions(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        guards: &GuardAnalysis<'_>,
+    ) -> bool {
+        if self.dispatch_branches.is_empty() {
+            return false;
+        }
+        let handlers: Vec<DefId> = self
+            .handler_calls
+            .iter()
+            .map(|(_, def_id, _, _)| *def_id)
+            .filter(|def_id| !self.instruction_summaries.contains_key(def_id))
+            .collect();
+        for def_id in handlers {
+            let is_transfer = |callee_path: &str| self.is_transfer(callee_path);
+            let summary = InstructionSummary::of(tcx, def_id, MAX_HELPER_DEPTH, &is_transfer)
+                .unwrap_or_default();
+            self.instruction_summaries.insert(def_id, summary);
+        }
+        let mut reentrancies: Vec<(Span, Span, Span)> = Vec::new();
+        for branch_bb in &self.dispatch_branches {
+            let arms: Vec<(mir::BasicBlock, DefId, &[Option<mir::Local>], Span)> = self
+                .handler_calls
+                .iter()
+                .filter_map(|(location, def_id, args, span)| {
+                    let arm = guards.arm_to(*branch_bb, *location)?;
+                    return Some((arm, *def_id, args.as_slice(), *span));
+                })
+                .collect();
+            for (arm, def_id, args, call_span) in &arms {
+                let summary = &self.instruction_summaries[def_id];
+                let Some(transfer_span) = summary.transfer_span else {
                     continue;
+                };
+                let stored_states: Vec<mir::Local> = summary
+                    .stored_after_transfer
+                    .iter()
+                    .filter_map(|index| args.get(*index).copied().flatten())
+                    .map(|local| self.local_sources.source_of(local))
+                    .collect();
+                let other_call = arms.iter().find(|(other_arm, other_def_id, other_args, _)| {
+                    let other_summary = &self.instruction_summaries[other_def_id];
+                    return other_arm != arm
+                        && other_def_id != def_id
+                        && other_args.iter().enumerate().any(|(index, arg)| {
+                            other_summary.read_args.contains(&index)
+                                && arg.is_some_and(|local| {
+                                    stored_states.contains(&self.local_sources.source_of(local))
+                                })
+                        });
+                });
+                if let Some((_, _, _, other_call_span)) = other_call {
+                    reentrancies.push((*call_span, transfer_span, *other_call_span));
                 }
-                info!("bb {:?}, last_bb {:?}, greater {:?}", bb, last_bb, bb > last_bb);
-                for block_statement in block_statements {
-                    // If the balance is assigned to a constant
-                    if let BlockStatement::Statement(statement) = block_statement {
-                        let mir::Statement { kind, .. } = statement;
-                        let status = self.visit_reentrancy_statement(kind);
-                        is_reentrancy = status || is_reentrancy;
-                        if is_reentrancy {
-                            break;
-                        }
+            }
+        }
+        reentrancies.sort();
+        reentrancies.dedup_by_key(|(call_span, _, _)| *call_span);
+        self.cross_instruction_spans = reentrancies;
+        return !self.cross_instruction_spans.is_empty();
+    }
+
+    /// Returns the spans of the ``LOAD``, ``TRANSFER`` and ``STORE`` instructions of the
+    /// reentrancy, if the function is reentrant and all three are known. They show the user that
+    /// the store has to be moved before the transfer, as checks-effects-interactions has it.
+    pub fn evidence_spans(&self) -> Option<(Span, Span, Span)> {
+        let store_span = self.reentrant_store_span()?;
+        return Some((self.load_span?, self.last_transfer_span()?, store_span));
+    }
+
+    /// Returns the blocks that can be executed after the terminator of the given block. They are
+    /// found by following the successors of the terminators rather than by comparing the indices
+    /// of the blocks, so a block that is reached again through the back edge of a loop is included,
+    /// as is the given block itself if it is inside a loop.
+    pub fn reachable_after(&self, bb: mir::BasicBlock) -> BTreeSet<mir::BasicBlock> {
+        let mut reachable_blocks = BTreeSet::new();
+        let mut worklist = self.successors(bb);
+        while let Some(next_bb) = worklist.pop() {
+            if reachable_blocks.insert(next_bb) {
+                worklist.extend(self.successors(next_bb));
+            }
+        }
+        return reachable_blocks;
+    }
+
+    /// Returns the successors of the terminator of the block.
+    fn successors(&self, bb: mir::BasicBlock) -> Vec<mir::BasicBlock> {
+        let Some(block_statements) = self.block_statements.get(&bb) else {
+            return Vec::new();
+        };
+        return block_statements
+            .iter()
+            .filter_map(|block_statement| match block_statement {
+                BlockStatement::TerminatorKind(kind) => Some(kind),
+                BlockStatement::Statement(_) => None,
+            })
+            .flat_map(|kind| kind.successors())
+            .collect();
+    }
+
+    /// Returns how confident the checker is of the reentrancy. A transfer that the function makes
+    /// itself is seen in its MIR, whereas a transfer made by a helper is only known from the summary
+    /// of the helper, which may transfer on a path that the caller does not take. A balance that is
+    /// read from the data of an account is the state of the contract, so a store of it after any
+    /// transfer is a reentrancy as well.
+    pub fn confidence(&self, taint_analysis: &TaintAnalysis) -> Confidence {
+        let transfers_itself = self
+            .function_lamport_transfer
+            .last_key_value()
+            .is_some_and(|(bb, _)| !self.helper_transfers.contains(bb));
+        let stores_account_data = self
+            .temporary_variable_for_balance
+            .is_some_and(|balance| taint_analysis.is_tainted_by(balance, TaintSource::AccountData));
+        return if transfers_itself || stores_account_data {
+            Confidence::High
+        } else {
+            Confidence::Medium
+        };
+    }
+
+    /// Returns the span of the last transfer of the function, which the reentrant store follows.
+    pub fn last_transfer_span(&self) -> Option<Span> {
+        let (last_bb, _) = self.function_lamport_transfer.last_key_value()?;
+        return self.transfer_spans.get(last_bb).copied();
+    }
+
+    /// Check if the function is guarded against reentrancy. The function is guarded if a flag of a
+    /// state, e.g. ``state.locked``, is read and branched on so that every ``TRANSFER`` instruction
+    /// is only reached while the flag is not set, is set to ``true`` after the read on every path
+    /// to the transfers, and is set back to ``false`` by a store that no transfer has to follow.
+    /// A reentrant call then finds the flag set and takes the other arm of the branch
+    pub fn is_guarded(&self, mir: &mir::Body<'tcx>, guards: &GuardAnalysis<'tcx>) -> bool {
+        let transfers: Vec<mir::Location> = self
+            .function_lamport_transfer
+            .keys()
+            .map(|bb| mir.terminator_loc(*bb))
+            .collect();
+        if transfers.is_empty() {
+            return false;
+        }
+        // The reads of the fields of a state, and the stores of constant flags into them
+        let mut reads: Vec<(mir::Location, mir::Place<'tcx>)> = Vec::new();
+        let mut set_flags: Vec<(mir::Location, mir::Place<'tcx>)> = Vec::new();
+        let mut cleared_flags: Vec<(mir::Location, mir::Place<'tcx>)> = Vec::new();
+        for (bb, block_data) in mir.basic_blocks.iter_enumerated() {
+            for (statement_index, statement) in block_data.statements.iter().enumerate() {
+                let location = mir::Location { block: bb, statement_index };
+                let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind else {
+                    continue;
+                };
+                match rvalue {
+                    mir::Rvalue::Use(mir::Operand::Copy(read_place))
+                        if place.projection.is_empty() && !read_place.projection.is_empty() =>
+                    {
+                        reads.push((location, *read_place));
                     }
-                    // If the balance is related to arithmetic operations. E.g., balance -= amount
-                    if let BlockStatement::TerminatorKind(kind) = block_statement {
-                        let status = self.visit_reentrancy_terminator(kind);
-                        is_reentrancy = status || is_reentrancy;
-                        if is_reentrancy {
-                            break;
+                    // The flag is a field of a state, not a local variable of the function
+                    mir::Rvalue::Use(mir::Operand::Constant(constant))
+                        if !place.projection.is_empty() =>
+                    {
+                        let value = constant.const_.try_to_scalar_int()
+                            .and_then(|scalar| scalar.try_to_bool().ok());
+                        match value {
+                            Some(true) => set_flags.push((location, *place)),
+                            Some(false) => cleared_flags.push((location, *place)),
+                            _ => {}
                         }
                     }
+                    _ => {}
                 }
             }
         }
-        return is_reentrancy;
+        info!("Reentrancy flags set {:?}, cleared {:?}", set_flags, cleared_flags);
+        return set_flags.iter().any(|(set, flag)| {
+            let is_checked = reads.iter().any(|(read, read_flag)| {
+                return read_flag == flag
+                    && guards.dominates(*read, *set)
+                    && transfers
+                        .iter()
+                        .all(|transfer| guards.branches_on_read(*read, *transfer, false));
+            });
+            let is_set = transfers.iter().all(|transfer| guards.dominates(*set, *transfer));
+            let is_cleared = cleared_flags.iter().any(|(clear, cleared_flag)| {
+                return cleared_flag == flag
+                    && guards.dominates(*set, *clear)
+                    && !transfers.iter().any(|transfer| guards.dominates(*clear, *transfer));
+            });
+            return is_checked && is_set && is_cleared;
+        });
     }
     
-    fn visit_reentrancy_terminator(&self, kind: &mir::TerminatorKind<'_>) -> bool {
+    /// Returns true if the terminator asserts that the balance does not overflow when it is
+    /// decremented, as it does for ``balance -= amount``.
+    pub fn visit_reentrancy_terminator(&self, kind: &mir::TerminatorKind<'_>) -> bool {
         if let mir::TerminatorKind::Assert { msg, .. } = kind {
             if let mir::AssertKind::Overflow(mir::BinOp::Sub, ref left_operand, _) = **msg {
                 if let mir::Operand::Copy(place) = left_operand {
@@ -97,7 +1095,8 @@ impl<'tcx> ReentrancyChecker<'tcx> {
         return false;
     }
 
-    fn visit_reentrancy_statement(&self, kind: &mir::StatementKind<'_>) -> bool {
+    /// Returns true if the statement assigns to the balance, e.g. ``balance = 0``.
+    pub fn visit_reentrancy_statement(&self, kind: &mir::StatementKind<'_>) -> bool {
         if let mir::StatementKind::Assign(box (place, _)) = kind {
             if let Some(temporary_place) = self.temporary_variable_for_balance {
                 if temporary_place.local == place.local {
@@ -110,49 +1109,486 @@ impl<'tcx> ReentrancyChecker<'tcx> {
     
 }
 
+impl<'tcx> Checker<'tcx> for ReentrancyChecker<'tcx> {
+    fn name(&self) -> &'static str {
+        return "reentrancy";
+    }
+
+    fn visit_statement(
+        &mut self,
+        _context: &CheckerContext<'_, 'tcx>,
+        location: mir::Location,
+        statement: &mir::Statement<'tcx>,
+    ) {
+        self.block_statements
+            .entry(location.block)
+            .or_insert(Vec::new())
+            .push(BlockStatement::Statement(statement.clone()));
+        let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind else {
+            return;
+        };
+        self.current_assign_destination = Some(*place);
+        self.local_sources.record(place, rvalue);
+        // The balance is copied or moved into the assigned place, or into an aggregate of it
+        match rvalue {
+            mir::Rvalue::Use(operand) | mir::Rvalue::ShallowInitBox(operand, _) => {
+                self.visit_balance_use(operand);
+            }
+            mir::Rvalue::Aggregate(_, operands) => {
+                for operand in operands {
+                    self.visit_balance_use(operand);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// A branch on the instruction data dispatches the instructions to their handlers
+    fn visit_terminator(
+        &mut self,
+        context: &CheckerContext<'_, 'tcx>,
+        location: mir::Location,
+        terminator: &mir::Terminator<'tcx>,
+        callee: Option<&ResolvedCallee>,
+    ) {
+        let bb = location.block;
+        let span = terminator.source_info.span;
+        self.block_statements
+            .entry(bb)
+            .or_insert(Vec::new())
+            .push(BlockStatement::TerminatorKind(terminator.kind.clone()));
+        self.terminator_spans.insert(bb, span);
+        if let mir::TerminatorKind::SwitchInt { discr, .. } = &terminator.kind {
+            let is_dispatch = discr.place().is_some_and(|discr_place| {
+                context.taint_analysis.is_tainted_by(discr_place, TaintSource::InstructionData)
+            });
+            if is_dispatch {
+                self.dispatch_branches.push(bb);
+            }
+        }
+        if let (Some(callee), Some((args, destination))) = (callee, call_of(terminator)) {
+            self.visit_reentrancy_call(context.tcx, location, callee, args, destination, span);
+        }
+    }
+
+    /// A function guarded by a reentrancy flag is reported as configured
+    fn finish(&mut self, context: &CheckerContext<'_, 'tcx>) -> Vec<CheckerFinding> {
+        let severity = if !self.is_guarded(context.mir, context.guards) {
+            Some(Severity::Critical)
+        } else {
+            match self.guarded_reentrancy {
+                GuardedReentrancy::Report => Some(Severity::Critical),
+                GuardedReentrancy::Downgrade => Some(Severity::Info),
+                GuardedReentrancy::Suppress => None,
+            }
+        };
+        let Some(severity) = severity else {
+            return Vec::new();
+        };
+        let mut findings: Vec<CheckerFinding> = Vec::new();
+        if self.check() {
+            self.ending_reentrancy_span = context.last_span.hi();
+            let span = Span::new(
+                self.starting_reentrancy_span,
+                self.ending_reentrancy_span,
+                SyntaxContext::root(),
+                None,
+            );
+            let mut finding = CheckerFinding::new(
+                self.name(),
+                severity,
+                span,
+                "possible reentrancy for the smart contract",
+            );
+            finding.confidence = self.confidence(context.taint_analysis);
+            finding.related = self
+                .last_transfer_span()
+                .map(|transfer_span| (transfer_span, "the lamports are transferred here"))
+                .into_iter()
+                .collect();
+            finding.evidence = self.evidence_spans();
+            findings.push(finding);
+        }
+        // A finding for each handler of an instruction that another instruction may reenter
+        if self.check_across_instructions(context.tcx, context.guards) {
+            for (call_span, transfer_span, other_call_span) in &self.cross_instruction_spans {
+                let mut finding = CheckerFinding::new(
+                    self.name(),
+                    severity,
+                    *call_span,
+                    "possible reentrancy across instructions for the smart contract",
+                );
+                finding.related = vec![
+                    (
+                        *transfer_span,
+                        "the lamports are transferred here, before the state is stored",
+                    ),
+                    (*other_call_span, "another instruction reads the state here"),
+                ];
+                findings.push(finding);
+            }
+        }
+        return findings;
+    }
+}
+
+// The locals that hold the clock, or a value derived from the timestamp or the slot of the clock
+#[derive(Default)]
+pub struct ClockSources {
+    // The locals that hold the clock, e.g. the result of ``Clock::get()``
+    pub clocks: HashSet<mir::Local>,
+    // The locals that hold the timestamp or the slot of the clock, or a value derived from them
+    pub clock_values: HashSet<mir::Local>,
+}
+
+impl ClockSources {
+    /// Returns true if the place holds a value derived from the timestamp or the slot of the clock,
+    /// or reads the timestamp or the slot field of a clock.
+    pub fn is_clock_time<'tcx>(
+        &self,
+        context: &CheckerContext<'_, 'tcx>,
+        place: &mir::Place<'tcx>,
+    ) -> bool {
+        return self.clock_values.contains(&place.local)
+            || (self.clocks.contains(&place.local)
+                && matches!(context.field_name(place).as_deref(), Some("unix_timestamp" | "slot")));
+    }
+
+    /// Record that the assigned local holds the clock or a value derived from the clock if the
+    /// rvalue uses one. Returns true if the rvalue uses a value derived from the timestamp or the
+    /// slot of the clock.
+    pub fn record<'tcx>(
+        &mut self,
+        context: &CheckerContext<'_, 'tcx>,
+        place: &mir::Place<'tcx>,
+        rvalue: &mir::Rvalue<'tcx>,
+    ) -> bool {
+        let used_places = used_places(rvalue);
+        let uses_clock_time = used_places.iter().any(|used| self.is_clock_time(context, used));
+        if !place.projection.is_empty() {
+            return uses_clock_time;
+        }
+        if uses_clock_time {
+            self.clock_values.insert(place.local);
+        }
+        let is_copy = matches!(
+            rvalue,
+            mir::Rvalue::Use(_) | mir::Rvalue::Ref(..) | mir::Rvalue::CopyForDeref(_)
+        );
+        if is_copy && used_places.iter().any(|used| self.clocks.contains(&used.local)) {
+            self.clocks.insert(place.local);
+        }
+        return uses_clock_time;
+    }
+
+    /// Record that the destination of the call holds the clock if the callee reads it, or if the
+    /// callee is given the clock, and a value derived from the clock if the callee is given one.
+    /// Returns true if the callee is given a value derived from the timestamp or the slot.
+    pub fn record_call<'tcx>(
+        &mut self,
+        context: &CheckerContext<'_, 'tcx>,
+        callee: &ResolvedCallee,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        destination: mir::Place<'tcx>,
+    ) -> bool {
+        let is_clock_type =
+            callee.name.contains("Clock") || callee.argument_type_key.contains("clock_Clock");
+        if is_clock_type
+            && (callee.name.ends_with(".get") || callee.name.contains("from_account_info"))
+        {
+            self.clocks.insert(destination.local);
+        }
+        let arg_places: Vec<mir::Place<'tcx>> =
+            args.iter().filter_map(|arg| arg.node.place()).collect();
+        let has_clock_time_arg = arg_places.iter().any(|arg| self.is_clock_time(context, arg));
+        let has_clock_arg = arg_places.iter().any(|arg| self.clocks.contains(&arg.local));
+        if destination.projection.is_empty() {
+            if has_clock_time_arg {
+                self.clock_values.insert(destination.local);
+            } else if has_clock_arg {
+                self.clocks.insert(destination.local);
+            }
+        }
+        return has_clock_time_arg;
+    }
+}
+
 // Hold states for the bad radomness
 pub struct BadrandomnessChecker {
-    // Check if the rand lib is used
+    // Check if the rand lib is used, or a value of the chain is used as a random selection
     pub check_for_rand_lib: bool,
+    // The locals that hold the recent blockhashes, or a value derived from them or from the
+    // timestamp or the slot of the clock
+    pub chain_values: HashSet<mir::Local>,
      // The span contains codes related to bad randomness
      pub bad_randomness_span: Span,
+    // The locals that hold the clock or a value derived from it
+    pub clock_sources: ClockSources,
 }
 
 impl BadrandomnessChecker {
     pub fn new() -> BadrandomnessChecker {
         return BadrandomnessChecker { 
             check_for_rand_lib: false, 
-            bad_randomness_span: rustc_span::DUMMY_SP
+            chain_values: HashSet::default(),
+            bad_randomness_span: rustc_span::DUMMY_SP,
+            clock_sources: ClockSources::default()
+        }
+    }
+    /// Record that the assigned local is derived from a value of the chain if the rvalue uses one.
+    /// ``uses_clock_time`` is true if the rvalue uses a value derived from the timestamp or the
+    /// slot of the clock. The remainder of a value of the chain, e.g. ``clock.slot % players``, is
+    /// a random selection.
+    pub fn record(
+        &mut self,
+        place: &mir::Place<'_>,
+        rvalue: &mir::Rvalue<'_>,
+        uses_clock_time: bool,
+        span: Span,
+    ) {
+        let is_derived = uses_clock_time
+            || match rvalue {
+                mir::Rvalue::Use(operand)
+                | mir::Rvalue::Cast(_, operand, _)
+                | mir::Rvalue::UnaryOp(_, operand) => {
+                    operand.place().is_some_and(|used| self.chain_values.contains(&used.local))
+                }
+                mir::Rvalue::BinaryOp(_, box (left, right)) => [left, right]
+                    .iter()
+                    .filter_map(|operand| operand.place())
+                    .any(|used| self.chain_values.contains(&used.local)),
+                mir::Rvalue::Ref(_, _, used) | mir::Rvalue::CopyForDeref(used) => {
+                    self.chain_values.contains(&used.local)
+                }
+                _ => false,
+            };
+        if !is_derived {
+            return;
+        }
+        if let mir::Rvalue::BinaryOp(mir::BinOp::Rem, _) = rvalue {
+            self.select_randomly(span);
+        }
+        if place.projection.is_empty() {
+            self.chain_values.insert(place.local);
+        }
+    }
+
+    /// Record that a predictable source of randomness is used at the span.
+    pub fn select_randomly(&mut self, span: Span) {
+        if !self.check_for_rand_lib {
+            self.check_for_rand_lib = true;
+            self.bad_randomness_span = span;
         }
     }
 
-    /// Check if the bad randomness happens. The bad randomness will possibly happens if 
-    /// ``solana_program::sysvar::clock::Clock`` is used
+    /// Check if the bad randomness happens. The bad randomness will possibly happens if a rand
+    /// lib is used outside of the tests, or if the remainder of the slot or the timestamp of
+    /// ``solana_program::sysvar::clock::Clock``, or of the recent blockhashes, picks a value
     pub fn check(&self) -> bool {
         return self.check_for_rand_lib;
     }
 }
 
+impl<'tcx> Checker<'tcx> for BadrandomnessChecker {
+    fn name(&self) -> &'static str {
+        return "bad_randomness";
+    }
+
+    fn visit_statement(
+        &mut self,
+        context: &CheckerContext<'_, 'tcx>,
+        _location: mir::Location,
+        statement: &mir::Statement<'tcx>,
+    ) {
+        if let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind {
+            let uses_clock_time = self.clock_sources.record(context, place, rvalue);
+            self.record(place, rvalue, uses_clock_time, statement.source_info.span);
+        }
+    }
+
+    /// A rand lib in the tests is harmless. The recent blockhashes, and the values derived from
+    /// them or from the clock, flow through the calls, and are a random selection if their
+    /// remainder is taken
+    fn visit_terminator(
+        &mut self,
+        context: &CheckerContext<'_, 'tcx>,
+        _location: mir::Location,
+        terminator: &mir::Terminator<'tcx>,
+        callee: Option<&ResolvedCallee>,
+    ) {
+        let (Some(callee), Some((args, destination))) = (callee, call_of(terminator)) else {
+            return;
+        };
+        let span = terminator.source_info.span;
+        if (callee.name.contains("fastrand")
+            || callee.name.contains("oorandom.implement_oorandom")
+            || callee.name.contains("nanorand.rand"))
+            && !utils::is_test_code(context.tcx, context.def_id)
+        {
+            self.select_randomly(span);
+        }
+        self.clock_sources.record_call(context, callee, args, destination);
+        let has_chain_arg = args.iter().filter_map(|arg| arg.node.place()).any(|arg| {
+            self.chain_values.contains(&arg.local)
+                || self.clock_sources.is_clock_time(context, &arg)
+        });
+        let is_recent_blockhashes = callee.name.contains("recent_blockhashes")
+            || callee.name.contains("RecentBlockhashes")
+            || callee.argument_type_key.contains("RecentBlockhashes");
+        if has_chain_arg {
+            let is_remainder = [".checked_rem", ".wrapping_rem", ".rem_euclid"]
+                .iter()
+                .any(|remainder| callee.name.ends_with(remainder));
+            if is_remainder {
+                self.select_randomly(span);
+            }
+        }
+        if (has_chain_arg || is_recent_blockhashes) && destination.projection.is_empty() {
+            self.chain_values.insert(destination.local);
+        }
+    }
+
+    fn finish(&mut self, _context: &CheckerContext<'_, 'tcx>) -> Vec<CheckerFinding> {
+        if !self.check() {
+            return Vec::new();
+        }
+        return vec![CheckerFinding::new(
+            self.name(),
+            Severity::Medium,
+            self.bad_randomness_span,
+            "possible bad randomness for the smart contract",
+        )];
+    }
+}
+
 // Hold states for the time manipulation
 pub struct TimeManipulationChecker {
-    // Check if the clock lib is used
-    pub check_for_clock_lib: bool,
+    // The locals that hold the clock or a value derived from it
+    pub clock_sources: ClockSources,
+    // The locals that hold the lamports of an account, or a reference to them
+    pub lamports: HashSet<mir::Local>,
+    // Check if a value derived from the clock decides a branch or a transfer of funds
+    pub check_for_clock_use: bool,
      // The span contains codes related to time manipulation
      pub time_manipulation_span: Span,
 }
 
 impl TimeManipulationChecker {
     pub fn new() -> TimeManipulationChecker {
-        return TimeManipulationChecker { 
-            check_for_clock_lib: false, 
+        return TimeManipulationChecker {
+            clock_sources: ClockSources::default(),
+            lamports: HashSet::default(),
+            check_for_clock_use: false,
             time_manipulation_span: rustc_span::DUMMY_SP
         }
     }
 
-    /// Check if the bad randomness happens. The bad randomness will possibly happens if 
-    /// ``solana_program::sysvar::clock::Clock`` is used
+    /// Record that the assigned local holds the lamports of an account if the rvalue uses them.
+    /// ``reads_clock_time`` is true if the rvalue uses a value derived from the timestamp or the
+    /// slot of the clock. Storing a value derived from the clock into the lamports of an account
+    /// is a use of the clock.
+    pub fn record(
+        &mut self,
+        place: &mir::Place<'_>,
+        rvalue: &mir::Rvalue<'_>,
+        reads_clock_time: bool,
+        span: Span,
+    ) {
+        if !place.projection.is_empty() {
+            if reads_clock_time && self.lamports.contains(&place.local) {
+                self.use_clock(span);
+            }
+            return;
+        }
+        let is_copy = matches!(
+            rvalue,
+            mir::Rvalue::Use(_) | mir::Rvalue::Ref(..) | mir::Rvalue::CopyForDeref(_)
+        );
+        if is_copy && used_places(rvalue).iter().any(|used| self.lamports.contains(&used.local)) {
+            self.lamports.insert(place.local);
+        }
+    }
+    /// Record that a value derived from the clock decides the branch or the transfer at the span.
+    pub fn use_clock(&mut self, span: Span) {
+        if !self.check_for_clock_use {
+            self.check_for_clock_use = true;
+            self.time_manipulation_span = span;
+        }
+    }
+
+    /// Check if the time manipulation happens. The time manipulation will possibly happens if
+    /// the timestamp or the slot of ``solana_program::sysvar::clock::Clock`` flows into a branch
+    /// condition or into the amount of a transfer, whereas only logging it is not reported
     pub fn check(&self) -> bool {
-        return self.check_for_clock_lib;
+        return self.check_for_clock_use;
+    }
+}
+
+impl<'tcx> Checker<'tcx> for TimeManipulationChecker {
+    fn name(&self) -> &'static str {
+        return "time_manipulation";
+    }
+
+    fn visit_statement(
+        &mut self,
+        context: &CheckerContext<'_, 'tcx>,
+        _location: mir::Location,
+        statement: &mir::Statement<'tcx>,
+    ) {
+        if let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind {
+            let reads_clock_time = self.clock_sources.record(context, place, rvalue);
+            self.record(place, rvalue, reads_clock_time, statement.source_info.span);
+        }
+    }
+
+    /// Branching on the timestamp or the slot of the clock is a time manipulation, as is passing
+    /// them to a transfer
+    fn visit_terminator(
+        &mut self,
+        context: &CheckerContext<'_, 'tcx>,
+        _location: mir::Location,
+        terminator: &mir::Terminator<'tcx>,
+        callee: Option<&ResolvedCallee>,
+    ) {
+        let span = terminator.source_info.span;
+        if let mir::TerminatorKind::SwitchInt { discr, .. } = &terminator.kind {
+            if let Some(discr_place) = discr.place() {
+                if self.clock_sources.is_clock_time(context, &discr_place) {
+                    self.use_clock(span);
+                }
+            }
+        }
+        let (Some(callee), Some((args, destination))) = (callee, call_of(terminator)) else {
+            return;
+        };
+        let has_clock_time_arg =
+            self.clock_sources.record_call(context, callee, args, destination);
+        let has_lamports_arg = args
+            .iter()
+            .filter_map(|arg| arg.node.place())
+            .any(|arg| self.lamports.contains(&arg.local));
+        if destination.projection.is_empty()
+            && (has_lamports_arg || callee.name.contains("try_borrow_mut_lamports"))
+        {
+            self.lamports.insert(destination.local);
+        }
+        if has_clock_time_arg
+            && (callee.name.ends_with(".transfer") || callee.name.contains("invoke"))
+        {
+            self.use_clock(span);
+        }
+    }
+
+    fn finish(&mut self, _context: &CheckerContext<'_, 'tcx>) -> Vec<CheckerFinding> {
+        if !self.check() {
+            return Vec::new();
+        }
+        return vec![CheckerFinding::new(
+            self.name(),
+            Severity::Medium,
+            self.time_manipulation_span,
+            "possible time manipulation for the smart contract",
+        )];
     }
 }
 
@@ -162,24 +1598,2854 @@ pub struct NumericalPrecisionErrorChecker {
     pub check_for_round_func: bool,
     // The span contains codes related to numerical precision error
     pub numerical_precision_error_span: Span,
+    // The locals that hold the quotient of an integer division, or a cast of it
+    pub quotients: HashSet<mir::Local>,
+    // The spans of the multiplications of a quotient
+    pub quotient_multiplications: Vec<Span>,
+    // The spans contain codes related to the division before the multiplication
+    pub division_before_multiplication_spans: Vec<Span>
 }
 
 impl NumericalPrecisionErrorChecker {
     pub fn new() -> NumericalPrecisionErrorChecker {
         return NumericalPrecisionErrorChecker {
             check_for_round_func: false,
-            numerical_precision_error_span: rustc_span::DUMMY_SP
+            numerical_precision_error_span: rustc_span::DUMMY_SP,
+            quotients: HashSet::default(),
+            quotient_multiplications: Vec::new(),
+            division_before_multiplication_spans: Vec::new()
         }
     }
 
-    /// Check if the numerical precision error happens. The numerical precision error will 
-    /// possibly happens if ``round`` function owned by ``float`` data type is used
-    pub fn check(&self) -> bool {
-        return self.check_for_round_func;
-    }
+    /// Record that the assigned local holds a quotient if the rvalue divides integers, or casts a
+    /// quotient, and that the rvalue at the span multiplies a quotient.
+    pub fn record(
+        &mut self,
+        place: &mir::Place<'_>,
+        rvalue: &mir::Rvalue<'_>,
+        is_integer: bool,
+        span: Span,
+    ) {
+        match rvalue {
+            mir::Rvalue::BinaryOp(mir::BinOp::Div, _) if is_integer => {
+                if place.projection.is_empty() {
+                    self.quotients.insert(place.local);
+                }
+            }
+            mir::Rvalue::Use(operand) | mir::Rvalue::Cast(_, operand, _) => {
+                if operand.place().is_some_and(|used| self.quotients.contains(&used.local))
+                    && place.projection.is_empty()
+                {
+                    self.quotients.insert(place.local);
+                }
+            }
+            mir::Rvalue::BinaryOp(
+                mir::BinOp::Mul | mir::BinOp::MulWithOverflow | mir::BinOp::MulUnchecked,
+                box (left, right),
+            ) => {
+                if [left, right]
+                    .iter()
+                    .filter_map(|operand| operand.place())
+                    .any(|used| self.quotients.contains(&used.local))
+                {
+                    self.quotient_multiplications.push(span);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Check if the numerical precision error happens. The numerical precision error will 
+    /// possibly happens if ``round`` function owned by ``float`` data type is used
+    pub fn check(&self) -> bool {
+        return self.check_for_round_func;
+    }
+
+    /// Check if a quotient of integers is multiplied, e.g. ``amount / total * reward``, which
+    /// loses the remainder of the division, whereas ``amount * reward / total`` does not. This is
+    /// reported by its own rule, so that it can be filtered apart from the rounding of floats
+    pub fn check_division_before_multiplication(&mut self) -> bool {
+        let mut spans = self.quotient_multiplications.clone();
+        spans.sort();
+        spans.dedup();
+        self.division_before_multiplication_spans = spans;
+        return !self.division_before_multiplication_spans.is_empty();
+    }
+}
+
+impl<'tcx> Checker<'tcx> for NumericalPrecisionErrorChecker {
+    fn name(&self) -> &'static str {
+        return "numerical_precision";
+    }
+
+    /// The quotients of integers and their multiplications are here
+    fn visit_statement(
+        &mut self,
+        context: &CheckerContext<'_, 'tcx>,
+        _location: mir::Location,
+        statement: &mir::Statement<'tcx>,
+    ) {
+        if let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind {
+            let is_integer = place.ty(&context.mir.local_decls, context.tcx).ty.is_integral();
+            self.record(place, rvalue, is_integer, statement.source_info.span);
+        }
+    }
+
+    /// A checked division produces a quotient, which flows through the unwrapping of the result
+    fn visit_terminator(
+        &mut self,
+        _context: &CheckerContext<'_, 'tcx>,
+        _location: mir::Location,
+        terminator: &mir::Terminator<'tcx>,
+        callee: Option<&ResolvedCallee>,
+    ) {
+        let (Some(callee), Some((args, destination))) = (callee, call_of(terminator)) else {
+            return;
+        };
+        let span = terminator.source_info.span;
+        if callee.name.contains(".round") {
+            self.check_for_round_func = true;
+            self.numerical_precision_error_span = span;
+        }
+        let has_quotient_arg = args
+            .iter()
+            .filter_map(|arg| arg.node.place())
+            .any(|arg| self.quotients.contains(&arg.local));
+        let is_division = [".checked_div", ".wrapping_div", ".saturating_div"]
+            .iter()
+            .any(|division| callee.name.ends_with(division));
+        let is_multiplication = [".checked_mul", ".wrapping_mul", ".saturating_mul"]
+            .iter()
+            .any(|multiplication| callee.name.ends_with(multiplication));
+        let is_unwrapping = [".unwrap", ".expect", ".ok_or", ".branch"]
+            .iter()
+            .any(|unwrapping| callee.name.ends_with(unwrapping));
+        if has_quotient_arg && is_multiplication {
+            self.quotient_multiplications.push(span);
+        }
+        if (is_division || (has_quotient_arg && is_unwrapping))
+            && destination.projection.is_empty()
+        {
+            self.quotients.insert(destination.local);
+        }
+    }
+
+    /// The multiplications of a quotient of integers are reported by their own rule
+    fn finish(&mut self, _context: &CheckerContext<'_, 'tcx>) -> Vec<CheckerFinding> {
+        let mut findings: Vec<CheckerFinding> = Vec::new();
+        if self.check() {
+            findings.push(CheckerFinding::new(
+                self.name(),
+                Severity::Low,
+                self.numerical_precision_error_span,
+                "possible numerical precision error for the smart contract",
+            ));
+        }
+        if self.check_division_before_multiplication() {
+            for span in &self.division_before_multiplication_spans {
+                findings.push(CheckerFinding::new(
+                    "division_before_multiplication",
+                    Severity::Medium,
+                    *span,
+                    "possible precision loss from dividing before multiplying, prefer multiplying first for the smart contract",
+                ));
+            }
+        }
+        return findings;
+    }
+}
+
+// Hold states for the saturating arithmetic masking an error
+pub struct SaturatingArithmeticChecker {
+    // The saturating operations with the locals of their arguments, keyed by the call location
+    pub saturating_operations: HashMap<mir::Location, (Span, Vec<mir::Local>)>,
+    // The locals that are compared by an ordering comparison, e.g. ``balance < amount``
+    pub compared_locals: HashSet<mir::Local>,
+    // The local that a temporary variable is copied or moved from
+    pub local_sources: LocalSources,
+    // The span contains codes related to saturating arithmetic masking an error
+    pub saturating_arithmetic_span: Span,
+}
+
+impl SaturatingArithmeticChecker {
+    pub fn new() -> SaturatingArithmeticChecker {
+        return SaturatingArithmeticChecker {
+            saturating_operations: HashMap::default(),
+            compared_locals: HashSet::default(),
+            local_sources: LocalSources::default(),
+            saturating_arithmetic_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Check if the saturating arithmetic masks an error. The error will possibly be masked if
+    /// ``saturating_sub`` or ``saturating_add`` is applied to a balance without any of its operands
+    /// being compared beforehand, so an amount that should have been rejected is silently clamped.
+    pub fn check(&mut self) -> bool {
+        let compared_sources: HashSet<mir::Local> = self
+            .compared_locals
+            .iter()
+            .map(|local| self.local_sources.source_of(*local))
+            .collect();
+        let mut unguarded_spans: Vec<Span> = self
+            .saturating_operations
+            .values()
+            .filter(|(_, locals)| {
+                !locals
+                    .iter()
+                    .any(|local| compared_sources.contains(&self.local_sources.source_of(*local)))
+            })
+            .map(|(span, _)| *span)
+            .collect();
+        unguarded_spans.sort();
+        if let Some(span) = unguarded_spans.first() {
+            self.saturating_arithmetic_span = *span;
+            return true;
+        }
+        return false;
+    }
+}
+
+// Hold states for the clock read from a passed account
+pub struct ClockAccountReadChecker {
+    // Check if the clock is deserialized from an account passed to the instruction
+    pub check_for_clock_account_read: bool,
+    // The span contains codes related to the manual clock account read
+    pub clock_account_read_span: Span,
+}
+
+impl ClockAccountReadChecker {
+    pub fn new() -> ClockAccountReadChecker {
+        return ClockAccountReadChecker {
+            check_for_clock_account_read: false,
+            clock_account_read_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Check if the clock is read manually from an account. The manual read will possibly be unsafe if
+    /// ``Clock::from_account_info`` is used, since the key of the passed account has to be validated
+    /// against the clock sysvar, whereas ``Clock::get`` is validated by the runtime
+    pub fn check(&self) -> bool {
+        return self.check_for_clock_account_read;
+    }
+}
+
+// Hold states for the panicking slice operations on untrusted lengths
+pub struct SlicePanicChecker {
+    // The byte slices passed in as arguments, whose lengths are controlled by the caller
+    pub tainted_slices: HashSet<mir::Local>,
+    // The borrows of account data, whose lengths are controlled by the caller like the arguments
+    pub account_data: HashSet<mir::Local>,
+    // The locals holding the length of a slice, mapped to the local holding that slice
+    pub slice_lengths: HashMap<mir::Local, mir::Local>,
+    // The locals that are compared by an ordering or equality comparison, e.g. ``data.len() < 8``
+    pub compared_locals: HashSet<mir::Local>,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The calls to ``split_at`` and ``chunks_exact`` with the local of the slice, keyed by the call location
+    pub panicking_calls: HashMap<mir::Location, (Span, mir::Local)>,
+    // The bounds checked indexing with the local of the slice length, keyed by the assert location
+    pub indexing_operations: HashMap<mir::Location, (Span, mir::Local)>,
+    // The results of ``try_into`` on a slice, mapped to the local of the slice
+    pub conversions: HashMap<mir::Local, mir::Local>,
+    // The calls to ``unwrap`` and ``expect`` on a conversion with the local of the converted slice,
+    // keyed by the call location
+    pub unwrapped_conversions: HashMap<mir::Location, (Span, mir::Local)>,
+    // The spans contain codes related to panicking slice operations
+    pub slice_panic_spans: Vec<Span>,
+    // The spans of the unwrapped conversions of untrusted slices
+    pub unwrapped_conversion_spans: Vec<Span>,
+}
+
+impl SlicePanicChecker {
+    pub fn new() -> SlicePanicChecker {
+        return SlicePanicChecker {
+            tainted_slices: HashSet::default(),
+            account_data: HashSet::default(),
+            slice_lengths: HashMap::default(),
+            compared_locals: HashSet::default(),
+            local_sources: LocalSources::default(),
+            panicking_calls: HashMap::default(),
+            indexing_operations: HashMap::default(),
+            conversions: HashMap::default(),
+            unwrapped_conversions: HashMap::default(),
+            slice_panic_spans: Vec::new(),
+            unwrapped_conversion_spans: Vec::new()
+        }
+    }
+
+    /// Check if a slice operation panics on an untrusted length. The operation will possibly panic if
+    /// ``split_at``, ``chunks_exact`` or direct indexing is applied to a byte slice passed in as an argument,
+    /// e.g. the instruction data, or borrowed from the data of an account, without its length being
+    /// compared beforehand. Indexing by a range, e.g. ``instruction_data[1..9]``, panics like
+    /// indexing by a position, and so does unwrapping the ``try_into`` of such a subslice into an
+    /// array. The lengths loaded by the compiler for its own bounds checks do not count as a
+    /// validation.
+    pub fn check(&mut self) -> bool {
+        let bounds_check_lengths: HashSet<mir::Local> = self
+            .indexing_operations
+            .values()
+            .map(|(_, length)| *length)
+            .collect();
+        let compared_sources: HashSet<mir::Local> = self
+            .compared_locals
+            .iter()
+            .map(|local| self.local_sources.source_of(*local))
+            .collect();
+        let validated_slices: HashSet<mir::Local> = self
+            .slice_lengths
+            .iter()
+            .filter(|(length, _)| {
+                compared_sources.contains(length) && !bounds_check_lengths.contains(length)
+            })
+            .map(|(_, slice)| self.local_sources.source_of(*slice))
+            .collect();
+        let indexed_slices = self
+            .indexing_operations
+            .values()
+            .filter_map(|(span, length)| self.slice_lengths.get(length).map(|slice| (*span, *slice)));
+        let is_risky = |slice: &mir::Local| {
+            let source = self.local_sources.source_of(*slice);
+            let is_tainted =
+                self.tainted_slices.contains(&source) || self.account_data.contains(&source);
+            return is_tainted && !validated_slices.contains(&source);
+        };
+        let mut risky_spans: Vec<Span> = self
+            .panicking_calls
+            .values()
+            .copied()
+            .chain(indexed_slices)
+            .filter(|(_, slice)| is_risky(slice))
+            .map(|(span, _)| span)
+            .collect();
+        risky_spans.sort();
+        risky_spans.dedup();
+        let mut unwrapped_spans: Vec<Span> = self
+            .unwrapped_conversions
+            .values()
+            .filter(|(_, slice)| is_risky(slice))
+            .map(|(span, _)| *span)
+            .collect();
+        unwrapped_spans.sort();
+        unwrapped_spans.dedup();
+        self.slice_panic_spans = risky_spans;
+        self.unwrapped_conversion_spans = unwrapped_spans;
+        return !self.slice_panic_spans.is_empty() || !self.unwrapped_conversion_spans.is_empty();
+    }
+}
+
+// Hold states for the transfer from an account to itself
+pub struct SelfTransferChecker {
+    // The transfers with the locals of their source and destination, keyed by the call location
+    pub transfers: HashMap<mir::Location, (Span, mir::Local, mir::Local)>,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The span contains codes related to the self transfer
+    pub self_transfer_span: Span,
+}
+
+impl SelfTransferChecker {
+    pub fn new() -> SelfTransferChecker {
+        return SelfTransferChecker {
+            transfers: HashMap::default(),
+            local_sources: LocalSources::default(),
+            self_transfer_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Check if the source and the destination of a transfer are the same account. The self transfer
+    /// will possibly happen if both the source and destination keys are taken from the same local,
+    /// e.g. ``system_instruction::transfer(&account.key, &account.key, amount)``, which leaves the
+    /// balances unchanged and can mask balance bugs
+    pub fn check(&mut self) -> bool {
+        let mut self_transfer_spans: Vec<Span> = self
+            .transfers
+            .values()
+            .filter(|(_, source, destination)| {
+                self.local_sources.source_of(*source) == self.local_sources.source_of(*destination)
+            })
+            .map(|(span, _, _)| *span)
+            .collect();
+        self_transfer_spans.sort();
+        if let Some(span) = self_transfer_spans.first() {
+            self.self_transfer_span = *span;
+            return true;
+        }
+        return false;
+    }
+}
+
+impl<'tcx> Checker<'tcx> for SelfTransferChecker {
+    fn name(&self) -> &'static str {
+        return "self_transfer";
+    }
+
+    fn visit_statement(
+        &mut self,
+        _context: &CheckerContext<'_, 'tcx>,
+        _location: mir::Location,
+        statement: &mir::Statement<'tcx>,
+    ) {
+        if let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind {
+            self.local_sources.record(place, rvalue);
+        }
+    }
+
+    /// Token program transfers take the program id as their first argument
+    fn visit_terminator(
+        &mut self,
+        _context: &CheckerContext<'_, 'tcx>,
+        location: mir::Location,
+        terminator: &mir::Terminator<'tcx>,
+        callee: Option<&ResolvedCallee>,
+    ) {
+        let (Some(callee), Some((args, _))) = (callee, call_of(terminator)) else {
+            return;
+        };
+        let span = terminator.source_info.span;
+        let callee_name = &callee.name;
+        if !callee_name.ends_with(".transfer") {
+            return;
+        }
+        let (source_index, destination_index) =
+            if !callee_name.contains("system_instruction") && callee_name.contains("token") {
+                (1, 2)
+            } else {
+                (0, 1)
+            };
+        let source = args.get(source_index).and_then(|arg| arg.node.place());
+        let destination = args.get(destination_index).and_then(|arg| arg.node.place());
+        if let (Some(source), Some(destination)) = (source, destination) {
+            self.transfers.insert(location, (span, source.local, destination.local));
+        }
+    }
+
+    fn finish(&mut self, _context: &CheckerContext<'_, 'tcx>) -> Vec<CheckerFinding> {
+        if !self.check() {
+            return Vec::new();
+        }
+        return vec![CheckerFinding::new(
+            self.name(),
+            Severity::Medium,
+            self.self_transfer_span,
+            "possible self transfer for the smart contract",
+        )];
+    }
+}
+
+// Hold states for the accounts that are debited and credited, or otherwise both mutated, without
+// checking that they differ
+pub struct DistinctAccountsChecker {
+    // The accounts whose lamports are borrowed mutably, keyed by the call location
+    pub lamport_borrows: HashMap<mir::Location, (Span, mir::Local)>,
+    // The accounts whose data is borrowed mutably, keyed by the call location
+    pub data_borrows: HashMap<mir::Location, (Span, mir::Local)>,
+    // The pairs of locals that are compared for (in)equality, e.g. the keys of two accounts
+    pub compared_pairs: HashSet<(mir::Local, mir::Local)>,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The span contains codes related to the missing inequality check
+    pub distinct_accounts_span: Span,
+    // True if the data of one of the accounts that are not compared is mutated
+    pub is_data_mutation: bool,
+}
+
+impl DistinctAccountsChecker {
+    pub fn new() -> DistinctAccountsChecker {
+        return DistinctAccountsChecker {
+            lamport_borrows: HashMap::default(),
+            data_borrows: HashMap::default(),
+            compared_pairs: HashSet::default(),
+            local_sources: LocalSources::default(),
+            distinct_accounts_span: rustc_span::DUMMY_SP,
+            is_data_mutation: false
+        }
+    }
+
+    /// Check if the lamports of two different account locals are both mutably borrowed, i.e. one
+    /// account is debited and the other one is credited, while the accounts are never compared,
+    /// e.g. ``vault.key != recipient.key``. If the caller can pass the same account for both roles,
+    /// the vault can be drained to pay the vault itself. Mutating the data of two accounts, or the
+    /// data of one account and the lamports of another one, is checked alike, since passing the
+    /// same account twice lets the second mutation count the first one again
+    pub fn check(&mut self) -> bool {
+        let compared_accounts: HashSet<(mir::Local, mir::Local)> = self
+            .compared_pairs
+            .iter()
+            .map(|(left, right)| {
+                (self.local_sources.source_of(*left), self.local_sources.source_of(*right))
+            })
+            .collect();
+        let lamport_borrows = self.lamport_borrows.iter().map(|borrow| (borrow, false));
+        let data_borrows = self.data_borrows.iter().map(|borrow| (borrow, true));
+        let mut borrows: Vec<(mir::Location, Span, mir::Local, bool)> = lamport_borrows
+            .chain(data_borrows)
+            .map(|((location, (span, account)), is_data)| {
+                (*location, *span, self.local_sources.source_of(*account), is_data)
+            })
+            .collect();
+        borrows.sort_by_key(|(location, _, _, _)| *location);
+        for (index, (_, span, credited, is_credited_data)) in borrows.iter().enumerate() {
+            for (_, _, debited, is_debited_data) in &borrows[..index] {
+                // Borrowing the same local twice is the business of the self transfer checker
+                if debited == credited {
+                    continue;
+                }
+                if !compared_accounts.contains(&(*debited, *credited))
+                    && !compared_accounts.contains(&(*credited, *debited))
+                {
+                    self.distinct_accounts_span = *span;
+                    self.is_data_mutation = *is_credited_data || *is_debited_data;
+                    return true;
+                }
+            }
+        }
+        return false;
+    }
+}
+
+// Hold states for the logging of whole account data or instruction data buffers
+pub struct LoggedDataChecker {
+    // Check for detecting a call to one of the logging functions, e.g. by msg!
+    pub check_for_log: bool,
+    // The spans of the byte buffers that are formatted or logged as a whole
+    pub logged_buffers: HashMap<mir::Location, Span>,
+    // The span contains codes related to the logged data
+    pub logged_data_span: Span,
+}
+
+impl LoggedDataChecker {
+    pub fn new() -> LoggedDataChecker {
+        return LoggedDataChecker {
+            check_for_log: false,
+            logged_buffers: HashMap::default(),
+            logged_data_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Check if a function that logs also formats or logs a whole byte buffer, e.g.
+    /// ``msg!("{:?}", instruction_data)``. Logging the full data of an account or an instruction
+    /// discloses state and costs compute units in proportion to its length
+    pub fn check(&mut self) -> bool {
+        if !self.check_for_log {
+            return false;
+        }
+        let mut logged_spans: Vec<Span> = self.logged_buffers.values().copied().collect();
+        logged_spans.sort();
+        if let Some(span) = logged_spans.first() {
+            self.logged_data_span = *span;
+            return true;
+        }
+        return false;
+    }
+}
+
+// Hold states for the accounts that are required to be writable but are only read
+pub struct OverPrivilegedAccountChecker {
+    // The accounts whose is_writable flag is read, with the span of the first read
+    pub writable_checks: HashMap<mir::Local, Span>,
+    // The accounts that are mutated, e.g. by borrowing their data or lamports mutably
+    pub mutated_accounts: HashSet<mir::Local>,
+    // Check for detecting a cross program invocation, which may write to any of the accounts
+    pub check_for_invoke: bool,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The span contains codes related to the over-privileged account
+    pub over_privileged_account_span: Span,
+}
+
+impl OverPrivilegedAccountChecker {
+    pub fn new() -> OverPrivilegedAccountChecker {
+        return OverPrivilegedAccountChecker {
+            writable_checks: HashMap::default(),
+            mutated_accounts: HashSet::default(),
+            check_for_invoke: false,
+            local_sources: LocalSources::default(),
+            over_privileged_account_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Check if an account is required to be writable, e.g. ``if !config.is_writable``, while
+    /// the function never mutates it. Requesting write access that is not needed violates least
+    /// privilege. Functions that invoke other programs are skipped, since the callee may write
+    pub fn check(&mut self) -> bool {
+        if self.check_for_invoke {
+            return false;
+        }
+        let mutated_accounts: HashSet<mir::Local> = self
+            .mutated_accounts
+            .iter()
+            .map(|account| self.local_sources.source_of(*account))
+            .collect();
+        let mut over_privileged_spans: Vec<Span> = self
+            .writable_checks
+            .iter()
+            .filter(|(account, _)| {
+                !mutated_accounts.contains(&self.local_sources.source_of(**account))
+            })
+            .map(|(_, span)| *span)
+            .collect();
+        over_privileged_spans.sort();
+        if let Some(span) = over_privileged_spans.first() {
+            self.over_privileged_account_span = *span;
+            return true;
+        }
+        return false;
+    }
+}
+
+// Hold states for the values that are logged as state but computed from storage that is not persisted
+pub struct EphemeralStateChecker {
+    // The maps that are created in the function body, so they are empty in every instruction
+    pub ephemeral_collections: HashSet<mir::Local>,
+    // The locals that hold the results of calls, with the locals of their arguments
+    pub call_results: HashMap<mir::Local, Vec<mir::Local>>,
+    // The values that are formatted, e.g. by msg!, keyed by the location of the formatting
+    pub formatted_values: HashMap<mir::Location, (Span, mir::Local)>,
+    // Check for detecting a call to one of the logging functions, e.g. by msg!
+    pub check_for_log: bool,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The span contains codes related to the misleading computation
+    pub ephemeral_state_span: Span,
+}
+
+impl EphemeralStateChecker {
+    pub fn new() -> EphemeralStateChecker {
+        return EphemeralStateChecker {
+            ephemeral_collections: HashSet::default(),
+            call_results: HashMap::default(),
+            formatted_values: HashMap::default(),
+            check_for_log: false,
+            local_sources: LocalSources::default(),
+            ephemeral_state_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Check if a value that is computed from a map created in the function body is logged, e.g.
+    /// a remaining balance that is looked up in ``let mut balances = HashMap::new()``. Since the map
+    /// starts out empty in every instruction, the logged value does not reflect the stored state
+    pub fn check(&mut self) -> bool {
+        if !self.check_for_log || self.ephemeral_collections.is_empty() {
+            return false;
+        }
+        // The results of calls that are given ephemeral values are ephemeral as well
+        let mut ephemeral_values: HashSet<mir::Local> = self.ephemeral_collections.clone();
+        loop {
+            let derived_values: Vec<mir::Local> = self
+                .call_results
+                .iter()
+                .filter(|(result, arguments)| {
+                    !ephemeral_values.contains(*result)
+                        && arguments.iter().any(|argument| {
+                            ephemeral_values.contains(&self.local_sources.source_of(*argument))
+                        })
+                })
+                .map(|(result, _)| *result)
+                .collect();
+            if derived_values.is_empty() {
+                break;
+            }
+            ephemeral_values.extend(derived_values);
+        }
+        let mut ephemeral_state_spans: Vec<Span> = self
+            .formatted_values
+            .values()
+            .filter(|(_, value)| {
+                ephemeral_values.contains(&self.local_sources.source_of(*value))
+            })
+            .map(|(span, _)| *span)
+            .collect();
+        ephemeral_state_spans.sort();
+        if let Some(span) = ephemeral_state_spans.first() {
+            self.ephemeral_state_span = *span;
+            return true;
+        }
+        return false;
+    }
+}
+
+// Hold states for the unsafe reinterpretation of account data
+pub struct UnsafeDataCastChecker {
+    // The raw pointers that are obtained from byte buffers, e.g. by data.as_ptr()
+    pub byte_pointers: HashSet<mir::Local>,
+    // The spans of the transmutes and raw pointer casts that are applied to byte buffers
+    pub unsafe_casts: HashMap<mir::Location, Span>,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The span contains codes related to the unsafe reinterpretation
+    pub unsafe_data_cast_span: Span,
+}
+
+impl UnsafeDataCastChecker {
+    pub fn new() -> UnsafeDataCastChecker {
+        return UnsafeDataCastChecker {
+            byte_pointers: HashSet::default(),
+            unsafe_casts: HashMap::default(),
+            local_sources: LocalSources::default(),
+            unsafe_data_cast_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Check if account data is reinterpreted as another type by ``std::mem::transmute`` or by a
+    /// raw pointer cast, e.g. ``data.as_ptr() as *const State``. Neither validates the contents of
+    /// the data, nor guarantees that its length and alignment fit the layout of the type
+    pub fn check(&mut self) -> bool {
+        let mut unsafe_cast_spans: Vec<Span> = self.unsafe_casts.values().copied().collect();
+        unsafe_cast_spans.sort();
+        if let Some(span) = unsafe_cast_spans.first() {
+            self.unsafe_data_cast_span = *span;
+            return true;
+        }
+        return false;
+    }
+}
+
+// Hold states for the lamports that are overwritten instead of being adjusted
+pub struct LamportsOverwriteChecker {
+    // The references to the lamports of an account, mapped to the account, e.g. the result of
+    // account.try_borrow_mut_lamports() and the values obtained from it by ? or deref_mut
+    pub lamport_refs: HashMap<mir::Local, mir::Local>,
+    // The accounts whose current balance is read, e.g. by account.lamports()
+    pub balance_reads: HashSet<mir::Local>,
+    // The constants that are assigned to the lamports of an account, keyed by the assignment
+    // location, along with the account and whether the constant is zero
+    pub overwrites: HashMap<mir::Location, (Span, mir::Local, bool)>,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The span contains codes related to the overwrite
+    pub lamports_overwrite_span: Span,
+}
+
+impl LamportsOverwriteChecker {
+    pub fn new() -> LamportsOverwriteChecker {
+        return LamportsOverwriteChecker {
+            lamport_refs: HashMap::default(),
+            balance_reads: HashSet::default(),
+            overwrites: HashMap::default(),
+            local_sources: LocalSources::default(),
+            lamports_overwrite_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Returns the account whose lamports the local refers to, if any.
+    pub fn account_of(&self, local: mir::Local) -> Option<mir::Local> {
+        return self.lamport_refs.get(&self.local_sources.source_of(local)).copied();
+    }
+
+    /// Check if a constant is assigned to the lamports of an account, e.g. ``**lamports = 1000``,
+    /// instead of adjusting the current balance. Whatever the account held before is clobbered.
+    /// Zeroing the lamports is how an account is closed, so it is only reported if the balance of
+    /// the account is never read, i.e. the funds are not moved to another account first
+    pub fn check(&mut self) -> bool {
+        let mut overwrite_spans: Vec<Span> = self
+            .overwrites
+            .values()
+            .filter(|(_, account, is_zero)| {
+                !*is_zero || !self.balance_reads.contains(&self.local_sources.source_of(*account))
+            })
+            .map(|(span, _, _)| *span)
+            .collect();
+        overwrite_spans.sort();
+        if let Some(span) = overwrite_spans.first() {
+            self.lamports_overwrite_span = *span;
+            return true;
+        }
+        return false;
+    }
+}
+
+// Hold states for the token amounts of different accounts that are compared without normalization
+pub struct TokenDecimalsChecker {
+    // The locals holding the amount of a token account, mapped to the account they are read from
+    pub amounts: HashMap<mir::Local, mir::Local>,
+    // The comparisons and arithmetic operations between the amounts of two different accounts,
+    // keyed by the location of the operation, with the two accounts
+    pub cross_account_operations: HashMap<mir::Location, (Span, mir::Local, mir::Local)>,
+    // The accounts whose mint or decimals are read, e.g. to ensure that the mints match
+    pub mint_reads: HashSet<mir::Local>,
+    // The locals holding the decimals of a mint, mapped to the account they are read from
+    pub decimals: HashMap<mir::Local, mir::Local>,
+    // Check if the decimals of two different mints are compared, e.g. to ensure they are the same
+    pub check_for_decimals_comparison: bool,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The span contains codes related to the comparison of the amounts
+    pub token_decimals_span: Span,
+}
+
+impl TokenDecimalsChecker {
+    pub fn new() -> TokenDecimalsChecker {
+        return TokenDecimalsChecker {
+            amounts: HashMap::default(),
+            cross_account_operations: HashMap::default(),
+            mint_reads: HashSet::default(),
+            decimals: HashMap::default(),
+            check_for_decimals_comparison: false,
+            local_sources: LocalSources::default(),
+            token_decimals_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Record the operation if its operands are the amounts of two different accounts, given as
+    /// the accounts that the operands are read from, if any.
+    pub fn record_operation(
+        &mut self,
+        location: mir::Location,
+        span: Span,
+        left_account: Option<mir::Local>,
+        right_account: Option<mir::Local>,
+    ) {
+        if let (Some(left_account), Some(right_account)) = (left_account, right_account) {
+            if left_account != right_account {
+                self.cross_account_operations.insert(location, (span, left_account, right_account));
+            }
+        }
+    }
+
+    /// Check if the raw amounts of two token accounts are compared, added or subtracted, e.g.
+    /// ``source.amount > destination.amount``, while neither their mints nor their decimals are
+    /// looked at. Accounts of different mints can have different decimals, so the raw amounts
+    /// are not in the same unit. The mints must be read from both of the accounts, a read of the
+    /// mint of some other account does not make the amounts comparable. The decimals of mint
+    /// accounts are not related to token accounts, so comparing the decimals of two mints is
+    /// taken to check the token accounts as well
+    pub fn check(&mut self) -> bool {
+        if self.check_for_decimals_comparison {
+            return false;
+        }
+        let mut operation_spans: Vec<Span> = self
+            .cross_account_operations
+            .values()
+            .filter(|(_, left_account, right_account)| {
+                return !self.mint_reads.contains(left_account)
+                    || !self.mint_reads.contains(right_account);
+            })
+            .map(|(span, _, _)| *span)
+            .collect();
+        operation_spans.sort();
+        if let Some(span) = operation_spans.first() {
+            self.token_decimals_span = *span;
+            return true;
+        }
+        return false;
+    }
+}
+
+// Hold states for the panics directly in the body of the entrypoint handler
+pub struct EntrypointPanicChecker {
+    // Check if the analyzed body is the handler that is registered with entrypoint!, e.g.
+    // process_instruction
+    pub check_for_entrypoint_handler: bool,
+    // The spans of the calls to unwrap, expect and the panic functions, keyed by the call location
+    pub panicking_calls: HashMap<mir::Location, Span>,
+    // The spans contain codes related to the panics in the handler
+    pub entrypoint_panic_spans: Vec<Span>,
+}
+
+impl EntrypointPanicChecker {
+    pub fn new(is_entrypoint_handler: bool) -> EntrypointPanicChecker {
+        return EntrypointPanicChecker {
+            check_for_entrypoint_handler: is_entrypoint_handler,
+            panicking_calls: HashMap::default(),
+            entrypoint_panic_spans: Vec::new()
+        }
+    }
+
+    /// Check if the handler of the entrypoint panics itself, e.g. by ``instruction_data.unwrap()``.
+    /// A panic aborts the whole transaction, and a panic in the handler is reached by any input
+    /// that the caller chooses, so it is the easiest denial of service. Panics in the helpers
+    /// that the handler calls are not reported
+    pub fn check(&mut self) -> bool {
+        if !self.check_for_entrypoint_handler {
+            return false;
+        }
+        let mut panic_spans: Vec<Span> = self.panicking_calls.values().copied().collect();
+        panic_spans.sort();
+        panic_spans.dedup();
+        self.entrypoint_panic_spans = panic_spans;
+        return !self.entrypoint_panic_spans.is_empty();
+    }
+}
+
+// Hold states for the authorization decisions that are based on the lamports of an account
+pub struct LamportsAuthorizationChecker {
+    // The locals holding the lamports of an account, as returned by lamports()
+    pub lamport_balances: HashSet<mir::Local>,
+    // The results of the comparisons with the lamports of an account, mapped to their spans
+    pub lamport_comparisons: HashMap<mir::Local, Span>,
+    // The branches on a comparison with the lamports, keyed by the location of the branch
+    pub authorizations: HashMap<mir::Location, Span>,
+    // Check if the function moves lamports, in which case the comparison checks for enough funds
+    pub check_for_lamport_transfer: bool,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The span contains codes related to the authorization
+    pub lamports_authorization_span: Span,
+}
+
+impl LamportsAuthorizationChecker {
+    pub fn new() -> LamportsAuthorizationChecker {
+        return LamportsAuthorizationChecker {
+            lamport_balances: HashSet::default(),
+            lamport_comparisons: HashMap::default(),
+            authorizations: HashMap::default(),
+            check_for_lamport_transfer: false,
+            local_sources: LocalSources::default(),
+            lamports_authorization_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Returns true if the local holds the lamports of an account, possibly through copies.
+    pub fn is_lamport_balance(&self, local: mir::Local) -> bool {
+        return self.lamport_balances.contains(&self.local_sources.source_of(local));
+    }
+
+    /// Check if the function decides whether to go on by comparing the lamports of an account,
+    /// e.g. ``if account.lamports() > MIN_STAKE``. Anyone can transfer lamports to an account in
+    /// a prior instruction of the same transaction, so the lamports do not prove anything about
+    /// the account. The decision should be based on the validated state of the program instead.
+    /// Comparisons in a function that moves lamports check for enough funds and are not reported
+    pub fn check(&mut self) -> bool {
+        if self.check_for_lamport_transfer {
+            return false;
+        }
+        let mut authorization_spans: Vec<Span> = self.authorizations.values().copied().collect();
+        authorization_spans.sort();
+        if let Some(span) = authorization_spans.first() {
+            self.lamports_authorization_span = *span;
+            return true;
+        }
+        return false;
+    }
+}
+
+// Hold states for the helper functions that transfer lamports without validating their accounts
+pub struct UnguardedTransferHelperChecker {
+    // The number of local functions that call the analyzed function
+    pub caller_count: usize,
+    // The spans of the transfers in the function, keyed by the location of the transfer
+    pub transfers: HashMap<mir::Location, Span>,
+    // Check if the function validates an account itself, e.g. by reading its signer flag or owner
+    pub check_for_account_validation: bool,
+    // The span contains codes related to the unguarded transfer
+    pub unguarded_transfer_span: Span,
+}
+
+impl UnguardedTransferHelperChecker {
+    pub fn new(caller_count: usize) -> UnguardedTransferHelperChecker {
+        return UnguardedTransferHelperChecker {
+            caller_count,
+            transfers: HashMap::default(),
+            check_for_account_validation: false,
+            unguarded_transfer_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Check if a helper function that is called from more than one function transfers lamports
+    /// without checking the signer flag or the owner of any account. The helper relies on its
+    /// callers to validate the accounts, which holds for the caller it was written for, but not
+    /// necessarily for the others
+    pub fn check(&mut self) -> bool {
+        if self.caller_count < 2 || self.check_for_account_validation {
+            return false;
+        }
+        let mut transfer_spans: Vec<Span> = self.transfers.values().copied().collect();
+        transfer_spans.sort();
+        if let Some(span) = transfer_spans.first() {
+            self.unguarded_transfer_span = *span;
+            return true;
+        }
+        return false;
+    }
+}
+
+// Hold states for the rent that is computed by hand from the data length of an account
+pub struct ManualRentChecker {
+    // The locals holding the data length of an account, or a value computed from it
+    pub data_lengths: HashSet<mir::Local>,
+    // The multiplications of a data length, keyed by the location of the multiplication
+    pub manual_computations: HashMap<mir::Location, Span>,
+    // Check if the rent is obtained from the Rent sysvar by minimum_balance
+    pub check_for_minimum_balance: bool,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The span contains codes related to the manual rent computation
+    pub manual_rent_span: Span,
+}
+
+impl ManualRentChecker {
+    pub fn new() -> ManualRentChecker {
+        return ManualRentChecker {
+            data_lengths: HashSet::default(),
+            manual_computations: HashMap::default(),
+            check_for_minimum_balance: false,
+            local_sources: LocalSources::default(),
+            manual_rent_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Returns true if the local holds a data length, possibly through copies.
+    pub fn is_data_length(&self, local: mir::Local) -> bool {
+        return self.data_lengths.contains(&self.local_sources.source_of(local));
+    }
+
+    /// Check if the lamports required by an account are computed by multiplying its data length,
+    /// e.g. ``(data_len + 128) * lamports_per_byte_year``, instead of by ``Rent::minimum_balance``.
+    /// The rates of the rent are set by the protocol, so hard coded rates can drift from them
+    pub fn check(&mut self) -> bool {
+        if self.check_for_minimum_balance {
+            return false;
+        }
+        let mut computation_spans: Vec<Span> =
+            self.manual_computations.values().copied().collect();
+        computation_spans.sort();
+        if let Some(span) = computation_spans.first() {
+            self.manual_rent_span = *span;
+            return true;
+        }
+        return false;
+    }
+}
+
+// Hold states for the errors of cross-program invocations that are matched but not handled
+pub struct SwallowedCpiErrorChecker {
+    // The locals holding the result of invoke or invoke_signed, mapped to the span of the call
+    pub cpi_results: HashMap<mir::Local, Span>,
+    // The locals holding the discriminant of an enum, mapped to the local holding the enum
+    pub discriminants: HashMap<mir::Local, mir::Local>,
+    // The spans of the invocations whose error arm does nothing, keyed by the branch location
+    pub swallowed_errors: HashMap<mir::Location, Span>,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The span contains codes related to the swallowed error
+    pub swallowed_cpi_error_span: Span,
+}
+
+impl SwallowedCpiErrorChecker {
+    pub fn new() -> SwallowedCpiErrorChecker {
+        return SwallowedCpiErrorChecker {
+            cpi_results: HashMap::default(),
+            discriminants: HashMap::default(),
+            swallowed_errors: HashMap::default(),
+            local_sources: LocalSources::default(),
+            swallowed_cpi_error_span: rustc_span::DUMMY_SP
+        }
+    }
+
+    /// Returns the span of the invocation whose result is matched by a branch on the given
+    /// discriminant, if any.
+    pub fn invocation_of(&self, discriminant: mir::Local) -> Option<Span> {
+        let discriminant = self.local_sources.source_of(discriminant);
+        let result = self.discriminants.get(&discriminant)?;
+        return self.cpi_results.get(&self.local_sources.source_of(*result)).copied();
+    }
+
+    /// Check if the result of a cross-program invocation is matched, e.g. by
+    /// ``if let Ok(..) = invoke(..)``, while the ``Err`` arm is empty or only logs. The function
+    /// then proceeds as if the invocation had succeeded, although none of its effects took place
+    pub fn check(&mut self) -> bool {
+        let mut error_spans: Vec<Span> = self.swallowed_errors.values().copied().collect();
+        error_spans.sort();
+        if let Some(span) = error_spans.first() {
+            self.swallowed_cpi_error_span = *span;
+            return true;
+        }
+        return false;
+    }
+}
+
+// Hold states for the accounts of a loop that are mutated without being validated in the iteration
+pub struct UnvalidatedLoopAccountChecker {
+    // The locals holding the next item of an iterator, e.g. of ``accounts.iter()``
+    pub loop_items: HashSet<mir::Local>,
+    // The validations of a loop item, i.e. reads of its signer flag, owner or key
+    pub validations: Vec<(mir::Location, mir::Local)>,
+    // The mutations of a loop item, keyed by their location
+    pub mutations: HashMap<mir::Location, (Span, mir::Local)>,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The spans contain codes related to the mutations without validation
+    pub unvalidated_loop_account_spans: Vec<Span>,
+}
+
+impl UnvalidatedLoopAccountChecker {
+    pub fn new() -> UnvalidatedLoopAccountChecker {
+        return UnvalidatedLoopAccountChecker {
+            loop_items: HashSet::default(),
+            validations: Vec::new(),
+            mutations: HashMap::default(),
+            local_sources: LocalSources::default(),
+            unvalidated_loop_account_spans: Vec::new()
+        }
+    }
+
+    /// Returns the loop item that the local holds, possibly through copies and reborrows.
+    pub fn loop_item_of(&self, local: mir::Local) -> Option<mir::Local> {
+        let source = self.local_sources.source_of(local);
+        if self.loop_items.contains(&source) {
+            return Some(source);
+        }
+        return None;
+    }
+
+    /// Check if an account of a loop, e.g. ``for account in accounts.iter()``, is mutated in an
+    /// iteration that does not validate it on every path to the mutation. A validation that only
+    /// happens in an ``if`` branch leaves the other accounts of the batch unvalidated
+    pub fn check(&mut self, guards: &GuardAnalysis<'_>) -> bool {
+        let mut mutation_spans: Vec<Span> = self
+            .mutations
+            .iter()
+            .filter(|(mutation_location, (_, item))| {
+                !guards.is_guarded(**mutation_location, |guard| {
+                    self.validations.contains(&(guard, *item))
+                })
+            })
+            .map(|(_, (span, _))| *span)
+            .collect();
+        mutation_spans.sort();
+        mutation_spans.dedup();
+        self.unvalidated_loop_account_spans = mutation_spans;
+        return !self.unvalidated_loop_account_spans.is_empty();
+    }
+}
+
+// Hold states for the first byte of the instruction data that is read without checking that the
+// instruction data is not empty
+pub struct UncheckedFirstByteChecker {
+    // The byte slices passed in as arguments, e.g. the instruction data
+    pub instruction_data: HashSet<mir::Local>,
+    // The locals that are assigned the constant zero
+    pub zero_locals: HashSet<mir::Local>,
+    // The locals holding the length of a slice, mapped to the local holding that slice
+    pub slice_lengths: HashMap<mir::Local, mir::Local>,
+    // The lengths loaded by the compiler for its own bounds checks
+    pub bounds_check_lengths: HashSet<mir::Local>,
+    // The comparisons and emptiness tests with the local of the compared value
+    pub guards: Vec<(mir::Location, mir::Local)>,
+    // The bounds checked accesses of the first byte with the local of the slice length, keyed by
+    // the assert location
+    pub first_byte_accesses: HashMap<mir::Location, (Span, mir::Local)>,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The spans contain codes related to the unchecked accesses of the first byte
+    pub unchecked_first_byte_spans: Vec<Span>,
+}
+
+impl UncheckedFirstByteChecker {
+    pub fn new() -> UncheckedFirstByteChecker {
+        return UncheckedFirstByteChecker {
+            instruction_data: HashSet::default(),
+            zero_locals: HashSet::default(),
+            slice_lengths: HashMap::default(),
+            bounds_check_lengths: HashSet::default(),
+            guards: Vec::new(),
+            first_byte_accesses: HashMap::default(),
+            local_sources: LocalSources::default(),
+            unchecked_first_byte_spans: Vec::new()
+        }
+    }
+
+    /// Returns the instruction data whose length the local holds, if any.
+    fn instruction_data_of_length(&self, length: mir::Local) -> Option<mir::Local> {
+        let slice = self.slice_lengths.get(&self.local_sources.source_of(length))?;
+        let source = self.local_sources.source_of(*slice);
+        if self.instruction_data.contains(&source) {
+            return Some(source);
+        }
+        return None;
+    }
+
+    /// Check if the first byte of the instruction data is read, e.g. ``instruction_data[0]`` to
+    /// obtain the tag of the instruction, without ``is_empty()`` or a comparison of ``len()``
+    /// guarding the read on every path to it. An empty instruction then panics the program.
+    /// Unlike the panicking slice operations, only the read of the first byte is reported
+    pub fn check(&mut self, guard_analysis: &GuardAnalysis<'_>) -> bool {
+        let guards: Vec<(mir::Location, mir::Local)> = self
+            .guards
+            .iter()
+            .filter(|(_, length)| {
+                !self.bounds_check_lengths.contains(&self.local_sources.source_of(*length))
+            })
+            .filter_map(|(location, length)| {
+                self.instruction_data_of_length(*length).map(|slice| (*location, slice))
+            })
+            .collect();
+        let mut access_spans: Vec<Span> = self
+            .first_byte_accesses
+            .iter()
+            .filter_map(|(access_location, (span, length))| {
+                let slice = self.instruction_data_of_length(*length)?;
+                let is_guarded = guard_analysis
+                    .is_guarded(*access_location, |guard| guards.contains(&(guard, slice)));
+                if is_guarded {
+                    return None;
+                }
+                return Some(*span);
+            })
+            .collect();
+        access_spans.sort();
+        access_spans.dedup();
+        self.unchecked_first_byte_spans = access_spans;
+        return !self.unchecked_first_byte_spans.is_empty();
+    }
+}
+
+// Hold states for the program derived addresses that are passed as accounts and trusted without
+// being re-derived
+pub struct UnverifiedPdaChecker {
+    // The spans of the invocations that a program derived address signs, i.e. of invoke_signed
+    pub signed_invocations: Vec<Span>,
+    // The locals holding an address derived by find_program_address or create_program_address
+    pub derived_keys: HashSet<mir::Local>,
+    // Check if a derived address is compared with another key
+    pub check_for_key_comparison: bool,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The spans contain codes related to the uses of unverified program derived addresses
+    pub unverified_pda_spans: Vec<Span>,
+}
+
+impl UnverifiedPdaChecker {
+    pub fn new() -> UnverifiedPdaChecker {
+        return UnverifiedPdaChecker {
+            signed_invocations: Vec::new(),
+            derived_keys: HashSet::default(),
+            check_for_key_comparison: false,
+            local_sources: LocalSources::default(),
+            unverified_pda_spans: Vec::new()
+        }
+    }
+
+    /// Returns true if the local holds a derived address, possibly through copies, reborrows
+    /// and projections, e.g. the first field of the result of ``find_program_address``.
+    pub fn is_derived_key(&self, local: mir::Local) -> bool {
+        return self.derived_keys.contains(&self.local_sources.source_of(local));
+    }
+
+    /// Check if a program derived address that is passed as an account signs an invocation, i.e.
+    /// ``invoke_signed``, while the function neither re-derives the address with
+    /// ``find_program_address`` or ``create_program_address`` nor compares the derived address
+    /// with a key. Without the comparison, any account can be passed in place of the address
+    pub fn check(&mut self) -> bool {
+        if self.check_for_key_comparison {
+            return false;
+        }
+        let mut invocation_spans = self.signed_invocations.clone();
+        invocation_spans.sort();
+        invocation_spans.dedup();
+        self.unverified_pda_spans = invocation_spans;
+        return !self.unverified_pda_spans.is_empty();
+    }
+}
+
+// Hold states for the counts read from the instruction data that drive a loop without being
+// validated against the length of the buffer
+pub struct UnvalidatedCountChecker {
+    // The locals holding a count, mapped to the local that the count was read into from the
+    // instruction data, e.g. ``n`` of ``let n = instruction_data[0] as usize``
+    pub counts: HashMap<mir::Local, mir::Local>,
+    // The locals holding the length of a slice or a value computed from it
+    pub lengths: HashSet<mir::Local>,
+    // The counts, by the local they were read into, that are compared with a length
+    pub validated_counts: HashSet<mir::Local>,
+    // The ranges that loops iterate over, e.g. ``0..n``, with the count bounding them
+    pub count_loops: Vec<(Span, mir::Local)>,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The spans contain codes related to the loops driven by unvalidated counts
+    pub unvalidated_count_spans: Vec<Span>,
+}
+
+impl UnvalidatedCountChecker {
+    pub fn new() -> UnvalidatedCountChecker {
+        return UnvalidatedCountChecker {
+            counts: HashMap::default(),
+            lengths: HashSet::default(),
+            validated_counts: HashSet::default(),
+            count_loops: Vec::new(),
+            local_sources: LocalSources::default(),
+            unvalidated_count_spans: Vec::new()
+        }
+    }
+
+    /// Returns the local that the count held by the local was read into, if it holds a count.
+    pub fn count_of(&self, local: mir::Local) -> Option<mir::Local> {
+        return self.counts.get(&self.local_sources.source_of(local)).copied();
+    }
+
+    /// Returns true if the local holds the length of a slice or a value computed from it.
+    pub fn is_length(&self, local: mir::Local) -> bool {
+        return self.lengths.contains(&self.local_sources.source_of(local));
+    }
+
+    /// Check if a loop parses as many items as a count read from the instruction data says, e.g.
+    /// ``for i in 0..instruction_data[0]``, while the count is never compared with the length of a
+    /// slice. A caller can then announce more items than the buffer holds, which panics the
+    /// program or reads past the items that belong to the count
+    pub fn check(&mut self) -> bool {
+        let mut loop_spans: Vec<Span> = self
+            .count_loops
+            .iter()
+            .filter(|(_, count)| !self.validated_counts.contains(count))
+            .map(|(span, _)| *span)
+            .collect();
+        loop_spans.sort();
+        loop_spans.dedup();
+        self.unvalidated_count_spans = loop_spans;
+        return !self.unvalidated_count_spans.is_empty();
+    }
+}
+
+// The prefixes of the logged messages that report an error, e.g. ``msg!("Invalid amount")``
+pub const ERROR_LOG_PREFIXES: [&str; 3] = ["Invalid", "Error", "Insufficient"];
+
+// Hold states for the successes that are returned after an error has been logged
+pub struct OkAfterErrorLogChecker {
+    // The logging calls whose message reports an error, keyed by the call location
+    pub error_logs: HashMap<mir::Location, Span>,
+    // The locations of the assignments of Ok to the return value
+    pub ok_returns: Vec<mir::Location>,
+    // The spans contain codes related to the error logs that are followed by a success
+    pub ok_after_error_log_spans: Vec<Span>,
+}
+
+impl OkAfterErrorLogChecker {
+    pub fn new() -> OkAfterErrorLogChecker {
+        return OkAfterErrorLogChecker {
+            error_logs: HashMap::default(),
+            ok_returns: Vec::new(),
+            ok_after_error_log_spans: Vec::new()
+        }
+    }
+
+    /// Returns true if the message of a logging call, as written in the source, reports an error.
+    pub fn is_error_message(call_snippet: &str) -> bool {
+        let Some(start) = call_snippet.find('"') else {
+            return false;
+        };
+        let message = &call_snippet[start + 1..];
+        return ERROR_LOG_PREFIXES.iter().any(|prefix| message.starts_with(prefix));
+    }
+
+    /// Returns true if there is a path from the location to the other location.
+    fn reaches(
+        basic_blocks: &mir::BasicBlocks<'_>,
+        from: mir::Location,
+        to: mir::Location,
+    ) -> bool {
+        if from.block == to.block && from.statement_index < to.statement_index {
+            return true;
+        }
+        let mut visited: HashSet<mir::BasicBlock> = HashSet::default();
+        let mut pending: Vec<mir::BasicBlock> =
+            basic_blocks[from.block].terminator().successors().collect();
+        while let Some(bb) = pending.pop() {
+            if bb == to.block {
+                return true;
+            }
+            if visited.insert(bb) {
+                pending.extend(basic_blocks[bb].terminator().successors());
+            }
+        }
+        return false;
+    }
+
+    /// Check if the function logs an error, e.g. ``msg!("Insufficient funds")``, on a path that
+    /// goes on to return ``Ok``. The caller then takes the failed instruction for a success, and
+    /// the state that the function mutated before or after the log is committed
+    pub fn check(&mut self, basic_blocks: &mir::BasicBlocks<'_>) -> bool {
+        let mut log_spans: Vec<Span> = self
+            .error_logs
+            .iter()
+            .filter(|(log_location, _)| {
+                self.ok_returns
+                    .iter()
+                    .any(|ok_location| Self::reaches(basic_blocks, **log_location, *ok_location))
+            })
+            .map(|(_, span)| *span)
+            .collect();
+        log_spans.sort();
+        log_spans.dedup();
+        self.ok_after_error_log_spans = log_spans;
+        return !self.ok_after_error_log_spans.is_empty();
+    }
+}
+
+// Hold states for the stack usage of the function, estimated from its local declarations
+pub struct StackUsageChecker {
+    // The span and the size in bytes of every local whose layout is known
+    pub local_sizes: Vec<(Span, u64)>,
+    // The estimated stack usage of the function in bytes
+    pub estimated_stack_usage: u64,
+    // The span of the first of the largest locals, if the function exceeds the limit
+    pub largest_local_span: Option<Span>
+}
+
+impl StackUsageChecker {
+    pub fn new() -> StackUsageChecker {
+        return StackUsageChecker {
+            local_sizes: Vec::new(),
+            estimated_stack_usage: 0,
+            largest_local_span: None
+        }
+    }
+
+    /// Check if the locals of the function, e.g. a ``[u8; 8192]`` buffer, need more than
+    /// stack_limit bytes. The stack frames of Solana programs are small, so such a function
+    /// overflows the stack when it is called. Every local is counted, since the compiler may or
+    /// may not let locals share a slot, which makes the estimate an upper bound
+    pub fn check(&mut self, stack_limit: u64) -> bool {
+        self.estimated_stack_usage = self.local_sizes.iter().map(|(_, size)| *size).sum();
+        if self.estimated_stack_usage <= stack_limit {
+            return false;
+        }
+        self.largest_local_span = self
+            .local_sizes
+            .iter()
+            .rev()
+            .max_by_key(|(_, size)| *size)
+            .map(|(span, _)| *span);
+        return true;
+    }
+}
+
+// Hold states for the lamports of an account that are borrowed while they are already borrowed
+pub struct LamportsDoubleBorrowChecker {
+    // The accounts whose lamports are borrowed, keyed by the locals that hold the borrow guards
+    pub guards: HashMap<mir::Local, mir::Local>,
+    // The spans of the borrows made while the lamports of the same account are borrowed
+    pub overlapping_borrows: Vec<Span>,
+    // The sources of the locals, to resolve the accounts and the guards
+    pub local_sources: LocalSources,
+    // The spans contain codes related to the double borrows
+    pub double_borrow_spans: Vec<Span>
+}
+
+impl LamportsDoubleBorrowChecker {
+    pub fn new() -> LamportsDoubleBorrowChecker {
+        return LamportsDoubleBorrowChecker {
+            guards: HashMap::default(),
+            overlapping_borrows: Vec::new(),
+            local_sources: LocalSources::default(),
+            double_borrow_spans: Vec::new()
+        }
+    }
+
+    /// Record a borrow of the lamports of the account into the guard local, which overlaps with
+    /// the guards of the account that have not been dropped yet.
+    pub fn borrow(&mut self, account: mir::Local, guard: mir::Local, span: Span) {
+        let account = self.local_sources.source_of(account);
+        if self.guards.values().any(|borrowed| *borrowed == account) {
+            self.overlapping_borrows.push(span);
+        }
+        self.guards.insert(guard, account);
+    }
+
+    /// Record that the local holds the guard that the other local holds, e.g. after ``?``.
+    pub fn propagate(&mut self, from: mir::Local, to: mir::Local) {
+        let from = self.local_sources.source_of(from);
+        if let Some(account) = self.guards.get(&from).copied() {
+            self.guards.insert(to, account);
+        }
+    }
+
+    /// Release the borrow of the account whose guard is held by the dropped local.
+    pub fn release(&mut self, dropped: mir::Local) {
+        let guard = self.local_sources.source_of(dropped);
+        let Some(account) = self.guards.get(&dropped).or(self.guards.get(&guard)).copied() else {
+            return;
+        };
+        self.guards.retain(|_, borrowed| *borrowed != account);
+    }
+
+    /// Check if the lamports of an account are borrowed mutably while the guard of an earlier
+    /// borrow is alive, e.g. ``**a.try_borrow_mut_lamports()? = **a.try_borrow_mut_lamports()?;``
+    /// keeps both temporary guards until the end of the statement, so the second borrow fails
+    pub fn check(&mut self) -> bool {
+        let mut borrow_spans = self.overlapping_borrows.clone();
+        borrow_spans.sort();
+        borrow_spans.dedup();
+        self.double_borrow_spans = borrow_spans;
+        return !self.double_borrow_spans.is_empty();
+    }
+}
+
+// The maximum number of seeds of a program derived address
+pub const MAX_SEEDS: usize = 16;
+// The maximum length in bytes of a seed of a program derived address
+pub const MAX_SEED_LEN: u64 = 32;
+
+// Hold states for the seeds of program derived addresses that exceed the limits of the runtime
+pub struct SeedLimitsChecker {
+    // The elements of the arrays, keyed by the locals that hold the arrays,
+    // an element is None if it is a constant operand
+    pub arrays: HashMap<mir::Local, Vec<Option<mir::Local>>>,
+    // The lengths of the constant seeds, e.g. ``b"vault"``, keyed by the locals that hold them
+    pub constant_seed_lengths: HashMap<mir::Local, u64>,
+    // The seeds passed to find_program_address or create_program_address, with the call spans
+    pub derivations: Vec<(Span, mir::Local)>,
+    // The seeds of the signers passed to invoke_signed, with the call spans
+    pub signed_invocations: Vec<(Span, mir::Local)>,
+    // The sources of the locals, to resolve the arrays that are passed as slices
+    pub local_sources: LocalSources,
+    // The spans contain codes related to the derivations that exceed the limits
+    pub seed_limit_spans: Vec<Span>
+}
+
+impl SeedLimitsChecker {
+    pub fn new() -> SeedLimitsChecker {
+        return SeedLimitsChecker {
+            arrays: HashMap::default(),
+            constant_seed_lengths: HashMap::default(),
+            derivations: Vec::new(),
+            signed_invocations: Vec::new(),
+            local_sources: LocalSources::default(),
+            seed_limit_spans: Vec::new()
+        }
+    }
+
+    /// Returns true if the seeds held by the local are known to be more than MAX_SEEDS,
+    /// or to include a constant seed that is longer than MAX_SEED_LEN bytes.
+    fn exceeds_limits(&self, seeds: mir::Local) -> bool {
+        let Some(elements) = self.arrays.get(&self.local_sources.source_of(seeds)) else {
+            return false;
+        };
+        if elements.len() > MAX_SEEDS {
+            return true;
+        }
+        return elements.iter().flatten().any(|seed| {
+            self.constant_seed_lengths
+                .get(&self.local_sources.source_of(*seed))
+                .or(self.constant_seed_lengths.get(seed))
+                .is_some_and(|length| *length > MAX_SEED_LEN)
+        });
+    }
+
+    /// Check if a program derived address is derived, or signed for, with more than 16 seeds or
+    /// with a constant seed longer than 32 bytes, e.g. ``find_program_address(&[LONG_SEED], id)``.
+    /// The runtime rejects such seeds, so the derivation or the invocation always fails
+    pub fn check(&mut self) -> bool {
+        let mut limit_spans: Vec<Span> = self
+            .derivations
+            .iter()
+            .filter(|(_, seeds)| self.exceeds_limits(*seeds))
+            .map(|(span, _)| *span)
+            .collect();
+        for (span, signers_seeds) in &self.signed_invocations {
+            let signers = self.local_sources.source_of(*signers_seeds);
+            let Some(signers) = self.arrays.get(&signers) else {
+                continue;
+            };
+            if signers.iter().flatten().any(|seeds| self.exceeds_limits(*seeds)) {
+                limit_spans.push(*span);
+            }
+        }
+        limit_spans.sort();
+        limit_spans.dedup();
+        self.seed_limit_spans = limit_spans;
+        return !self.seed_limit_spans.is_empty();
+    }
+}
+
+// The methods whose result refers to the data of their first argument
+pub const DERIVING_METHODS: [&str; 12] = [
+    ".deref", ".deref_mut", ".index", ".index_mut", ".as_slice", ".as_mut_slice", ".as_bytes",
+    ".as_ref", ".as_mut", ".branch", ".unwrap", ".clone",
+];
+
+// Hold states for the writes of data of a variable length into account data
+pub struct UnboundedAccountWriteChecker {
+    // The parameters of the function that hold data of a variable length, e.g. a Vec<u8>
+    pub variable_data: HashSet<mir::Local>,
+    // The results of the mutable borrows of account data
+    pub account_data: HashSet<mir::Local>,
+    // The data whose length is taken, keyed by the locals that hold the lengths
+    pub lengths: HashMap<mir::Local, mir::Local>,
+    // The locals that are ordered against another value, e.g. a maximum length
+    pub compared_locals: HashSet<mir::Local>,
+    // The writes, with their spans, of a source into a destination
+    pub writes: Vec<(Span, mir::Local, mir::Local)>,
+    // The sources of the locals, to resolve the account data and the data that is written
+    pub local_sources: LocalSources,
+    // The spans contain codes related to the unbounded writes
+    pub unbounded_write_spans: Vec<Span>
+}
+
+impl UnboundedAccountWriteChecker {
+    pub fn new() -> UnboundedAccountWriteChecker {
+        return UnboundedAccountWriteChecker {
+            variable_data: HashSet::default(),
+            account_data: HashSet::default(),
+            lengths: HashMap::default(),
+            compared_locals: HashSet::default(),
+            writes: Vec::new(),
+            local_sources: LocalSources::default(),
+            unbounded_write_spans: Vec::new()
+        }
+    }
+
+    /// Check if data of a variable length that the caller supplies, e.g. a ``Vec<u8>``
+    /// parameter, is written into the data of an account without its length being compared
+    /// with a cap. The data can then exceed the size of the account, or grow the account until
+    /// it cannot be used any more
+    pub fn check(&mut self) -> bool {
+        let capped_data: HashSet<mir::Local> = self
+            .lengths
+            .iter()
+            .filter(|(length, _)| self.compared_locals.contains(length))
+            .map(|(_, data)| self.local_sources.source_of(*data))
+            .collect();
+        let mut write_spans: Vec<Span> = self
+            .writes
+            .iter()
+            .filter(|(_, destination, source)| {
+                let source = self.local_sources.source_of(*source);
+                self.account_data.contains(&self.local_sources.source_of(*destination))
+                    && self.variable_data.contains(&source)
+                    && !capped_data.contains(&source)
+            })
+            .map(|(span, _, _)| *span)
+            .collect();
+        write_spans.sort();
+        write_spans.dedup();
+        self.unbounded_write_spans = write_spans;
+        return !self.unbounded_write_spans.is_empty();
+    }
+}
+
+// Hold states for the payouts that are decided by a value read from the clock
+pub struct ClockPayoutChecker {
+    // The locals that hold the clock or a value derived from it, e.g. ``clock.slot % n``
+    pub clock_values: HashSet<mir::Local>,
+    // The span of the first read of the clock
+    pub clock_read_span: Option<Span>,
+    // The spans of the payouts whose account or amount is derived from the clock
+    pub clock_payouts: Vec<Span>,
+    // The spans contain codes related to the payouts decided by the clock
+    pub clock_payout_spans: Vec<Span>
+}
+
+impl ClockPayoutChecker {
+    pub fn new() -> ClockPayoutChecker {
+        return ClockPayoutChecker {
+            clock_values: HashSet::default(),
+            clock_read_span: None,
+            clock_payouts: Vec::new(),
+            clock_payout_spans: Vec::new()
+        }
+    }
+
+    /// Record that the local holds the clock that is read at the span.
+    pub fn read_clock(&mut self, clock: mir::Local, span: Span) {
+        self.clock_values.insert(clock);
+        self.clock_read_span.get_or_insert(span);
+    }
+
+    /// Returns true if the place is derived from the clock, or is indexed by a value that is.
+    pub fn is_clock_value(&self, place: &mir::Place<'_>) -> bool {
+        return self.clock_values.contains(&place.local)
+            || place.projection.iter().any(|elem| match elem {
+                mir::ProjectionElem::Index(index) => self.clock_values.contains(&index),
+                _ => false,
+            });
+    }
+
+    /// Record that the assigned local is derived from the clock if the rvalue uses a clock value.
+    pub fn record(&mut self, place: &mir::Place<'_>, rvalue: &mir::Rvalue<'_>) {
+        let is_derived = match rvalue {
+            mir::Rvalue::Use(operand)
+            | mir::Rvalue::Cast(_, operand, _)
+            | mir::Rvalue::UnaryOp(_, operand) => {
+                operand.place().is_some_and(|used| self.is_clock_value(&used))
+            }
+            mir::Rvalue::BinaryOp(_, box (left, right)) => [left, right]
+                .iter()
+                .filter_map(|operand| operand.place())
+                .any(|used| self.is_clock_value(&used)),
+            mir::Rvalue::Ref(_, _, used) | mir::Rvalue::CopyForDeref(used) => {
+                self.is_clock_value(used)
+            }
+            _ => false,
+        };
+        if is_derived && place.projection.is_empty() {
+            self.clock_values.insert(place.local);
+        }
+    }
+
+    /// Check if the account that receives a payout, or its amount, is derived from the clock,
+    /// e.g. a prize winner picked by ``clock.slot % players.len()``. The time manipulation and
+    /// bad randomness checkers only report the read of the clock, whereas the validator that
+    /// produces the slot can choose the winner here, so these findings are the more severe
+    pub fn check(&mut self) -> bool {
+        if self.clock_read_span.is_none() {
+            return false;
+        }
+        let mut payout_spans = self.clock_payouts.clone();
+        payout_spans.sort();
+        payout_spans.dedup();
+        self.clock_payout_spans = payout_spans;
+        return !self.clock_payout_spans.is_empty();
+    }
+}
+
+// Hold states for the lamports of accounts that are mutated without a signer check of the account
+pub struct MissingSignerChecker {
+    // The reads of the signer flag, or the calls of a signer check helper, with the account and
+    // the span of the check, keyed by the location of the check
+    pub signer_checks: Vec<(mir::Location, mir::Local, Span)>,
+    // The mutable borrows of lamports, with the account and the span of the borrow
+    pub lamport_mutations: Vec<(mir::Location, mir::Local, Span)>,
+    // The accounts whose signature is checked before the body is entered, i.e. the Signer
+    // accounts of the Context of an Anchor handler
+    pub signed_accounts: HashSet<mir::Local>,
+    // The references to the lamports of an account, mapped to the account, e.g. the result of
+    // account.try_borrow_mut_lamports() and the values obtained from it by ? or deref_mut
+    pub lamport_refs: HashMap<mir::Local, mir::Local>,
+    // The locals holding the lamports of an account, mapped to the account
+    pub balances: HashMap<mir::Local, mir::Local>,
+    // The accounts whose lamports are decreased or overwritten, rather than only increased
+    pub debited_accounts: HashSet<mir::Local>,
+    // The sources of the locals, to resolve the accounts
+    pub local_sources: LocalSources,
+    // The spans of the mutations without a signer check of their account, each with the span of a
+    // signer check of another account that dominates the mutation, if there is one
+    pub missing_signer_spans: Vec<(Span, Option<Span>)>
+}
+
+impl MissingSignerChecker {
+    pub fn new() -> MissingSignerChecker {
+        return MissingSignerChecker {
+            signer_checks: Vec::new(),
+            lamport_mutations: Vec::new(),
+            signed_accounts: HashSet::default(),
+            lamport_refs: HashMap::default(),
+            balances: HashMap::default(),
+            debited_accounts: HashSet::default(),
+            local_sources: LocalSources::default(),
+            missing_signer_spans: Vec::new()
+        }
+    }
+
+    /// Returns the account whose lamports the local refers to, if any.
+    pub fn account_of(&self, local: mir::Local) -> Option<mir::Local> {
+        return self.lamport_refs.get(&self.local_sources.source_of(local)).copied();
+    }
+
+    /// Check if the lamports of an account are borrowed mutably on a path on which the signer flag
+    /// of the account has not been checked, e.g. after ``if !user.is_signer`` only the lamports of
+    /// user may be mutated. A check of the signer flag of another account does not authorize the
+    /// mutation, so it is reported as well, along with that check. Crediting an account does not
+    /// need its signature, so the lamports of an account that are only increased, e.g. those of
+    /// the user of a withdrawal, may be mutated as well. So may the lamports of the signed
+    /// accounts, e.g. a Signer of an Anchor Context
+    pub fn check(&mut self, guards: &GuardAnalysis<'_>) -> bool {
+        let debited_accounts: HashSet<mir::Local> = self
+            .debited_accounts
+            .iter()
+            .map(|account| self.local_sources.source_of(*account))
+            .collect();
+        let mut missing_signers: Vec<(Span, Option<Span>)> = Vec::new();
+        for (mutation_location, account, mutation_span) in &self.lamport_mutations {
+            let account = self.local_sources.source_of(*account);
+            if self.signed_accounts.contains(&account) || !debited_accounts.contains(&account) {
+                continue;
+            }
+            let dominating_checks: Vec<&(mir::Location, mir::Local, Span)> = self
+                .signer_checks
+                .iter()
+                .filter(|(check_location, _, _)| {
+                    guards.protects(*check_location, *mutation_location)
+                })
+                .collect();
+            let is_signed = dominating_checks
+                .iter()
+                .any(|(_, checked, _)| self.local_sources.source_of(*checked) == account);
+            if !is_signed {
+                let other_check = dominating_checks.first().map(|(_, _, span)| *span);
+                missing_signers.push((*mutation_span, other_check));
+            }
+        }
+        missing_signers.sort();
+        missing_signers.dedup();
+        self.missing_signer_spans = missing_signers;
+        return !self.missing_signer_spans.is_empty();
+    }
+}
+
+// Hold states for the data of accounts that is read without validating the owner of the account
+pub struct OwnerCheckChecker {
+    // The results of next_account_info, i.e. the accounts passed to the instruction
+    pub next_accounts: HashSet<mir::Local>,
+    // The accounts whose owner the locals hold or refer to, keyed by the locals
+    pub owner_values: HashMap<mir::Local, mir::Local>,
+    // The comparisons of the owner of an account with another local, None for a constant
+    pub owner_comparisons: Vec<(mir::Location, mir::Local, Option<mir::Local>)>,
+    // The reads of the data of an account, with the account and the span of the read
+    pub data_reads: Vec<(mir::Location, mir::Local, Span)>,
+    // The sources of the locals, to resolve the accounts
+    pub local_sources: LocalSources,
+    // The spans of the reads of account data without an owner check
+    pub unchecked_owner_spans: Vec<Span>,
+    // The spans of the reads of account data whose owner is only compared with a hardcoded key
+    pub hardcoded_owner_spans: Vec<Span>
+}
+
+impl OwnerCheckChecker {
+    pub fn new() -> OwnerCheckChecker {
+        return OwnerCheckChecker {
+            next_accounts: HashSet::default(),
+            owner_values: HashMap::default(),
+            owner_comparisons: Vec::new(),
+            data_reads: Vec::new(),
+            local_sources: LocalSources::default(),
+            unchecked_owner_spans: Vec::new(),
+            hardcoded_owner_spans: Vec::new()
+        }
+    }
+
+    /// Record that the assigned local holds the owner of an account if the read place is the
+    /// owner field of the account, or a local that holds the owner.
+    pub fn record_owner(
+        &mut self,
+        place: &mir::Place<'_>,
+        read_place: &mir::Place<'_>,
+        is_owner_field: bool,
+    ) {
+        if !place.projection.is_empty() {
+            return;
+        }
+        if is_owner_field {
+            self.owner_values.insert(place.local, read_place.local);
+        } else if let Some(account) = self.owner_values.get(&read_place.local).copied() {
+            self.owner_values.insert(place.local, account);
+        }
+    }
+
+    /// Record an equality comparison of the operands if one of them holds the owner of an account.
+    pub fn compare(
+        &mut self,
+        location: mir::Location,
+        left: Option<mir::Local>,
+        right: Option<mir::Local>,
+    ) {
+        for (owner, other) in [(left, right), (right, left)] {
+            if let Some(account) = owner.and_then(|owner| self.owner_values.get(&owner).copied()) {
+                self.owner_comparisons.push((location, account, other));
+            }
+        }
+    }
+
+    /// Check if the data of an account that is passed to the instruction, i.e. obtained from
+    /// ``next_account_info``, is read without a prior comparison of ``account.owner`` with the id
+    /// of the program. An attacker can pass an account of another program with forged data.
+    /// Comparing the owner only with a hardcoded key, rather than with the program_id argument
+    /// that is one of the first arg_count locals, is reported separately
+    pub fn check(&mut self, guards: &GuardAnalysis<'_>, arg_count: usize) -> bool {
+        let is_argument = |local: mir::Local| local.index() >= 1 && local.index() <= arg_count;
+        let mut unchecked_spans: Vec<Span> = Vec::new();
+        let mut hardcoded_spans: Vec<Span> = Vec::new();
+        for (read_location, account, read_span) in &self.data_reads {
+            let account = self.local_sources.source_of(*account);
+            if !self.next_accounts.contains(&account) {
+                continue;
+            }
+            let comparisons: Vec<Option<mir::Local>> = self
+                .owner_comparisons
+                .iter()
+                .filter(|(compare_location, owner_account, _)| {
+                    self.local_sources.source_of(*owner_account) == account
+                        && guards.protects(*compare_location, *read_location)
+                })
+                .map(|(_, _, other)| *other)
+                .collect();
+            let is_program_id_check = comparisons.iter().flatten().any(|other| {
+                is_argument(self.local_sources.source_of(*other))
+            });
+            if comparisons.is_empty() {
+                unchecked_spans.push(*read_span);
+            } else if !is_program_id_check {
+                hardcoded_spans.push(*read_span);
+            }
+        }
+        unchecked_spans.sort();
+        unchecked_spans.dedup();
+        hardcoded_spans.sort();
+        hardcoded_spans.dedup();
+        self.unchecked_owner_spans = unchecked_spans;
+        self.hardcoded_owner_spans = hardcoded_spans;
+        return !self.unchecked_owner_spans.is_empty() || !self.hardcoded_owner_spans.is_empty();
+    }
+}
+
+// Hold states for the arithmetic on balances that may overflow or underflow
+pub struct IntegerOverflowChecker {
+    // The references to the values of a map, e.g. the results of balances.get_mut(key) and of
+    // balances.entry(key).or_insert(0), and the values obtained from them by ok_or, ? or unwrap
+    pub balance_refs: HashSet<mir::Local>,
+    // The results of the arithmetic on a balance, keyed by the local that holds the result, with
+    // the location and span of the operation, its operator, the balance and the amount
+    pub operation_results:
+        HashMap<mir::Local, (mir::Location, Span, mir::BinOp, mir::Local, Option<mir::Local>)>,
+    // The operations whose result is stored back to their balance, e.g. ``*entry += amount``
+    pub balance_updates: Vec<(mir::Location, Span, mir::BinOp, mir::Local, Option<mir::Local>)>,
+    // The comparisons of two values, such as a balance and an amount, with their location
+    pub comparisons: Vec<(mir::Location, Option<mir::Local>, Option<mir::Local>)>,
+    // The sources of the locals, to resolve the balances and the amounts
+    pub local_sources: LocalSources,
+    // The spans of the unchecked operations on balances, each with the message that suggests the
+    // checked operation to use instead
+    pub unchecked_operations: Vec<(Span, &'static str)>,
+    // The spans of the unchecked operations whose amount is read from the instruction data or the
+    // data of an account
+    pub tainted_operations: HashSet<Span>
+}
+
+impl IntegerOverflowChecker {
+    pub fn new() -> IntegerOverflowChecker {
+        return IntegerOverflowChecker {
+            balance_refs: HashSet::default(),
+            operation_results: HashMap::default(),
+            balance_updates: Vec::new(),
+            comparisons: Vec::new(),
+            local_sources: LocalSources::default(),
+            unchecked_operations: Vec::new(),
+            tainted_operations: HashSet::default()
+        }
+    }
+
+    /// Returns the balance that the place refers to if it dereferences a reference to the value
+    /// of a map, e.g. ``*entry``.
+    pub fn balance_of(&self, place: &mir::Place<'_>) -> Option<mir::Local> {
+        if !place.is_indirect_first_projection() {
+            return None;
+        }
+        let source = self.local_sources.source_of(place.local);
+        return self.balance_refs.contains(&source).then_some(source);
+    }
+
+    /// Returns how confident the checker is of the overflow of the operation at the span. An
+    /// amount that the caller chooses can be made as large as the overflow needs.
+    pub fn confidence(&self, span: Span) -> Confidence {
+        return if self.tainted_operations.contains(&span) {
+            Confidence::High
+        } else {
+            Confidence::Medium
+        };
+    }
+
+    /// Returns the message for an unchecked operation of the binary operator, which suggests the
+    /// checked operation to use instead, or None if the operator does not overflow.
+    pub fn overflow_message(bin_op: mir::BinOp) -> Option<&'static str> {
+        return match bin_op {
+            mir::BinOp::Add | mir::BinOp::AddWithOverflow => Some(
+                "possible overflow of unchecked addition to a balance, prefer checked_add for the \
+                 smart contract",
+            ),
+            mir::BinOp::Sub | mir::BinOp::SubWithOverflow => Some(
+                "possible underflow of unchecked subtraction from a balance, prefer checked_sub \
+                 for the smart contract",
+            ),
+            mir::BinOp::Mul | mir::BinOp::MulWithOverflow => Some(
+                "possible overflow of unchecked multiplication of a balance, prefer checked_mul \
+                 for the smart contract",
+            ),
+            _ => None,
+        };
+    }
+
+    /// Check if a balance is updated by arithmetic that may overflow or underflow, e.g.
+    /// ``*entry += amount``. The overflow checks of MIR are left out of release builds, where the
+    /// balance silently wraps around, so ``checked_add`` and the like should be used instead.
+    /// Only the u64 and i64 values of a map, or the lamports of an account, that the result of the
+    /// operation is stored back to count as balances, so arithmetic on other values behind a
+    /// reference, such as the fields of a struct, is left alone. A subtraction after a comparison
+    /// of the balance with the amount, e.g. ``if *balance < amount { return Err(..) }``, does not
+    /// underflow either
+    pub fn check(&mut self, guards: &GuardAnalysis<'_>) -> bool {
+        let mut unchecked_operations: Vec<(Span, &'static str)> = Vec::new();
+        for (location, span, bin_op, balance, amount) in &self.balance_updates {
+            let Some(message) = IntegerOverflowChecker::overflow_message(*bin_op) else {
+                continue;
+            };
+            let is_subtraction = matches!(bin_op, mir::BinOp::Sub | mir::BinOp::SubWithOverflow);
+            let is_compared = amount.is_some()
+                && self.comparisons.iter().any(|(comparison_location, left, right)| {
+                    let operands = [*left, *right];
+                    operands.contains(&Some(*balance))
+                        && operands.contains(amount)
+                        && guards.protects(*comparison_location, *location)
+                });
+            if !is_subtraction || !is_compared {
+                unchecked_operations.push((*span, message));
+            }
+        }
+        unchecked_operations.sort();
+        unchecked_operations.dedup();
+        self.unchecked_operations = unchecked_operations;
+        return !self.unchecked_operations.is_empty();
+    }
+}
+
+// Hold states for the maps that are mutated as contract state but never persisted to account data
+pub struct NonPersistentStateChecker {
+    // The maps that are created in the function body, keyed by the local with the allocation span
+    pub collections: HashMap<mir::Local, Span>,
+    // The locals that are borrowed mutably, e.g. to insert into a map or to pass a map to a helper
+    pub mutable_borrows: HashSet<mir::Local>,
+    // Check for detecting a write of account data, e.g. by ``try_borrow_mut_data`` or ``serialize``
+    pub check_for_persist: bool,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The spans of the allocations of the maps that are never persisted
+    pub non_persistent_state_spans: Vec<Span>
+}
+
+impl NonPersistentStateChecker {
+    pub fn new() -> NonPersistentStateChecker {
+        return NonPersistentStateChecker {
+            collections: HashMap::default(),
+            mutable_borrows: HashSet::default(),
+            check_for_persist: false,
+            local_sources: LocalSources::default(),
+            non_persistent_state_spans: Vec::new()
+        }
+    }
+
+    /// Check if a map that is created in the function body is mutated, but nothing is written to
+    /// the data of an account before the function returns, e.g. deposits into
+    /// ``let mut balances = HashMap::new()``. The map starts out empty in every instruction, so
+    /// the mutations are lost once the instruction has been processed.
+    pub fn check(&mut self) -> bool {
+        if self.check_for_persist {
+            return false;
+        }
+        let mutated_collections: HashSet<mir::Local> = self
+            .mutable_borrows
+            .iter()
+            .map(|local| self.local_sources.source_of(*local))
+            .collect();
+        let mut spans: Vec<Span> = self
+            .collections
+            .iter()
+            .filter(|(collection, _)| mutated_collections.contains(*collection))
+            .map(|(_, span)| *span)
+            .collect();
+        spans.sort();
+        spans.dedup();
+        self.non_persistent_state_spans = spans;
+        return !self.non_persistent_state_spans.is_empty();
+    }
+}
+
+// Hold states for the cross-program invocations of programs whose ids are taken from passed
+// accounts
+pub struct ArbitraryCpiChecker {
+    // The results of next_account_info, i.e. the accounts passed to the instruction
+    pub next_accounts: HashSet<mir::Local>,
+    // The accounts whose key the locals hold or refer to, keyed by the locals
+    pub key_values: HashMap<mir::Local, mir::Local>,
+    // The instructions that are built, mapped to the local of their program id
+    pub instruction_program_ids: HashMap<mir::Local, mir::Local>,
+    // The comparisons of the key of an account with a value that is not the key of an account
+    pub key_comparisons: Vec<(mir::Location, mir::Local)>,
+    // The operands of all equality comparisons, e.g. of a program id read from the instruction
+    // data with a known id
+    pub value_comparisons: Vec<(mir::Location, mir::Local)>,
+    // The calls to invoke and invoke_signed, with the local of the instruction and the call span
+    pub invocations: Vec<(mir::Location, mir::Local, Span)>,
+    // The sources of the locals, to resolve the instructions and the accounts
+    pub local_sources: LocalSources,
+    // The spans of the invocations of a program whose id is not checked
+    pub arbitrary_cpi_spans: Vec<Span>
+}
+
+impl ArbitraryCpiChecker {
+    pub fn new() -> ArbitraryCpiChecker {
+        return ArbitraryCpiChecker {
+            next_accounts: HashSet::default(),
+            key_values: HashMap::default(),
+            instruction_program_ids: HashMap::default(),
+            key_comparisons: Vec::new(),
+            value_comparisons: Vec::new(),
+            invocations: Vec::new(),
+            local_sources: LocalSources::default(),
+            arbitrary_cpi_spans: Vec::new()
+        }
+    }
+
+    /// Record that the assigned local holds the key of an account if the read place is the key
+    /// field of the account, or a local that holds the key.
+    pub fn record_key(
+        &mut self,
+        place: &mir::Place<'_>,
+        read_place: &mir::Place<'_>,
+        is_key_field: bool,
+    ) {
+        if !place.projection.is_empty() {
+            return;
+        }
+        if is_key_field {
+            self.key_values.insert(place.local, read_place.local);
+        } else if let Some(account) = self.key_values.get(&read_place.local).copied() {
+            self.key_values.insert(place.local, account);
+        }
+    }
+
+    /// Record an equality comparison of the operands if one of them holds the key of an account
+    /// and the other one does not, e.g. ``token_program.key == &spl_token::id()``.
+    pub fn compare(
+        &mut self,
+        location: mir::Location,
+        left: Option<mir::Local>,
+        right: Option<mir::Local>,
+    ) {
+        let account_of = |local: Option<mir::Local>| {
+            local.and_then(|local| {
+                self.key_values
+                    .get(&local)
+                    .or_else(|| self.key_values.get(&self.local_sources.source_of(local)))
+                    .copied()
+            })
+        };
+        match (account_of(left), account_of(right)) {
+            (Some(account), None) | (None, Some(account)) => {
+                self.key_comparisons.push((location, account));
+            }
+            _ => {}
+        }
+        for local in [left, right].into_iter().flatten() {
+            self.value_comparisons.push((location, self.local_sources.source_of(local)));
+        }
+    }
+
+    /// Check if ``invoke`` or ``invoke_signed`` is called with an instruction whose program id is
+    /// the key of an account passed to the instruction, i.e. an argument among the first arg_count
+    /// locals or a result of ``next_account_info``, while the key is not compared beforehand with a
+    /// known id, e.g. ``spl_token::id()``. An attacker can pass a program of their own in place of
+    /// the expected one, which is then invoked with the accounts and signers of the caller.
+    /// A program id that is read from the instruction data or the data of an account is as
+    /// arbitrary, unless it is compared beforehand as well
+    pub fn check(
+        &mut self,
+        guards: &GuardAnalysis<'_>,
+        arg_count: usize,
+        taint_analysis: &TaintAnalysis,
+    ) -> bool {
+        let is_passed = |account: mir::Local| {
+            (account.index() >= 1 && account.index() <= arg_count)
+                || self.next_accounts.contains(&account)
+        };
+        let mut spans: Vec<Span> = Vec::new();
+        for (invoke_location, instruction, invoke_span) in &self.invocations {
+            let instruction = self.local_sources.source_of(*instruction);
+            let Some(program_id) = self.instruction_program_ids.get(&instruction) else {
+                continue;
+            };
+            let account = self
+                .key_values
+                .get(program_id)
+                .or_else(|| self.key_values.get(&self.local_sources.source_of(*program_id)));
+            let Some(account) = account else {
+                let program_id = self.local_sources.source_of(*program_id);
+                let is_checked = guards.is_guarded(*invoke_location, |guard| {
+                    self.value_comparisons.contains(&(guard, program_id))
+                });
+                if taint_analysis.is_tainted(mir::Place::from(program_id)) && !is_checked {
+                    spans.push(*invoke_span);
+                }
+                continue;
+            };
+            let account = self.local_sources.source_of(*account);
+            if !is_passed(account) {
+                continue;
+            }
+            let is_checked = guards.is_guarded(*invoke_location, |guard| {
+                self.key_comparisons.iter().any(|(compare_location, compared)| {
+                    *compare_location == guard && self.local_sources.source_of(*compared) == account
+                })
+            });
+            if !is_checked {
+                spans.push(*invoke_span);
+            }
+        }
+        spans.sort();
+        spans.dedup();
+        self.arbitrary_cpi_spans = spans;
+        return !self.arbitrary_cpi_spans.is_empty();
+    }
+}
+
+// Hold states for the program derived addresses that are created with a bump seed of the caller
+pub struct PdaBumpChecker {
+    // The elements of the arrays, keyed by the locals that hold the arrays,
+    // an element is None if it is a constant operand
+    pub arrays: HashMap<mir::Local, Vec<Option<mir::Local>>>,
+    // The arguments that the caller supplies, i.e. the bytes and byte slices such as the
+    // instruction data
+    pub untrusted_args: HashSet<mir::Local>,
+    // The seeds passed to create_program_address, with the call spans
+    pub creations: Vec<(Span, mir::Local)>,
+    // The sources of the locals, to resolve the seeds and the bytes they are made of
+    pub local_sources: LocalSources,
+    // The spans of the creations whose bump seed is supplied by the caller
+    pub pda_bump_spans: Vec<Span>
+}
+
+impl PdaBumpChecker {
+    pub fn new() -> PdaBumpChecker {
+        return PdaBumpChecker {
+            arrays: HashMap::default(),
+            untrusted_args: HashSet::default(),
+            creations: Vec::new(),
+            local_sources: LocalSources::default(),
+            pda_bump_spans: Vec::new()
+        }
+    }
+
+    /// Returns true if one of the seeds held by the local, or a byte of one of the seeds, is read
+    /// from an argument that the caller supplies, e.g. ``&[instruction_data[0]]``, or computed from
+    /// the instruction data, e.g. ``&[instruction_data[0] ^ 1]``.
+    fn has_untrusted_seed(&self, seeds: mir::Local, taint_analysis: &TaintAnalysis) -> bool {
+        let mut visited: HashSet<mir::Local> = HashSet::new();
+        let mut worklist = vec![seeds];
+        while let Some(local) = worklist.pop() {
+            let source = self.local_sources.source_of(local);
+            if !visited.insert(source) {
+                continue;
+            }
+            let is_from_instruction_data = taint_analysis
+                .is_tainted_by(mir::Place::from(local), TaintSource::InstructionData);
+            if self.untrusted_args.contains(&source) || is_from_instruction_data {
+                return true;
+            }
+            if let Some(elements) = self.arrays.get(&source).or(self.arrays.get(&local)) {
+                worklist.extend(elements.iter().flatten());
+            }
+        }
+        return false;
+    }
+
+    /// Check if ``create_program_address`` is given a bump seed that is read from the instruction
+    /// data or another argument of the caller, rather than the canonical bump that
+    /// ``find_program_address`` returns or a bump stored in an account. Any bump that the caller
+    /// chooses yields a valid address, so one program can be made to accept many addresses
+    pub fn check(&mut self, taint_analysis: &TaintAnalysis) -> bool {
+        let mut spans: Vec<Span> = self
+            .creations
+            .iter()
+            .filter(|(_, seeds)| self.has_untrusted_seed(*seeds, taint_analysis))
+            .map(|(span, _)| *span)
+            .collect();
+        spans.sort();
+        spans.dedup();
+        self.pda_bump_spans = spans;
+        return !self.pda_bump_spans.is_empty();
+    }
+}
+
+// Hold states for the accounts that are closed without zeroing their data
+pub struct CloseAuthorityChecker {
+    // The assignments of zero to the lamports of an account, keyed by the assignment location,
+    // along with the account
+    pub lamport_zeroings: HashMap<mir::Location, (Span, mir::Local)>,
+    // The accounts whose current balance is read, e.g. by account.lamports()
+    pub balance_reads: HashSet<mir::Local>,
+    // Check for detecting that account data is zeroed, e.g. by data.fill(0) or sol_memset
+    pub check_for_data_zeroing: bool,
+    // The spans of the lamport drains of accounts whose data is not zeroed
+    pub close_authority_spans: Vec<Span>
+}
+
+impl CloseAuthorityChecker {
+    pub fn new() -> CloseAuthorityChecker {
+        return CloseAuthorityChecker {
+            lamport_zeroings: HashMap::default(),
+            balance_reads: HashSet::default(),
+            check_for_data_zeroing: false,
+            close_authority_spans: Vec::new()
+        }
+    }
+
+    /// Check if an account is closed by moving its balance elsewhere and zeroing its lamports,
+    /// e.g. ``**account.try_borrow_mut_lamports()? = 0`` after crediting the balance to another
+    /// account, while the body never zeroes account data. Until the runtime garbage collects the
+    /// account, another instruction of the same transaction can refund the rent and revive the
+    /// account with all of its data, so the data should be zeroed whether before or after the drain
+    pub fn check(&mut self) -> bool {
+        if self.check_for_data_zeroing {
+            return false;
+        }
+        let mut spans: Vec<Span> = self
+            .lamport_zeroings
+            .values()
+            .filter(|(_, account)| self.balance_reads.contains(account))
+            .map(|(span, _)| *span)
+            .collect();
+        spans.sort();
+        spans.dedup();
+        self.close_authority_spans = spans;
+        return !self.close_authority_spans.is_empty();
+    }
+}
+
+// Hold states for the casts that truncate an amount read from the instruction data or a balance
+pub struct TruncatingCastChecker {
+    // The locals that hold a value read from the instruction data or from a balance, or derived
+    // from it, with the span of the read
+    pub amounts: HashMap<mir::Local, Span>,
+    // The narrowing casts of amounts, with the span of the cast, the span of the read of the
+    // amount, and the source and the target types
+    pub narrowing_casts: Vec<(Span, Span, String, String)>,
+    // The spans contain codes related to the truncating casts, as in narrowing_casts
+    pub truncating_cast_spans: Vec<(Span, Span, String, String)>
+}
+
+impl TruncatingCastChecker {
+    pub fn new() -> TruncatingCastChecker {
+        return TruncatingCastChecker {
+            amounts: HashMap::default(),
+            narrowing_casts: Vec::new(),
+            truncating_cast_spans: Vec::new()
+        }
+    }
+
+    /// Returns the span of the read of the amount that the place holds, if it holds one.
+    pub fn amount_of(&self, place: &mir::Place<'_>) -> Option<Span> {
+        return self.amounts.get(&place.local).copied();
+    }
+
+    /// Record that the assigned local holds an amount if the rvalue uses one. The discriminant of
+    /// an enum is never an amount, so its casts, e.g. ``instruction as u8``, are not recorded.
+    pub fn record(&mut self, place: &mir::Place<'_>, rvalue: &mir::Rvalue<'_>) {
+        if !place.projection.is_empty() {
+            return;
+        }
+        let used_places: Vec<mir::Place<'_>> = match rvalue {
+            mir::Rvalue::Use(operand)
+            | mir::Rvalue::Cast(_, operand, _)
+            | mir::Rvalue::UnaryOp(_, operand) => operand.place().into_iter().collect(),
+            mir::Rvalue::BinaryOp(_, box (left, right)) => {
+                [left, right].iter().filter_map(|operand| operand.place()).collect()
+            }
+            mir::Rvalue::Ref(_, _, used) | mir::Rvalue::CopyForDeref(used) => vec![*used],
+            _ => Vec::new(),
+        };
+        if let Some(read_span) = used_places.iter().find_map(|used| self.amount_of(used)) {
+            self.amounts.entry(place.local).or_insert(read_span);
+        }
+    }
+
+    /// Check if an amount read from the instruction data or from a balance, such as the lamports
+    /// of an account, is cast to a narrower integer type, e.g. ``amount as u32``. Such a cast
+    /// silently drops the high bits, so ``u32::try_from`` should be used instead. Widening casts
+    /// are not recorded
+    pub fn check(&mut self) -> bool {
+        let mut casts = self.narrowing_casts.clone();
+        casts.sort();
+        casts.dedup();
+        self.truncating_cast_spans = casts;
+        return !self.truncating_cast_spans.is_empty();
+    }
+}
+
+// Hold states for the accounts that are created with lamports that are not computed by the rent
+pub struct RentExemptionChecker {
+    // The locals that hold the lamports computed by Rent::minimum_balance, or a value derived
+    // from them
+    pub rent_balances: HashSet<mir::Local>,
+    // The locals that hold a constant, or a copy of one
+    pub constants: HashSet<mir::Local>,
+    // The account creations whose lamports are not known to be rent exempt, with the span of the
+    // creation and whether the lamports are a constant
+    pub creations: Vec<(Span, bool)>,
+    // The spans contain codes related to the account creations, as in creations
+    pub rent_exemption_spans: Vec<(Span, bool)>
+}
+
+impl RentExemptionChecker {
+    pub fn new() -> RentExemptionChecker {
+        return RentExemptionChecker {
+            rent_balances: HashSet::default(),
+            constants: HashSet::default(),
+            creations: Vec::new(),
+            rent_exemption_spans: Vec::new()
+        }
+    }
+
+    /// Record that the assigned local holds rent exempt lamports if the rvalue uses them, e.g.
+    /// ``rent.minimum_balance(len) + extra``, and that it holds a constant if the rvalue is one.
+    pub fn record(&mut self, place: &mir::Place<'_>, rvalue: &mir::Rvalue<'_>) {
+        if !place.projection.is_empty() {
+            return;
+        }
+        let used_operands: Vec<&mir::Operand<'_>> = match rvalue {
+            mir::Rvalue::Use(operand)
+            | mir::Rvalue::Cast(_, operand, _)
+            | mir::Rvalue::UnaryOp(_, operand) => vec![operand],
+            mir::Rvalue::BinaryOp(_, box (left, right)) => vec![left, right],
+            _ => Vec::new(),
+        };
+        let uses_rent_balance = used_operands
+            .iter()
+            .filter_map(|operand| operand.place())
+            .any(|used| self.rent_balances.contains(&used.local));
+        if uses_rent_balance {
+            self.rent_balances.insert(place.local);
+        }
+        if let mir::Rvalue::Use(operand) = rvalue {
+            if self.is_constant(operand) {
+                self.constants.insert(place.local);
+            }
+        }
+    }
+
+    /// Returns true if the operand is a constant, or a local that holds a copy of one.
+    pub fn is_constant(&self, operand: &mir::Operand<'_>) -> bool {
+        return match operand {
+            mir::Operand::Constant(_) => true,
+            mir::Operand::Copy(place) | mir::Operand::Move(place) => {
+                place.projection.is_empty() && self.constants.contains(&place.local)
+            }
+        };
+    }
+
+    /// Records the creation of an account at span with the given lamports, unless the lamports
+    /// are computed by Rent::minimum_balance.
+    pub fn create_account(&mut self, lamports: &mir::Operand<'_>, span: Span) {
+        let is_rent_balance = lamports
+            .place()
+            .is_some_and(|lamports| self.rent_balances.contains(&lamports.local));
+        if !is_rent_balance {
+            self.creations.push((span, self.is_constant(lamports)));
+        }
+    }
+
+    /// Check if an account is created by ``system_instruction::create_account`` with lamports
+    /// that do not trace back to ``Rent::minimum_balance``. An account whose balance is below the
+    /// minimum for its data length is not rent exempt, so the runtime refuses to create it or
+    /// reaps it.
+    /// Lamports that are hard coded are recorded as such, since the minimum changes with the data
+    /// length and with the rent of the cluster
+    pub fn check(&mut self) -> bool {
+        let mut creations = self.creations.clone();
+        creations.sort();
+        creations.dedup();
+        self.rent_exemption_spans = creations;
+        return !self.rent_exemption_spans.is_empty();
+    }
+}
+
+// Hold states for the data and lamports of accounts that are mutated without checking that the
+// account is writable
+pub struct IsWritableChecker {
+    // The reads of the writable flag, or the calls of a writable check helper, with the account
+    // and the span of the check
+    pub writable_checks: Vec<(mir::Location, mir::Local, Span)>,
+    // The mutable borrows of data or lamports, with the account and the span of the borrow
+    pub mutations: Vec<(mir::Location, mir::Local, Span)>,
+    // The accounts whose key is compared with an address that the program derives, i.e. the
+    // program derived addresses that the program signs for
+    pub signed_accounts: HashSet<mir::Local>,
+    // The accounts whose writable flag is checked before the body is entered, i.e. the accounts
+    // of the Context of an Anchor handler with the mut constraint
+    pub writable_accounts: HashSet<mir::Local>,
+    // The sources of the locals, to resolve the accounts
+    pub local_sources: LocalSources,
+    // The spans of the mutations without a writable check of their account
+    pub is_writable_spans: Vec<Span>
+}
+
+impl IsWritableChecker {
+    pub fn new() -> IsWritableChecker {
+        return IsWritableChecker {
+            writable_checks: Vec::new(),
+            mutations: Vec::new(),
+            signed_accounts: HashSet::default(),
+            writable_accounts: HashSet::default(),
+            local_sources: LocalSources::default(),
+            is_writable_spans: Vec::new()
+        }
+    }
+
+    /// Records that the account of the key is a program derived address of the program, since its
+    /// key is compared with a derived address, e.g. ``if vault.key != &expected_vault``.
+    pub fn record_signed_account(&mut self, key: mir::Local) {
+        let account = self.local_sources.source_of(key);
+        self.signed_accounts.insert(account);
+    }
+
+    /// Check if the data or the lamports of an account are borrowed mutably on a path on which the
+    /// writable flag of the account has not been read, e.g. without ``if !vault.is_writable``.
+    /// The runtime rejects the writes to an account that the transaction has not marked writable,
+    /// but the failure then surfaces late and without the error of the program. The program derived
+    /// addresses of the program are skipped, since the program decides about them itself, and so
+    /// are the writable accounts, whose flag has been checked before
+    pub fn check(&mut self, guards: &GuardAnalysis<'_>) -> bool {
+        let mut unchecked_mutations: Vec<Span> = Vec::new();
+        for (mutation_location, account, mutation_span) in &self.mutations {
+            let account = self.local_sources.source_of(*account);
+            if self.signed_accounts.contains(&account)
+                || self.writable_accounts.contains(&account)
+            {
+                continue;
+            }
+            let is_checked = guards.is_guarded(*mutation_location, |guard| {
+                self.writable_checks.iter().any(|(check_location, checked, _)| {
+                    *check_location == guard && self.local_sources.source_of(*checked) == account
+                })
+            });
+            if !is_checked {
+                unchecked_mutations.push(*mutation_span);
+            }
+        }
+        unchecked_mutations.sort();
+        unchecked_mutations.dedup();
+        self.is_writable_spans = unchecked_mutations;
+        return !self.is_writable_spans.is_empty();
+    }
+}
+
+// Hold states for the state that is looked up by the key of an account while the signature of
+// another account is checked
+pub struct AccountSubstitutionChecker {
+    // The reads of the signer flag, or the calls of a signer check helper, with the account and
+    // the span of the check
+    pub signer_checks: Vec<(mir::Location, mir::Local, Span)>,
+    // The accounts whose key the locals hold or refer to, keyed by the locals
+    pub key_values: HashMap<mir::Local, mir::Local>,
+    // The lookups of a map, with the local of the key and the span of the lookup
+    pub key_lookups: Vec<(mir::Location, mir::Local, Span)>,
+    // The sources of the locals, to resolve the keys and the accounts
+    pub local_sources: LocalSources,
+    // The spans of the lookups by the key of an account that is not checked to be a signer, each
+    // with the span of the signer check of the other account
+    pub account_substitution_spans: Vec<(Span, Span)>
+}
+
+impl AccountSubstitutionChecker {
+    pub fn new() -> AccountSubstitutionChecker {
+        return AccountSubstitutionChecker {
+            signer_checks: Vec::new(),
+            key_values: HashMap::default(),
+            key_lookups: Vec::new(),
+            local_sources: LocalSources::default(),
+            account_substitution_spans: Vec::new()
+        }
+    }
+
+    /// Record that the assigned local holds the key of an account if the read place is the key
+    /// field of the account, or a local that holds the key.
+    pub fn record_key(
+        &mut self,
+        place: &mir::Place<'_>,
+        read_place: &mir::Place<'_>,
+        is_key_field: bool,
+    ) {
+        if !place.projection.is_empty() {
+            return;
+        }
+        if is_key_field {
+            self.key_values.insert(place.local, read_place.local);
+        } else if let Some(account) = self.key_values.get(&read_place.local).copied() {
+            self.key_values.insert(place.local, account);
+        }
+    }
+
+    /// Check if a map is looked up by the key of an account on a path on which the signer flag of
+    /// another account, but not of the account itself, has been checked, e.g. when the balances
+    /// are looked up by ``user_account.key`` after ``if !contract_account.is_signer``. Anyone can
+    /// pass the key of a victim as the account, since the account does not have to sign, and then
+    /// operate on the state of the victim. Lookups in functions that do not check a signer at all
+    /// are left to the missing signer checker
+    pub fn check(&mut self, guards: &GuardAnalysis<'_>) -> bool {
+        let mut substitutions: Vec<(Span, Span)> = Vec::new();
+        for (lookup_location, key, lookup_span) in &self.key_lookups {
+            let account = self
+                .key_values
+                .get(key)
+                .or_else(|| self.key_values.get(&self.local_sources.source_of(*key)));
+            let Some(account) = account else {
+                continue;
+            };
+            let account = self.local_sources.source_of(*account);
+            let dominating_checks: Vec<&(mir::Location, mir::Local, Span)> = self
+                .signer_checks
+                .iter()
+                .filter(|(check_location, _, _)| {
+                    guards.protects(*check_location, *lookup_location)
+                })
+                .collect();
+            let Some((_, _, other_check)) = dominating_checks.first() else {
+                continue;
+            };
+            let is_signed = dominating_checks
+                .iter()
+                .any(|(_, checked, _)| self.local_sources.source_of(*checked) == account);
+            if !is_signed {
+                substitutions.push((*lookup_span, *other_check));
+            }
+        }
+        substitutions.sort();
+        substitutions.dedup();
+        self.account_substitution_spans = substitutions;
+        return !self.account_substitution_spans.is_empty();
+    }
+}
+
+// Hold states for the panics that the input of the caller decides on
+pub struct PanicReachabilityChecker {
+    // The calls to unwrap and expect on an Option or a Result with the local of the unwrapped
+    // value, except those on a conversion of a slice, which the slice panic checker reports
+    pub unwraps: Vec<(mir::Location, mir::Local, Span)>,
+    // The calls to the panic functions, e.g. by ``panic!``
+    pub panics: Vec<(mir::Location, Span)>,
+    // The branches on a tainted value with the local of the value
+    pub tainted_branches: Vec<(mir::Location, mir::Local, Span)>,
+    // The spans of the panics that tainted input decides on, each with the data that the input
+    // comes from and the span of the branch that leads to an explicit panic
+    pub panic_reachability_spans: Vec<(Span, TaintSource, Option<Span>)>
+}
+
+impl PanicReachabilityChecker {
+    pub fn new() -> PanicReachabilityChecker {
+        return PanicReachabilityChecker {
+            unwraps: Vec::new(),
+            panics: Vec::new(),
+            tainted_branches: Vec::new(),
+            panic_reachability_spans: Vec::new()
+        }
+    }
+
+    /// Returns the data that the value of the local comes from, if it is tainted.
+    fn taint_source_of(local: mir::Local, taint_analysis: &TaintAnalysis) -> Option<TaintSource> {
+        let place = mir::Place::from(local);
+        return [TaintSource::InstructionData, TaintSource::AccountData]
+            .into_iter()
+            .find(|source| taint_analysis.is_tainted_by(place, *source));
+    }
+
+    /// Check if a panic is decided by the input of the caller, i.e. if ``unwrap`` or ``expect``
+    /// is called on an Option or a Result that comes from the instruction data or the data of an
+    /// account, e.g. ``instruction_data.get(1..9).unwrap()``, or if ``panic!`` is only reached on
+    /// an arm of a branch on such a value. An attacker can then abort any transaction that goes
+    /// through the function with the input they choose. Unwrapping values that do not come from
+    /// the input, e.g. constants, cannot be triggered by the attacker and is not reported
+    pub fn check(&mut self, guards: &GuardAnalysis<'_>, taint_analysis: &TaintAnalysis) -> bool {
+        let mut panic_spans: Vec<(Span, TaintSource, Option<Span>)> = Vec::new();
+        for (_, unwrapped, unwrap_span) in &self.unwraps {
+            if let Some(source) = Self::taint_source_of(*unwrapped, taint_analysis) {
+                panic_spans.push((*unwrap_span, source, None));
+            }
+        }
+        for (panic_location, panic_span) in &self.panics {
+            // The blocks are visited in order, so the nearest branch to the panic is visited last
+            let branch = self.tainted_branches.iter().rev().find_map(|(location, value, span)| {
+                if !guards.branches_to(location.block, *panic_location) {
+                    return None;
+                }
+                return Self::taint_source_of(*value, taint_analysis).map(|source| (source, *span));
+            });
+            if let Some((source, branch_span)) = branch {
+                panic_spans.push((*panic_span, source, Some(branch_span)));
+            }
+        }
+        panic_spans.sort_by_key(|(span, _, _)| *span);
+        panic_spans.dedup_by_key(|(span, _, _)| *span);
+        self.panic_reachability_spans = panic_spans;
+        return !self.panic_reachability_spans.is_empty();
+    }
+}
+
+// Hold states for the results of cross-program invocations that are never checked
+pub struct UncheckedCpiResultChecker {
+    // The locals holding the result of an invocation, mapped to the span of the call
+    pub cpi_results: HashMap<mir::Local, Span>,
+    // The locals that are read other than by a copy or move into another local, e.g. by ``?``,
+    // by a match on their discriminant or by being returned
+    pub read_locals: HashSet<mir::Local>,
+    // The local that a temporary variable is copied, moved or reborrowed from
+    pub local_sources: LocalSources,
+    // The spans of the invocations whose result is never read
+    pub unchecked_cpi_result_spans: Vec<Span>
+}
+
+impl UncheckedCpiResultChecker {
+    pub fn new() -> UncheckedCpiResultChecker {
+        return UncheckedCpiResultChecker {
+            cpi_results: HashMap::default(),
+            read_locals: HashSet::default(),
+            local_sources: LocalSources::default(),
+            unchecked_cpi_result_spans: Vec::new()
+        }
+    }
+
+    /// Returns true if a call of the function with the given path invokes another program and
+    /// returns whether the invocation succeeded, i.e. invoke, invoke_signed or a CPI helper of
+    /// Anchor such as ``anchor_spl::token::transfer``.
+    pub fn is_invocation(callee_path: &str) -> bool {
+        return is_path_of(callee_path, "invoke")
+            || is_path_of(callee_path, "invoke_signed")
+            || anchor::is_cpi_transfer(callee_path);
+    }
+
+    /// Records the locals that the assignment reads. A plain copy, move or reborrow into another
+    /// local is not a read, the local that it is copied into is read in its place. An assignment
+    /// to the return place or into a field is a read, the caller gets the value.
+    pub fn visit_assign(&mut self, place: &mir::Place<'_>, rvalue: &mir::Rvalue<'_>) {
+        self.local_sources.record(place, rvalue);
+        let is_copy = matches!(
+            rvalue,
+            mir::Rvalue::Use(mir::Operand::Copy(_) | mir::Operand::Move(_))
+                | mir::Rvalue::Ref(..)
+                | mir::Rvalue::CopyForDeref(_)
+        );
+        if is_copy && place.projection.is_empty() && place.local != mir::RETURN_PLACE {
+            return;
+        }
+        let read_places: Vec<mir::Place<'_>> = match rvalue {
+            mir::Rvalue::Use(operand)
+            | mir::Rvalue::Repeat(operand, _)
+            | mir::Rvalue::Cast(_, operand, _)
+            | mir::Rvalue::UnaryOp(_, operand) => operand.place().into_iter().collect(),
+            mir::Rvalue::BinaryOp(_, box (left_operand, right_operand)) => {
+                [left_operand, right_operand].iter().filter_map(|operand| operand.place()).collect()
+            }
+            mir::Rvalue::Aggregate(_, operands) => {
+                operands.iter().filter_map(|operand| operand.place()).collect()
+            }
+            mir::Rvalue::Ref(_, _, read_place)
+            | mir::Rvalue::CopyForDeref(read_place)
+            | mir::Rvalue::Discriminant(read_place) => vec![*read_place],
+            _ => Vec::new(),
+        };
+        self.read_locals.extend(read_places.iter().map(|read_place| read_place.local));
+    }
+
+    /// Records the locals that a call reads through its arguments, e.g. the result that ``?``
+    /// gives to ``Try::branch`` or that ``is_err`` is called on.
+    pub fn visit_call_args(&mut self, args: &[Spanned<mir::Operand<'_>>]) {
+        for arg in args {
+            if let Some(place) = arg.node.place() {
+                self.read_locals.insert(place.local);
+            }
+        }
+    }
+
+    /// Check if the result of a cross-program invocation is never read, e.g. by ``invoke(..);``
+    /// or ``let _ = invoke(..);``, so that neither ``?`` nor a match decides on the error. The
+    /// function then proceeds as if the invocation had succeeded, although the invoked program
+    /// may have failed. A result that is matched while the ``Err`` arm does nothing is a
+    /// swallowed error instead, see SwallowedCpiErrorChecker
+    pub fn check(&mut self) -> bool {
+        let read_sources: HashSet<mir::Local> = self
+            .read_locals
+            .iter()
+            .map(|local| self.local_sources.source_of(*local))
+            .collect();
+        let mut result_spans: Vec<Span> = self
+            .cpi_results
+            .iter()
+            .filter(|(result, _)| **result != mir::RETURN_PLACE && !read_sources.contains(result))
+            .map(|(_, span)| *span)
+            .collect();
+        result_spans.sort();
+        result_spans.dedup();
+        self.unchecked_cpi_result_spans = result_spans;
+        return !self.unchecked_cpi_result_spans.is_empty();
+    }
+}
+
+// The calls that deserialize a value from bytes, by Borsh or bincode or by hand
+const DESERIALIZERS: [&str; 4] =
+    [".try_from_slice", ".deserialize", ".from_le_bytes", ".from_be_bytes"];
+
+// The calls whose result is derived from their first argument, e.g. the bytes of ``&data[..8]``
+const DATA_DERIVATIONS: [&str; 18] = [
+    ".deref", ".deref_mut", ".borrow", ".borrow_mut", ".index", ".index_mut", ".branch",
+    ".unwrap", ".expect", ".ok", ".ok_or", ".map_err", ".try_into", ".as_ref", ".get", ".first",
+    ".len", ".as_slice",
+];
+
+// Hold states for the data of accounts that is deserialized without validating its length or its
+// discriminator
+pub struct UncheckedDeserializeChecker {
+    // The locals holding the data of an account, mapped to the account and the span of the read
+    pub data_reads: HashMap<mir::Local, (mir::Local, Span)>,
+    // The comparisons with the local of a compared value, e.g. of ``data.len()`` with the size of
+    // the state or of ``data[0]`` with the discriminator of the type
+    pub comparisons: Vec<(mir::Location, mir::Local)>,
+    // The lengths loaded by the compiler for its own bounds checks
+    pub bounds_check_lengths: HashSet<mir::Local>,
+    // The deserializations with the local of the deserialized bytes and the span of the call
+    pub deserializations: Vec<(mir::Location, mir::Local, Span)>,
+    // The local that a temporary variable is copied, moved, reborrowed or derived from
+    pub local_sources: LocalSources,
+    // The spans of the deserializations without a validation, each with the span of the read of
+    // the account data
+    pub unchecked_deserialize_spans: Vec<(Span, Span)>
 }
 
+impl UncheckedDeserializeChecker {
+    pub fn new() -> UncheckedDeserializeChecker {
+        return UncheckedDeserializeChecker {
+            data_reads: HashMap::default(),
+            comparisons: Vec::new(),
+            bounds_check_lengths: HashSet::default(),
+            deserializations: Vec::new(),
+            local_sources: LocalSources::default(),
+            unchecked_deserialize_spans: Vec::new()
+        }
+    }
 
+    /// Returns true if the callee deserializes a value from the bytes given as its argument.
+    pub fn is_deserializer(callee_name: &str) -> bool {
+        return DESERIALIZERS.iter().any(|deserializer| callee_name.ends_with(deserializer));
+    }
 
+    /// Returns true if the result of the callee is derived from its first argument.
+    pub fn is_derivation(callee_name: &str) -> bool {
+        return DATA_DERIVATIONS.iter().any(|derivation| callee_name.ends_with(derivation));
+    }
 
+    /// Returns the account and the span of the read of its data that the local is derived from,
+    /// if the local is derived from the data of an account.
+    pub fn data_of(&self, local: mir::Local) -> Option<(mir::Local, Span)> {
+        let (account, span) = self.data_reads.get(&self.local_sources.source_of(local))?;
+        return Some((self.local_sources.source_of(*account), *span));
+    }
 
+    /// Check if the data of an account is deserialized, by ``try_from_slice``, ``deserialize``
+    /// or by hand, e.g. ``u64::from_le_bytes(data[..8].try_into()?)``, while no comparison of the
+    /// length of the data or of its discriminator guards the deserialization on every path to it.
+    /// An account of another type, which has the same layout or happens to be long enough, is
+    /// then reinterpreted as the expected type
+    pub fn check(&mut self, guard_analysis: &GuardAnalysis<'_>) -> bool {
+        let validations: Vec<(mir::Location, mir::Local)> = self
+            .comparisons
+            .iter()
+            .filter(|(_, compared)| {
+                !self.bounds_check_lengths.contains(&self.local_sources.source_of(*compared))
+            })
+            .filter_map(|(location, compared)| {
+                self.data_of(*compared).map(|(account, _)| (*location, account))
+            })
+            .collect();
+        let mut deserialize_spans: Vec<(Span, Span)> = self
+            .deserializations
+            .iter()
+            .filter_map(|(location, bytes, span)| {
+                let (account, read_span) = self.data_of(*bytes)?;
+                let is_guarded = guard_analysis
+                    .is_guarded(*location, |guard| validations.contains(&(guard, account)));
+                if is_guarded {
+                    return None;
+                }
+                return Some((*span, read_span));
+            })
+            .collect();
+        deserialize_spans.sort();
+        deserialize_spans.dedup_by_key(|(span, _)| *span);
+        self.unchecked_deserialize_spans = deserialize_spans;
+        return !self.unchecked_deserialize_spans.is_empty();
+    }
+}
@@ -9,8 +9,9 @@
 
 use std::cell::RefCell;
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter, Result};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::time::Instant;
 
@@ -19,18 +20,26 @@ use log_derive::{logfn, logfn_inputs};
 
 use mirai_annotations::*;
 use rustc_errors::Diag;
-use rustc_hir::def_id::{DefId, DefIndex};
+use rustc_hir::def_id::{DefId, DefIndex, LOCAL_CRATE};
 use rustc_middle::mir;
 use rustc_middle::ty::{GenericArgsRef, TyCtxt};
 use rustc_session::Session;
+use rustc_span::{BytePos, FileName, Span};
 
+use crate::analysis_cache;
+use crate::analysis_cache::AnalysisCache;
+use crate::anchor;
 use crate::body_visitor::BodyVisitor;
 use crate::call_graph::CallGraph;
 use crate::constant_domain::ConstantValueCache;
 use crate::expected_errors;
 use crate::known_names::KnownNamesCache;
 use crate::options::Options;
+use crate::report;
+use crate::report::{CrateReport, Finding, FindingDelta, SarifRun, SuppressedFinding};
 use crate::summaries::SummaryCache;
+use crate::suppression;
+use crate::suppression::Suppressions;
 use crate::tag_domain::Tag;
 use crate::type_visitor::TypeCache;
 use crate::utils;
@@ -42,17 +51,40 @@ use crate::utils;
 // 'compilation is the lifetime of the call to MiraiCallbacks::after_analysis.
 // 'tcx is the lifetime of the closure call that calls analyze_with_mirai, which calls analyze_some_bodies.
 pub struct CrateVisitor<'compilation, 'tcx> {
+    /// The findings of the functions analyzed with --analysis-cache, by the hash of their MIR.
+    pub analysis_cache: AnalysisCache,
+    /// The functions whose bodies were analyzed, rather than reported from the analysis cache.
+    pub analyzed_functions: Vec<String>,
+    /// Set if only some of the functions of the crate that would otherwise be analyzed were
+    /// analyzed, e.g. because of --fn-filter, so that the whole crate cannot be checked for
+    /// baseline drift.
+    pub analysis_is_partial: bool,
     pub buffered_diagnostics: Vec<Diag<'compilation, ()>>,
+    /// The findings that are written by --write-baseline, i.e. the reported findings and those
+    /// suppressed by the baseline.
+    pub baseline_findings: Vec<Finding>,
+    /// The local functions that call each local function, collected before any body is analyzed.
+    pub callers: HashMap<DefId, HashSet<DefId>>,
+    /// The canonical paths of the files that were analyzed with --only-changed-files.
+    pub changed_files: Option<HashSet<PathBuf>>,
     pub constant_time_tag_cache: Option<Tag>,
     pub constant_time_tag_not_found: bool,
     pub constant_value_cache: ConstantValueCache<'tcx>,
     pub diagnostics_for: HashMap<DefId, Vec<Diag<'compilation, ()>>>,
     pub file_name: &'compilation str,
+    pub findings: Vec<Finding>,
+    /// The function that each of the findings was found in.
+    pub finding_functions: Vec<DefId>,
     pub generic_args_cache: HashMap<DefId, GenericArgsRef<'tcx>>,
     pub known_names_cache: KnownNamesCache,
     pub options: &'compilation Options,
+    /// The findings of the previous analysis of the crate, if it is analyzed again in watch mode.
+    pub previous_findings: Option<Vec<Finding>>,
     pub session: &'compilation Session,
     pub summary_cache: SummaryCache<'tcx>,
+    pub stale_baseline: Vec<Finding>,
+    pub suppressed_findings: Vec<SuppressedFinding>,
+    pub suppressions: Suppressions,
     pub tcx: TyCtxt<'tcx>,
     pub type_cache: Rc<RefCell<TypeCache<'tcx>>>,
     pub test_run: bool,
@@ -72,6 +104,11 @@ impl<'compilation> CrateVisitor<'compilation, '_> {
         let start_instant = Instant::now();
         // Determine the functions we want to analyze.
         let selected_functions = self.get_selected_function_list();
+        let changed_files = self.get_changed_files();
+        self.analysis_is_partial = selected_functions.is_some() || self.options.fn_filter.is_some();
+
+        self.collect_callers();
+        let anchor_handlers = self.collect_anchor_handlers();
 
         // Get the entry function
         let entry_fn_def_id = if let Some((def_id, _)) = self.tcx.entry_fn(()) {
@@ -85,6 +122,19 @@ impl<'compilation> CrateVisitor<'compilation, '_> {
         for local_def_id in self.tcx.hir().body_owners() {
             let def_id = local_def_id.to_def_id();
             let name = utils::summary_key_str(self.tcx, def_id);
+            if anchor::is_dispatch(self.tcx, def_id) {
+                debug!(
+                    "skipping function {} as it is generated by Anchor, its handler is analyzed",
+                    name
+                );
+                continue;
+            }
+            if let Some(files) = &changed_files {
+                if !self.is_defined_in(files, def_id) {
+                    debug!("skipping function {} as its file has not changed", name);
+                    continue;
+                }
+            }
             if let Some(selections) = &selected_functions {
                 if !self.included_in(selections.as_ref(), name.as_ref(), def_id) {
                     if self.options.single_func.is_none() {
@@ -97,7 +147,10 @@ impl<'compilation> CrateVisitor<'compilation, '_> {
                 }
                 info!("analyzing selected function {}", name);
             } else if !building_standard_summaries {
-                if !utils::is_public(def_id, self.tcx) && def_id != entry_fn_def_id {
+                if !utils::is_public(def_id, self.tcx)
+                    && def_id != entry_fn_def_id
+                    && !anchor_handlers.contains(&def_id)
+                {
                     debug!("skipping function {} as it is not public", name);
                     continue;
                 } else if self
@@ -122,17 +175,82 @@ impl<'compilation> CrateVisitor<'compilation, '_> {
             } else {
                 info!("analyzing function {}", name);
             }
+            if let Some(fn_filter) = &self.options.fn_filter {
+                let def_path = self.tcx.def_path_str(def_id);
+                if !fn_filter.is_match(&def_path) {
+                    debug!(
+                        "skipping function {} as it does not match the function filter",
+                        def_path
+                    );
+                    continue;
+                }
+            }
 
             self.call_graph.add_croot(def_id);
-            self.analyze_body(def_id);
+            if self.options.analysis_cache.is_some() {
+                let mir_hash = analysis_cache::mir_hash(self.tcx, def_id);
+                if self.restore_cached_findings(def_id, &name, &mir_hash) {
+                    debug!("skipping function {} as its MIR has not changed", name);
+                    continue;
+                }
+                let first_finding = self.findings.len();
+                self.analyze_body(def_id);
+                let crate_name = self.tcx.crate_name(LOCAL_CRATE);
+                self.analysis_cache.insert(
+                    crate_name.as_str(),
+                    &name,
+                    mir_hash,
+                    first_finding..self.findings.len(),
+                );
+            } else {
+                self.analyze_body(def_id);
+            }
+            self.analyzed_functions.push(self.tcx.def_path_str(def_id));
             if start_instant.elapsed().as_secs() > self.options.max_analysis_time_for_crate {
                 info!("exceeded total time allowed for crate analysis");
+                self.analysis_is_partial = true;
                 break;
             }
         }
+        self.changed_files = changed_files;
         self.emit_or_check_diagnostics();
     }
 
+    /// Record the callers of the local functions that are called directly by the functions of
+    /// the crate. The smart contract checkers look at the callers of a function while the crate
+    /// is being analyzed, so they have to be known before the first body is analyzed.
+    fn collect_callers(&mut self) {
+        for local_def_id in self.tcx.hir().body_owners() {
+            let caller = local_def_id.to_def_id();
+            if !self.tcx.def_kind(caller).is_fn_like() || !self.tcx.is_mir_available(caller) {
+                continue;
+            }
+            let mir = self.tcx.optimized_mir(caller);
+            for block in mir.basic_blocks.iter() {
+                if let mir::TerminatorKind::Call { func, .. } = &block.terminator().kind {
+                    if let Some((callee, _)) = func.const_fn_def() {
+                        if callee.is_local() {
+                            self.callers.entry(callee).or_default().insert(caller);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the instruction handlers of an Anchor program, i.e. the functions that the dispatch
+    /// functions generated by the #[program] macro pass a Context to. The handlers are analyzed in
+    /// place of the dispatch functions, even if the module of the program is not public.
+    fn collect_anchor_handlers(&self) -> HashSet<DefId> {
+        self.tcx
+            .hir()
+            .body_owners()
+            .map(|local_def_id| local_def_id.to_def_id())
+            .filter(|def_id| anchor::is_dispatch(self.tcx, *def_id))
+            .filter_map(|dispatch| anchor::handler_of(self.tcx, dispatch))
+            .collect()
+    }
+
     /// Use compilation options to determine a list of functions to analyze.
     /// If this returns None, default logic is used by the caller.
     #[logfn(TRACE)]
@@ -158,6 +276,39 @@ impl<'compilation> CrateVisitor<'compilation, '_> {
         }
     }
 
+    /// If only changed files should be analyzed, returns the canonical paths of those files.
+    /// The list comes from the options if provided there, otherwise from git.
+    #[logfn(TRACE)]
+    fn get_changed_files(&self) -> Option<HashSet<PathBuf>> {
+        if !self.options.only_changed_files {
+            return None;
+        }
+        let files = match &self.options.changed_files {
+            Some(files) => files.iter().map(PathBuf::from).collect(),
+            None => match utils::changed_files_from_git_status() {
+                Some(files) => files,
+                None => {
+                    warn!("could not obtain the changed files from git, analyzing all functions");
+                    return None;
+                }
+            },
+        };
+        info!("restricting analysis to changed files: {:?}", files);
+        Some(files.iter().filter_map(|f| f.canonicalize().ok()).collect())
+    }
+
+    // Determine whether this function is defined in one of the given files.
+    #[logfn(TRACE)]
+    fn is_defined_in(&self, files: &HashSet<PathBuf>, def_id: DefId) -> bool {
+        let span = self.tcx.def_span(def_id).source_callsite();
+        if let FileName::Real(file_name) = self.session.source_map().span_to_filename(span) {
+            if let Some(path) = file_name.local_path() {
+                return path.canonicalize().is_ok_and(|p| files.contains(&p));
+            }
+        }
+        false
+    }
+
     // Determine whether this function is included in the analysis.
     #[logfn(TRACE)]
     fn included_in(&self, list: &[String], name: &str, def_id: DefId) -> bool {
@@ -196,6 +347,71 @@ impl<'compilation> CrateVisitor<'compilation, '_> {
         checked_assume!(old_diags.is_none());
     }
 
+    /// Reports the findings that the analysis cache has for the function instead of analyzing it
+    /// again, if the hash of its MIR is the one the findings were cached with. Returns false if the
+    /// function has to be analyzed.
+    fn restore_cached_findings(&mut self, def_id: DefId, function: &str, mir_hash: &str) -> bool {
+        let crate_name = self.tcx.crate_name(LOCAL_CRATE).to_string();
+        let Some(findings) = self
+            .analysis_cache
+            .findings_for(&crate_name, function, mir_hash)
+        else {
+            return false;
+        };
+        let findings = findings.to_vec();
+        let first_finding = self.findings.len();
+        // The function has not moved since its findings were cached, so their offsets still locate
+        // their spans in its file
+        let source_file = self
+            .session
+            .source_map()
+            .lookup_source_file(self.tcx.def_span(def_id).source_callsite().lo());
+        let file_name = source_file.name.prefer_local().to_string();
+        let mut diagnostics = Vec::new();
+        for finding in findings {
+            // When the crate is analyzed again in watch mode, only the changed findings are printed
+            if finding.file == file_name && self.previous_findings.is_none() {
+                let span = Span::with_root_ctxt(
+                    source_file.start_pos + BytePos(finding.span_start),
+                    source_file.start_pos + BytePos(finding.span_end),
+                );
+                diagnostics.push(
+                    self.session
+                        .dcx()
+                        .struct_span_warn(span, finding.message.clone()),
+                );
+            }
+            if self.options.write_baseline.is_some() {
+                self.baseline_findings.push(finding.clone());
+            }
+            self.findings.push(finding);
+            self.finding_functions.push(def_id);
+        }
+        self.diagnostics_for.insert(def_id, diagnostics);
+        self.analysis_cache.insert(
+            &crate_name,
+            function,
+            mir_hash.to_string(),
+            first_finding..self.findings.len(),
+        );
+        true
+    }
+
+    /// Writes the findings of the functions of this crate to the cache file given by
+    /// --analysis-cache, if any, along with the hashes of their MIR.
+    pub fn write_analysis_cache(&self) {
+        let Some(cache_path) = &self.options.analysis_cache else {
+            return;
+        };
+        let crate_name = self.tcx.crate_name(LOCAL_CRATE).to_string();
+        if let Err(e) = self
+            .analysis_cache
+            .write(cache_path, &crate_name, &self.findings)
+        {
+            self.session.dcx().warn(e);
+        }
+    }
+
     /// Extract test functions from the promoted constants of a test runner main function.
     ///
     /// Currently, the #[test] attribute generates code like this:
@@ -296,6 +512,251 @@ impl<'compilation> CrateVisitor<'compilation, '_> {
         }
     }
 
+    /// Prints the findings that were suppressed, if asked for, along with what suppressed them.
+    pub fn print_suppressed_findings(&mut self) {
+        if !self.options.show_suppressed {
+            return;
+        }
+        self.suppressed_findings.sort();
+        for suppressed_finding in &self.suppressed_findings {
+            eprintln!("{suppressed_finding}");
+        }
+    }
+
+    /// Warns about the baseline entries that no longer match a finding of this crate and
+    /// removes them from the baseline if --prune-baseline was given.
+    /// Only the entries of the checkers that ran, and with --only-changed-files of the files that
+    /// were analyzed, can be judged. If only some of the functions were analyzed, e.g. with
+    /// --fn-filter, no entry can be judged and the baseline is left alone.
+    pub fn check_baseline_drift(&mut self) {
+        let Some(baseline_path) = &self.options.baseline else {
+            return;
+        };
+        if self.analysis_is_partial {
+            if self.options.prune_baseline {
+                self.session.dcx().warn(
+                    "the baseline is not pruned, since only some of the functions were analyzed",
+                );
+            }
+            return;
+        }
+        let options = self.options;
+        let changed_files = &self.changed_files;
+        let is_analyzed = |entry: &Finding| {
+            options.is_checker_enabled(&entry.checker_name)
+                && changed_files.as_ref().is_none_or(|files| {
+                    Path::new(&entry.file)
+                        .canonicalize()
+                        .is_ok_and(|path| files.contains(&path))
+                })
+        };
+        self.stale_baseline = self
+            .suppressions
+            .stale_entries(self.session.source_map(), is_analyzed);
+        for stale_entry in &self.stale_baseline {
+            let reason = if Path::new(&stale_entry.file).exists() {
+                "no longer matches a finding"
+            } else {
+                "is for a file that no longer exists"
+            };
+            self.session.dcx().warn(format!(
+                "baseline drift: the entry `{stale_entry}` {reason}"
+            ));
+        }
+        if self.options.prune_baseline && !self.stale_baseline.is_empty() {
+            if let Err(e) = suppression::prune_baseline(baseline_path, &self.stale_baseline) {
+                self.session.dcx().warn(e);
+            }
+        }
+    }
+
+    /// Writes the findings of this crate to the baseline file given by --write-baseline, if any.
+    pub fn write_baseline(&self) {
+        let Some(baseline_path) = &self.options.write_baseline else {
+            return;
+        };
+        let crate_name = self.tcx.crate_name(LOCAL_CRATE).to_string();
+        if let Err(e) =
+            suppression::write_baseline(baseline_path, &crate_name, &self.baseline_findings)
+        {
+            self.session.dcx().warn(e);
+        }
+    }
+
+    /// Records a shortest call path from an entry point of the crate to the function of every
+    /// reentrancy finding, which shows how the input of a caller reaches the transfer.
+    /// The functions that are reported from the analysis cache add no calls to the call graph, so
+    /// their findings keep the path they were cached with if the graph has none.
+    pub fn annotate_reach_paths(&mut self) {
+        for (finding, def_id) in self.findings.iter_mut().zip(&self.finding_functions) {
+            if finding.checker_name == "reentrancy" {
+                let reach_path = self.call_graph.reach_path(*def_id);
+                if !reach_path.is_empty() {
+                    finding.reach_path = reach_path;
+                }
+            }
+        }
+    }
+
+    /// Prints the findings that are new and the findings that were resolved since the previous
+    /// analysis of the crate in watch mode, and returns them.
+    pub fn print_finding_delta(&self) -> Option<FindingDelta> {
+        let previous_findings = self.previous_findings.as_ref()?;
+        let delta = FindingDelta::new(previous_findings, &self.findings);
+        let crate_name = self.tcx.crate_name(LOCAL_CRATE);
+        if delta.is_empty() {
+            eprintln!("{crate_name}: no new or resolved findings");
+        } else {
+            eprintln!(
+                "{crate_name}: {} new and {} resolved",
+                delta.new.len(),
+                delta.resolved.len()
+            );
+            eprint!("{delta}");
+        }
+        Some(delta)
+    }
+
+    /// Stores the findings of this crate in the report directory, if one was given, where they
+    /// are combined with the findings of the other crates of the workspace.
+    pub fn write_report(&mut self) {
+        let Some(report_dir) = &self.options.report_dir else {
+            if self.options.emit_annotated {
+                self.session
+                    .dcx()
+                    .warn("--emit annotated requires --report-dir, no sources are annotated");
+            }
+            if self.options.emit_jsonl {
+                self.session
+                    .dcx()
+                    .warn("--emit jsonl requires --report-dir, no findings.jsonl is written");
+            }
+            return;
+        };
+        let crate_name = self.tcx.crate_name(LOCAL_CRATE).to_string();
+        let crate_report = CrateReport::new(
+            crate_name,
+            self.findings.clone(),
+            self.suppressed_findings.clone(),
+            self.stale_baseline.clone(),
+        );
+        if let Err(e) = report::write_crate_report(
+            Path::new(report_dir),
+            &crate_report,
+            self.options.report_style,
+            self.options.emit_jsonl,
+            std::env::var("MIRAI_RUN_ID").ok().as_deref(),
+        ) {
+            self.session
+                .dcx()
+                .warn(format!("could not write the report to {report_dir}: {e}"));
+        }
+        if self.options.emit_annotated {
+            let annotated_dir = Path::new(report_dir).join("annotated");
+            if let Err(e) = report::write_annotated_sources(&annotated_dir, &self.findings) {
+                self.session.dcx().warn(format!(
+                    "could not write the annotated sources to {}: {e}",
+                    annotated_dir.display()
+                ));
+            }
+        }
+    }
+
+    /// Adds the findings of this crate to the SARIF log, if a path for it was given.
+    pub fn write_sarif_report(&self) {
+        let Some(sarif_path) = &self.options.sarif_path else {
+            return;
+        };
+        let crate_name = self.tcx.crate_name(LOCAL_CRATE).to_string();
+        let run = SarifRun::new(
+            &crate_name,
+            &self.findings,
+            self.options.rule_doc_url.as_deref(),
+        );
+        if let Err(e) = report::write_sarif_report(Path::new(sarif_path), run) {
+            self.session.dcx().warn(format!(
+                "could not write the SARIF log to {sarif_path}: {e}"
+            ));
+        }
+    }
+
+    /// Adds the findings of this crate to the HTML report, if a path for it was given.
+    pub fn write_html_report(&self) {
+        let Some(html_path) = &self.options.html_path else {
+            return;
+        };
+        let crate_name = self.tcx.crate_name(LOCAL_CRATE).to_string();
+        if let Err(e) = report::write_html_report(Path::new(html_path), &crate_name, &self.findings)
+        {
+            self.session.dcx().warn(format!(
+                "could not write the HTML report to {html_path}: {e}"
+            ));
+        }
+    }
+
+    /// Writes the findings of this crate in the format given by --format, to the file given by
+    /// --output or, for the text format, to stderr.
+    pub fn write_output(&self) {
+        let Some(output_format) = self.options.output_format else {
+            return;
+        };
+        let crate_name = self.tcx.crate_name(LOCAL_CRATE).to_string();
+        let formatter = report::formatter_for(output_format, self.options.rule_doc_url.as_deref());
+        let output_path = self.options.output_path.as_deref();
+        if let Err(e) =
+            formatter.write_findings(output_path.map(Path::new), &crate_name, &self.findings)
+        {
+            let destination = output_path.unwrap_or("stderr");
+            self.session.dcx().warn(format!(
+                "could not write the findings to {destination}: {e}"
+            ));
+        }
+    }
+
+    /// Writes the call graph of this crate, annotated with the functions of its findings, to the
+    /// dot file given by --callgraph-with-findings, if any.
+    pub fn write_callgraph_with_findings(&self) {
+        let Some(dot_path) = &self.options.callgraph_with_findings else {
+            return;
+        };
+        let findings: Vec<(DefId, &Finding)> = self
+            .finding_functions
+            .iter()
+            .copied()
+            .zip(&self.findings)
+            .collect();
+        if let Err(e) = self
+            .call_graph
+            .to_dot_with_findings(Path::new(dot_path), &findings)
+        {
+            self.session.dcx().warn(format!(
+                "could not write the call graph with findings to {dot_path}: {e}"
+            ));
+        }
+    }
+
+    /// Fails the compilation if a finding is at least as severe as the level given by --fail-on.
+    pub fn check_fail_on(&self) {
+        let Some(fail_on) = self.options.fail_on else {
+            return;
+        };
+        let failing_findings = self
+            .findings
+            .iter()
+            .filter(|finding| finding.severity >= fail_on)
+            .count();
+        if failing_findings > 0 {
+            let noun = if failing_findings == 1 {
+                "finding"
+            } else {
+                "findings"
+            };
+            self.session.dcx().fatal(format!(
+                "{failing_findings} {noun} of at least {fail_on} severity"
+            ));
+        }
+    }
+
     pub fn print_summaries(&mut self) {
         if !self.options.print_summaries {
             return;
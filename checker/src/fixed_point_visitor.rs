@@ -13,13 +13,19 @@ use rpds::{HashTrieMap, HashTrieSet};
 use mirai_annotations::*;
 use rustc_data_structures::graph::dominators::Dominators;
 use rustc_middle::mir;
-use rustc_span::{Span, SyntaxContext};
+use rustc_middle::ty::TyKind;
+use rustc_span::Span;
 
 use crate::abstract_value::{AbstractValue, AbstractValueTrait};
 use crate::block_visitor::BlockVisitor;
 use crate::body_visitor::BodyVisitor;
+use crate::contract_errors::{CheckerContext, CheckerFinding};
 use crate::environment::Environment;
+use crate::guard_analysis::GuardAnalysis;
+use crate::mir_walker::MirWalker;
 use crate::options::DiagLevel;
+use crate::report::Severity;
+use crate::taint_analysis::TaintSource;
 use crate::{abstract_value, k_limits};
 
 pub struct FixedPointVisitor<'fixed, 'analysis, 'compilation, 'tcx> {
@@ -87,64 +93,708 @@ impl<'fixed, 'analysis, 'compilation, 'tcx>
                 }
             }
         }
-        
-        // Emit a warning if the analyzed body contains reentrancy
-        let is_reentrancy = self.bv.reentrancy_checker.check();
-        if is_reentrancy {
-            self.bv.reentrancy_checker.ending_reentrancy_span = self.bv.current_span.hi();
-            let warning_message = "possible reentrancy for the smart contract";
-            let span = Span::new(
-                self.bv.reentrancy_checker.starting_reentrancy_span,
-                self.bv.reentrancy_checker.ending_reentrancy_span,
-                SyntaxContext::root(),
-                None,
+
+        // The checks that protect the operations of the body, e.g. a signer check of a transfer
+        let guards = GuardAnalysis::new(self.bv.tcx, self.bv.mir);
+
+        // Emit a warning for each payout that is decided by the clock,
+        // which raises the severity of the time manipulation of the Checker trait
+        if self.bv.clock_payout_checker.check() {
+            let clock_read_span = self.bv.clock_payout_checker.clock_read_span;
+            let related: Vec<(Span, &str)> = clock_read_span
+                .map(|read_span| (read_span, "the clock is read here"))
+                .into_iter()
+                .collect();
+            for span in self.bv.clock_payout_checker.clock_payout_spans.clone() {
+                self.bv.emit_contract_warning_with_related(
+                    "clock_payout",
+                    Severity::High,
+                    span,
+                    "possible payout decided by the clock for the smart contract",
+                    &related,
+                );
+            }
+        }
+
+        // Emit a warning if the analyzed body contains saturating arithmetic masking an error
+        let is_saturating_arithmetic = self.bv.saturating_arithmetic_checker.check();
+        if is_saturating_arithmetic {
+            let span = self.bv.saturating_arithmetic_checker.saturating_arithmetic_span;
+            self.bv.emit_contract_warning(
+                "saturating_arithmetic",
+                Severity::Medium,
+                span,
+                "possible saturating arithmetic masking an error for the smart contract",
+            );
+        }
+
+        // Emit a warning if the analyzed body reads the clock from a passed account
+        let is_clock_account_read = self.bv.clock_account_read_checker.check();
+        if is_clock_account_read {
+            let span = self.bv.clock_account_read_checker.clock_account_read_span;
+            self.bv.emit_contract_warning(
+                "clock_account_read",
+                Severity::Low,
+                span,
+                "possible unvalidated clock account read, prefer Clock::get() for the smart contract",
+            );
+        }
+
+        // Emit the findings of the checkers of the Checker trait, e.g. of a reentrancy or of a
+        // transfer from an account to itself, which are fed by a single walk of the body
+        let context = CheckerContext {
+            tcx: self.bv.tcx,
+            mir: self.bv.mir,
+            def_id: self.bv.def_id,
+            taint_analysis: &self.bv.taint_analysis,
+            guards: &guards,
+            last_span: self.bv.current_span,
+        };
+        MirWalker::new(self.bv.mir, &self.bv.resolved_callees)
+            .walk(&context, &mut self.bv.checkers);
+        let findings: Vec<CheckerFinding> = self
+            .bv
+            .checkers
+            .iter_mut()
+            .flat_map(|checker| checker.finish(&context))
+            .collect();
+        for finding in findings {
+            self.bv.emit_contract_warning_with_evidence(
+                finding.checker_name,
+                finding.severity,
+                finding.confidence,
+                finding.span,
+                finding.message,
+                &finding.related,
+                finding.evidence,
+            );
+        }
+
+        // Emit a warning if the analyzed body debits and credits accounts that may be the same
+        let is_missing_distinct_accounts = self.bv.distinct_accounts_checker.check();
+        if is_missing_distinct_accounts {
+            let span = self.bv.distinct_accounts_checker.distinct_accounts_span;
+            let message = if self.bv.distinct_accounts_checker.is_data_mutation {
+                "possible missing check that the mutated accounts differ for the smart contract"
+            } else {
+                "possible missing check that the debited and credited accounts differ for the smart contract"
+            };
+            self.bv
+                .emit_contract_warning("distinct_accounts", Severity::High, span, message);
+        }
+
+        // Emit a warning if the analyzed body logs whole account data or instruction data
+        let is_logged_data = self.bv.logged_data_checker.check();
+        if is_logged_data {
+            let span = self.bv.logged_data_checker.logged_data_span;
+            self.bv.emit_contract_warning(
+                "logged_data",
+                Severity::Low,
+                span,
+                "possible logging of whole account or instruction data for the smart contract",
+            );
+        }
+
+        // Emit a warning if the analyzed body requires an account to be writable but only reads it
+        let is_over_privileged_account = self.bv.over_privileged_account_checker.check();
+        if is_over_privileged_account {
+            let span = self
+                .bv
+                .over_privileged_account_checker
+                .over_privileged_account_span;
+            self.bv.emit_contract_warning(
+                "over_privileged_account",
+                Severity::Low,
+                span,
+                "possible writable account that is only read for the smart contract",
+            );
+        }
+
+        // Emit a warning if the analyzed body logs state that is computed from storage that is not persisted
+        let is_ephemeral_state = self.bv.ephemeral_state_checker.check();
+        if is_ephemeral_state {
+            let span = self.bv.ephemeral_state_checker.ephemeral_state_span;
+            self.bv.emit_contract_warning(
+                "ephemeral_state",
+                Severity::Low,
+                span,
+                "possible logging of state that is not persisted for the smart contract",
+            );
+        }
+
+        // Emit a warning if the analyzed body reinterprets account data with an unsafe cast
+        let is_unsafe_data_cast = self.bv.unsafe_data_cast_checker.check();
+        if is_unsafe_data_cast {
+            let span = self.bv.unsafe_data_cast_checker.unsafe_data_cast_span;
+            self.bv.emit_contract_warning(
+                "unsafe_data_cast",
+                Severity::High,
+                span,
+                "possible unsafe reinterpretation of account data for the smart contract",
+            );
+        }
+
+        // Emit a warning if the analyzed body overwrites the lamports of an account with a constant
+        let is_lamports_overwrite = self.bv.lamports_overwrite_checker.check();
+        if is_lamports_overwrite {
+            let span = self.bv.lamports_overwrite_checker.lamports_overwrite_span;
+            self.bv.emit_contract_warning(
+                "lamports_overwrite",
+                Severity::Medium,
+                span,
+                "possible overwrite of the account balance for the smart contract",
+            );
+        }
+
+        // Emit a warning if the analyzed body compares token amounts that may differ in decimals
+        let is_token_decimals = self.bv.token_decimals_checker.check();
+        if is_token_decimals {
+            let span = self.bv.token_decimals_checker.token_decimals_span;
+            self.bv.emit_contract_warning(
+                "token_decimals",
+                Severity::Medium,
+                span,
+                "possible comparison of token amounts with different decimals for the smart contract",
+            );
+        }
+
+        // Emit a warning for each panic directly in the body of the entrypoint handler
+        let is_entrypoint_panic = self.bv.entrypoint_panic_checker.check();
+        if is_entrypoint_panic {
+            for span in self
+                .bv
+                .entrypoint_panic_checker
+                .entrypoint_panic_spans
+                .clone()
+            {
+                self.bv.emit_contract_warning(
+                    "entrypoint_panic",
+                    Severity::High,
+                    span,
+                    "possible panic in the entrypoint handler for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning if the analyzed body authorizes an action by the lamports of an account
+        let is_lamports_authorization = self.bv.lamports_authorization_checker.check();
+        if is_lamports_authorization {
+            let span = self
+                .bv
+                .lamports_authorization_checker
+                .lamports_authorization_span;
+            self.bv.emit_contract_warning(
+                "lamports_authorization",
+                Severity::High,
+                span,
+                "possible authorization by the account balance for the smart contract",
+            );
+        }
+
+        // Emit a warning if the analyzed body is a shared helper that transfers without validation
+        let is_unguarded_transfer_helper = self.bv.unguarded_transfer_helper_checker.check();
+        if is_unguarded_transfer_helper {
+            let span = self
+                .bv
+                .unguarded_transfer_helper_checker
+                .unguarded_transfer_span;
+            self.bv.emit_contract_warning(
+                "unguarded_transfer_helper",
+                Severity::Medium,
+                span,
+                "possible transfer by a helper that does not validate its accounts for the smart contract",
+            );
+        }
+
+        // Emit a warning if the analyzed body computes the rent of an account by hand
+        let is_manual_rent = self.bv.manual_rent_checker.check();
+        if is_manual_rent {
+            let span = self.bv.manual_rent_checker.manual_rent_span;
+            self.bv.emit_contract_warning(
+                "manual_rent",
+                Severity::Low,
+                span,
+                "possible manual rent computation instead of Rent::minimum_balance for the smart contract",
+            );
+        }
+
+        // Emit a warning if the analyzed body ignores the error of a cross-program invocation
+        let is_swallowed_cpi_error = self.bv.swallowed_cpi_error_checker.check();
+        if is_swallowed_cpi_error {
+            let span = self.bv.swallowed_cpi_error_checker.swallowed_cpi_error_span;
+            self.bv.emit_contract_warning(
+                "swallowed_cpi_error",
+                Severity::High,
+                span,
+                "possible ignored error of a cross-program invocation for the smart contract",
             );
-            let warning = self
+        }
+
+        // Emit a warning for each cross-program invocation whose result is never checked
+        if self.bv.unchecked_cpi_result_checker.check() {
+            for span in self
+                .bv
+                .unchecked_cpi_result_checker
+                .unchecked_cpi_result_spans
+                .clone()
+            {
+                self.bv.emit_contract_warning(
+                    "unchecked_cpi_result",
+                    Severity::High,
+                    span,
+                    "possible unchecked result of a cross-program invocation, propagate its error with ? for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each deserialization of account data that no length or discriminator
+        // check guards
+        if self.bv.unchecked_deserialize_checker.check(&guards) {
+            for (span, read_span) in self
+                .bv
+                .unchecked_deserialize_checker
+                .unchecked_deserialize_spans
+                .clone()
+            {
+                self.bv.emit_contract_warning_with_related(
+                    "unchecked_deserialize",
+                    Severity::High,
+                    span,
+                    "possible deserialization of account data without a length or discriminator check for the smart contract",
+                    &[(read_span, "the data of the account is read here")],
+                );
+            }
+        }
+
+        // Emit a warning for each mutation of a loop account that the iteration does not validate
+        let is_unvalidated_loop_account = self.bv.unvalidated_loop_account_checker.check(&guards);
+        if is_unvalidated_loop_account {
+            for span in self
+                .bv
+                .unvalidated_loop_account_checker
+                .unvalidated_loop_account_spans
+                .clone()
+            {
+                self.bv.emit_contract_warning(
+                    "unvalidated_loop_account",
+                    Severity::High,
+                    span,
+                    "possible mutation of an unvalidated account in a loop for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each mutation of lamports without a signer check of their account
+        let is_missing_signer = self.bv.missing_signer_checker.check(&guards);
+        if is_missing_signer {
+            for (span, other_check) in self.bv.missing_signer_checker.missing_signer_spans.clone() {
+                let related: Vec<(Span, &str)> = other_check
+                    .map(|check_span| (check_span, "only the signer of another account is checked"))
+                    .into_iter()
+                    .collect();
+                self.bv.emit_contract_warning_with_related(
+                    "missing_signer",
+                    Severity::High,
+                    span,
+                    "possible mutation of lamports without a signer check of the account for the smart contract",
+                    &related,
+                );
+            }
+        }
+
+        // Emit a warning for each lookup of state by the key of an account that is not the signer
+        if self.bv.account_substitution_checker.check(&guards) {
+            let spans = self
+                .bv
+                .account_substitution_checker
+                .account_substitution_spans
+                .clone();
+            for (span, signer_check) in spans {
+                self.bv.emit_contract_warning_with_related(
+                    "account_substitution",
+                    Severity::High,
+                    span,
+                    "possible lookup of state by the key of an account that is not checked to be a signer for the smart contract",
+                    &[(signer_check, "only the signer of another account is checked")],
+                );
+            }
+        }
+
+        // Emit a warning for each mutation of an account that is not checked to be writable
+        if self.bv.is_writable_checker.check(&guards) {
+            for span in self.bv.is_writable_checker.is_writable_spans.clone() {
+                self.bv.emit_contract_warning(
+                    "is_writable",
+                    Severity::Low,
+                    span,
+                    "possible mutation of an account without checking that it is writable for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each read of account data whose owner is not checked,
+        // or is only compared with a hardcoded key
+        let arg_count = self.bv.mir.arg_count;
+        if self.bv.owner_check_checker.check(&guards, arg_count) {
+            for span in self.bv.owner_check_checker.unchecked_owner_spans.clone() {
+                self.bv.emit_contract_warning(
+                    "owner_check",
+                    Severity::High,
+                    span,
+                    "possible read of account data without an owner check for the smart contract",
+                );
+            }
+            for span in self.bv.owner_check_checker.hardcoded_owner_spans.clone() {
+                self.bv.emit_contract_warning(
+                    "owner_check",
+                    Severity::Medium,
+                    span,
+                    "possible owner check against a hardcoded key instead of the program id for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each invocation of a program whose id is taken from a passed account
+        if self
+            .bv
+            .arbitrary_cpi_checker
+            .check(&guards, arg_count, &self.bv.taint_analysis)
+        {
+            for span in self.bv.arbitrary_cpi_checker.arbitrary_cpi_spans.clone() {
+                self.bv.emit_contract_warning(
+                    "arbitrary_cpi",
+                    Severity::High,
+                    span,
+                    "possible invocation of an arbitrary program whose id is not checked for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each program derived address created with a bump seed of the caller
+        if self.bv.pda_bump_checker.check(&self.bv.taint_analysis) {
+            for span in self.bv.pda_bump_checker.pda_bump_spans.clone() {
+                self.bv.emit_contract_warning(
+                    "pda_bump",
+                    Severity::High,
+                    span,
+                    "possible program derived address with a bump seed of the caller, prefer find_program_address for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each narrowing cast of an amount, which refers to the read of the amount
+        if self.bv.truncating_cast_checker.check() {
+            for (span, read_span, source_ty, target_ty) in self
+                .bv
+                .truncating_cast_checker
+                .truncating_cast_spans
+                .clone()
+            {
+                let read =
+                    format!("the amount is read here, and cast from {source_ty} to {target_ty}");
+                self.bv.emit_contract_warning_with_related(
+                    "truncating_cast",
+                    Severity::High,
+                    span,
+                    "possible truncation of an amount by a narrowing cast, prefer try_from for the smart contract",
+                    &[(read_span, read.as_str())],
+                );
+            }
+        }
+
+        // Emit a warning for each account that is created with lamports that are not computed by
+        // Rent::minimum_balance, which is more specific if the lamports are hard coded
+        if self.bv.rent_exemption_checker.check() {
+            for (span, is_constant) in self.bv.rent_exemption_checker.rent_exemption_spans.clone() {
+                let message = if is_constant {
+                    "possible account creation with hard coded lamports that may not be rent exempt, prefer Rent::minimum_balance for the smart contract"
+                } else {
+                    "possible account creation with lamports that are not computed by Rent::minimum_balance for the smart contract"
+                };
+                self.bv
+                    .emit_contract_warning("rent_exemption", Severity::Medium, span, message);
+            }
+        }
+
+        // Emit a warning for each drain of the lamports of an account whose data is not zeroed
+        if self.bv.close_authority_checker.check() {
+            for span in self
+                .bv
+                .close_authority_checker
+                .close_authority_spans
+                .clone()
+            {
+                self.bv.emit_contract_warning(
+                    "close_authority",
+                    Severity::High,
+                    span,
+                    "possible closed account that can be revived with its data, prefer zeroing the data for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each update of a balance by arithmetic that may overflow
+        if self.bv.integer_overflow_checker.check(&guards) {
+            for (span, message) in self
+                .bv
+                .integer_overflow_checker
+                .unchecked_operations
+                .clone()
+            {
+                let confidence = self.bv.integer_overflow_checker.confidence(span);
+                self.bv.emit_contract_warning_with_confidence(
+                    "integer_overflow",
+                    Severity::Medium,
+                    confidence,
+                    span,
+                    message,
+                    &[],
+                );
+            }
+        }
+
+        // Emit a warning for each allocation of a map that is mutated but never persisted
+        if self.bv.non_persistent_state_checker.check() {
+            for span in self
+                .bv
+                .non_persistent_state_checker
+                .non_persistent_state_spans
+                .clone()
+            {
+                self.bv.emit_contract_warning(
+                    "non_persistent_state",
+                    Severity::High,
+                    span,
+                    "possible state that is never persisted to account data for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each read of the first byte of instruction data that may be empty
+        let is_unchecked_first_byte = self.bv.unchecked_first_byte_checker.check(&guards);
+        if is_unchecked_first_byte {
+            for span in self
+                .bv
+                .unchecked_first_byte_checker
+                .unchecked_first_byte_spans
+                .clone()
+            {
+                self.bv.emit_contract_warning(
+                    "unchecked_first_byte",
+                    Severity::High,
+                    span,
+                    "possible read of the first byte of empty instruction data for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each invocation signed by a program derived address that is not verified
+        let is_unverified_pda = self.bv.unverified_pda_checker.check();
+        if is_unverified_pda {
+            for span in self.bv.unverified_pda_checker.unverified_pda_spans.clone() {
+                self.bv.emit_contract_warning(
+                    "unverified_pda",
+                    Severity::High,
+                    span,
+                    "possible use of a program derived address that is not re-derived for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each loop driven by a count that is not validated against a length
+        let is_unvalidated_count = self.bv.unvalidated_count_checker.check();
+        if is_unvalidated_count {
+            for span in self
+                .bv
+                .unvalidated_count_checker
+                .unvalidated_count_spans
+                .clone()
+            {
+                self.bv.emit_contract_warning(
+                    "unvalidated_count",
+                    Severity::High,
+                    span,
+                    "possible loop over a count from the instruction data that is not validated against its length for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each logged error that is followed by returning a success
+        let is_ok_after_error_log = self
+            .bv
+            .ok_after_error_log_checker
+            .check(&self.bv.mir.basic_blocks);
+        if is_ok_after_error_log {
+            for span in self
+                .bv
+                .ok_after_error_log_checker
+                .ok_after_error_log_spans
+                .clone()
+            {
+                self.bv.emit_contract_warning(
+                    "ok_after_error_log",
+                    Severity::Medium,
+                    span,
+                    "possible success returned after logging an error for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each borrow of lamports that are already borrowed
+        if self.bv.lamports_double_borrow_checker.check() {
+            for span in self
+                .bv
+                .lamports_double_borrow_checker
+                .double_borrow_spans
+                .clone()
+            {
+                self.bv.emit_contract_warning(
+                    "lamports_double_borrow",
+                    Severity::High,
+                    span,
+                    "possible double borrow of the lamports of an account for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each derivation whose seeds exceed the limits of the runtime
+        if self.bv.seed_limits_checker.check() {
+            for span in self.bv.seed_limits_checker.seed_limit_spans.clone() {
+                self.bv.emit_contract_warning(
+                    "seed_limits",
+                    Severity::High,
+                    span,
+                    "possible program derived address with too many or too long seeds for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each write of unbounded data of the caller into account data
+        let mir = self.bv.mir;
+        for arg in mir.args_iter() {
+            if let TyKind::Adt(def, _) = mir.local_decls[arg].ty.kind() {
+                let type_name = self.bv.tcx.item_name(def.did());
+                if type_name.as_str() == "Vec" || type_name.as_str() == "String" {
+                    self.bv
+                        .unbounded_account_write_checker
+                        .variable_data
+                        .insert(arg);
+                }
+            }
+        }
+        if self.bv.unbounded_account_write_checker.check() {
+            for span in self
                 .bv
-                .cv
-                .session
-                .dcx()
-                .struct_span_warn(span, warning_message);
-            self.bv.emit_diagnostic(warning);
-        }
-
-        // Emit a warning if the analyzed body contains time manipulation
-        let is_time_manipulation = self.bv.time_manipulation_checker.check();
-        if is_time_manipulation {
-            let warning_message = "possible time manipulation for the smart contract";
-            let warning = self
+                .unbounded_account_write_checker
+                .unbounded_write_spans
+                .clone()
+            {
+                self.bv.emit_contract_warning(
+                    "unbounded_account_write",
+                    Severity::Medium,
+                    span,
+                    "possible write of unbounded user data into account data for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning if the locals of the analyzed body exceed the stack limit,
+        // the bodies of promoted constants are not on the stack
+        let local_sizes: Vec<(Span, u64)> = mir
+            .local_decls
+            .iter()
+            .filter_map(|local_decl| {
+                let ty_and_layout = self.bv.type_visitor().layout_of(local_decl.ty).ok()?;
+                Some((local_decl.source_info.span, ty_and_layout.size.bytes()))
+            })
+            .collect();
+        self.bv.stack_usage_checker.local_sizes = local_sizes;
+        let stack_limit = self.bv.cv.options.stack_limit;
+        if mir.source.promoted.is_none() && self.bv.stack_usage_checker.check(stack_limit) {
+            let estimate = format!(
+                "the largest local, of an estimated {} bytes of stack",
+                self.bv.stack_usage_checker.estimated_stack_usage
+            );
+            let related: Vec<(Span, &str)> = self
                 .bv
-                .cv
-                .session
-                .dcx()
-                .struct_span_warn(self.bv.time_manipulation_checker.time_manipulation_span, warning_message);
-            self.bv.emit_diagnostic(warning);
-        }
-
-        // Emit a warning if the analyzed body contains bad randomness
-        let is_bad_randomness = self.bv.bad_randomness_checker.check();
-        if is_bad_randomness {
-            let warning_message = "possible bad randomness for the smart contract";
-            let warning = self
+                .stack_usage_checker
+                .largest_local_span
+                .map(|local_span| (local_span, estimate.as_str()))
+                .into_iter()
+                .collect();
+            let function_span = self.bv.tcx.def_span(self.bv.def_id);
+            self.bv.emit_contract_warning_with_related(
+                "stack_usage",
+                Severity::Medium,
+                function_span,
+                "possible stack overflow from large locals for the smart contract",
+                &related,
+            );
+        }
+
+        // Emit a warning for each slice operation in the analyzed body that panics on an untrusted length
+        let is_slice_panic = self.bv.slice_panic_checker.check();
+        if is_slice_panic {
+            for span in self.bv.slice_panic_checker.slice_panic_spans.clone() {
+                self.bv.emit_contract_warning(
+                    "slice_panic",
+                    Severity::Medium,
+                    span,
+                    "possible panicking slice operation on an untrusted length, prefer get or split_at_checked for the smart contract",
+                );
+            }
+            for span in self
                 .bv
-                .cv
-                .session
-                .dcx()
-                .struct_span_warn(self.bv.bad_randomness_checker.bad_randomness_span, warning_message);
-            self.bv.emit_diagnostic(warning);
-        }
-
-        // Emit a warning if the analyzed body contains numerical precision error
-        let is_numerical_precision_error = self.bv.numerical_precision_checker.check();
-        if is_numerical_precision_error {
-            let warning_message = "possible numerical precision error for the smart contract";
-            let warning = self
+                .slice_panic_checker
+                .unwrapped_conversion_spans
+                .clone()
+            {
+                self.bv.emit_contract_warning(
+                    "slice_panic",
+                    Severity::Medium,
+                    span,
+                    "possible panic from unwrapping the conversion of an untrusted subslice, prefer handling the error of try_into for the smart contract",
+                );
+            }
+        }
+
+        // Emit a warning for each panic that the input of the caller decides on, unless the panic
+        // is in the entrypoint handler and so already reported for the handler
+        if self
+            .bv
+            .panic_reachability_checker
+            .check(&guards, &self.bv.taint_analysis)
+        {
+            for (span, source, branch_span) in self
                 .bv
-                .cv
-                .session
-                .dcx()
-                .struct_span_warn(self.bv.numerical_precision_checker.numerical_precision_error_span, warning_message);
-            self.bv.emit_diagnostic(warning);
+                .panic_reachability_checker
+                .panic_reachability_spans
+                .clone()
+            {
+                if self
+                    .bv
+                    .entrypoint_panic_checker
+                    .entrypoint_panic_spans
+                    .contains(&span)
+                {
+                    continue;
+                }
+                let message = match source {
+                    TaintSource::InstructionData => "possible panic that the instruction data can trigger, prefer returning an error for the smart contract",
+                    TaintSource::AccountData => "possible panic that the data of an account can trigger, prefer returning an error for the smart contract",
+                };
+                let related: Vec<(Span, &str)> = branch_span
+                    .map(|branch_span| (branch_span, "the branch that leads to the panic"))
+                    .into_iter()
+                    .collect();
+                self.bv.emit_contract_warning_with_related(
+                    "panic_reachability",
+                    Severity::Medium,
+                    span,
+                    message,
+                    &related,
+                );
+            }
         }
     }
 
@@ -0,0 +1,280 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// Decides for the checkers whether an operation of a function body, such as a mutation of
+// lamports, is protected by a check, such as a read of the signer flag. A check protects the
+// operation if it is made on every path to the operation and, if the body branches on the result
+// of the check, the operation is only reached on the arm on which the check holds. A check that
+// is the condition of an if therefore protects the then branch, but not the else branch.
+
+use std::collections::HashMap;
+
+use rustc_data_structures::graph::dominators::Dominators;
+use rustc_middle::mir;
+use rustc_middle::ty::TyCtxt;
+
+pub struct GuardAnalysis<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    mir: &'tcx mir::Body<'tcx>,
+    // The dominator tree of the basic blocks of the body
+    dominators: &'tcx Dominators<mir::BasicBlock>,
+}
+
+impl<'tcx> GuardAnalysis<'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>, mir: &'tcx mir::Body<'tcx>) -> GuardAnalysis<'tcx> {
+        GuardAnalysis {
+            tcx,
+            mir,
+            dominators: mir.basic_blocks.dominators(),
+        }
+    }
+
+    /// Returns true if a guard that the predicate matches protects the sink. The predicate is
+    /// only asked about the locations that dominate the sink, which are found by walking up the
+    /// dominator tree from the block of the sink.
+    pub fn is_guarded(
+        &self,
+        sink: mir::Location,
+        predicate: impl Fn(mir::Location) -> bool,
+    ) -> bool {
+        let mut block = Some(sink.block);
+        while let Some(bb) = block {
+            // Within the block of the sink, only the statements before the sink are guards
+            let end = if bb == sink.block {
+                sink.statement_index
+            } else {
+                self.mir.basic_blocks[bb].statements.len() + 1
+            };
+            for statement_index in 0..end {
+                let guard = mir::Location {
+                    block: bb,
+                    statement_index,
+                };
+                if predicate(guard) && self.protects(guard, sink) {
+                    return true;
+                }
+            }
+            block = self.dominators.immediate_dominator(bb);
+        }
+        false
+    }
+
+    /// Returns true if the check at guard protects the sink, i.e. the check dominates the sink and
+    /// the sink is only reached on an arm of a branch on the result of the check on which the
+    /// check holds. A check whose result is not branched on, e.g. a call of a helper that returns
+    /// an error, or whose meaning is not known, e.g. a comparison of lengths, only has to dominate.
+    pub fn protects(&self, guard: mir::Location, sink: mir::Location) -> bool {
+        // A sink that is never executed is not protected, and has no dominators to ask about
+        if !self.dominators.is_reachable(sink.block) || !guard.dominates(sink, self.dominators) {
+            return false;
+        }
+        let Some((result, holds_if_true)) = self.result_of(guard) else {
+            return true;
+        };
+        let branches = self.branches_on(result, holds_if_true);
+        if branches.is_empty() {
+            return true;
+        }
+        branches.iter().any(|(branch_bb, holding_arm)| {
+            holding_arm.is_some_and(|arm| self.edge_dominates(*branch_bb, arm, sink.block))
+        })
+    }
+
+    /// Returns true if the statement at guard is executed on every path to the sink.
+    pub fn dominates(&self, guard: mir::Location, sink: mir::Location) -> bool {
+        self.dominators.is_reachable(sink.block) && guard.dominates(sink, self.dominators)
+    }
+
+    /// Returns true if the value that the statement at guard reads is branched on, and the sink is
+    /// only reached on an arm of the branch on which the value is the given one, e.g. the arm on
+    /// which a reentrancy flag is not set. Unlike for protects, a read that is not branched on does
+    /// not protect the sink.
+    pub fn branches_on_read(&self, guard: mir::Location, sink: mir::Location, value: bool) -> bool {
+        if !self.dominates(guard, sink) {
+            return false;
+        }
+        let statements = &self.mir.basic_blocks[guard.block].statements;
+        let Some(statement) = statements.get(guard.statement_index) else {
+            return false;
+        };
+        let mir::StatementKind::Assign(box (place, _)) = &statement.kind else {
+            return false;
+        };
+        if !place.projection.is_empty() {
+            return false;
+        }
+        self.branches_on(place.local, value)
+            .iter()
+            .any(|(branch_bb, arm)| {
+                arm.is_some_and(|arm| self.edge_dominates(*branch_bb, arm, sink.block))
+            })
+    }
+
+    /// Returns true if the sink is only reached on some of the arms of the branch that ends the
+    /// block, i.e. whether the sink is executed depends on the value that the block branches on.
+    pub fn branches_to(&self, branch_bb: mir::BasicBlock, sink: mir::Location) -> bool {
+        self.arm_to(branch_bb, sink).is_some()
+    }
+
+    /// Returns the arm of the branch that ends the block that every path to the sink takes, if
+    /// there is one, e.g. the arm of a match on the instruction that calls the handler of the
+    /// instruction.
+    pub fn arm_to(
+        &self,
+        branch_bb: mir::BasicBlock,
+        sink: mir::Location,
+    ) -> Option<mir::BasicBlock> {
+        if !self.dominators.is_reachable(sink.block) {
+            return None;
+        }
+        self.mir.basic_blocks[branch_bb]
+            .terminator()
+            .successors()
+            .find(|arm| self.edge_dominates(branch_bb, *arm, sink.block))
+    }
+
+    /// Returns the local that the check at the location assigns its result to, along with
+    /// whether a true result means that the check holds, e.g. false for ``user.key != expected``.
+    /// Returns None if the location is not a check of a known meaning.
+    fn result_of(&self, guard: mir::Location) -> Option<(mir::Local, bool)> {
+        let block_data = &self.mir.basic_blocks[guard.block];
+        if let Some(statement) = block_data.statements.get(guard.statement_index) {
+            let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind else {
+                return None;
+            };
+            if !place.projection.is_empty() || !self.mir.local_decls[place.local].ty.is_bool() {
+                return None;
+            }
+            let holds_if_true = match rvalue {
+                mir::Rvalue::Use(_) | mir::Rvalue::CopyForDeref(_) => true,
+                mir::Rvalue::BinaryOp(mir::BinOp::Eq, _) => true,
+                mir::Rvalue::BinaryOp(mir::BinOp::Ne, _) => false,
+                mir::Rvalue::UnaryOp(mir::UnOp::Not, _) => false,
+                _ => return None,
+            };
+            return Some((place.local, holds_if_true));
+        }
+        let mir::TerminatorKind::Call {
+            func, destination, ..
+        } = &block_data.terminator().kind
+        else {
+            return None;
+        };
+        if !destination.projection.is_empty() {
+            return None;
+        }
+        let (callee_def_id, _) = func.const_fn_def()?;
+        let callee_name = self.tcx.def_path_str(callee_def_id);
+        if callee_name.ends_with("::eq") {
+            Some((destination.local, true))
+        } else if callee_name.ends_with("::ne") {
+            Some((destination.local, false))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the branches on the result of a check or on a copy or negation of it, each with the
+    /// arm on which the check holds. An assertion that continues only if the check fails has no
+    /// such arm.
+    fn branches_on(
+        &self,
+        result: mir::Local,
+        holds_if_true: bool,
+    ) -> Vec<(mir::BasicBlock, Option<mir::BasicBlock>)> {
+        // The locals that hold the result, each with whether a true value means the check holds
+        let mut results: HashMap<mir::Local, bool> = HashMap::from([(result, holds_if_true)]);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block_data in self.mir.basic_blocks.iter() {
+                for statement in &block_data.statements {
+                    let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind else {
+                        continue;
+                    };
+                    let (operand, is_negation) = match rvalue {
+                        mir::Rvalue::Use(operand) => (operand, false),
+                        mir::Rvalue::UnaryOp(mir::UnOp::Not, operand) => (operand, true),
+                        _ => continue,
+                    };
+                    let Some(holds) = self.result_held_by(operand, &results) else {
+                        continue;
+                    };
+                    if place.projection.is_empty() && !results.contains_key(&place.local) {
+                        results.insert(place.local, holds != is_negation);
+                        changed = true;
+                    }
+                }
+            }
+        }
+        let mut branches = Vec::new();
+        for (bb, block_data) in self.mir.basic_blocks.iter_enumerated() {
+            match &block_data.terminator().kind {
+                mir::TerminatorKind::SwitchInt { discr, targets } => {
+                    let Some(holds_if_true) = self.result_held_by(discr, &results) else {
+                        continue;
+                    };
+                    // A switch on a bool has an arm for one of the values and otherwise the other
+                    let false_arm = targets.iter().find(|(value, _)| *value == 0);
+                    let true_arm = targets.iter().find(|(value, _)| *value == 1);
+                    let (false_arm, true_arm) = match (false_arm, true_arm) {
+                        (Some((_, false_arm)), _) => (false_arm, targets.otherwise()),
+                        (None, Some((_, true_arm))) => (targets.otherwise(), true_arm),
+                        (None, None) => continue,
+                    };
+                    let holding_arm = if holds_if_true { true_arm } else { false_arm };
+                    branches.push((bb, Some(holding_arm)));
+                }
+                mir::TerminatorKind::Assert {
+                    cond,
+                    expected,
+                    target,
+                    ..
+                } => {
+                    let Some(holds_if_true) = self.result_held_by(cond, &results) else {
+                        continue;
+                    };
+                    let holding_arm = (*expected == holds_if_true).then_some(*target);
+                    branches.push((bb, holding_arm));
+                }
+                _ => {}
+            }
+        }
+        branches
+    }
+
+    /// Returns whether a true value of the operand means that the check holds, if the operand is
+    /// one of the locals that hold the result of the check.
+    fn result_held_by(
+        &self,
+        operand: &mir::Operand<'tcx>,
+        results: &HashMap<mir::Local, bool>,
+    ) -> Option<bool> {
+        let place = operand.place()?;
+        if !place.projection.is_empty() {
+            return None;
+        }
+        results.get(&place.local).copied()
+    }
+
+    /// Returns true if every path to the sink block takes the edge from the branch block to the
+    /// arm, i.e. the arm dominates the sink block and is only entered from the branch block, from
+    /// a block that is never executed or through the back edge of a loop within the arm.
+    fn edge_dominates(
+        &self,
+        branch_bb: mir::BasicBlock,
+        arm: mir::BasicBlock,
+        sink_bb: mir::BasicBlock,
+    ) -> bool {
+        self.dominators.dominates(arm, sink_bb)
+            && self.mir.basic_blocks.predecessors()[arm]
+                .iter()
+                .all(|pred| {
+                    *pred == branch_bb
+                        || !self.dominators.is_reachable(*pred)
+                        || self.dominators.dominates(arm, *pred)
+                })
+    }
+}
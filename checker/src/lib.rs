@@ -74,6 +74,8 @@ macro_rules! check_for_early_return {
 }
 
 pub mod abstract_value;
+pub mod analysis_cache;
+pub mod anchor;
 pub mod block_visitor;
 pub mod body_visitor;
 pub mod bool_domain;
@@ -86,14 +88,21 @@ pub mod environment;
 pub mod expected_errors;
 pub mod expression;
 pub mod fixed_point_visitor;
+pub mod guard_analysis;
 pub mod interval_domain;
 pub mod k_limits;
 pub mod known_names;
+pub mod mir_walker;
 pub mod options;
 pub mod path;
+pub mod report;
+pub mod rule_policy;
 pub mod smt_solver;
+pub mod span_utils;
 pub mod summaries;
+pub mod suppression;
 pub mod tag_domain;
+pub mod taint_analysis;
 pub mod type_visitor;
 pub mod utils;
 #[cfg(feature = "z3")]
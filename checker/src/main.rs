@@ -23,12 +23,16 @@ extern crate rustc_session;
 
 use itertools::Itertools;
 use log::*;
+use mirai::call_graph::CallGraphConfig;
 use mirai::callbacks;
 use mirai::options::Options;
 use mirai::utils;
 use mirai_annotations::*;
 use std::env;
-use std::path::Path;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
 
 fn main() {
     let early_error_handler =
@@ -75,6 +79,11 @@ fn main() {
     let mut rustc_command_line_arguments = options.parse(&args[1..], &early_error_handler, false);
     info!("MIRAI options modified by command line: {:?}", options);
 
+    // With --config-check, the config is validated instead of compiling and analyzing the crate
+    if options.config_check {
+        std::process::exit(check_config(&options));
+    }
+
     rustc_driver::install_ice_hook(rustc_driver::DEFAULT_BUG_REPORT_URL, |_| ());
     let result = rustc_driver::catch_fatal_errors(|| {
         // Add back the binary name
@@ -129,14 +138,35 @@ fn main() {
             }
         }
 
-        let mut callbacks = callbacks::MiraiCallbacks::new(options);
+        let watch = options.watch;
+        let mut callbacks = callbacks::MiraiCallbacks::new(options.clone());
         debug!(
             "rustc_command_line_arguments {:?}",
             rustc_command_line_arguments
         );
         let compiler =
             rustc_driver::RunCompiler::new(&rustc_command_line_arguments, &mut callbacks);
-        compiler.run()
+        let result = compiler.run();
+        // Without source files to watch, e.g. for a build script, there is nothing to analyze again
+        if !watch || callbacks.source_files().is_empty() {
+            return result;
+        }
+        let mut findings = callbacks.findings().to_vec();
+        let mut source_files = callbacks.source_files().to_vec();
+        loop {
+            wait_for_change(&source_files);
+            let mut callbacks = callbacks::MiraiCallbacks::new(options.clone())
+                .with_previous_findings(findings.clone());
+            // An edit can break the compilation, which should not end watch mode
+            let _ = rustc_driver::catch_fatal_errors(|| {
+                rustc_driver::RunCompiler::new(&rustc_command_line_arguments, &mut callbacks).run()
+            });
+            // If the crate was not analyzed, the next analysis is compared with the same findings
+            if !callbacks.source_files().is_empty() {
+                findings = callbacks.findings().to_vec();
+                source_files = callbacks.source_files().to_vec();
+            }
+        }
     });
     let exit_code = match result {
         Ok(_) => rustc_driver::EXIT_SUCCESS,
@@ -144,3 +174,43 @@ fn main() {
     };
     std::process::exit(exit_code);
 }
+
+/// Validates the call graph config of the options, printing the error that it has.
+/// Returns the exit code, which is nonzero if the config is invalid.
+fn check_config(options: &Options) -> i32 {
+    let Some(config_path) = &options.call_graph_config else {
+        println!("no call graph config is given");
+        return rustc_driver::EXIT_SUCCESS;
+    };
+    match CallGraphConfig::check_file(Path::new(config_path)) {
+        Ok(()) => {
+            println!("{config_path}: the call graph config is valid");
+            rustc_driver::EXIT_SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{config_path}: {error}");
+            rustc_driver::EXIT_FAILURE
+        }
+    }
+}
+
+/// Blocks until one of the given files is modified, removed or created.
+fn wait_for_change(files: &[PathBuf]) {
+    let modification_times = || {
+        files
+            .iter()
+            .map(|file| {
+                fs::metadata(file)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+            })
+            .collect::<Vec<_>>()
+    };
+    let initial_modification_times = modification_times();
+    loop {
+        thread::sleep(Duration::from_millis(500));
+        if modification_times() != initial_modification_times {
+            return;
+        }
+    }
+}
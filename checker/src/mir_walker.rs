@@ -0,0 +1,80 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// Walks the statements and the terminators of a function body exactly once, dispatching each of
+// them to every registered checker of the Checker trait. The abstract interpretation of the body
+// visits a block again for each iteration of a loop until it reaches a fixed point, and once more
+// to check for errors, so the checkers that only look at the shape of the MIR are fed by this walk
+// instead.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::mir;
+
+use crate::contract_errors::{Checker, CheckerContext};
+
+// The callee of a call of the body, as visit_call of the block visitor resolved it
+#[derive(Clone, Debug)]
+pub struct ResolvedCallee {
+    // The function that is called, once the operand of the call is resolved to a function
+    // reference and specialized to the generic arguments of the caller
+    pub def_id: DefId,
+    // The summary key of the callee, see utils::summary_key_str
+    pub name: Rc<str>,
+    // The key of the types of the arguments that the callee is specialized for
+    pub argument_type_key: Rc<str>,
+}
+
+pub struct MirWalker<'a, 'tcx> {
+    mir: &'tcx mir::Body<'tcx>,
+    callees: &'a HashMap<mir::Location, ResolvedCallee>,
+}
+
+impl<'a, 'tcx> MirWalker<'a, 'tcx> {
+    /// Returns a walker of the body, whose calls have the callees that the abstract interpretation
+    /// of the body resolved, keyed by the location of their terminator.
+    pub fn new(
+        mir: &'tcx mir::Body<'tcx>,
+        callees: &'a HashMap<mir::Location, ResolvedCallee>,
+    ) -> MirWalker<'a, 'tcx> {
+        MirWalker { mir, callees }
+    }
+
+    /// Visits the basic blocks of the body in order, and dispatches each statement, and then the
+    /// terminator of the block, to all the checkers. The callee of a call is the one that
+    /// visit_call of the block visitor called, so a call of a trait method is a call of its
+    /// implementation when the generic arguments of the caller determine it. A call that the
+    /// abstract interpretation does not resolve, or never reaches, has no callee.
+    pub fn walk(
+        &self,
+        context: &CheckerContext<'_, 'tcx>,
+        checkers: &mut [Box<dyn Checker<'tcx> + 'tcx>],
+    ) {
+        for (bb, data) in self.mir.basic_blocks.iter_enumerated() {
+            for (statement_index, statement) in data.statements.iter().enumerate() {
+                let location = mir::Location {
+                    block: bb,
+                    statement_index,
+                };
+                for checker in checkers.iter_mut() {
+                    checker.visit_statement(context, location, statement);
+                }
+            }
+            let Some(terminator) = &data.terminator else {
+                continue;
+            };
+            let location = mir::Location {
+                block: bb,
+                statement_index: data.statements.len(),
+            };
+            let callee = self.callees.get(&location);
+            for checker in checkers.iter_mut() {
+                checker.visit_terminator(context, location, terminator, callee);
+            }
+        }
+    }
+}
@@ -3,14 +3,22 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
 use clap::error::ErrorKind;
 use clap::parser::ValueSource;
-use clap::{Arg, Command};
+use clap::{Arg, ArgAction, Command};
 use itertools::Itertools;
+use regex::Regex;
 
 use mirai_annotations::*;
 use rustc_session::EarlyDiagCtxt;
 
+use crate::contract_errors::{is_opt_in_checker, CHECKER_NAMES};
+use crate::report::{Confidence, Severity};
+use crate::rule_policy::{resolve_checkers, PolicySuppression, RulePolicy};
+
 /// Creates the clap::Command metadata for argument parsing.
 fn make_options_parser(running_test_harness: bool) -> Command {
     // We could put this into lazy_static! with a Mutex around, but we really do not expect
@@ -64,7 +72,178 @@ fn make_options_parser(running_test_harness: bool) -> Command {
         .arg(Arg::new("print_summaries")
             .long("print_summaries")
             .num_args(0)
-            .help("Print out function summaries (work in progress)"));
+            .help("Print out function summaries (work in progress)"))
+        .arg(Arg::new("fn_filter")
+            .long("fn-filter")
+            .num_args(1)
+            .value_name("REGEX")
+            .help("Only analyze functions whose path matches the regular expression, e.g. `withdraw`.")
+            .long_help("The regular expression is matched against the path of every function that would otherwise be analyzed, such as `vault::withdraw_all`. A match anywhere in the path suffices, use `^` and `$` to match the whole path."))
+        .arg(Arg::new("only_changed_files")
+            .long("only-changed-files")
+            .num_args(0)
+            .help("Only analyze functions defined in files that git reports as modified or added.")
+            .long_help("Uses `git status --porcelain` to find the modified, added, renamed and untracked files of the working tree and skips functions defined elsewhere. Intended for quick pre-commit runs."))
+        .arg(Arg::new("report_dir")
+            .long("report-dir")
+            .num_args(1)
+            .help("Path to a directory where the findings of the smart contract checkers are reported.")
+            .long_help("The findings of each analyzed crate are stored in the directory and combined into report.json and report.txt, which have a section with summary counts for every crate of the workspace, followed by the overall total."))
+        .arg(Arg::new("report_style")
+            .long("report-style")
+            .num_args(1)
+            .value_parser(["compact", "pretty"])
+            .default_value("compact")
+            .help("Layout of the findings in the text report.")
+            .long_help("With `compact`, every finding is a single line of the form `checker file:line:col severity message`, which suits grep and log aggregators.\nWith `pretty`, every finding spans several lines and shows the source line it was found on.\n"))
+        .arg(Arg::new("emit")
+            .long("emit")
+            .num_args(1..)
+            .action(ArgAction::Append)
+            .value_parser(["annotated", "jsonl"])
+            .help("Write additional renderings of the findings to the report directory.")
+            .long_help("With `annotated`, a copy of every source file with findings is written to the `annotated` subdirectory of --report-dir, at the same relative path as the file. The line of each finding ends with a comment such as `// <<< reentrancy: possible reentrancy for the smart contract`, and the source is otherwise unchanged. Requires --report-dir.\nWith `jsonl`, findings.jsonl is written to --report-dir next to report.json, with a JSON object on every line for a finding of the workspace. Besides the line and column of its start, and the end_line and end_column just after its end, every finding has the byte offsets span_start and span_end of its start and end in the file."))
+        .arg(Arg::new("sarif")
+            .long("sarif")
+            .num_args(1)
+            .value_name("PATH")
+            .help("Path to a file where the findings are written as a SARIF 2.1.0 log, for code scanning.")
+            .long_help("The log has a run for every analyzed crate, so the crates of a workspace can share the file. Every checker is described by a rule whose id is the name of the checker, e.g. `reentrancy`, and every finding is a result of its rule."))
+        .arg(Arg::new("html")
+            .long("html")
+            .num_args(1)
+            .value_name("PATH")
+            .help("Path to a file where the findings are written as an HTML page, for reviewing them in a browser.")
+            .long_help("The page groups the findings by file, with an index of the files at the top. Every finding shows its checker, severity and confidence, and the source lines around it with the span of the finding highlighted. The findings of every analyzed crate are kept in `PATH.json`, so the crates of a workspace can share the page."))
+        .arg(Arg::new("format")
+            .long("format")
+            .num_args(1)
+            .value_parser(["text", "json", "sarif", "html"])
+            .help("Format of the findings that are written to --output, `text` by default.")
+            .long_help("With `text`, every finding is a line of the form `file:line:col: [severity] checker: message`, like a diagnostic of rustc. The lines are written to stderr unless --output is given.\nWith `json`, the output is a JSON array of the findings.\nWith `sarif`, the output is a SARIF 2.1.0 log, as written by --sarif.\nWith `html`, the output is an HTML page, as written by --html.\nThe formats other than `text` require --output. The crates of a workspace can share the output, which then has the findings of all of them. Without --format and --output the findings are only emitted as diagnostics.\n"))
+        .arg(Arg::new("output")
+            .long("output")
+            .num_args(1)
+            .value_name("PATH")
+            .help("Path to a file where the findings are written in the format given by --format."))
+        .arg(Arg::new("callgraph_with_findings")
+            .long("callgraph-with-findings")
+            .num_args(1)
+            .value_name("PATH")
+            .help("Path to a dot file where the call graph of the crate is written with the functions that have findings highlighted.")
+            .long_help("The graph has a node for every function of the crate and an edge for every call between them, and can be displayed with Graphviz. Entry points are drawn as boxes. A function with a finding is filled in red if one of its findings is Critical, and in orange otherwise, with the checkers of its findings in its tooltip. Every analyzed crate overwrites the file, so give a crate of a workspace its own path."))
+        .arg(Arg::new("rule_doc_url")
+            .long("rule-doc-url")
+            .num_args(1)
+            .value_name("TEMPLATE")
+            .help("Link every finding to the documentation of its checker, e.g. `https://wiki/rules/{rule_id}`.")
+            .long_help("The URL of the documentation is obtained by replacing `{rule_id}` in the template with the name of the checker. It is shown with the findings in the text report and stored as their helpUri in the JSON report. By default findings have no link."))
+        .arg(Arg::new("baseline")
+            .long("baseline")
+            .num_args(1)
+            .help("Path to a JSON file with previously reported findings that should not be reported again.")
+            .long_help("The file holds a JSON array of findings in the form they appear in a report, as written by --write-baseline. A finding is suppressed if the baseline has a finding of the same crate and checker with the same fingerprint, which is a hash of the function of the finding and of the tokens of its source lines, so the entry still matches after lines have been added or removed above the finding. Entries without a fingerprint match the findings of the same checker for the same file and line."))
+        .arg(Arg::new("write_baseline")
+            .long("write-baseline")
+            .num_args(1)
+            .value_name("PATH")
+            .help("Write the findings to a baseline file, for use with --baseline.")
+            .long_help("The entries of the analyzed crate in the file are replaced by its findings, whereas the entries of the other crates are kept, so the crates of a workspace can share the file. Findings that are suppressed by the baseline given with --baseline are written as well, those suppressed in the source or by the rule policy are not."))
+        .arg(Arg::new("prune_baseline")
+            .long("prune-baseline")
+            .num_args(0)
+            .help("Remove the entries that no longer match a finding from the baseline.")
+            .long_help("A baseline entry has drifted if it does not match a finding and its file no longer exists or belongs to the analyzed crate. Drifted entries are always warned about, with this option they are also removed from the baseline file."))
+        .arg(Arg::new("show_suppressed")
+            .long("show-suppressed")
+            .num_args(0)
+            .help("Print the suppressed findings along with what suppressed them.")
+            .long_help("Findings can be suppressed by an #[allow(checker_name)] attribute, a `// contract-analysis:allow(checker_name)` comment or the baseline. The suppressed findings are printed and included in the report with their suppression source."))
+        .arg(Arg::new("rules_from")
+            .long("rules-from")
+            .num_args(1)
+            .value_name("POLICY")
+            .help("Load the checkers to run, their severities and suppressions from a JSON rule policy.")
+            .long_help("The policy has the keys `enable` and `disable`, which list checkers or groups of checkers, `severities`, which maps checker names to levels, and `suppressions`, which lists objects with a `checker_name`, a `file` and an optional `line`. The checkers of the `default` group run unless they are disabled, those of the other groups, e.g. `pedantic`, only run if they are enabled. The policy is overlaid on the defaults and every name in it must be known. --rule-severity takes precedence over the severities of the policy."))
+        .arg(Arg::new("only")
+            .long("only")
+            .num_args(1)
+            .value_delimiter(',')
+            .action(ArgAction::Append)
+            .value_name("CHECKERS")
+            .help("Run only the given checkers or groups of checkers, e.g. `--only reentrancy,owner-check`.")
+            .long_help("The names are separated by commas and may be written with dashes or underscores. A checker of an opt-in group, e.g. `pedantic`, runs if it is named. Every name must be known."))
+        .arg(Arg::new("skip")
+            .long("skip")
+            .num_args(1)
+            .value_delimiter(',')
+            .action(ArgAction::Append)
+            .value_name("CHECKERS")
+            .help("Do not run the given checkers or groups of checkers, e.g. `--skip numerical-precision`.")
+            .long_help("The names are separated by commas and may be written with dashes or underscores. A skipped checker does not run even if --only or the rule policy names it. Every name must be known."))
+        .arg(Arg::new("rule_severity")
+            .long("rule-severity")
+            .num_args(1..)
+            .action(ArgAction::Append)
+            .value_name("CHECKER=LEVEL")
+            .help("Override the severity of the findings of a checker, e.g. `--rule-severity reentrancy=error numerical_precision=info`.")
+            .long_help("The level is one of info, low, medium, high or critical, where warning means medium and error means high. An override takes precedence over the severity declared by the checker, so it also decides whether the findings of the checker trigger --fail-on. The option can be repeated."))
+        .arg(Arg::new("fail_on")
+            .long("fail-on")
+            .num_args(1)
+            .value_name("LEVEL")
+            .help("Fail the analysis if there are findings of at least the given severity.")
+            .long_help("The level is one of info, low, medium, high or critical, where warning means medium and error means high. Suppressed findings do not count."))
+        .arg(Arg::new("min_confidence")
+            .long("min-confidence")
+            .num_args(1)
+            .value_name("LEVEL")
+            .help("Only report the findings of at least the given confidence.")
+            .long_help("The level is one of low, medium or high. Every checker has a confidence, which is high for patterns that the checker sees entirely in the MIR of a function, such as a balance that is stored after a transfer, and low for heuristics, such as any call of round. A checker may be more or less confident of a particular finding. Findings of a lower confidence are not reported at all, so they neither appear in the reports nor trigger --fail-on. The reports list the most confident findings first."))
+        .arg(Arg::new("watch")
+            .long("watch")
+            .num_args(0)
+            .help("Analyze the crate again whenever one of its source files changes.")
+            .long_help("After the first analysis, the source files of the crate are polled for changes. Every following analysis prints only the findings that are new, prefixed by `+`, and the findings that were resolved, prefixed by `-`, since the previous analysis. Findings keep their identity when lines above them are edited."))
+        .arg(Arg::new("analysis_cache")
+            .long("analysis-cache")
+            .num_args(1)
+            .value_name("PATH")
+            .help("Path to a file where the findings of every function are cached, so that the functions whose MIR has not changed are not analyzed again.")
+            .long_help("The file keeps a hash of the MIR of every analyzed function, together with the findings of the function, and is created if it does not exist yet. A function whose hash is the same as in the cache is reported with its cached findings instead of being analyzed. The hash covers the position of the function in its file, so a function that an edit above it moved is analyzed again, and it does not cover the functions it calls, nor the options of the analysis. Use the same options with the same cache file, e.g. in watch mode or in a pre-commit hook. The crates of a workspace can share the file."))
+        .arg(Arg::new("config_check")
+            .long("config-check")
+            .num_args(0)
+            .help("Validate the call graph config and exit without analyzing anything.")
+            .long_help("The config given by --call_graph_config is parsed strictly. A key that the config does not have is reported along with the keys that it may have, and so are invalid values, such as an unknown datalog_backend. The config is parsed just as strictly when it is used for an analysis. The exit code is nonzero if the config is invalid."))
+        .arg(Arg::new("stack_limit")
+            .long("stack-limit")
+            .num_args(1)
+            .value_name("BYTES")
+            .default_value("4096")
+            .help("The stack usage above which a function is reported by the stack_usage checker.")
+            .long_help("The stack usage of a function is estimated by adding up the sizes of its locals. The default is 4096 bytes, the size of a stack frame of a Solana program."))
+        .arg(Arg::new("guarded_reentrancy")
+            .long("guarded-reentrancy")
+            .num_args(1)
+            .value_parser(["report", "downgrade", "suppress"])
+            .default_value("downgrade")
+            .help("How to report reentrancy in functions guarded by a reentrancy flag.")
+            .long_help("A function is guarded if it branches on a flag of an account state, e.g. `if state.locked { return Err(..) }`, so that its transfers are only reached while the flag is not set, sets the flag, e.g. `state.locked = true`, on every path to its transfers and clears it again after them, so that a reentrant call is rejected.\nWith `report`, guarded functions are reported like any other.\nWith `downgrade`, the default, their reentrancy findings have severity info.\nWith `suppress`, they are not reported.\n"))
+        .arg(Arg::new("reentrancy_transfers")
+            .long("reentrancy-transfers")
+            .num_args(1)
+            .value_parser(["lamports", "cpi"])
+            .default_value("lamports")
+            .help("Which calls are transfers that a reentrant store of a balance has to follow.")
+            .long_help("A call of `invoke` or `invoke_signed` is always a transfer, since the invoked program may call back into the contract.\nWith `lamports`, a borrow of the lamports of an account, e.g. `account.try_borrow_mut_lamports()`, is a transfer as well.\nWith `cpi`, only the invocations of other programs are transfers, so the lamports that the contract moves between its own accounts for bookkeeping are not reported.\n"))
+        .arg(Arg::new("safe_transfer")
+            .long("safe-transfer")
+            .num_args(1..)
+            .action(ArgAction::Append)
+            .value_name("PATH")
+            .help("Functions whose calls are never transfers for the reentrancy checker, e.g. `vault::move_lamports`.")
+            .long_help("A function is named by its path, or by the end of its path after a `::`, so `move_lamports` names `vault::move_lamports` as well. A call of a safe transfer is not a transfer even if the function moves lamports or invokes another program, which suits helpers that only do internal accounting."));
     if running_test_harness {
         parser = parser.arg(Arg::new("test_only")
             .long("test_only")
@@ -76,7 +255,7 @@ fn make_options_parser(running_test_harness: bool) -> Command {
 }
 
 /// Represents options passed to MIRAI.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Options {
     pub single_func: Option<String>,
     pub test_only: bool,
@@ -88,6 +267,58 @@ pub struct Options {
     pub call_graph_config: Option<String>,
     pub print_function_names: bool,
     pub print_summaries: bool,
+    /// Only the functions whose path matches the filter are analyzed.
+    pub fn_filter: Option<Regex>,
+    pub only_changed_files: bool,
+    /// The files to restrict analysis to when only_changed_files is set.
+    /// If None, the list is obtained from `git status --porcelain` when analysis starts.
+    pub changed_files: Option<Vec<String>>,
+    pub report_dir: Option<String>,
+    pub report_style: ReportStyle,
+    /// Write annotated copies of the source files with findings to the report directory.
+    pub emit_annotated: bool,
+    /// Write the findings as JSON lines to the report directory.
+    pub emit_jsonl: bool,
+    /// The path of the SARIF log that the findings are written to.
+    pub sarif_path: Option<String>,
+    /// The path of the HTML report that the findings are written to.
+    pub html_path: Option<String>,
+    /// The format of the findings written to output_path, if --format or --output was given.
+    pub output_format: Option<OutputFormat>,
+    /// The path of the file that the findings are written to in output_format.
+    pub output_path: Option<String>,
+    /// The path of the dot file that the call graph annotated with the findings is written to.
+    pub callgraph_with_findings: Option<String>,
+    pub rule_doc_url: Option<String>,
+    pub baseline: Option<String>,
+    /// The path of the baseline file that the findings are written to.
+    pub write_baseline: Option<String>,
+    pub prune_baseline: bool,
+    pub show_suppressed: bool,
+    pub rules_from: Option<String>,
+    /// The checkers that run because the rule policy enables them, although they are opt-in.
+    pub enabled_checkers: HashSet<String>,
+    /// The checkers that do not run because the rule policy disables them or --skip names them.
+    pub disabled_checkers: HashSet<String>,
+    /// The checkers named by --only, if it is given, in which case no other checker runs.
+    pub only_checkers: Option<HashSet<String>>,
+    /// The suppressions of the rule policy.
+    pub policy_suppressions: Vec<PolicySuppression>,
+    /// The severities that replace the ones declared by the checkers, keyed by checker name.
+    pub rule_severities: HashMap<String, Severity>,
+    pub fail_on: Option<Severity>,
+    /// The confidence below which findings are not reported.
+    pub min_confidence: Option<Confidence>,
+    pub watch: bool,
+    pub guarded_reentrancy: GuardedReentrancy,
+    pub reentrancy_transfers: ReentrancyTransfers,
+    /// The functions whose calls the reentrancy checker does not treat as transfers.
+    pub safe_transfers: Vec<String>,
+    /// The path of the file that caches the findings of the functions by the hash of their MIR.
+    pub analysis_cache: Option<String>,
+    pub config_check: bool,
+    /// The estimated stack usage in bytes above which a function is reported.
+    pub stack_limit: u64,
 }
 
 /// Represents diag level.
@@ -108,7 +339,76 @@ pub enum DiagLevel {
     Paranoid,
 }
 
+/// Represents the layout of the findings in the text report.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ReportStyle {
+    /// One line per finding.
+    #[default]
+    Compact,
+    /// Several lines per finding, including the source line of the finding.
+    Pretty,
+}
+
+/// Represents the format of the findings that are written to --output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OutputFormat {
+    /// One line per finding, like a diagnostic of rustc.
+    #[default]
+    Text,
+    /// A JSON array of the findings.
+    Json,
+    /// A SARIF 2.1.0 log.
+    Sarif,
+    /// An HTML page.
+    Html,
+}
+
+/// Represents how reentrancy is reported in functions that are guarded by a reentrancy flag.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum GuardedReentrancy {
+    /// Report the reentrancy with the severity of the checker.
+    Report,
+    /// Report the reentrancy with severity info.
+    #[default]
+    Downgrade,
+    /// Do not report the reentrancy.
+    Suppress,
+}
+
+/// Represents which calls the reentrancy checker treats as transfers.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ReentrancyTransfers {
+    /// Both the invocations of other programs and the borrows of the lamports of an account.
+    #[default]
+    Lamports,
+    /// Only the invocations of other programs.
+    Cpi,
+}
+
 impl Options {
+    /// Returns true if the findings of the checker are reported, i.e. if the checker is not opt-in
+    /// or the rule policy enables it, and the rule policy does not disable it. If --only is given,
+    /// the checker must be one of the checkers that it names instead.
+    pub fn is_checker_enabled(&self, checker_name: &str) -> bool {
+        if self.disabled_checkers.contains(checker_name) {
+            return false;
+        }
+        if let Some(only_checkers) = &self.only_checkers {
+            return only_checkers.contains(checker_name);
+        }
+        !is_opt_in_checker(checker_name) || self.enabled_checkers.contains(checker_name)
+    }
+
+    /// Returns true if the findings are written in a form that includes the call path from an
+    /// entry point of a reentrancy finding, or the call graph is written with the findings, in
+    /// which case the edges of the call graph are collected even if no graph output is configured.
+    pub fn needs_call_graph_edges(&self) -> bool {
+        self.report_dir.is_some()
+            || self.output_format == Some(OutputFormat::Json)
+            || self.analysis_cache.is_some()
+            || self.callgraph_with_findings.is_some()
+    }
+
     /// Parse options from an argument string. The argument string will be split using unix
     /// shell escaping rules. Any content beyond the leftmost `--` token will be returned
     /// (excluding this token).
@@ -245,6 +545,187 @@ impl Options {
         ) {
             self.print_summaries = true;
         }
+        if let Some(fn_filter) = matches.get_one::<String>("fn_filter") {
+            self.fn_filter = Some(
+                Regex::new(fn_filter)
+                    .unwrap_or_else(|e| handler.early_fatal(format!("--fn-filter: {e}"))),
+            );
+        }
+        if !matches!(
+            matches.value_source("only_changed_files"),
+            Some(ValueSource::DefaultValue)
+        ) {
+            self.only_changed_files = true;
+        }
+        if matches.contains_id("report_dir") {
+            self.report_dir = matches.get_one::<String>("report_dir").cloned();
+        }
+        if matches.contains_id("report_style") {
+            self.report_style = match matches.get_one::<String>("report_style").unwrap().as_str() {
+                "compact" => ReportStyle::Compact,
+                "pretty" => ReportStyle::Pretty,
+                _ => assume_unreachable!(),
+            };
+        }
+        if let Some(emits) = matches.get_many::<String>("emit") {
+            for emit in emits {
+                match emit.as_str() {
+                    "annotated" => self.emit_annotated = true,
+                    "jsonl" => self.emit_jsonl = true,
+                    _ => assume_unreachable!(),
+                }
+            }
+        }
+        if matches.contains_id("sarif") {
+            self.sarif_path = matches.get_one::<String>("sarif").cloned();
+        }
+        if matches.contains_id("html") {
+            self.html_path = matches.get_one::<String>("html").cloned();
+        }
+        if matches.contains_id("format") || matches.contains_id("output") {
+            let format = matches
+                .get_one::<String>("format")
+                .map_or("text", String::as_str);
+            self.output_path = matches.get_one::<String>("output").cloned();
+            if format != "text" && self.output_path.is_none() {
+                handler.early_fatal(format!("--format {format} requires --output"));
+            }
+            self.output_format = Some(match format {
+                "text" => OutputFormat::Text,
+                "json" => OutputFormat::Json,
+                "sarif" => OutputFormat::Sarif,
+                "html" => OutputFormat::Html,
+                _ => assume_unreachable!(),
+            });
+        }
+        if matches.contains_id("callgraph_with_findings") {
+            self.callgraph_with_findings = matches
+                .get_one::<String>("callgraph_with_findings")
+                .cloned();
+        }
+        if matches.contains_id("rule_doc_url") {
+            self.rule_doc_url = matches.get_one::<String>("rule_doc_url").cloned();
+        }
+        if matches.contains_id("baseline") {
+            self.baseline = matches.get_one::<String>("baseline").cloned();
+        }
+        if matches.contains_id("write_baseline") {
+            self.write_baseline = matches.get_one::<String>("write_baseline").cloned();
+        }
+        if !matches!(
+            matches.value_source("prune_baseline"),
+            Some(ValueSource::DefaultValue)
+        ) {
+            self.prune_baseline = true;
+        }
+        if !matches!(
+            matches.value_source("show_suppressed"),
+            Some(ValueSource::DefaultValue)
+        ) {
+            self.show_suppressed = true;
+        }
+        // The rule policy is applied first, so that the options given on the command line
+        // take precedence over it
+        if let Some(policy_path) = matches.get_one::<String>("rules_from") {
+            let policy = RulePolicy::load(policy_path)
+                .unwrap_or_else(|e| handler.early_fatal(format!("--rules-from: {e}")));
+            self.rules_from = Some(policy_path.clone());
+            self.enabled_checkers = policy.enabled_checkers;
+            self.disabled_checkers = policy.disabled_checkers;
+            self.rule_severities.extend(policy.severities);
+            self.policy_suppressions = policy.suppressions;
+        }
+        // Checker names are written with underscores, the command line may use dashes instead
+        let checker_names_of = |option: &str| {
+            matches.get_many::<String>(option).map(|names| {
+                let names: Vec<String> = names.map(|name| name.replace('-', "_")).collect();
+                resolve_checkers(&names)
+                    .unwrap_or_else(|e| handler.early_fatal(format!("--{option}: {e}")))
+            })
+        };
+        if let Some(only_checkers) = checker_names_of("only") {
+            self.only_checkers = Some(only_checkers);
+        }
+        if let Some(skipped_checkers) = checker_names_of("skip") {
+            self.disabled_checkers.extend(skipped_checkers);
+        }
+        if let Some(overrides) = matches.get_many::<String>("rule_severity") {
+            for rule_severity in overrides {
+                let Some((checker_name, level)) = rule_severity.split_once('=') else {
+                    handler.early_fatal(format!(
+                        "--rule-severity expects CHECKER=LEVEL, found {rule_severity}"
+                    ))
+                };
+                if !CHECKER_NAMES.contains(&checker_name) {
+                    handler.early_fatal(format!(
+                        "--rule-severity names an unknown checker {checker_name}, expected one of {}",
+                        CHECKER_NAMES.join(", ")
+                    ));
+                }
+                let severity = Severity::from_str(level)
+                    .unwrap_or_else(|e| handler.early_fatal(format!("--rule-severity: {e}")));
+                self.rule_severities
+                    .insert(checker_name.to_string(), severity);
+            }
+        }
+        if let Some(level) = matches.get_one::<String>("fail_on") {
+            self.fail_on = Some(
+                Severity::from_str(level)
+                    .unwrap_or_else(|e| handler.early_fatal(format!("--fail-on: {e}"))),
+            );
+        }
+        if let Some(level) = matches.get_one::<String>("min_confidence") {
+            self.min_confidence = Some(
+                Confidence::from_str(level)
+                    .unwrap_or_else(|e| handler.early_fatal(format!("--min-confidence: {e}"))),
+            );
+        }
+        if !matches!(
+            matches.value_source("watch"),
+            Some(ValueSource::DefaultValue)
+        ) {
+            self.watch = true;
+        }
+        if matches.contains_id("analysis_cache") {
+            self.analysis_cache = matches.get_one::<String>("analysis_cache").cloned();
+        }
+        if !matches!(
+            matches.value_source("config_check"),
+            Some(ValueSource::DefaultValue)
+        ) {
+            self.config_check = true;
+        }
+        if let Some(stack_limit) = matches.get_one::<String>("stack_limit") {
+            self.stack_limit = stack_limit
+                .parse::<u64>()
+                .unwrap_or_else(|_| handler.early_fatal("--stack-limit expects an integer"));
+        }
+        if matches.contains_id("guarded_reentrancy") {
+            self.guarded_reentrancy = match matches
+                .get_one::<String>("guarded_reentrancy")
+                .unwrap()
+                .as_str()
+            {
+                "report" => GuardedReentrancy::Report,
+                "downgrade" => GuardedReentrancy::Downgrade,
+                "suppress" => GuardedReentrancy::Suppress,
+                _ => assume_unreachable!(),
+            };
+        }
+        if matches.contains_id("reentrancy_transfers") {
+            self.reentrancy_transfers = match matches
+                .get_one::<String>("reentrancy_transfers")
+                .unwrap()
+                .as_str()
+            {
+                "lamports" => ReentrancyTransfers::Lamports,
+                "cpi" => ReentrancyTransfers::Cpi,
+                _ => assume_unreachable!(),
+            };
+        }
+        if let Some(safe_transfers) = matches.get_many::<String>("safe_transfer") {
+            self.safe_transfers = safe_transfers.cloned().collect();
+        }
         args[rustc_args_start..].to_vec()
     }
 }
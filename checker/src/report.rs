@@ -0,0 +1,1486 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+use fs2::FileExt;
+use serde::{Deserialize, Serialize};
+
+use rustc_span::source_map::SourceMap;
+use rustc_span::Span;
+
+use crate::contract_errors::{checker_confidence, CHECKER_DESCRIPTIONS};
+use crate::options::{OutputFormat, ReportStyle};
+use crate::span_utils::finding_location;
+
+/// How severe the consequences of a finding are.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => f.write_str("info"),
+            Severity::Low => f.write_str("low"),
+            Severity::Medium => f.write_str("medium"),
+            Severity::High => f.write_str("high"),
+            Severity::Critical => f.write_str("critical"),
+        }
+    }
+}
+
+/// Parses a severity from its name. The names `warning` and `error` are accepted as well, meaning
+/// medium and high respectively, so that levels can be given in the terms of other linters.
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Severity, String> {
+        match s {
+            "info" => Ok(Severity::Info),
+            "low" => Ok(Severity::Low),
+            "medium" | "warning" => Ok(Severity::Medium),
+            "high" | "error" => Ok(Severity::High),
+            "critical" => Ok(Severity::Critical),
+            _ => Err(format!(
+                "unknown severity {s}, expected one of info, low, medium (warning), high (error) or critical"
+            )),
+        }
+    }
+}
+
+/// How confident a checker is that a finding is a real issue rather than an intended pattern.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Confidence {
+    Low,
+    /// The confidence of the findings of reports written before findings had a confidence.
+    #[default]
+    Medium,
+    High,
+}
+
+impl fmt::Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Confidence::Low => f.write_str("low"),
+            Confidence::Medium => f.write_str("medium"),
+            Confidence::High => f.write_str("high"),
+        }
+    }
+}
+
+impl FromStr for Confidence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Confidence, String> {
+        match s {
+            "low" => Ok(Confidence::Low),
+            "medium" => Ok(Confidence::Medium),
+            "high" => Ok(Confidence::High),
+            _ => Err(format!(
+                "unknown confidence {s}, expected one of low, medium or high"
+            )),
+        }
+    }
+}
+
+/// A warning issued by one of the smart contract checkers, located in the source of the crate
+/// that it originates from.
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct Finding {
+    /// The name of the crate that was being analyzed when the finding was produced.
+    pub crate_name: String,
+    pub file: String,
+    /// The line of the start of the finding, starting from 1.
+    pub line: usize,
+    /// The column of the start of the finding, starting from 1.
+    pub column: usize,
+    /// The line of the end of the finding, starting from 1.
+    #[serde(default)]
+    pub end_line: usize,
+    /// The column just after the end of the finding, starting from 1.
+    #[serde(default)]
+    pub end_column: usize,
+    /// The byte offset of the start of the finding in its file.
+    #[serde(default)]
+    pub span_start: u32,
+    /// The byte offset of the end of the finding in its file.
+    #[serde(default)]
+    pub span_end: u32,
+    /// The name of the checker that produced the finding, e.g. `reentrancy`.
+    pub checker_name: String,
+    pub severity: Severity,
+    /// How confident the checker is of the finding, see `contract_errors::checker_confidence`.
+    #[serde(default)]
+    pub confidence: Confidence,
+    pub message: String,
+    /// The source line that the finding starts on. It is kept with the finding, since the crate
+    /// reports of a workspace are combined by processes that run in different directories.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub snippet: String,
+    /// Identifies the finding independently of its line, so that a baseline entry still matches
+    /// the finding after lines have been added or removed above it, see `with_fingerprint`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub fingerprint: String,
+    /// The link to the documentation of the checker, if a template for it was given.
+    #[serde(rename = "helpUri", default, skip_serializing_if = "String::is_empty")]
+    pub help_uri: String,
+    /// The names of the functions on a shortest call path from an entry point of the crate to
+    /// the function of the finding, which is recorded for reentrancy findings.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reach_path: Vec<String>,
+    /// Other locations that explain the finding, e.g. the transfer that a reentrant store follows.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedLocation>,
+    /// The load, transfer and store that make up a reentrancy, for reentrancy findings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<ReentrancyEvidence>,
+}
+
+/// The parameters of the 64 bit FNV-1a hash that fingerprints findings. Unlike the hashers of the
+/// standard library, it is the same for every build, so fingerprints can be stored in a baseline.
+pub(crate) const FINGERPRINT_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FINGERPRINT_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Adds the bytes of a part of a fingerprint to the hash, followed by a separator, so that the
+/// parts `ab`, `c` do not hash like `a`, `bc`.
+pub(crate) fn fingerprint_hash(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes.iter().chain([&0]) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FINGERPRINT_PRIME);
+    }
+    hash
+}
+
+/// A location in the source that is related to a finding, together with what it has to do with it.
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct RelatedLocation {
+    pub file: String,
+    /// The line of the start of the location, starting from 1.
+    pub line: usize,
+    /// The column of the start of the location, starting from 1.
+    pub column: usize,
+    pub message: String,
+}
+
+impl RelatedLocation {
+    /// Creates a related location for the given span, which is located like the span of a finding.
+    pub fn new(source_map: &SourceMap, span: Span, message: &str) -> RelatedLocation {
+        let location = finding_location(source_map, span);
+        RelatedLocation {
+            file: location.file,
+            line: location.start_line,
+            column: location.start_col,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// The instructions of a reentrancy: the load of the balance of the user, the transfer of the
+/// lamports that follows it, during which the contract may be reentered, and the store of the
+/// balance after the transfer. Moving the store before the transfer fixes the reentrancy.
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct ReentrancyEvidence {
+    pub load: RelatedLocation,
+    pub transfer: RelatedLocation,
+    pub store: RelatedLocation,
+}
+
+impl ReentrancyEvidence {
+    pub fn new(
+        source_map: &SourceMap,
+        load: Span,
+        transfer: Span,
+        store: Span,
+    ) -> ReentrancyEvidence {
+        ReentrancyEvidence {
+            load: RelatedLocation::new(source_map, load, "the balance is loaded here"),
+            transfer: RelatedLocation::new(
+                source_map,
+                transfer,
+                "the lamports are transferred here",
+            ),
+            store: RelatedLocation::new(source_map, store, "the balance is stored here"),
+        }
+    }
+}
+
+/// Renders the related location in the form `file:line:col message`.
+impl fmt::Display for RelatedLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{} {}",
+            self.file, self.line, self.column, self.message
+        )
+    }
+}
+
+impl Finding {
+    /// Creates a finding for the given span. Spans inside macro expansions are reported at the
+    /// location of the outermost macro call, see `span_utils::finding_location`.
+    pub fn new(
+        crate_name: &str,
+        checker_name: &str,
+        severity: Severity,
+        source_map: &SourceMap,
+        span: Span,
+        message: &str,
+    ) -> Finding {
+        let location = finding_location(source_map, span);
+        let span = span.source_callsite();
+        let span_start = source_map.lookup_byte_offset(span.lo()).pos.0;
+        let span_end = source_map.lookup_byte_offset(span.hi()).pos.0;
+        let snippet = source_map
+            .lookup_source_file(span.lo())
+            .get_line(location.start_line - 1)
+            .map(|line| line.to_string())
+            .unwrap_or_default();
+        Finding {
+            crate_name: crate_name.to_string(),
+            file: location.file,
+            line: location.start_line,
+            column: location.start_col,
+            end_line: location.end_line,
+            end_column: location.end_col,
+            span_start,
+            span_end: span_end.max(span_start),
+            checker_name: checker_name.to_string(),
+            severity,
+            confidence: checker_confidence(checker_name),
+            message: message.to_string(),
+            snippet,
+            fingerprint: String::new(),
+            help_uri: String::new(),
+            reach_path: Vec::new(),
+            related: Vec::new(),
+            evidence: None,
+        }
+    }
+
+    /// Replaces the confidence of the checker with the confidence of this particular finding.
+    pub fn with_confidence(mut self, confidence: Confidence) -> Finding {
+        self.confidence = confidence;
+        self
+    }
+
+    /// Attaches the given locations, each with what it has to do with the finding.
+    pub fn with_related(mut self, source_map: &SourceMap, related: &[(Span, &str)]) -> Finding {
+        self.related = related
+            .iter()
+            .map(|(span, message)| RelatedLocation::new(source_map, *span, message))
+            .collect();
+        self
+    }
+
+    /// Attaches the load, transfer and store of a reentrancy to the finding.
+    pub fn with_evidence(mut self, evidence: Option<ReentrancyEvidence>) -> Finding {
+        self.evidence = evidence;
+        self
+    }
+
+    /// Fingerprints the finding with a hash of the name of its checker, which is also the id of
+    /// its rule, the name of the function it was found in and the tokens of the source lines it
+    /// covers. The tokens are separated by whitespace, so that reindenting the lines leaves the
+    /// fingerprint unchanged, as does moving them to other lines.
+    pub fn with_fingerprint(
+        mut self,
+        function_name: &str,
+        source_map: &SourceMap,
+        span: Span,
+    ) -> Finding {
+        let source_file = source_map.lookup_source_file(span.source_callsite().lo());
+        let mut hash = FINGERPRINT_OFFSET_BASIS;
+        for part in [self.checker_name.as_str(), function_name] {
+            hash = fingerprint_hash(hash, part.as_bytes());
+        }
+        // Lines are numbered from 1, but get_line takes an index starting from 0
+        for line_index in self.line - 1..self.end_line.max(self.line) {
+            if let Some(line) = source_file.get_line(line_index) {
+                for token in line.split_whitespace() {
+                    hash = fingerprint_hash(hash, token.as_bytes());
+                }
+            }
+        }
+        self.fingerprint = format!("{hash:016x}");
+        self
+    }
+
+    /// Links the finding to the documentation of its checker, which is found at the URL that
+    /// results from replacing `{rule_id}` in the template with the name of the checker.
+    pub fn with_help_uri(mut self, rule_doc_url: Option<&str>) -> Finding {
+        if let Some(template) = rule_doc_url {
+            self.help_uri = template.replace("{rule_id}", &self.checker_name);
+        }
+        self
+    }
+
+    /// Returns true if the finding reports the same issue as the other finding. Findings are
+    /// identified by their source line rather than its number, so that a finding keeps its
+    /// identity when the lines above it are edited.
+    pub fn is_same_issue(&self, other: &Finding) -> bool {
+        let same_line = if self.snippet.is_empty() || other.snippet.is_empty() {
+            self.line == other.line
+        } else {
+            self.snippet.trim() == other.snippet.trim()
+        };
+        self.checker_name == other.checker_name
+            && self.file == other.file
+            && self.message == other.message
+            && same_line
+    }
+
+    /// Returns true if the finding is at the same location as the other finding, i.e. it is
+    /// produced by the same checker, and so belongs to the same rule, at the same line of the same
+    /// file. A pattern that matches in several blocks of a body, e.g. of an unrolled loop, is then
+    /// reported once for its line.
+    pub fn is_duplicate_of(&self, other: &Finding) -> bool {
+        self.checker_name == other.checker_name
+            && self.file == other.file
+            && self.line == other.line
+    }
+
+    /// Renders the finding over several lines, with the source line it starts on, a marker below
+    /// the columns it covers on that line and the locations related to it.
+    pub fn to_pretty_text(&self) -> String {
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let mut text = format!(
+            "{} [{}, {} confidence]: {}\n{gutter}--> {}:{}:{}\n",
+            self.checker_name,
+            self.severity,
+            self.confidence,
+            self.message,
+            self.file,
+            self.line,
+            self.column
+        );
+        if !self.snippet.is_empty() {
+            let marker_indent: String = self
+                .snippet
+                .chars()
+                .take(self.column - 1)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            text.push_str(&format!("{gutter} |\n"));
+            text.push_str(&format!("{line_number} | {}\n", self.snippet));
+            // A finding that ends on a later line is marked up to the end of its first line
+            let marker_end = if self.end_line == self.line {
+                self.end_column
+            } else {
+                self.snippet.chars().count() + 1
+            };
+            let marker = "^".repeat(marker_end.saturating_sub(self.column).max(1));
+            text.push_str(&format!("{gutter} | {marker_indent}{marker}\n"));
+        }
+        for related in &self.related {
+            text.push_str(&format!("{gutter} = related: {related}\n"));
+        }
+        if let Some(evidence) = &self.evidence {
+            text.push_str(&format!("{gutter} = load: {}\n", evidence.load));
+            text.push_str(&format!("{gutter} = transfer: {}\n", evidence.transfer));
+            text.push_str(&format!("{gutter} = store: {}\n", evidence.store));
+        }
+        if !self.help_uri.is_empty() {
+            text.push_str(&format!("{gutter} = docs: {}\n", self.help_uri));
+        }
+        text
+    }
+}
+
+/// Renders the finding on a single line, in the form `checker file:line:col severity message`,
+/// followed by the link to the documentation of the checker in parentheses if there is one.
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}:{}:{} {} {}",
+            self.checker_name, self.file, self.line, self.column, self.severity, self.message
+        )?;
+        if !self.help_uri.is_empty() {
+            write!(f, " ({})", self.help_uri)?;
+        }
+        Ok(())
+    }
+}
+
+/// The way in which the user suppressed a finding.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuppressionSource {
+    /// An `#[allow(checker_name)]` attribute on the function or an enclosing item.
+    Attribute,
+    /// A `// contract-analysis:allow(checker_name)` comment on or above the line of the finding,
+    /// or above the function of the finding.
+    Comment,
+    /// An entry in the baseline file.
+    Baseline,
+    /// A suppression of the rule policy.
+    Policy,
+}
+
+impl fmt::Display for SuppressionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SuppressionSource::Attribute => f.write_str("attribute"),
+            SuppressionSource::Comment => f.write_str("comment"),
+            SuppressionSource::Baseline => f.write_str("baseline"),
+            SuppressionSource::Policy => f.write_str("policy"),
+        }
+    }
+}
+
+/// A finding that is not reported because the user suppressed it.
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct SuppressedFinding {
+    pub finding: Finding,
+    pub source: SuppressionSource,
+}
+
+impl fmt::Display for SuppressedFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (suppressed by {})", self.finding, self.source)
+    }
+}
+
+/// The findings that are new and the findings that were resolved since the previous analysis
+/// of a crate, which is what watch mode prints.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FindingDelta {
+    pub new: Vec<Finding>,
+    pub resolved: Vec<Finding>,
+}
+
+impl FindingDelta {
+    pub fn new(previous: &[Finding], current: &[Finding]) -> FindingDelta {
+        let mut new: Vec<Finding> = current
+            .iter()
+            .filter(|finding| !previous.iter().any(|p| p.is_same_issue(finding)))
+            .cloned()
+            .collect();
+        let mut resolved: Vec<Finding> = previous
+            .iter()
+            .filter(|finding| !current.iter().any(|c| c.is_same_issue(finding)))
+            .cloned()
+            .collect();
+        new.sort();
+        resolved.sort();
+        FindingDelta { new, resolved }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.new.is_empty() && self.resolved.is_empty()
+    }
+}
+
+/// Renders a line for every finding, prefixed by `+` if it is new and by `-` if it was resolved.
+impl fmt::Display for FindingDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for finding in &self.new {
+            writeln!(f, "+ {finding}")?;
+        }
+        for finding in &self.resolved {
+            writeln!(f, "- {finding}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The number of findings in a part of a report, in total and per checker.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FindingCounts {
+    pub total: usize,
+    pub by_checker: BTreeMap<String, usize>,
+}
+
+impl FindingCounts {
+    fn new<'a>(findings: impl IntoIterator<Item = &'a Finding>) -> FindingCounts {
+        let mut counts = FindingCounts::default();
+        for finding in findings {
+            counts.total += 1;
+            *counts
+                .by_checker
+                .entry(finding.checker_name.clone())
+                .or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+impl fmt::Display for FindingCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let noun = if self.total == 1 { "finding" } else { "findings" };
+        write!(f, "{} {}", self.total, noun)?;
+        if !self.by_checker.is_empty() {
+            let per_checker: Vec<String> = self
+                .by_checker
+                .iter()
+                .map(|(checker_name, count)| format!("{checker_name}: {count}"))
+                .collect();
+            write!(f, " ({})", per_checker.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// The findings of a single crate, sorted by confidence, most confident first, and then by location,
+/// together with their counts.
+/// The suppressed findings are only included if they were asked for and do not contribute to the counts,
+/// neither do the baseline entries that have drifted from the code.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CrateReport {
+    pub crate_name: String,
+    pub summary: FindingCounts,
+    pub findings: Vec<Finding>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed: Vec<SuppressedFinding>,
+    /// The baseline entries that no longer match a finding of the crate.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stale_baseline: Vec<Finding>,
+}
+
+impl CrateReport {
+    pub fn new(
+        crate_name: String,
+        mut findings: Vec<Finding>,
+        mut suppressed: Vec<SuppressedFinding>,
+        mut stale_baseline: Vec<Finding>,
+    ) -> CrateReport {
+        findings.sort_by(|x, y| y.confidence.cmp(&x.confidence).then_with(|| x.cmp(y)));
+        suppressed.sort();
+        stale_baseline.sort();
+        CrateReport {
+            crate_name,
+            summary: FindingCounts::new(&findings),
+            findings,
+            suppressed,
+            stale_baseline,
+        }
+    }
+}
+
+/// The findings of all the analyzed crates of a workspace, partitioned per crate,
+/// along with the counts over all crates.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WorkspaceReport {
+    pub crates: Vec<CrateReport>,
+    pub total: FindingCounts,
+}
+
+impl WorkspaceReport {
+    pub fn new(mut crates: Vec<CrateReport>) -> WorkspaceReport {
+        crates.sort_by(|x, y| x.crate_name.cmp(&y.crate_name));
+        let total = FindingCounts::new(crates.iter().flat_map(|c| c.findings.iter()));
+        WorkspaceReport { crates, total }
+    }
+
+    /// Renders the findings of all crates as a stream of JSON objects, one finding per line.
+    pub fn to_json_lines(&self) -> serde_json::Result<String> {
+        let mut json_lines = String::new();
+        for finding in self.crates.iter().flat_map(|c| c.findings.iter()) {
+            json_lines.push_str(&serde_json::to_string(finding)?);
+            json_lines.push('\n');
+        }
+        Ok(json_lines)
+    }
+
+    /// Renders the report as text, with a section for each crate followed by the overall total.
+    /// In the compact style every finding is a single line, ready to be searched with grep,
+    /// whereas in the pretty style every finding is shown together with its source line.
+    pub fn to_text(&self, style: ReportStyle) -> String {
+        let mut text = String::new();
+        for crate_report in &self.crates {
+            text.push_str(&format!(
+                "crate {}: {}\n",
+                crate_report.crate_name, crate_report.summary
+            ));
+            for finding in &crate_report.findings {
+                match style {
+                    ReportStyle::Compact => text.push_str(&format!("{finding}\n")),
+                    ReportStyle::Pretty => {
+                        text.push('\n');
+                        text.push_str(&finding.to_pretty_text());
+                    }
+                }
+            }
+            if !crate_report.suppressed.is_empty() {
+                if style == ReportStyle::Pretty {
+                    text.push('\n');
+                }
+                text.push_str(&format!("suppressed: {}\n", crate_report.suppressed.len()));
+                for suppressed_finding in &crate_report.suppressed {
+                    text.push_str(&format!("{suppressed_finding}\n"));
+                }
+            }
+            if !crate_report.stale_baseline.is_empty() {
+                if style == ReportStyle::Pretty {
+                    text.push('\n');
+                }
+                text.push_str(&format!(
+                    "stale baseline entries: {}\n",
+                    crate_report.stale_baseline.len()
+                ));
+                for stale_entry in &crate_report.stale_baseline {
+                    text.push_str(&format!("{stale_entry}\n"));
+                }
+            }
+            if style == ReportStyle::Pretty {
+                text.push('\n');
+            }
+        }
+        let noun = if self.crates.len() == 1 {
+            "crate"
+        } else {
+            "crates"
+        };
+        text.push_str(&format!(
+            "total: {} in {} {}\n",
+            self.total,
+            self.crates.len(),
+            noun
+        ));
+        text
+    }
+}
+
+/// Stores the report of one crate in report_dir and regenerates the workspace wide report.json
+/// and report.txt from the reports of all the crates stored there so far, as well as
+/// findings.jsonl if emit_jsonl is set.
+/// Cargo analyzes the crates of a workspace in separate, possibly concurrent, processes,
+/// so the directory is locked while the workspace report is being regenerated.
+/// The processes of one `cargo mirai` run share its run_id. The first of them to store its report
+/// removes the crate reports of earlier runs, so that crates that no longer exist, or are no
+/// longer analyzed, do not linger in the workspace report.
+pub fn write_crate_report(
+    report_dir: &Path,
+    crate_report: &CrateReport,
+    style: ReportStyle,
+    emit_jsonl: bool,
+    run_id: Option<&str>,
+) -> io::Result<()> {
+    let crates_dir = report_dir.join("crates");
+    fs::create_dir_all(&crates_dir)?;
+    let lock_file = File::create(report_dir.join(".lock"))?;
+    // Use the fs2 methods explicitly, newer toolchains have inherent File locking methods
+    FileExt::lock_exclusive(&lock_file)?;
+    let result = match run_id {
+        Some(run_id) => start_run(&crates_dir, run_id),
+        None => Ok(()),
+    }
+    .and_then(|()| {
+        write_workspace_report(report_dir, &crates_dir, crate_report, style, emit_jsonl)
+    });
+    FileExt::unlock(&lock_file)?;
+    result
+}
+
+/// Removes the crate reports in crates_dir if they were stored by a run other than run_id,
+/// and records run_id as the run that owns the directory.
+fn start_run(crates_dir: &Path, run_id: &str) -> io::Result<()> {
+    let run_path = crates_dir.join(".run");
+    if fs::read_to_string(&run_path).is_ok_and(|owner| owner == run_id) {
+        return Ok(());
+    }
+    for entry in fs::read_dir(crates_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|e| e == "json") {
+            fs::remove_file(path)?;
+        }
+    }
+    fs::write(run_path, run_id)
+}
+
+fn write_workspace_report(
+    report_dir: &Path,
+    crates_dir: &Path,
+    crate_report: &CrateReport,
+    style: ReportStyle,
+    emit_jsonl: bool,
+) -> io::Result<()> {
+    let crate_report_path = crates_dir.join(format!("{}.json", crate_report.crate_name));
+    fs::write(crate_report_path, serde_json::to_string_pretty(crate_report)?)?;
+    let mut crate_reports = Vec::new();
+    for entry in fs::read_dir(crates_dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|e| e == "json") {
+            let crate_report: CrateReport = serde_json::from_str(&fs::read_to_string(path)?)?;
+            crate_reports.push(crate_report);
+        }
+    }
+    let workspace_report = WorkspaceReport::new(crate_reports);
+    fs::write(
+        report_dir.join("report.json"),
+        serde_json::to_string_pretty(&workspace_report)?,
+    )?;
+    fs::write(
+        report_dir.join("report.txt"),
+        workspace_report.to_text(style),
+    )?;
+    if emit_jsonl {
+        fs::write(
+            report_dir.join("findings.jsonl"),
+            workspace_report.to_json_lines()?,
+        )?;
+    }
+    Ok(())
+}
+
+/// The start of the comment that marks the line of a finding in an annotated source file.
+pub const ANNOTATION_MARKER: &str = "// <<<";
+
+/// Returns a copy of the source with a comment like `// <<< reentrancy: possible reentrancy ...`
+/// at the end of the line of each of the findings. The lines are otherwise left as they are,
+/// including their line endings, so the copy can be compared with the source line by line.
+pub fn annotate_source(source: &str, findings: &[&Finding]) -> String {
+    let mut markers: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for finding in findings {
+        markers.entry(finding.line).or_default().push(format!(
+            "{ANNOTATION_MARKER} {}: {}",
+            finding.checker_name, finding.message
+        ));
+    }
+    let mut annotated = String::with_capacity(source.len());
+    for (index, line) in source.split_inclusive('\n').enumerate() {
+        let Some(line_markers) = markers.get(&(index + 1)) else {
+            annotated.push_str(line);
+            continue;
+        };
+        let content = line.trim_end_matches(['\r', '\n']);
+        annotated.push_str(content);
+        for marker in line_markers {
+            annotated.push(' ');
+            annotated.push_str(marker);
+        }
+        annotated.push_str(&line[content.len()..]);
+    }
+    annotated
+}
+
+/// Returns the path of the annotated copy of the given source file in annotated_dir.
+/// Absolute paths are made relative, so that every copy ends up inside annotated_dir.
+pub fn annotated_path(annotated_dir: &Path, file: &str) -> PathBuf {
+    let relative: PathBuf = Path::new(file)
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect();
+    annotated_dir.join(relative)
+}
+
+/// Writes an annotated copy of every source file that has findings to annotated_dir,
+/// see annotate_source. Files that cannot be read, e.g. those of macro expansions, are skipped.
+pub fn write_annotated_sources(annotated_dir: &Path, findings: &[Finding]) -> io::Result<()> {
+    let mut findings_per_file: BTreeMap<&str, Vec<&Finding>> = BTreeMap::new();
+    for finding in findings {
+        findings_per_file
+            .entry(finding.file.as_str())
+            .or_default()
+            .push(finding);
+    }
+    for (file, file_findings) in findings_per_file {
+        let Ok(source) = fs::read_to_string(file) else {
+            continue;
+        };
+        let path = annotated_path(annotated_dir, file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, annotate_source(&source, &file_findings))?;
+    }
+    Ok(())
+}
+
+/// The version of SARIF that the SARIF log conforms to.
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// The schema of the version of SARIF that the SARIF log conforms to.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// The name of the tool in the SARIF log.
+pub const SARIF_TOOL_NAME: &str = "contract-analysis";
+
+/// A SARIF 2.1.0 log of the findings, as it is uploaded to code scanning.
+/// It has a run for every analyzed crate, since the crates of a workspace are analyzed by
+/// separate processes that write to the same log.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SarifReport {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<SarifRun>,
+}
+
+/// The findings of one crate, together with the rules of all the checkers.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    /// Identifies the run by the name of its crate.
+    pub automation_details: SarifAutomationDetails,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SarifDriver {
+    pub name: String,
+    pub version: String,
+    pub rules: Vec<SarifRule>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SarifAutomationDetails {
+    pub id: String,
+}
+
+/// Describes a checker, whose name is the id of the rule.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    pub short_description: SarifMessage,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub help_uri: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+/// A finding, as a result of the rule of its checker.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    /// The index of the rule in the rules of the driver.
+    pub rule_index: usize,
+    /// One of `error`, `warning` or `note`.
+    pub level: String,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related_locations: Vec<SarifLocation>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<SarifMessage>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    pub region: SarifRegion,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+}
+
+/// The start of a location, with its line and column starting from 1, and its end if it is known,
+/// where the end column is the column just after the end of the location.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub start_line: usize,
+    pub start_column: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+}
+
+impl SarifLocation {
+    fn new(
+        file: &str,
+        line: usize,
+        column: usize,
+        end: Option<(usize, usize)>,
+        message: Option<&str>,
+    ) -> SarifLocation {
+        SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                // URIs separate their segments with forward slashes on every platform
+                artifact_location: SarifArtifactLocation {
+                    uri: file.replace('\\', "/"),
+                },
+                region: SarifRegion {
+                    start_line: line,
+                    start_column: column,
+                    end_line: end.map(|(end_line, _)| end_line),
+                    end_column: end.map(|(_, end_column)| end_column),
+                },
+            },
+            message: message.map(|text| SarifMessage {
+                text: text.to_string(),
+            }),
+        }
+    }
+}
+
+impl SarifRun {
+    /// Creates the run of the crate with the given findings. There is a rule for every checker,
+    /// linked to its documentation if a template for the links was given.
+    pub fn new(crate_name: &str, findings: &[Finding], rule_doc_url: Option<&str>) -> SarifRun {
+        let rules: Vec<SarifRule> = CHECKER_DESCRIPTIONS
+            .iter()
+            .map(|(checker_name, description)| SarifRule {
+                id: checker_name.to_string(),
+                short_description: SarifMessage {
+                    text: description.to_string(),
+                },
+                help_uri: rule_doc_url
+                    .map(|template| template.replace("{rule_id}", checker_name))
+                    .unwrap_or_default(),
+            })
+            .collect();
+        let results = findings
+            .iter()
+            .map(|finding| SarifResult {
+                rule_id: finding.checker_name.clone(),
+                rule_index: rules
+                    .iter()
+                    .position(|rule| rule.id == finding.checker_name)
+                    .unwrap_or_default(),
+                level: sarif_level(finding.severity).to_string(),
+                message: SarifMessage {
+                    text: finding.message.clone(),
+                },
+                locations: vec![SarifLocation::new(
+                    &finding.file,
+                    finding.line,
+                    finding.column,
+                    // Findings of reports from before the end was recorded have no end
+                    (finding.end_line > 0).then_some((finding.end_line, finding.end_column)),
+                    None,
+                )],
+                related_locations: finding
+                    .related
+                    .iter()
+                    .map(|r| SarifLocation::new(&r.file, r.line, r.column, None, Some(&r.message)))
+                    .collect(),
+            })
+            .collect();
+        SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: SARIF_TOOL_NAME.to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    rules,
+                },
+            },
+            automation_details: SarifAutomationDetails {
+                id: crate_name.to_string(),
+            },
+            results,
+        }
+    }
+}
+
+/// Returns the SARIF level of a finding with the given severity.
+pub fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low | Severity::Info => "note",
+    }
+}
+
+/// Adds the run of a crate to the SARIF log at sarif_path, replacing the run of a previous
+/// analysis of the same crate. The crates of a workspace are analyzed by separate, possibly
+/// concurrent, processes, so the log is locked while it is being updated.
+pub fn write_sarif_report(sarif_path: &Path, run: SarifRun) -> io::Result<()> {
+    if let Some(parent) = sarif_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let lock_file = File::create(format!("{}.lock", sarif_path.display()))?;
+    // Use the fs2 methods explicitly, newer toolchains have inherent File locking methods
+    FileExt::lock_exclusive(&lock_file)?;
+    let result = update_sarif_report(sarif_path, run);
+    FileExt::unlock(&lock_file)?;
+    result
+}
+
+fn update_sarif_report(sarif_path: &Path, run: SarifRun) -> io::Result<()> {
+    // A log that cannot be read is replaced, e.g. a log of an older version of the tool
+    let mut sarif_report = fs::read_to_string(sarif_path)
+        .ok()
+        .and_then(|sarif_json| serde_json::from_str::<SarifReport>(&sarif_json).ok())
+        .unwrap_or_else(|| SarifReport {
+            schema: SARIF_SCHEMA.to_string(),
+            version: SARIF_VERSION.to_string(),
+            runs: Vec::new(),
+        });
+    sarif_report
+        .runs
+        .retain(|r| r.automation_details.id != run.automation_details.id);
+    sarif_report.runs.push(run);
+    sarif_report
+        .runs
+        .sort_by(|x, y| x.automation_details.id.cmp(&y.automation_details.id));
+    fs::write(sarif_path, serde_json::to_string_pretty(&sarif_report)?)
+}
+
+/// The number of source lines that are shown before and after the line of a finding in the
+/// HTML report.
+pub const HTML_CONTEXT_LINES: usize = 2;
+
+/// A finding of the HTML report, together with the source lines around its start. The lines are
+/// read when the crate of the finding is analyzed, since the reports of a workspace are combined
+/// by processes that run in different directories.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HtmlFinding {
+    pub finding: Finding,
+    /// The line of the first of the context lines, starting from 1.
+    pub context_start: usize,
+    pub context: Vec<String>,
+}
+
+impl HtmlFinding {
+    /// Reads the lines around the start of the finding from its file. If the file cannot be
+    /// read, e.g. if the finding is in a macro expansion, the snippet is the only context.
+    pub fn new(finding: Finding) -> HtmlFinding {
+        let context_start = finding.line.saturating_sub(HTML_CONTEXT_LINES).max(1);
+        let context: Vec<String> = fs::read_to_string(&finding.file)
+            .map(|source| {
+                source
+                    .lines()
+                    .skip(context_start - 1)
+                    .take(finding.line + HTML_CONTEXT_LINES + 1 - context_start)
+                    .map(|line| line.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if context.is_empty() {
+            let context = vec![finding.snippet.clone()];
+            return HtmlFinding {
+                context_start: finding.line,
+                finding,
+                context,
+            };
+        }
+        HtmlFinding {
+            finding,
+            context_start,
+            context,
+        }
+    }
+
+    /// Returns the range of the characters of the given context line that the span of the finding
+    /// covers, if it covers any.
+    fn highlighted_range(&self, line_number: usize, line: &str) -> Option<(usize, usize)> {
+        let finding = &self.finding;
+        if line_number < finding.line || line_number > finding.end_line {
+            return None;
+        }
+        let start = if line_number == finding.line {
+            finding.column - 1
+        } else {
+            0
+        };
+        let end = if line_number == finding.end_line {
+            finding.end_column - 1
+        } else {
+            line.chars().count()
+        };
+        (start < end).then_some((start, end))
+    }
+}
+
+/// Replaces the characters that have a meaning in HTML with their character references.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The style sheet of the HTML report, which is included in the page so that the report is a
+/// single file.
+const HTML_STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+pre { background: #f6f8fa; padding: 0.5em; overflow-x: auto; }
+mark { background: #ffd7d5; }
+.line-number { color: #6a737d; user-select: none; }
+.critical, .high { color: #b31d28; font-weight: bold; }
+.medium { color: #b08800; font-weight: bold; }
+.low, .info { color: #586069; }";
+
+/// Renders the findings as an HTML page. The findings are grouped by file, with an index of the
+/// files at the top, and every finding shows its checker, severity and confidence, its source
+/// lines with the span of the finding highlighted, and links to the previous and next finding.
+pub fn render_html(findings: &[HtmlFinding]) -> String {
+    let mut findings_per_file: BTreeMap<&str, Vec<&HtmlFinding>> = BTreeMap::new();
+    for html_finding in findings {
+        findings_per_file
+            .entry(html_finding.finding.file.as_str())
+            .or_default()
+            .push(html_finding);
+    }
+    for file_findings in findings_per_file.values_mut() {
+        file_findings.sort_by(|x, y| x.finding.cmp(&y.finding));
+    }
+    let finding_count = findings.len();
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{SARIF_TOOL_NAME} findings</title>\n"));
+    html.push_str(&format!(
+        "<style>\n{HTML_STYLE}\n</style>\n</head>\n<body>\n"
+    ));
+    html.push_str(&format!("<h1>{SARIF_TOOL_NAME} findings</h1>\n"));
+    html.push_str(&format!(
+        "<p>{finding_count} {} in {} {}</p>\n",
+        if finding_count == 1 {
+            "finding"
+        } else {
+            "findings"
+        },
+        findings_per_file.len(),
+        if findings_per_file.len() == 1 {
+            "file"
+        } else {
+            "files"
+        }
+    ));
+    html.push_str("<nav id=\"index\">\n<ul>\n");
+    for (file_index, (file, file_findings)) in findings_per_file.iter().enumerate() {
+        html.push_str(&format!(
+            "<li><a href=\"#file-{}\">{}</a> ({})</li>\n",
+            file_index + 1,
+            escape_html(file),
+            file_findings.len()
+        ));
+    }
+    html.push_str("</ul>\n</nav>\n");
+    // The findings are numbered in the order they appear on the page, starting from 1
+    let mut finding_number = 0;
+    for (file_index, (file, file_findings)) in findings_per_file.iter().enumerate() {
+        html.push_str(&format!(
+            "<section id=\"file-{}\">\n<h2>{}</h2>\n",
+            file_index + 1,
+            escape_html(file)
+        ));
+        for html_finding in file_findings {
+            finding_number += 1;
+            render_html_finding(&mut html, html_finding, finding_number, finding_count);
+        }
+        html.push_str("</section>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Renders a finding of the HTML report as an article, see render_html.
+fn render_html_finding(
+    html: &mut String,
+    html_finding: &HtmlFinding,
+    finding_number: usize,
+    finding_count: usize,
+) {
+    let finding = &html_finding.finding;
+    html.push_str(&format!("<article id=\"finding-{finding_number}\">\n"));
+    html.push_str(&format!(
+        "<h3><a href=\"#finding-{finding_number}\">{}</a>: {}</h3>\n",
+        escape_html(&finding.checker_name),
+        escape_html(&finding.message)
+    ));
+    html.push_str(&format!(
+        "<p><span class=\"{}\">{} severity</span>, {} confidence, crate {} at line {}, column {}</p>\n",
+        finding.severity,
+        finding.severity,
+        finding.confidence,
+        escape_html(&finding.crate_name),
+        finding.line,
+        finding.column
+    ));
+    html.push_str("<pre>\n");
+    for (index, line) in html_finding.context.iter().enumerate() {
+        let line_number = html_finding.context_start + index;
+        html.push_str(&format!(
+            "<span class=\"line-number\">{line_number:>5}</span> "
+        ));
+        match html_finding.highlighted_range(line_number, line) {
+            Some((start, end)) => {
+                let before: String = line.chars().take(start).collect();
+                let marked: String = line.chars().skip(start).take(end - start).collect();
+                let after: String = line.chars().skip(end).collect();
+                html.push_str(&format!(
+                    "{}<mark>{}</mark>{}\n",
+                    escape_html(&before),
+                    escape_html(&marked),
+                    escape_html(&after)
+                ));
+            }
+            None => {
+                html.push_str(&escape_html(line));
+                html.push('\n');
+            }
+        }
+    }
+    html.push_str("</pre>\n");
+    if !finding.related.is_empty() {
+        html.push_str("<ul>\n");
+        for related in &finding.related {
+            html.push_str(&format!(
+                "<li>related: {} at line {}, column {}</li>\n",
+                escape_html(&related.message),
+                related.line,
+                related.column
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+    if !finding.help_uri.is_empty() {
+        let help_uri = escape_html(&finding.help_uri);
+        html.push_str(&format!("<p><a href=\"{help_uri}\">{help_uri}</a></p>\n"));
+    }
+    let mut links = Vec::new();
+    if finding_number > 1 {
+        links.push(format!(
+            "<a href=\"#finding-{}\">previous</a>",
+            finding_number - 1
+        ));
+    }
+    links.push("<a href=\"#index\">index</a>".to_string());
+    if finding_number < finding_count {
+        links.push(format!(
+            "<a href=\"#finding-{}\">next</a>",
+            finding_number + 1
+        ));
+    }
+    html.push_str(&format!("<p>{}</p>\n</article>\n", links.join(" | ")));
+}
+
+/// Adds the findings of a crate to the HTML report at html_path, replacing the findings of a
+/// previous analysis of the same crate. The findings of all crates are kept next to the report in
+/// `{html_path}.json`, from which the report is rendered again. The crates of a workspace are
+/// analyzed by separate, possibly concurrent, processes, so the report is locked while it is
+/// being updated.
+pub fn write_html_report(
+    html_path: &Path,
+    crate_name: &str,
+    findings: &[Finding],
+) -> io::Result<()> {
+    if let Some(parent) = html_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let lock_file = File::create(format!("{}.lock", html_path.display()))?;
+    // Use the fs2 methods explicitly, newer toolchains have inherent File locking methods
+    FileExt::lock_exclusive(&lock_file)?;
+    let result = update_html_report(html_path, crate_name, findings);
+    FileExt::unlock(&lock_file)?;
+    result
+}
+
+fn update_html_report(html_path: &Path, crate_name: &str, findings: &[Finding]) -> io::Result<()> {
+    let findings_path = format!("{}.json", html_path.display());
+    // Findings that cannot be read are replaced, e.g. those of an older version of the tool
+    let mut html_findings: Vec<HtmlFinding> = fs::read_to_string(&findings_path)
+        .ok()
+        .and_then(|findings_json| serde_json::from_str(&findings_json).ok())
+        .unwrap_or_default();
+    html_findings.retain(|f| f.finding.crate_name != crate_name);
+    html_findings.extend(findings.iter().cloned().map(HtmlFinding::new));
+    fs::write(
+        &findings_path,
+        serde_json::to_string_pretty(&html_findings)?,
+    )?;
+    fs::write(html_path, render_html(&html_findings))
+}
+
+/// Writes the findings of a crate in one of the formats that --format selects. The crates of a
+/// workspace are analyzed by separate, possibly concurrent, processes that may share the output,
+/// so a formatter replaces the findings of a previous analysis of the same crate in the output and
+/// keeps those of the other crates.
+pub trait Formatter {
+    /// Writes the findings of the crate to the output path, or to the default destination of the
+    /// format if no path is given.
+    fn write_findings(
+        &self,
+        output_path: Option<&Path>,
+        crate_name: &str,
+        findings: &[Finding],
+    ) -> io::Result<()>;
+}
+
+/// Returns the formatter of the format, which links the rules of SARIF logs to rule_doc_url.
+pub fn formatter_for(format: OutputFormat, rule_doc_url: Option<&str>) -> Box<dyn Formatter> {
+    match format {
+        OutputFormat::Text => Box::new(TextFormatter),
+        OutputFormat::Json => Box::new(JsonFormatter),
+        OutputFormat::Sarif => Box::new(SarifFormatter {
+            rule_doc_url: rule_doc_url.map(|url| url.to_string()),
+        }),
+        OutputFormat::Html => Box::new(HtmlFormatter),
+    }
+}
+
+/// Renders every finding on a line of the form `file:line:col: [severity] checker: message`, like
+/// a diagnostic of rustc. Without an output path the lines are written to stderr. The findings of
+/// every crate are kept next to an output file in `{output_path}.json`, like those of an HTML page.
+pub struct TextFormatter;
+
+impl TextFormatter {
+    /// Renders the finding on its line of the text format.
+    pub fn render_finding(finding: &Finding) -> String {
+        format!(
+            "{}:{}:{}: [{}] {}: {}",
+            finding.file,
+            finding.line,
+            finding.column,
+            finding.severity,
+            finding.checker_name,
+            finding.message
+        )
+    }
+}
+
+impl Formatter for TextFormatter {
+    fn write_findings(
+        &self,
+        output_path: Option<&Path>,
+        crate_name: &str,
+        findings: &[Finding],
+    ) -> io::Result<()> {
+        let Some(output_path) = output_path else {
+            for finding in findings {
+                eprintln!("{}", TextFormatter::render_finding(finding));
+            }
+            return Ok(());
+        };
+        with_output_lock(output_path, || {
+            let findings_path = PathBuf::from(format!("{}.json", output_path.display()));
+            let all_findings = update_crate_findings(&findings_path, crate_name, findings)?;
+            let text: String = all_findings
+                .iter()
+                .map(|finding| TextFormatter::render_finding(finding) + "\n")
+                .collect();
+            fs::write(output_path, text)
+        })
+    }
+}
+
+/// Writes the findings of every crate to the output as a JSON array, in the form they appear in
+/// a report.
+pub struct JsonFormatter;
+
+impl Formatter for JsonFormatter {
+    fn write_findings(
+        &self,
+        output_path: Option<&Path>,
+        crate_name: &str,
+        findings: &[Finding],
+    ) -> io::Result<()> {
+        let output_path = required_output_path(output_path, "json")?;
+        with_output_lock(output_path, || {
+            update_crate_findings(output_path, crate_name, findings).map(|_| ())
+        })
+    }
+}
+
+/// Writes the findings to the output as a SARIF log, see write_sarif_report.
+pub struct SarifFormatter {
+    pub rule_doc_url: Option<String>,
+}
+
+impl Formatter for SarifFormatter {
+    fn write_findings(
+        &self,
+        output_path: Option<&Path>,
+        crate_name: &str,
+        findings: &[Finding],
+    ) -> io::Result<()> {
+        let output_path = required_output_path(output_path, "sarif")?;
+        let run = SarifRun::new(crate_name, findings, self.rule_doc_url.as_deref());
+        write_sarif_report(output_path, run)
+    }
+}
+
+/// Writes the findings to the output as an HTML page, see write_html_report.
+pub struct HtmlFormatter;
+
+impl Formatter for HtmlFormatter {
+    fn write_findings(
+        &self,
+        output_path: Option<&Path>,
+        crate_name: &str,
+        findings: &[Finding],
+    ) -> io::Result<()> {
+        let output_path = required_output_path(output_path, "html")?;
+        write_html_report(output_path, crate_name, findings)
+    }
+}
+
+fn required_output_path<'a>(output_path: Option<&'a Path>, format: &str) -> io::Result<&'a Path> {
+    output_path.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("--format {format} requires --output"),
+        )
+    })
+}
+
+/// Runs the update of the output while the output is locked, creating its directory if needed.
+fn with_output_lock(output_path: &Path, update: impl FnOnce() -> io::Result<()>) -> io::Result<()> {
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let lock_file = File::create(format!("{}.lock", output_path.display()))?;
+    // Use the fs2 methods explicitly, newer toolchains have inherent File locking methods
+    FileExt::lock_exclusive(&lock_file)?;
+    let result = update();
+    FileExt::unlock(&lock_file)?;
+    result
+}
+
+/// Replaces the findings of the crate in the JSON array of findings at findings_path and returns
+/// the findings of all the crates, sorted by crate and location. Findings that cannot be read are
+/// replaced, e.g. those of an older version of the tool.
+fn update_crate_findings(
+    findings_path: &Path,
+    crate_name: &str,
+    findings: &[Finding],
+) -> io::Result<Vec<Finding>> {
+    let mut all_findings: Vec<Finding> = fs::read_to_string(findings_path)
+        .ok()
+        .and_then(|findings_json| serde_json::from_str(&findings_json).ok())
+        .unwrap_or_default();
+    all_findings.retain(|finding| finding.crate_name != crate_name);
+    all_findings.extend(findings.iter().cloned());
+    all_findings.sort();
+    fs::write(findings_path, serde_json::to_string_pretty(&all_findings)?)?;
+    Ok(all_findings)
+}
@@ -0,0 +1,137 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::str::FromStr;
+
+use log::*;
+use serde::Deserialize;
+
+use crate::contract_errors::{
+    checkers_of_group, CHECKER_NAMES, DEFAULT_CHECKER_GROUP, OPT_IN_CHECKER_GROUPS,
+};
+use crate::report::Severity;
+
+/// A policy of the checkers to run, as it is kept in a file shared by the crates of an organization,
+/// e.g.
+/// ```json
+/// {
+///   "enable": ["pedantic"],
+///   "disable": ["time_manipulation"],
+///   "severities": { "manual_rent": "medium" },
+///   "suppressions": [{ "checker_name": "reentrancy", "file": "src/legacy.rs" }]
+/// }
+/// ```
+/// The policy is overlaid on the defaults, so a checker that the policy does not mention runs if it
+/// runs by default, and the options given on the command line take precedence over the policy.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RulePolicy {
+    /// The checkers or groups of checkers to run although they do not run by default.
+    pub enable: Vec<String>,
+    /// The checkers or groups of checkers not to run.
+    pub disable: Vec<String>,
+    /// The severities that replace the ones declared by the checkers, keyed by checker name.
+    pub severities: HashMap<String, String>,
+    /// The findings that should not be reported.
+    pub suppressions: Vec<PolicySuppression>,
+}
+
+/// Suppresses the findings of a checker in a file, or only on one line of the file.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PolicySuppression {
+    pub checker_name: String,
+    pub file: String,
+    #[serde(default)]
+    pub line: Option<usize>,
+}
+
+impl PolicySuppression {
+    /// Returns true if the suppression applies to a finding of the checker at the file and line.
+    pub fn matches(&self, checker_name: &str, file: &str, line: usize) -> bool {
+        self.checker_name == checker_name
+            && self.file == file
+            && self.line.is_none_or(|l| l == line)
+    }
+}
+
+/// The effect of a rule policy on the checkers, after it has been validated against the checkers.
+#[derive(Clone, Debug, Default)]
+pub struct ResolvedRulePolicy {
+    pub enabled_checkers: HashSet<String>,
+    pub disabled_checkers: HashSet<String>,
+    pub severities: HashMap<String, Severity>,
+    pub suppressions: Vec<PolicySuppression>,
+}
+
+impl RulePolicy {
+    /// Loads the rule policy at the given path and resolves its groups into checkers.
+    /// Every checker, group or severity that the policy names must be known.
+    pub fn load(policy_path: &str) -> Result<ResolvedRulePolicy, String> {
+        let policy_json = fs::read_to_string(policy_path)
+            .map_err(|e| format!("could not read the rule policy {policy_path}: {e}"))?;
+        let policy: RulePolicy = serde_json::from_str(&policy_json)
+            .map_err(|e| format!("could not parse the rule policy {policy_path}: {e}"))?;
+        let resolved = policy
+            .resolve()
+            .map_err(|e| format!("invalid rule policy {policy_path}: {e}"))?;
+        info!(
+            "loaded a rule policy enabling {} and disabling {} checkers",
+            resolved.enabled_checkers.len(),
+            resolved.disabled_checkers.len()
+        );
+        Ok(resolved)
+    }
+
+    /// Resolves the groups of the policy into checkers and parses its severities.
+    pub fn resolve(&self) -> Result<ResolvedRulePolicy, String> {
+        let mut resolved = ResolvedRulePolicy {
+            enabled_checkers: resolve_checkers(&self.enable)?,
+            disabled_checkers: resolve_checkers(&self.disable)?,
+            ..ResolvedRulePolicy::default()
+        };
+        for (checker_name, level) in &self.severities {
+            check_checker_name(checker_name)?;
+            let severity = Severity::from_str(level)?;
+            resolved.severities.insert(checker_name.clone(), severity);
+        }
+        for suppression in &self.suppressions {
+            check_checker_name(&suppression.checker_name)?;
+        }
+        resolved.suppressions = self.suppressions.clone();
+        Ok(resolved)
+    }
+}
+
+/// Returns the checkers named by the given names of checkers and groups.
+/// Every name must be known.
+pub fn resolve_checkers(names: &[String]) -> Result<HashSet<String>, String> {
+    let mut checkers = HashSet::new();
+    for name in names {
+        if let Some(group) = checkers_of_group(name) {
+            checkers.extend(group.into_iter().map(String::from));
+        } else {
+            check_checker_name(name)?;
+            checkers.insert(name.clone());
+        }
+    }
+    Ok(checkers)
+}
+
+fn check_checker_name(checker_name: &str) -> Result<(), String> {
+    if CHECKER_NAMES.contains(&checker_name) {
+        return Ok(());
+    }
+    let group_names: Vec<&str> = std::iter::once(DEFAULT_CHECKER_GROUP)
+        .chain(OPT_IN_CHECKER_GROUPS.iter().map(|(name, _)| *name))
+        .collect();
+    Err(format!(
+        "unknown checker {checker_name}, expected one of {} or one of the groups {}",
+        CHECKER_NAMES.join(", "),
+        group_names.join(", ")
+    ))
+}
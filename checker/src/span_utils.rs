@@ -0,0 +1,37 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use rustc_span::source_map::SourceMap;
+use rustc_span::Span;
+
+/// Where a span is in the source, with lines and columns starting from 1.
+/// The end is the position just after the last character of the span, as in SARIF regions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FindingLocation {
+    pub file: String,
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+/// Returns the location of the span in the source. A span inside a macro expansion is located at
+/// the outermost macro call, rather than at the definition of the macro, so that both of its
+/// endpoints are in the file of the call. The endpoints of a span over several lines are looked up
+/// separately, and the end is never before the start.
+pub fn finding_location(source_map: &SourceMap, span: Span) -> FindingLocation {
+    let span = span.source_callsite();
+    let start = source_map.lookup_char_pos(span.lo());
+    let end = source_map.lookup_char_pos(span.hi().max(span.lo()));
+    let (start_line, start_col) = (start.line, start.col.0 + 1);
+    let (end_line, end_col) = (end.line, end.col.0 + 1).max((start_line, start_col));
+    FindingLocation {
+        file: start.file.name.prefer_local().to_string(),
+        start_line,
+        start_col,
+        end_line,
+        end_col,
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+
+use fs2::FileExt;
+use log::*;
+
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{sym, Span};
+
+use crate::report::{Finding, SuppressionSource};
+use crate::rule_policy::PolicySuppression;
+
+/// The prefix of a comment that suppresses findings on its own line and on the line below it,
+/// e.g. `// contract-analysis:allow(reentrancy)`. Above a function, or on the line of its
+/// signature, the comment suppresses the findings of the whole function.
+pub const ALLOW_COMMENT_PREFIX: &str = "contract-analysis:allow(";
+
+/// The name that an allow comment or attribute uses to suppress the findings of every checker,
+/// e.g. `// contract-analysis:allow(all)`.
+pub const ALLOW_ALL: &str = "all";
+
+/// Decides which findings of the smart contract checkers have been suppressed by the user.
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    /// The previously reported findings that should not be reported again.
+    baseline: Vec<Finding>,
+    /// The indices of the baseline entries that match a finding of the crate being analyzed.
+    matched: HashSet<usize>,
+    /// The suppressions of the rule policy.
+    policy: Vec<PolicySuppression>,
+}
+
+impl Suppressions {
+    /// Loads the findings of the baseline file at the given path, which is a JSON array of findings
+    /// in the same form as they appear in a report.
+    pub fn load(baseline_path: Option<&str>) -> Result<Suppressions, String> {
+        let Some(baseline_path) = baseline_path else {
+            return Ok(Suppressions::default());
+        };
+        let baseline_json = fs::read_to_string(baseline_path)
+            .map_err(|e| format!("could not read the baseline {baseline_path}: {e}"))?;
+        let baseline: Vec<Finding> = serde_json::from_str(&baseline_json)
+            .map_err(|e| format!("could not parse the baseline {baseline_path}: {e}"))?;
+        info!("loaded {} baseline findings", baseline.len());
+        Ok(Suppressions {
+            baseline,
+            matched: HashSet::new(),
+            policy: Vec::new(),
+        })
+    }
+
+    /// Adds the suppressions of the rule policy.
+    pub fn with_policy(mut self, policy: &[PolicySuppression]) -> Suppressions {
+        self.policy = policy.to_vec();
+        self
+    }
+
+    /// Returns the source of the suppression of the given finding, which was produced for the
+    /// given span in the body of def_id, or None if the finding has not been suppressed.
+    pub fn suppression_for(
+        &mut self,
+        tcx: TyCtxt<'_>,
+        source_map: &SourceMap,
+        def_id: DefId,
+        span: Span,
+        finding: &Finding,
+    ) -> Option<SuppressionSource> {
+        // The baseline entry is looked up first, so that it does not count as drifted if the
+        // finding is also suppressed in the source
+        let baseline_index = self
+            .baseline
+            .iter()
+            .position(|entry| is_baseline_match(entry, finding));
+        if let Some(index) = baseline_index {
+            self.matched.insert(index);
+        }
+        if is_allowed_by_attribute(tcx, def_id, &finding.checker_name) {
+            return Some(SuppressionSource::Attribute);
+        }
+        if is_allowed_by_comment(source_map, span, &finding.checker_name)
+            || is_allowed_by_function_comment(tcx, source_map, def_id, &finding.checker_name)
+        {
+            return Some(SuppressionSource::Comment);
+        }
+        if self
+            .policy
+            .iter()
+            .any(|p| p.matches(&finding.checker_name, &finding.file, finding.line))
+        {
+            return Some(SuppressionSource::Policy);
+        }
+        baseline_index.map(|_| SuppressionSource::Baseline)
+    }
+
+    /// Returns the baseline entries that have drifted from the code, i.e. the entries that did not
+    /// match any finding and that are either for a file that no longer exists or for a file of the
+    /// crate that was analyzed. Entries for the files of other crates cannot be judged here, and
+    /// neither can the entries for which is_analyzed returns false, e.g. those of a checker that
+    /// did not run.
+    pub fn stale_entries(
+        &self,
+        source_map: &SourceMap,
+        is_analyzed: impl Fn(&Finding) -> bool,
+    ) -> Vec<Finding> {
+        let crate_files: HashSet<String> = source_map
+            .files()
+            .iter()
+            .map(|file| file.name.prefer_local().to_string())
+            .collect();
+        self.baseline
+            .iter()
+            .enumerate()
+            .filter(|(index, entry)| {
+                !self.matched.contains(index)
+                    && (!Path::new(&entry.file).exists() || crate_files.contains(&entry.file))
+                    && is_analyzed(entry)
+            })
+            .map(|(_, entry)| entry.clone())
+            .collect()
+    }
+}
+
+/// Removes the given stale entries from the baseline file at baseline_path.
+/// The crates of a workspace are analyzed by separate processes that may prune the same baseline,
+/// so the file is re-read under a lock and only the given entries are removed from it.
+pub fn prune_baseline(baseline_path: &str, stale_entries: &[Finding]) -> Result<(), String> {
+    let lock_file = File::create(format!("{baseline_path}.lock"))
+        .map_err(|e| format!("could not lock the baseline {baseline_path}: {e}"))?;
+    // Use the fs2 methods explicitly, newer toolchains have inherent File locking methods
+    FileExt::lock_exclusive(&lock_file)
+        .map_err(|e| format!("could not lock the baseline {baseline_path}: {e}"))?;
+    let result = Suppressions::load(Some(baseline_path)).and_then(|suppressions| {
+        let baseline: Vec<&Finding> = suppressions
+            .baseline
+            .iter()
+            .filter(|entry| !stale_entries.contains(entry))
+            .collect();
+        let baseline_json = serde_json::to_string_pretty(&baseline)
+            .map_err(|e| format!("could not serialize the baseline {baseline_path}: {e}"))?;
+        fs::write(baseline_path, baseline_json)
+            .map_err(|e| format!("could not write the baseline {baseline_path}: {e}"))
+    });
+    FileExt::unlock(&lock_file)
+        .map_err(|e| format!("could not unlock the baseline {baseline_path}: {e}"))?;
+    result
+}
+
+/// Replaces the entries of the given crate in the baseline file at baseline_path with its findings,
+/// creating the file if it does not exist yet.
+/// Like prune_baseline, the file is re-read under a lock, since the crates of a workspace may all
+/// write to the same baseline.
+pub fn write_baseline(
+    baseline_path: &str,
+    crate_name: &str,
+    findings: &[Finding],
+) -> Result<(), String> {
+    let lock_file = File::create(format!("{baseline_path}.lock"))
+        .map_err(|e| format!("could not lock the baseline {baseline_path}: {e}"))?;
+    // Use the fs2 methods explicitly, newer toolchains have inherent File locking methods
+    FileExt::lock_exclusive(&lock_file)
+        .map_err(|e| format!("could not lock the baseline {baseline_path}: {e}"))?;
+    let existing_path = Some(baseline_path).filter(|path| Path::new(path).exists());
+    let result = Suppressions::load(existing_path).and_then(|suppressions| {
+        let mut baseline: Vec<&Finding> = suppressions
+            .baseline
+            .iter()
+            .filter(|entry| entry.crate_name != crate_name)
+            .chain(findings)
+            .collect();
+        baseline.sort();
+        let baseline_json = serde_json::to_string_pretty(&baseline)
+            .map_err(|e| format!("could not serialize the baseline {baseline_path}: {e}"))?;
+        fs::write(baseline_path, baseline_json)
+            .map_err(|e| format!("could not write the baseline {baseline_path}: {e}"))
+    });
+    FileExt::unlock(&lock_file)
+        .map_err(|e| format!("could not unlock the baseline {baseline_path}: {e}"))?;
+    result
+}
+
+/// Returns true if the baseline entry is for the finding. Entries with a fingerprint match the
+/// findings of the same crate and checker with the same fingerprint, wherever they are in the file.
+/// Entries without one, e.g. those written before findings were fingerprinted or by hand, match the
+/// findings of the same checker at the same file and line.
+fn is_baseline_match(entry: &Finding, finding: &Finding) -> bool {
+    if entry.checker_name != finding.checker_name {
+        return false;
+    }
+    if entry.fingerprint.is_empty() || finding.fingerprint.is_empty() {
+        entry.file == finding.file && entry.line == finding.line
+    } else {
+        entry.crate_name == finding.crate_name && entry.fingerprint == finding.fingerprint
+    }
+}
+
+/// Returns true if the item defining def_id, or one of the items it is nested in, has an
+/// `#[allow(checker_name)]` attribute. Since rustc does not know the checkers, such an attribute
+/// is best written as `#[allow(unknown_lints, checker_name)]`.
+fn is_allowed_by_attribute(tcx: TyCtxt<'_>, def_id: DefId, checker_name: &str) -> bool {
+    let mut current = Some(def_id);
+    while let Some(def_id) = current {
+        for attr in tcx.get_attrs(def_id, sym::allow) {
+            if let Some(items) = attr.meta_item_list() {
+                if items.iter().any(|item| {
+                    item.ident()
+                        .is_some_and(|i| allows_checker(i.name.as_str(), checker_name))
+                }) {
+                    return true;
+                }
+            }
+        }
+        current = tcx.opt_parent(def_id);
+    }
+    false
+}
+
+/// Returns true if the line of the span, or the line above it, contains an allow comment
+/// that names the checker.
+fn is_allowed_by_comment(source_map: &SourceMap, span: Span, checker_name: &str) -> bool {
+    let location = source_map.lookup_char_pos(span.source_callsite().lo());
+    // Lines are numbered from 1, but get_line takes an index starting from 0
+    let line_index = location.line - 1;
+    let first_line_index = line_index.saturating_sub(1);
+    (first_line_index..=line_index).any(|index| {
+        location
+            .file
+            .get_line(index)
+            .is_some_and(|line| allow_comment_includes(&line, checker_name))
+    })
+}
+
+/// Returns true if the item defining def_id, or one of the items it is nested in, is preceded by an
+/// allow comment that names the checker. The comment may be separated from the signature of the
+/// item by other comments and by attributes, or be at the end of the line of the signature.
+fn is_allowed_by_function_comment(
+    tcx: TyCtxt<'_>,
+    source_map: &SourceMap,
+    def_id: DefId,
+    checker_name: &str,
+) -> bool {
+    let mut current = Some(def_id);
+    while let Some(def_id) = current {
+        let location = source_map.lookup_char_pos(tcx.def_span(def_id).source_callsite().lo());
+        // Lines are numbered from 1, but get_line takes an index starting from 0
+        let mut line_index = location.line - 1;
+        while let Some(line) = location.file.get_line(line_index) {
+            if allow_comment_includes(&line, checker_name) {
+                return true;
+            }
+            let is_signature = line_index == location.line - 1;
+            let trimmed = line.trim_start();
+            if line_index == 0
+                || !(is_signature || trimmed.starts_with("//") || trimmed.starts_with("#["))
+            {
+                break;
+            }
+            line_index -= 1;
+        }
+        current = tcx.opt_parent(def_id);
+    }
+    false
+}
+
+/// Returns true if a checker name of an allow comment or attribute, or `all`, names the checker.
+fn allows_checker(name: &str, checker_name: &str) -> bool {
+    name == checker_name || name == ALLOW_ALL
+}
+
+/// Returns true if the line has an allow comment whose list of checker names includes checker_name.
+fn allow_comment_includes(line: &str, checker_name: &str) -> bool {
+    let Some(start) = line.find(ALLOW_COMMENT_PREFIX) else {
+        return false;
+    };
+    let names = &line[start + ALLOW_COMMENT_PREFIX.len()..];
+    let Some(end) = names.find(')') else {
+        return false;
+    };
+    names[..end]
+        .split(',')
+        .any(|name| allows_checker(name.trim(), checker_name))
+}
@@ -0,0 +1,200 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// Tracks the values of a function body that originate from data that the caller controls, i.e.
+// the instruction data and the data of the accounts passed to the instruction, so that a checker
+// can tell whether a value that reaches a sensitive operation, such as the amount of an update or
+// the program id of an invocation, may have been chosen by an attacker.
+
+use std::collections::HashSet;
+
+use rustc_middle::mir;
+use rustc_middle::ty::{Ty, TyCtxt, TyKind, UintTy};
+use rustc_span::source_map::Spanned;
+
+// The data that a tainted value originates from
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TaintSource {
+    // The bytes of the instruction, e.g. ``instruction_data[0]``
+    InstructionData,
+    // The data of an account, e.g. ``account.try_borrow_data()?``
+    AccountData,
+}
+
+// The locals of a function body that hold tainted values
+#[derive(Default)]
+pub struct TaintAnalysis {
+    // The locals that hold values read from the instruction data
+    instruction_data: HashSet<mir::Local>,
+    // The locals that hold values read from the data of an account
+    account_data: HashSet<mir::Local>,
+}
+
+impl TaintAnalysis {
+    /// Seeds the taint on the arguments of the body that are byte slices, such as the instruction
+    /// data, and on the reads of the data field of an AccountInfo, then propagates it through the
+    /// assignments, casts, operations and calls of the body until no other local becomes tainted.
+    pub fn new<'tcx>(tcx: TyCtxt<'tcx>, mir: &mir::Body<'tcx>) -> TaintAnalysis {
+        let mut analysis = TaintAnalysis::default();
+        for local in mir.args_iter() {
+            if Self::is_byte_slice_ref(mir.local_decls[local].ty) {
+                analysis.instruction_data.insert(local);
+            }
+        }
+        // Every pass but the last taints another local, so the passes are bounded by the locals
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for block in mir.basic_blocks.iter() {
+                for statement in &block.statements {
+                    if let mir::StatementKind::Assign(box (place, rvalue)) = &statement.kind {
+                        changed |= analysis.propagate_assign(tcx, mir, place, rvalue);
+                    }
+                }
+                if let mir::TerminatorKind::Call {
+                    func,
+                    args,
+                    destination,
+                    ..
+                } = &block.terminator().kind
+                {
+                    changed |= analysis.propagate_call(tcx, mir, func, args, destination);
+                }
+            }
+        }
+        analysis
+    }
+
+    /// Returns true if the value of the place may originate from the instruction data or from the
+    /// data of an account. A place is tainted if its local is, e.g. ``(*_2)[0]`` of the
+    /// instruction data ``_2``.
+    pub fn is_tainted(&self, place: mir::Place<'_>) -> bool {
+        self.instruction_data.contains(&place.local) || self.account_data.contains(&place.local)
+    }
+
+    /// Returns true if the value of the place may originate from the given data.
+    pub fn is_tainted_by(&self, place: mir::Place<'_>, source: TaintSource) -> bool {
+        match source {
+            TaintSource::InstructionData => self.instruction_data.contains(&place.local),
+            TaintSource::AccountData => self.account_data.contains(&place.local),
+        }
+    }
+
+    /// Taints the assigned local with the sources of the operands of the rvalue. An assignment to
+    /// a field or an element of a local, e.g. ``_5.0 = move _3``, taints the whole local.
+    fn propagate_assign<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        mir: &mir::Body<'tcx>,
+        place: &mir::Place<'tcx>,
+        rvalue: &mir::Rvalue<'tcx>,
+    ) -> bool {
+        let read_places: Vec<mir::Place<'tcx>> = match rvalue {
+            mir::Rvalue::Use(operand)
+            | mir::Rvalue::Cast(_, operand, _)
+            | mir::Rvalue::UnaryOp(_, operand)
+            | mir::Rvalue::Repeat(operand, _) => operand.place().into_iter().collect(),
+            mir::Rvalue::BinaryOp(_, box (left_operand, right_operand)) => {
+                [left_operand, right_operand]
+                    .iter()
+                    .filter_map(|operand| operand.place())
+                    .collect()
+            }
+            mir::Rvalue::Aggregate(_, operands) => operands
+                .iter()
+                .filter_map(|operand| operand.place())
+                .collect(),
+            mir::Rvalue::Ref(_, _, read_place) | mir::Rvalue::CopyForDeref(read_place) => {
+                vec![*read_place]
+            }
+            _ => return false,
+        };
+        let mut changed = false;
+        for read_place in read_places {
+            changed |= self.taint_from(place.local, read_place.local);
+            if Self::reads_account_data(tcx, mir, &read_place) {
+                changed |= self.account_data.insert(place.local);
+            }
+        }
+        changed
+    }
+
+    /// Taints the destination of the call with the sources of its arguments, e.g. the amount that
+    /// ``u64::from_le_bytes`` makes of the instruction data, and with the data of an account if
+    /// the call borrows it. The bump that ``find_program_address`` returns is canonical, whatever
+    /// the seeds that it is given, so its result is not tainted by them.
+    fn propagate_call<'tcx>(
+        &mut self,
+        tcx: TyCtxt<'tcx>,
+        mir: &mir::Body<'tcx>,
+        func: &mir::Operand<'tcx>,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        destination: &mir::Place<'tcx>,
+    ) -> bool {
+        let callee_name = func
+            .const_fn_def()
+            .map(|(def_id, _)| tcx.def_path_str(def_id))
+            .unwrap_or_default();
+        if callee_name.ends_with("find_program_address") {
+            return false;
+        }
+        let mut changed = false;
+        if callee_name.contains("try_borrow_data") || callee_name.contains("try_borrow_mut_data") {
+            changed |= self.account_data.insert(destination.local);
+        }
+        for arg in args {
+            if let Some(arg_place) = arg.node.place() {
+                changed |= self.taint_from(destination.local, arg_place.local);
+                if Self::reads_account_data(tcx, mir, &arg_place) {
+                    changed |= self.account_data.insert(destination.local);
+                }
+            }
+        }
+        changed
+    }
+
+    /// Taints the local with the sources of the source local. Returns true if it was not already
+    /// tainted by all of them.
+    fn taint_from(&mut self, local: mir::Local, source: mir::Local) -> bool {
+        let mut changed = false;
+        if self.instruction_data.contains(&source) {
+            changed |= self.instruction_data.insert(local);
+        }
+        if self.account_data.contains(&source) {
+            changed |= self.account_data.insert(local);
+        }
+        changed
+    }
+
+    /// Returns true if the place selects the data field of an AccountInfo, e.g.
+    /// ``(*account).data``, which holds the data of the account behind a RefCell.
+    fn reads_account_data<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        mir: &mir::Body<'tcx>,
+        place: &mir::Place<'tcx>,
+    ) -> bool {
+        place.iter_projections().any(|(base, elem)| {
+            let mir::ProjectionElem::Field(field_index, _) = elem else {
+                return false;
+            };
+            let TyKind::Adt(def, _) = base.ty(&mir.local_decls, tcx).ty.kind() else {
+                return false;
+            };
+            def.is_struct()
+                && tcx.def_path_str(def.did()).ends_with("AccountInfo")
+                && def.non_enum_variant().fields[field_index].name.as_str() == "data"
+        })
+    }
+
+    /// Returns true if the type is a reference to a slice of bytes, such as the instruction data.
+    fn is_byte_slice_ref(ty: Ty<'_>) -> bool {
+        if let TyKind::Ref(_, ty, _) = ty.kind() {
+            if let TyKind::Slice(elem_ty) = ty.kind() {
+                return matches!(elem_ty.kind(), TyKind::Uint(UintTy::U8));
+            }
+        }
+        false
+    }
+}
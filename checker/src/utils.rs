@@ -4,6 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
 use std::rc::Rc;
 
 use log::debug;
@@ -19,6 +21,7 @@ use rustc_middle::ty::print::{FmtPrinter, Printer};
 use rustc_middle::ty::{
     FloatTy, GenericArgKind, GenericArgsRef, IntTy, Ty, TyCtxt, TyKind, UintTy,
 };
+use rustc_span::sym;
 
 /// Returns the location of the rust system binaries that are associated with this build of Mirai.
 /// The location is obtained by looking at the contents of the environmental variables that were
@@ -45,6 +48,59 @@ pub fn find_sysroot() -> String {
     }
 }
 
+/// Returns the absolute paths of the files that `git status --porcelain -z` reports as modified,
+/// added, renamed or untracked in the working tree that contains the current directory.
+/// Returns None if git is not available or the current directory is not in a work tree.
+#[logfn_inputs(TRACE)]
+pub fn changed_files_from_git_status() -> Option<Vec<PathBuf>> {
+    let toplevel = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let toplevel = PathBuf::from(String::from_utf8_lossy(&toplevel.stdout).trim());
+    let status = Command::new("git")
+        .args(["status", "--porcelain", "-z", "--untracked-files=all"])
+        .current_dir(&toplevel)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    Some(
+        parse_git_status_porcelain(&String::from_utf8_lossy(&status.stdout))
+            .into_iter()
+            .map(|path| toplevel.join(path))
+            .collect(),
+    )
+}
+
+/// Extracts the paths, relative to the root of the work tree, of the entries in the output of
+/// `git status --porcelain -z` that still exist in the work tree. I.e. deleted files are omitted
+/// and renamed or copied files are represented by their new name. The entries are separated by
+/// NUL and paths are not quoted, and every rename or copy is followed by an extra field with the
+/// original path, which is skipped. The status has a column for the index and one for the work
+/// tree, and a file is deleted if the work tree column says so, or if the index column does and
+/// the file is not in the work tree again.
+pub fn parse_git_status_porcelain(output: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut fields = output.split('\0');
+    while let Some(entry) = fields.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+        let (status, path) = entry.split_at(3);
+        let mut columns = status.chars();
+        let (index, work_tree) = (columns.next(), columns.next());
+        if matches!(index, Some('R') | Some('C')) {
+            fields.next();
+        }
+        if work_tree == Some('D') || (index == Some('D') && work_tree == Some(' ')) {
+            continue;
+        }
+        paths.push(path.to_string());
+    }
+    paths
+}
+
 /// Returns true if the function identified by def_id is a has a parameter that is, or contains,
 /// a function pointer or closure or generator that can be called by the function.
 /// The function body is not analyzed to determine that such parameters are actually called
@@ -425,6 +481,34 @@ pub fn is_foreign_contract(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
     }
 }
 
+/// Returns true if def_id is the function that the `entrypoint!` macro of solana_program declares,
+/// i.e. the unmangled function named entrypoint, which calls the handler given to the macro.
+pub fn is_solana_entrypoint(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    tcx.opt_item_name(def_id)
+        .is_some_and(|name| name.as_str() == "entrypoint")
+        && tcx.has_attr(def_id, sym::no_mangle)
+}
+
+/// Returns true if the item defining def_id, or one of the items it is nested in, has a
+/// `#[cfg(test)]` attribute, e.g. a function of a `tests` module.
+pub fn is_test_code(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    let mut current = Some(def_id);
+    while let Some(def_id) = current {
+        for attr in tcx.get_attrs(def_id, sym::cfg) {
+            if let Some(items) = attr.meta_item_list() {
+                if items
+                    .iter()
+                    .any(|item| item.is_word() && item.has_name(sym::test))
+                {
+                    return true;
+                }
+            }
+        }
+        current = tcx.opt_parent(def_id);
+    }
+    false
+}
+
 #[logfn_inputs(TRACE)]
 fn push_component_name(component_data: DefPathData, target: &mut String) {
     use DefPathData::*;
@@ -0,0 +1,54 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// Discovers the contract crates among the members of a workspace, so that "cargo mirai" can be
+// run at the root of a workspace that also has crates that are not contracts, such as the checker
+// itself, and analyze only the contracts.
+
+use std::path::{Path, PathBuf};
+
+use cargo_metadata::{Metadata, Package};
+
+/// The directory, relative to the root of the workspace, that the contract crates are found in
+/// if no other directory is given with --contracts-dir.
+pub const DEFAULT_CONTRACTS_DIR: &str = "contracts";
+
+/// Returns the directory that holds the contract crates of the workspace. A relative contracts_dir
+/// is taken to be relative to the root of the workspace, and the default directory is used if
+/// contracts_dir is not given.
+pub fn contracts_dir(metadata: &Metadata, contracts_dir: Option<&str>) -> PathBuf {
+    let workspace_root = metadata.workspace_root.as_std_path();
+    workspace_root.join(contracts_dir.unwrap_or(DEFAULT_CONTRACTS_DIR))
+}
+
+/// Returns the members of the workspace whose manifests are found below contracts_dir, ordered by
+/// the path of their manifests. A member that is listed more than once in the members of the root
+/// Cargo.toml is only returned once.
+/// If contracts_dir does not exist, the workspace is not laid out as a collection of contracts and
+/// all of its members are returned.
+pub fn contract_members<'a>(metadata: &'a Metadata, contracts_dir: &Path) -> Vec<&'a Package> {
+    let contracts_dir = contracts_dir
+        .canonicalize()
+        .unwrap_or_else(|_| contracts_dir.to_path_buf());
+    let analyze_all = !contracts_dir.is_dir();
+    let mut members: Vec<&Package> = metadata
+        .workspace_packages()
+        .into_iter()
+        .filter(|package| {
+            analyze_all || is_below(package.manifest_path.as_std_path(), &contracts_dir)
+        })
+        .collect();
+    members.sort_by(|x, y| x.manifest_path.cmp(&y.manifest_path));
+    members.dedup_by(|x, y| x.id == y.id);
+    members
+}
+
+/// Returns true if the manifest at manifest_path is in dir or one of its subdirectories.
+fn is_below(manifest_path: &Path, dir: &Path) -> bool {
+    manifest_path
+        .canonicalize()
+        .unwrap_or_else(|_| manifest_path.to_path_buf())
+        .starts_with(dir)
+}
@@ -0,0 +1,21 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that analyzing a file again with --analysis-cache only analyzes the functions whose MIR
+// has changed. fee_edited.rs is this file after an edit of flat_fee, which leaves the other
+// functions where they are, so rounded_fee is reported with its cached finding.
+
+pub fn main() {}
+
+pub fn rounded_fee(amount: f64, fee_rate: f64) -> u64 {
+    (amount * fee_rate).round() as u64
+}
+
+pub fn flat_fee(amount: u64) -> u64 {
+    amount.saturating_sub(100)
+}
+
+//~ possible numerical precision error for the smart contract
@@ -0,0 +1,17 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that the findings of a baseline written by --write-baseline are still suppressed after
+// the lines of the findings have moved. The test driver writes the baseline, then analyzes a copy
+// of this file with lines inserted above the functions and checks that no finding is new.
+
+pub fn rounded_fee(fee: f64) -> f64 {
+    fee.round()
+}
+
+pub fn rounded_share(amount: f64, count: f64) -> f64 {
+    (amount / count).round()
+}
@@ -0,0 +1,52 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A synthetic body with thousands of statements, for timing the walk that feeds the checkers of
+// the Checker trait, e.g. with ``--only self_transfer``. The macros expand to 512 transfers
+// between two distinct accounts, each of which takes a handful of statements, inside a loop
+// that the abstract interpretation visits until it reaches a fixed point. The integration tests
+// run it with start_driver_benchmark, which prints how long its analysis takes.
+
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+}
+
+pub struct Instruction {
+    pub lamports: u64,
+}
+
+pub mod system_instruction {
+    use crate::{Instruction, Pubkey};
+
+    pub fn transfer(_from: &Pubkey, _to: &Pubkey, lamports: u64) -> Instruction {
+        Instruction { lamports }
+    }
+}
+
+macro_rules! repeat_8 {
+    ($($body:tt)*) => {
+        $($body)* $($body)* $($body)* $($body)* $($body)* $($body)* $($body)* $($body)*
+    };
+}
+
+pub fn transfer_all(from: &AccountInfo, to: &AccountInfo, amounts: &[u64]) -> u64 {
+    let mut total = 0;
+    for amount in amounts {
+        repeat_8! {
+            repeat_8! {
+                repeat_8! {
+                    let instruction = system_instruction::transfer(&from.key, &to.key, *amount);
+                    total += instruction.lamports % 7;
+                }
+            }
+        }
+    }
+    total
+}
+
+pub fn main() {}
@@ -0,0 +1,104 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// Call graph with a call through a vtable, which has a static edge to the trait
+// method and a virtual edge to each of its two implementations.
+
+pub trait Shape {
+    fn area(self: Box<Self>) -> u32;
+}
+
+pub struct Circle {}
+
+impl Shape for Circle {
+    fn area(self: Box<Self>) -> u32 {
+        1
+    }
+}
+
+pub struct Square {}
+
+impl Shape for Square {
+    fn area(self: Box<Self>) -> u32 {
+        2
+    }
+}
+
+pub fn total_area(shape: Box<dyn Shape>) -> u32 {
+    shape.area()
+}
+
+pub fn main() {}
+
+/* CONFIG
+{
+    "reductions": [],
+    "included_crates": [],
+    "datalog_config": {
+        "datalog_backend": "DifferentialDatalog"
+    }
+}
+*/
+
+/* EXPECTED:DOT
+digraph {
+    0 [ label = "\"dyn_trait::{impl#0}::area\"" ]
+    1 [ label = "\"dyn_trait::{impl#1}::area\"" ]
+    2 [ label = "\"dyn_trait::total_area\"" ]
+    3 [ label = "\"dyn_trait::main\"" ]
+    4 [ label = "\"dyn_trait::Shape::area\"" ]
+    2 -> 4 [ ]
+    2 -> 0 [ ]
+    2 -> 1 [ ]
+}
+*/
+
+/* EXPECTED:DDLOG
+start;
+insert Edge(0,2,0);
+insert Edge(1,2,1);
+insert Edge(2,2,4);
+insert EdgeType(0,1);
+insert EdgeType(1,1);
+insert EdgeType(2,0);
+commit;
+*/
+
+/* EXPECTED:TYPEMAP
+{
+  "0": "std::boxed::Box<dyn Shape>",
+  "1": "virtual std::boxed::Box<dyn Shape>"
+}
+*/
+
+/* EXPECTED:CALL_SITES{
+  "files": [
+    "tests/call_graph/dyn_trait.rs"
+  ],
+  "callables": [
+    {
+      "name": "/dyn_trait/total_area(std::boxed::Box<dyn [Binder { value: Trait(Shape), bound_vars: [] }] + 'static, std::alloc::Global>)->u32",
+      "file_index": 0,
+      "first_line": 30,
+      "local": true
+    },
+    {
+      "name": "/dyn_trait/Shape::area(std::boxed::Box<Self/#0, std::alloc::Global>)->u32",
+      "file_index": 0,
+      "first_line": 11,
+      "local": false
+    }
+  ],
+  "calls": [
+    [
+      0,
+      31,
+      5,
+      0,
+      1
+    ]
+  ]
+}*/
@@ -56,12 +56,14 @@ digraph {
 */
 
 /* EXPECTED:SOUFFLE
-2,30,0,1
+2,3
+0,0,1
 1,0,1
 2,1,2
 3,1,3
 4,3,4
-5,4,30,0
+5,4,3
+0,0
 1,1
 2,0
 3,0
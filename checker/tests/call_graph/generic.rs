@@ -33,8 +33,8 @@ pub fn main() {
 /* EXPECTED:DOT
 digraph {
     0 [ label = "\"generic::main\"" ]
-    1 [ label = "\"generic::{impl#0}::bar\"" ]
-    2 [ label = "\"generic::{impl#0}::foo\"" ]
+    1 [ label = "\"generic::{impl#0}::bar::<u8>\"" ]
+    2 [ label = "\"generic::{impl#0}::foo::<i8, &str>\"" ]
     0 -> 1 [ ]
     0 -> 1 [ ]
     0 -> 2 [ ]
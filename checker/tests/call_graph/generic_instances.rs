@@ -0,0 +1,89 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// Call graph with a generic function that is called with two type arguments,
+// which has a node, an edge and an edge type for each of its instances.
+
+fn id<T>(x: T) -> T {
+    x
+}
+pub fn main() {
+    let _ = id(1u32);
+    let _ = id(true);
+}
+
+/* CONFIG
+{
+    "reductions": [],
+    "included_crates": [],
+    "datalog_config": {
+        "datalog_backend": "DifferentialDatalog"
+    }
+}
+*/
+
+/* EXPECTED:DOT
+digraph {
+    0 [ label = "\"generic_instances::main\"" ]
+    1 [ label = "\"generic_instances::id::<u32>\"" ]
+    2 [ label = "\"generic_instances::id::<bool>\"" ]
+    0 -> 1 [ ]
+    0 -> 2 [ ]
+}
+*/
+
+/* EXPECTED:DDLOG
+start;
+insert Dom(1,2);
+insert Edge(0,0,1);
+insert Edge(1,0,2);
+insert EdgeType(0,0);
+insert EdgeType(1,1);
+commit;
+*/
+
+/* EXPECTED:TYPEMAP
+{
+  "0": "u32",
+  "1": "bool"
+}
+*/
+
+/* EXPECTED:CALL_SITES{
+  "files": [
+    "tests/call_graph/generic_instances.rs"
+  ],
+  "callables": [
+    {
+      "name": "/generic_instances/main()->()",
+      "file_index": 0,
+      "first_line": 13,
+      "local": true
+    },
+    {
+      "name": "/generic_instances/id(T/#0)->T/#0",
+      "file_index": 0,
+      "first_line": 10,
+      "local": true
+    }
+  ],
+  "calls": [
+    [
+      0,
+      14,
+      13,
+      0,
+      1
+    ],
+    [
+      0,
+      15,
+      13,
+      0,
+      1
+    ]
+  ]
+}*/
@@ -32,10 +32,10 @@ pub fn main() {
 
 /* EXPECTED:DOT
 digraph {
-    0 [ label = "\"static::main\"" ]
+    0 [ label = "\"static::main\"" shape = box ]
     1 [ label = "\"static::fn1\"" ]
     2 [ label = "\"static::fn2\"" ]
-    3 [ label = "\"static::fn3\"" ]
+    3 [ label = "\"static::fn3\"" peripheries = 2 ]
     0 -> 1 [ ]
     1 -> 2 [ ]
     2 -> 3 [ ]
@@ -53,6 +53,17 @@ insert EdgeType(2,0);
 commit;
 */
 
+/* EXPECTED:ENTRY_POINTS
+{
+  "entry_points": [
+    "static::main"
+  ],
+  "leaves": [
+    "static::fn3"
+  ]
+}
+*/
+
 /* EXPECTED:TYPEMAP
 {
   "0": "u32"
@@ -0,0 +1,162 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// Call graph with static calls, single type, dominance of a branch, no loops.
+// Output with the DifferentialDatalog backend of the config, and then with the Soufflé
+// datalog backend, which must give the same Dom, Edge and EdgeType relations.
+
+fn scale(x: u32) -> u32 {
+    x / 2
+}
+fn check(x: u32) -> bool {
+    x > 10
+}
+fn clamp(x: u32) -> u32 {
+    if x > 100 {
+        100
+    } else {
+        x
+    }
+}
+fn route(x: u32) -> u32 {
+    let y = scale(x);
+    if check(y) {
+        clamp(y)
+    } else {
+        y
+    }
+}
+pub fn main() {
+    route(1);
+}
+
+/* CONFIG
+{
+    "reductions": [],
+    "included_crates": [],
+    "datalog_config": {
+        "datalog_backend": "DifferentialDatalog"
+    }
+}
+*/
+
+/* EXPECTED:DOT
+digraph {
+    0 [ label = "\"static_dom_branch::main\"" ]
+    1 [ label = "\"static_dom_branch::route\"" ]
+    2 [ label = "\"static_dom_branch::scale\"" ]
+    3 [ label = "\"static_dom_branch::check\"" ]
+    4 [ label = "\"static_dom_branch::clamp\"" ]
+    0 -> 1 [ ]
+    1 -> 2 [ ]
+    1 -> 3 [ ]
+    1 -> 4 [ ]
+}
+*/
+
+/* EXPECTED:DDLOG
+start;
+insert Dom(2,3);
+insert Dom(2,4);
+insert Dom(3,4);
+insert Edge(0,0,1);
+insert Edge(1,1,2);
+insert Edge(2,1,3);
+insert Edge(3,1,4);
+insert EdgeType(0,0);
+insert EdgeType(1,0);
+insert EdgeType(2,0);
+insert EdgeType(3,0);
+commit;
+*/
+
+/* EXPECTED:SOUFFLE
+2,3
+2,4
+3,4
+0,0,1
+1,1,2
+2,1,3
+3,1,4
+0,0
+1,0
+2,0
+3,0
+*/
+
+/* EXPECTED:TYPEMAP
+{
+  "0": "u32"
+}
+*/
+
+/* EXPECTED:CALL_SITES{
+  "files": [
+    "tests/call_graph/static_dom_branch.rs"
+  ],
+  "callables": [
+    {
+      "name": "/static_dom_branch/route(u32)->u32",
+      "file_index": 0,
+      "first_line": 24,
+      "local": true
+    },
+    {
+      "name": "/static_dom_branch/scale(u32)->u32",
+      "file_index": 0,
+      "first_line": 11,
+      "local": true
+    },
+    {
+      "name": "/static_dom_branch/check(u32)->bool",
+      "file_index": 0,
+      "first_line": 14,
+      "local": true
+    },
+    {
+      "name": "/static_dom_branch/clamp(u32)->u32",
+      "file_index": 0,
+      "first_line": 17,
+      "local": true
+    },
+    {
+      "name": "/static_dom_branch/main()->()",
+      "file_index": 0,
+      "first_line": 32,
+      "local": true
+    }
+  ],
+  "calls": [
+    [
+      0,
+      25,
+      13,
+      0,
+      1
+    ],
+    [
+      0,
+      26,
+      8,
+      0,
+      2
+    ],
+    [
+      0,
+      27,
+      9,
+      0,
+      3
+    ],
+    [
+      0,
+      33,
+      5,
+      4,
+      0
+    ]
+  ]
+}*/
@@ -0,0 +1,113 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// Linear call graph rooted at a configured entry point instead of main.
+// The public helper and main are pruned, since process_instruction calls neither.
+
+fn fn1(x: u32) -> u32 {
+    fn2(x)
+}
+fn fn2(x: u32) -> u32 {
+    x
+}
+pub fn process_instruction(x: u32) -> u32 {
+    fn1(x)
+}
+pub fn helper(x: u32) -> u32 {
+    fn2(x)
+}
+pub fn main() {}
+
+/* CONFIG
+{
+    "reductions": ["PruneUnreachable", "Fold"],
+    "included_crates": ["static_entry_point"],
+    "datalog_config": {
+        "datalog_backend": "DifferentialDatalog",
+        "entry_points": ["process_instruction"]
+    }
+}
+*/
+
+/* EXPECTED:DOT
+digraph {
+    0 [ label = "\"static_entry_point::process_instruction\"" ]
+    1 [ label = "\"static_entry_point::fn1\"" ]
+    2 [ label = "\"static_entry_point::fn2\"" ]
+    0 -> 1 [ ]
+    1 -> 2 [ ]
+}
+*/
+
+/* EXPECTED:DDLOG
+start;
+insert Edge(0,0,1);
+insert Edge(1,1,2);
+insert EdgeType(0,0);
+insert EdgeType(1,0);
+commit;
+*/
+
+/* EXPECTED:TYPEMAP
+{
+  "0": "u32"
+}
+*/
+
+/* EXPECTED:CALL_SITES{
+  "files": [
+    "tests/call_graph/static_entry_point.rs"
+  ],
+  "callables": [
+    {
+      "name": "/static_entry_point/fn1(u32)->u32",
+      "file_index": 0,
+      "first_line": 10,
+      "local": true
+    },
+    {
+      "name": "/static_entry_point/fn2(u32)->u32",
+      "file_index": 0,
+      "first_line": 13,
+      "local": true
+    },
+    {
+      "name": "/static_entry_point/process_instruction(u32)->u32",
+      "file_index": 0,
+      "first_line": 16,
+      "local": true
+    },
+    {
+      "name": "/static_entry_point/helper(u32)->u32",
+      "file_index": 0,
+      "first_line": 19,
+      "local": true
+    }
+  ],
+  "calls": [
+    [
+      0,
+      11,
+      5,
+      0,
+      1
+    ],
+    [
+      0,
+      17,
+      5,
+      2,
+      0
+    ],
+    [
+      0,
+      20,
+      5,
+      3,
+      1
+    ]
+  ]
+}*/
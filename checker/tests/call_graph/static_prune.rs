@@ -0,0 +1,132 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// Linear call graph with single type, no dominance, no loops.
+// The public helper fn4 is analyzed, but pruned since main does not call it.
+
+fn fn1(x: u32) -> u32 {
+    fn2(x)
+}
+fn fn2(x: u32) -> u32 {
+    fn3(x)
+}
+fn fn3(x: u32) -> u32 {
+    x
+}
+pub fn main() {
+    let x = 1;
+    fn1(x);
+}
+pub fn fn4(x: u32) -> u32 {
+    fn3(x)
+}
+
+/* CONFIG
+{
+    "reductions": ["PruneUnreachable", "Fold"],
+    "included_crates": ["static_prune"],
+    "datalog_config": {
+        "datalog_backend": "DifferentialDatalog"
+    }
+}
+*/
+
+/* EXPECTED:DOT
+digraph {
+    0 [ label = "\"static_prune::main\"" ]
+    1 [ label = "\"static_prune::fn1\"" ]
+    2 [ label = "\"static_prune::fn2\"" ]
+    3 [ label = "\"static_prune::fn3\"" ]
+    0 -> 1 [ ]
+    1 -> 2 [ ]
+    2 -> 3 [ ]
+}
+*/
+
+/* EXPECTED:DDLOG
+start;
+insert Edge(0,0,1);
+insert Edge(1,1,2);
+insert Edge(2,2,3);
+insert EdgeType(0,0);
+insert EdgeType(1,0);
+insert EdgeType(2,0);
+commit;
+*/
+
+/* EXPECTED:TYPEMAP
+{
+  "0": "u32"
+}
+*/
+
+/* EXPECTED:CALL_SITES{
+  "files": [
+    "tests/call_graph/static_prune.rs"
+  ],
+  "callables": [
+    {
+      "name": "/static_prune/fn1(u32)->u32",
+      "file_index": 0,
+      "first_line": 10,
+      "local": true
+    },
+    {
+      "name": "/static_prune/fn2(u32)->u32",
+      "file_index": 0,
+      "first_line": 13,
+      "local": true
+    },
+    {
+      "name": "/static_prune/fn3(u32)->u32",
+      "file_index": 0,
+      "first_line": 16,
+      "local": true
+    },
+    {
+      "name": "/static_prune/main()->()",
+      "file_index": 0,
+      "first_line": 19,
+      "local": true
+    },
+    {
+      "name": "/static_prune/fn4(u32)->u32",
+      "file_index": 0,
+      "first_line": 23,
+      "local": true
+    }
+  ],
+  "calls": [
+    [
+      0,
+      11,
+      5,
+      0,
+      1
+    ],
+    [
+      0,
+      14,
+      5,
+      1,
+      2
+    ],
+    [
+      0,
+      21,
+      5,
+      3,
+      0
+    ],
+    [
+      0,
+      24,
+      5,
+      4,
+      2
+    ]
+  ]
+}*/
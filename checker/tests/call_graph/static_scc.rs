@@ -0,0 +1,104 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// Call graph with mutually recursive static calls that are collapsed into a single node.
+
+fn fn1(x: u32) -> u32 {
+    if x > 1 {
+        fn2(x - 1)
+    } else {
+        x
+    }
+}
+fn fn2(x: u32) -> u32 {
+    fn1(x)
+}
+pub fn main() {
+    let x = 3;
+    fn1(x);
+}
+
+/* CONFIG
+{
+    "reductions": ["CollapseSccs"],
+    "included_crates": [],
+    "datalog_config": {
+        "datalog_backend": "DifferentialDatalog"
+    }
+}
+*/
+
+/* EXPECTED:DOT
+digraph {
+    0 [ label = "\"static_scc::main\"" ]
+    1 [ label = "\"static_scc::fn1, static_scc::fn2\"" ]
+    0 -> 1 [ ]
+}
+*/
+
+/* EXPECTED:DDLOG
+start;
+insert Edge(0,0,1);
+insert EdgeType(0,0);
+insert SccMember(1,1);
+insert SccMember(1,2);
+commit;
+*/
+
+/* EXPECTED:TYPEMAP
+{
+  "0": "u32"
+}
+*/
+
+/* EXPECTED:CALL_SITES{
+  "files": [
+    "tests/call_graph/static_scc.rs"
+  ],
+  "callables": [
+    {
+      "name": "/static_scc/fn1(u32)->u32",
+      "file_index": 0,
+      "first_line": 9,
+      "local": true
+    },
+    {
+      "name": "/static_scc/fn2(u32)->u32",
+      "file_index": 0,
+      "first_line": 16,
+      "local": true
+    },
+    {
+      "name": "/static_scc/main()->()",
+      "file_index": 0,
+      "first_line": 19,
+      "local": true
+    }
+  ],
+  "calls": [
+    [
+      0,
+      11,
+      9,
+      0,
+      1
+    ],
+    [
+      0,
+      17,
+      5,
+      1,
+      0
+    ],
+    [
+      0,
+      21,
+      5,
+      2,
+      0
+    ]
+  ]
+}*/
@@ -0,0 +1,15 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that restricts analysis to the files that git reports as changed.
+// The test driver mocks the changed file list so that it only contains this file,
+// which means that the precision error in the unchanged module must not be reported.
+
+pub mod unchanged;
+
+pub fn rounded_share(amount: f64) -> f64 {
+    amount.round() //~ possible numerical precision error for the smart contract
+}
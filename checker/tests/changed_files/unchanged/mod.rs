@@ -0,0 +1,11 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// Not in the changed file list, so nothing in here should be analyzed.
+
+pub fn rounded_fee(fee: f64) -> f64 {
+    fee.round()
+}
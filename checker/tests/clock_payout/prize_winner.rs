@@ -0,0 +1,64 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that paying a prize to the player picked by the slot of the clock is a high finding
+// that refers to the read of the clock, while reading the clock only to check a deadline is not
+
+use std::cell::{RefCell, RefMut};
+
+pub mod sysvar {
+    pub struct Clock {
+        pub slot: u64,
+    }
+
+    impl Clock {
+        pub fn get() -> Option<Clock> {
+            Some(Clock { slot: 0 })
+        }
+    }
+}
+
+use sysvar::Clock;
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn pay_random_winner(players: &[AccountInfo], prize: u64) -> Result<(), ProgramError> {
+    let clock = Clock::get().ok_or(ProgramError)?;
+    if players.is_empty() {
+        return Err(ProgramError);
+    }
+    let index = (clock.slot % players.len() as u64) as usize;
+    let winner = &players[index];
+    let mut lamports = winner.try_borrow_mut_lamports()?; //~ possible payout decided by the clock for the smart contract
+    *lamports = lamports.checked_add(prize).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn pay_first_before_deadline(
+    players: &[AccountInfo],
+    prize: u64,
+    deadline: u64,
+) -> Result<(), ProgramError> {
+    let clock = Clock::get().ok_or(ProgramError)?;
+    if clock.slot > deadline || players.is_empty() {
+        return Err(ProgramError);
+    }
+    let mut lamports = players[0].try_borrow_mut_lamports()?;
+    *lamports = lamports.checked_add(prize).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn main() {}
@@ -0,0 +1,190 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A withdraw that debits the contract account without a signer check and stores the balance after
+// the transfer, whose findings the tests of --fail-on and of the outputs of the findings share
+
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+
+pub type Pubkey = [u8; 32];
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        match self.lamports.try_borrow_mut() {
+            Ok(lamports) => Ok(lamports),
+            Err(_) => Err(ProgramError),
+        }
+    }
+}
+
+pub fn withdraw(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if user_account.key == contract_account.key {
+        return Err(ProgramError);
+    }
+    let balance = balances.get_mut(&user_account.key).ok_or(ProgramError)?;
+    if *balance < amount {
+        return Err(ProgramError);
+    }
+    let mut contract_lamports = contract_account.try_borrow_mut_lamports()?;
+    *contract_lamports = contract_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+    *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+    *balance -= amount;
+    Ok(())
+}
+
+pub fn process_instruction(
+    balances: &mut HashMap<Pubkey, u64>,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let [user_account, contract_account] = accounts else {
+        return Err(ProgramError);
+    };
+    withdraw(balances, amount, user_account, contract_account)
+}
+
+pub fn entrypoint(
+    balances: &mut HashMap<Pubkey, u64>,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> u64 {
+    let Some(amount) = input.first() else {
+        return 1;
+    };
+    match process_instruction(balances, accounts, *amount as u64) {
+        Ok(()) => 0,
+        Err(_) => 1,
+    }
+}
+
+pub fn main() {}
+
+// --callgraph-with-findings writes the functions of the crate and the calls between them, with the
+// withdraw that hosts the critical reentrancy filled in red
+/* EXPECTED:DOT
+digraph {
+    0 [ label = "\"reentrant_withdraw::{impl#0}::try_borrow_mut_lamports\"" ]
+    1 [ label = "\"reentrant_withdraw::withdraw\"" style = filled fillcolor = red tooltip = "reentrancy, missing_signer" ]
+    2 [ label = "\"reentrant_withdraw::process_instruction\"" ]
+    3 [ label = "\"reentrant_withdraw::entrypoint\"" shape = box ]
+    4 [ label = "\"reentrant_withdraw::main\"" shape = box ]
+    1 -> 0 [ ]
+    2 -> 1 [ ]
+    3 -> 2 [ ]
+}
+*/
+
+// --sarif writes a result of the rule of each finding, at the line of the finding, and with the
+// transfer that the store of the balance follows as the related location of the reentrancy
+/* EXPECTED:SARIF
+reentrancy error 48 1
+missing_signer error 44 0
+*/
+
+// The reentrancy refers to the last transfer that the store of the balance follows, and records
+// the load of the balance, that transfer and the store as its evidence
+/* EXPECTED:RELATED
+reentrancy 48 related 46 evidence 40 46 48
+*/
+
+// The findings are listed here rather than marked on their lines, which the HTML report shows
+/* EXPECTED:FINDINGS
+[
+    (missing_signer, 44, high),
+    (reentrancy, 48, critical),
+]
+*/
+
+// --html shows each finding with the source lines around it, the span of the finding highlighted
+// and its related locations
+/* EXPECTED:HTML
+<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>contract-analysis findings</title>
+<style>
+body { font-family: sans-serif; margin: 2em; }
+pre { background: #f6f8fa; padding: 0.5em; overflow-x: auto; }
+mark { background: #ffd7d5; }
+.line-number { color: #6a737d; user-select: none; }
+.critical, .high { color: #b31d28; font-weight: bold; }
+.medium { color: #b08800; font-weight: bold; }
+.low, .info { color: #586069; }
+</style>
+</head>
+<body>
+<h1>contract-analysis findings</h1>
+<p>2 findings in 1 file</p>
+<nav id="index">
+<ul>
+<li><a href="#file-1">reentrant_withdraw.rs</a> (2)</li>
+</ul>
+</nav>
+<section id="file-1">
+<h2>reentrant_withdraw.rs</h2>
+<article id="finding-1">
+<h3><a href="#finding-1">missing_signer</a>: possible mutation of lamports without a signer check of the account for the smart contract</h3>
+<p><span class="high">high severity</span>, medium confidence, crate reentrant_withdraw at line 44, column 33</p>
+<pre>
+<span class="line-number">   42</span>         return Err(ProgramError);
+<span class="line-number">   43</span>     }
+<span class="line-number">   44</span>     let mut contract_lamports = <mark>contract_account.try_borrow_mut_lamports()</mark>?;
+<span class="line-number">   45</span>     *contract_lamports = contract_lamports.checked_sub(amount).ok_or(ProgramError)?;
+<span class="line-number">   46</span>     let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+</pre>
+<p><a href="#index">index</a> | <a href="#finding-2">next</a></p>
+</article>
+<article id="finding-2">
+<h3><a href="#finding-2">reentrancy</a>: possible reentrancy for the smart contract</h3>
+<p><span class="critical">critical severity</span>, high confidence, crate reentrant_withdraw at line 48, column 5</p>
+<pre>
+<span class="line-number">   46</span>     let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+<span class="line-number">   47</span>     *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+<span class="line-number">   48</span>     <mark>*balance -= amount;</mark>
+<span class="line-number">   49</span> <mark>    Ok(())</mark>
+<span class="line-number">   50</span> <mark>}</mark>
+</pre>
+<ul>
+<li>related: the lamports are transferred here at line 46, column 29</li>
+</ul>
+<p><a href="#finding-1">previous</a> | <a href="#index">index</a></p>
+</article>
+</section>
+</body>
+</html>
+*/
+
+// --format text with --output writes each finding as a line of the form
+// `file:line:col: [severity] checker: message`
+/* EXPECTED:TEXT
+reentrant_withdraw.rs:44:33: [high] missing_signer: possible mutation of lamports without a signer check of the account for the smart contract
+reentrant_withdraw.rs:48:5: [critical] reentrancy: possible reentrancy for the smart contract
+*/
+
+// The critical reentrancy fails the analysis at any --fail-on threshold, and so does the high
+// missing signer below critical, unless --rule-severity demotes them
+/* EXPECTED:FAIL_ON
+--fail-on high: fail
+--fail-on critical: fail
+--rule-severity reentrancy=high --fail-on critical: pass
+--rule-severity reentrancy=info --fail-on high: fail
+--rule-severity reentrancy=info missing_signer=info --fail-on high: pass
+*/
@@ -0,0 +1,21 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that a low finding only fails the analysis with a --fail-on threshold of low,
+// or with a higher threshold once its severity is raised with --rule-severity
+
+pub fn rounded_fee(amount: f64, fee_rate: f64) -> u64 {
+    (amount * fee_rate).round() as u64 //~ possible numerical precision error for the smart contract
+}
+
+pub fn main() {}
+
+/* EXPECTED:FAIL_ON
+--fail-on low: fail
+--fail-on medium: pass
+--fail-on critical: pass
+--rule-severity numerical_precision=high --fail-on medium: fail
+*/
@@ -0,0 +1,68 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that a lottery which reads the clock once is flagged both for closing the draw by the
+// slot and for picking the winner by the remainder of the timestamp, and that its prize is flagged
+// both for rounding a float and for multiplying a quotient. The time manipulation and the bad
+// randomness checkers each follow the clock through the same function, and the numerical precision
+// checker reports the rounding and the multiplication under their two rules.
+
+pub struct ProgramError;
+
+pub mod sysvar {
+    use super::ProgramError;
+
+    pub struct Clock {
+        pub slot: u64,
+        pub unix_timestamp: i64,
+    }
+
+    impl Clock {
+        pub fn get() -> Result<Clock, ProgramError> {
+            Ok(Clock {
+                slot: 0,
+                unix_timestamp: 0,
+            })
+        }
+    }
+}
+
+use sysvar::Clock;
+
+pub fn draw(tickets: &[u64], closing_slot: u64) -> Result<u64, ProgramError> {
+    let clock = Clock::get()?;
+    if clock.slot <= closing_slot {
+        return Err(ProgramError);
+    }
+    let count = tickets.len() as u64;
+    if count == 0 {
+        return Err(ProgramError);
+    }
+    let winner = clock.unix_timestamp as u64 % count;
+    tickets.get(winner as usize).copied().ok_or(ProgramError)
+}
+
+pub fn prize_share(pot: u64, winners: u64) -> u64 {
+    if winners == 0 {
+        return 0;
+    }
+    pot / winners * 100
+}
+
+pub fn prize_bonus(share: u64, bonus_rate: f64) -> u64 {
+    (share as f64 * bonus_rate).round() as u64
+}
+
+pub fn main() {}
+
+/* EXPECTED:FINDINGS
+[
+    (time_manipulation, 37, medium),
+    (bad_randomness, 44, medium),
+    (division_before_multiplication, 52, medium),
+    (numerical_precision, 56, low)
+]
+*/
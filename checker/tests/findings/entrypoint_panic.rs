@@ -0,0 +1,74 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that unwrapping in the handler registered with entrypoint! is flagged, while unwrapping
+// in a helper that the handler calls is not. The handler is modeled after process_instruction of
+// contracts/overflow/contract_one, and the macro after the entrypoint! of solana_program, which
+// declares an unmangled function named entrypoint that passes the input to the handler.
+
+pub struct Pubkey(pub [u8; 32]);
+
+pub enum ProgramError {
+    MissingRequiredSignature,
+    InvalidInstructionData,
+}
+
+pub struct AccountInfo {
+    pub is_signer: bool,
+}
+
+macro_rules! entrypoint {
+    ($process_instruction:ident) => {
+        /// # Safety
+        /// The input has to point to the given number of bytes of instruction data.
+        #[no_mangle]
+        pub unsafe extern "C" fn entrypoint(input: *const u8, length: usize) -> u64 {
+            let instruction_data = unsafe { std::slice::from_raw_parts(input, length) };
+            let program_id = Pubkey([0; 32]);
+            let accounts: Vec<AccountInfo> = Vec::new();
+            match $process_instruction(&program_id, &accounts, instruction_data) {
+                Ok(()) => 0,
+                Err(_) => 1,
+            }
+        }
+    };
+}
+
+entrypoint!(process_instruction);
+
+const DEPOSIT_LIMIT: &str = "1000000000";
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let user_account = accounts.first().ok_or(ProgramError::InvalidInstructionData)?;
+    if !user_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if instruction_data.len() < 9 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let amount = u64::from_le_bytes(instruction_data[1..9].try_into().unwrap());
+    deposit(amount)
+}
+
+pub fn deposit(amount: u64) -> Result<(), ProgramError> {
+    let limit: u64 = DEPOSIT_LIMIT.parse().unwrap();
+    if amount > limit {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    Ok(())
+}
+
+pub fn main() {}
+
+/* EXPECTED:FINDINGS
+[
+    (entrypoint_panic, 56, high),
+]
+*/
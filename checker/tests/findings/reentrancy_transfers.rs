@@ -0,0 +1,102 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that with --reentrancy-transfers cpi, storing the balance after moving lamports between
+// the accounts of the contract is not flagged, while storing it after invoking another program is.
+// The unsigned debit of the contract in withdraw is reported as well
+
+// MIRAI_FLAGS --reentrancy-transfers cpi
+
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub const TOKEN_PROGRAM_ID: Pubkey = Pubkey([6; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub mod program {
+    use super::{AccountInfo, ProgramError, Pubkey};
+
+    pub struct Instruction {
+        pub program_id: Pubkey,
+        pub data: Vec<u8>,
+    }
+
+    pub fn invoke(
+        instruction: &Instruction,
+        _accounts: &[&AccountInfo],
+    ) -> Result<(), ProgramError> {
+        if instruction.data.is_empty() {
+            return Err(ProgramError);
+        }
+        Ok(())
+    }
+
+    pub fn transfer(token_program_id: &Pubkey, amount: u64) -> Instruction {
+        Instruction {
+            program_id: *token_program_id,
+            data: amount.to_le_bytes().to_vec(),
+        }
+    }
+}
+
+pub fn withdraw(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if user_account.key == contract_account.key {
+        return Err(ProgramError);
+    }
+    let balance = balances.get_mut(&user_account.key).ok_or(ProgramError)?;
+    if *balance < amount {
+        return Err(ProgramError);
+    }
+    let mut contract_lamports = contract_account.try_borrow_mut_lamports()?;
+    *contract_lamports = contract_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+    *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+    *balance -= amount;
+    Ok(())
+}
+
+pub fn withdraw_tokens(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    let balance = balances.get_mut(&user_account.key).ok_or(ProgramError)?;
+    if *balance < amount {
+        return Err(ProgramError);
+    }
+    program::invoke(&program::transfer(&TOKEN_PROGRAM_ID, amount), &[user_account])?;
+    *balance -= amount;
+    Ok(())
+}
+
+pub fn main() {}
+
+/* EXPECTED:FINDINGS
+[
+    (missing_signer, 73, high),
+    (reentrancy, 91, critical),
+]
+*/
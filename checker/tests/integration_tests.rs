@@ -23,11 +23,12 @@ extern crate rustc_driver;
 extern crate rustc_session;
 extern crate tempfile;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::fs::read_to_string;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Instant;
 
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
@@ -39,7 +40,9 @@ use walkdir::WalkDir;
 
 use mirai::call_graph::{CallGraphConfig, CallGraphReduction, DatalogBackend, DatalogConfig};
 use mirai::callbacks;
+use mirai::contract_errors::CHECKER_NAMES;
 use mirai::options::{DiagLevel, Options};
+use mirai::report::{self, Confidence, Finding, SarifReport, Severity, WorkspaceReport};
 use mirai::utils;
 use mirai_annotations::{assume, unrecoverable};
 
@@ -54,10 +57,7 @@ fn run_pass() {
         ),
         ("contracts", find_extern_library("contracts")),
     ];
-    let mut run_pass_path = PathBuf::from_str("tests/run-pass").unwrap();
-    if !run_pass_path.exists() {
-        run_pass_path = PathBuf::from_str("checker/tests/run-pass").unwrap();
-    }
+    let run_pass_path = tests_path("run-pass");
     let files = run_directory(run_pass_path);
     let result = invoke_driver_on_files(
         files,
@@ -65,22 +65,319 @@ fn run_pass() {
         &(start_driver as fn(DriverConfig) -> usize),
     );
     assert_eq!(result, 0);
-    run_call_graph_tests();
+    run_tests_in("call_graph", start_driver_call_graph);
+    run_souffle_parity_tests();
+    run_ddlog_determinism_tests();
+    run_tests_in("changed_files", start_driver_changed_files);
+    run_report_tests();
+    run_tests_in("suppression", start_driver_suppression);
+    run_tests_in("baseline", start_driver_baseline);
+    run_tests_in("fail_on", start_driver_fail_on);
+    run_watch_tests();
+    run_analysis_cache_tests();
+    run_tests_in("reach_path", start_driver_reach_path);
+    run_tests_in("reentrancy_guard", start_driver_reentrancy_guard);
+    run_config_check_tests();
+    run_test_file(REENTRANT_WITHDRAW, start_driver_related_locations);
+    run_tests_in("rules_from", start_driver_rules_from);
+    run_test_file(REENTRANT_WITHDRAW, start_driver_annotated);
+    run_tests_in("clock_payout", start_driver_clock_payout);
+    run_test_file(REENTRANT_WITHDRAW, start_driver_sarif);
+    run_test_file(REENTRANT_WITHDRAW, start_driver_html);
+    run_test_file(REENTRANT_WITHDRAW, start_driver_callgraph_findings);
+    run_test_file(REENTRANT_WITHDRAW, start_driver_output_format);
+    run_tests_in("findings", start_driver_findings);
+    run_test_file(REENTRANT_WITHDRAW, start_driver_findings);
+    run_tests_in("only_checkers", start_driver_only_checkers);
+    run_test_file("benchmarks/many_transfers.rs", start_driver_benchmark);
 }
 
-// Run the tests in the tests/call_graph directory.
-fn run_call_graph_tests() {
-    let mut call_graph_tests_path = PathBuf::from_str("tests/call_graph").unwrap();
-    if !call_graph_tests_path.exists() {
-        call_graph_tests_path = PathBuf::from_str("checker/tests/call_graph").unwrap();
-    }
-    let files = run_directory(call_graph_tests_path);
+// Run the tests of the tests/call_graph directory that expect both a DDLOG and a SOUFFLE output
+// once more, with the Soufflé backend, checking that both backends output the same relations.
+fn run_souffle_parity_tests() {
+    let files = run_directory(tests_path("call_graph"))
+        .into_iter()
+        .filter(|(file_name, _)| {
+            let test_case_data = fs::read_to_string(file_name).expect("Failed to read test case");
+            test_case_data.contains("/* EXPECTED:DDLOG")
+                && test_case_data.contains("/* EXPECTED:SOUFFLE")
+        })
+        .collect::<Vec<_>>();
+    assert!(!files.is_empty(), "no call graph test expects both datalog outputs");
+    let result = invoke_driver_on_files(
+        files,
+        Vec::<(&str, String)>::new(),
+        &(start_driver_call_graph_souffle as fn(DriverConfig) -> usize),
+    );
+    assert_eq!(result, 0);
+}
+
+// Run the static_fold test of the tests/call_graph directory twice, checking that the DDLOG output
+// of the second run is identical to that of the first.
+fn run_ddlog_determinism_tests() {
+    let static_fold_path = tests_path("call_graph/static_fold.rs");
+    let temp_dir = TempDir::new().expect("failed to create a temp dir");
+    let files = ["first", "second"]
+        .iter()
+        .map(|run| {
+            let output_dir_path = temp_dir.path().join(run).join("static_fold.rs");
+            fs::create_dir_all(&output_dir_path).expect("failed to create test output dir");
+            (
+                static_fold_path.to_str().unwrap().to_string(),
+                output_dir_path.to_str().unwrap().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let output_paths = files
+        .iter()
+        .map(|(_, output_dir_path)| format!("{output_dir_path}/graph.dat"))
+        .collect::<Vec<_>>();
     let result = invoke_driver_on_files(
         files,
         Vec::<(&str, String)>::new(),
         &(start_driver_call_graph as fn(DriverConfig) -> usize),
     );
     assert_eq!(result, 0);
+    let outputs = output_paths
+        .iter()
+        .map(|path| fs::read(path).expect("Failed to read the DDLOG output"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        outputs[0], outputs[1],
+        "the DDLOG output differs between runs"
+    );
+}
+
+// Run the tests in the tests/report directory. Each file is treated as a member crate of the same
+// workspace, so they all report into one directory and the combined report is checked afterwards.
+fn run_report_tests() {
+    let report_tests_path = tests_path("report");
+    let files = run_directory(report_tests_path);
+    let member_two_file = files
+        .iter()
+        .map(|(file_name, _)| file_name.clone())
+        .find(|file_name| file_name.ends_with("member_two.rs"))
+        .expect("Failed to find member_two.rs");
+    let report_dir = TempDir::new().expect("failed to create a temp dir");
+    let report_dir_path = report_dir.path().to_str().unwrap().to_string();
+    let result = files
+        .into_iter()
+        .fold(0, |acc, (file_name, temp_dir_path)| {
+            acc + start_driver_report(
+                DriverConfig {
+                    file_name,
+                    temp_dir_path,
+                    extern_deps: vec![],
+                },
+                &report_dir_path,
+                "--emit jsonl",
+            )
+        });
+    assert_eq!(result, 0);
+
+    let report_json = fs::read_to_string(report_dir.path().join("report.json"))
+        .expect("Failed to read the JSON report");
+    let report: WorkspaceReport =
+        serde_json::from_str(&report_json).expect("Failed to deserialize the JSON report");
+    let crate_names: Vec<&str> = report
+        .crates
+        .iter()
+        .map(|c| c.crate_name.as_str())
+        .collect();
+    assert_eq!(crate_names, vec!["member_one", "member_two"]);
+    for crate_report in report.crates.iter() {
+        assert_eq!(crate_report.summary.total, crate_report.findings.len());
+        assert!(crate_report
+            .findings
+            .iter()
+            .all(|f| f.crate_name == crate_report.crate_name));
+    }
+    assert_eq!(report.crates[0].summary.total, 2);
+    assert_eq!(report.crates[1].summary.total, 1);
+    assert_eq!(report.total.total, 3);
+    assert_eq!(report.total.by_checker["numerical_precision"], 2);
+    assert_eq!(report.total.by_checker["saturating_arithmetic"], 1);
+
+    let report_text = fs::read_to_string(report_dir.path().join("report.txt"))
+        .expect("Failed to read the text report");
+    assert!(report_text.contains(
+        "crate member_one: 2 findings (numerical_precision: 1, saturating_arithmetic: 1)"
+    ));
+    assert!(report_text.contains("crate member_two: 1 finding (numerical_precision: 1)"));
+    assert!(report_text.contains(
+        "total: 3 findings in 2 crates (numerical_precision: 2, saturating_arithmetic: 1)"
+    ));
+    // The default compact style renders each finding as a single line
+    let compact_line = format!(
+        "numerical_precision {member_two_file}:10:5 low possible numerical precision error for the smart contract"
+    );
+    assert!(report_text.lines().any(|line| line == compact_line));
+
+    // The JSON lines have a finding on every line, whose byte offsets are at its line and column
+    let findings_jsonl = fs::read_to_string(report_dir.path().join("findings.jsonl"))
+        .expect("Failed to read the JSON lines");
+    let findings: Vec<Finding> = findings_jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Failed to deserialize a JSON line"))
+        .collect();
+    assert_eq!(findings.len(), 3);
+    for finding in &findings {
+        let source = fs::read_to_string(&finding.file).expect("Failed to read a test case");
+        let start = finding.span_start as usize;
+        assert!(start <= finding.span_end as usize && finding.span_end as usize <= source.len());
+        let line_start = source[..start].rfind('\n').map_or(0, |index| index + 1);
+        assert_eq!(source[..start].matches('\n').count() + 1, finding.line);
+        assert_eq!(start - line_start + 1, finding.column);
+        // The end is located like the start, also for a finding that spans several lines
+        let end = finding.span_end as usize;
+        let end_line_start = source[..end].rfind('\n').map_or(0, |index| index + 1);
+        assert_eq!(source[..end].matches('\n').count() + 1, finding.end_line);
+        assert_eq!(end - end_line_start + 1, finding.end_column);
+    }
+
+    // With a documentation template, every finding links to the documentation of its checker
+    let doc_report_dir = TempDir::new().expect("failed to create a temp dir");
+    let doc_report_dir_path = doc_report_dir.path().to_str().unwrap().to_string();
+    let doc_temp_dir = TempDir::new().expect("failed to create a temp dir");
+    let result = start_driver_report(
+        DriverConfig {
+            file_name: member_two_file,
+            temp_dir_path: doc_temp_dir.path().to_str().unwrap().to_string(),
+            extern_deps: vec![],
+        },
+        &doc_report_dir_path,
+        "--rule-doc-url https://wiki/rules/{rule_id}",
+    );
+    assert_eq!(result, 0);
+    let doc_report_json = fs::read_to_string(doc_report_dir.path().join("report.json"))
+        .expect("Failed to read the JSON report");
+    assert!(doc_report_json.contains(r#""helpUri": "https://wiki/rules/numerical_precision""#));
+    let doc_report: WorkspaceReport =
+        serde_json::from_str(&doc_report_json).expect("Failed to deserialize the JSON report");
+    assert!(doc_report.crates[0]
+        .findings
+        .iter()
+        .all(|f| f.help_uri == format!("https://wiki/rules/{}", f.checker_name)));
+
+    // The saturating arithmetic of member_one is more confident than its rounding, so it is listed
+    // first
+    let member_one_findings: Vec<(&str, Confidence)> = report.crates[0]
+        .findings
+        .iter()
+        .map(|f| (f.checker_name.as_str(), f.confidence))
+        .collect();
+    assert_eq!(
+        member_one_findings,
+        vec![
+            ("saturating_arithmetic", Confidence::Medium),
+            ("numerical_precision", Confidence::Low)
+        ]
+    );
+}
+
+// Run the tests in the tests/config_check directory, which check a call graph config with a
+// misspelled key and one with an unknown datalog backend.
+fn run_config_check_tests() {
+    let config_check_path = tests_path("config_check");
+    let error = CallGraphConfig::check_file(&config_check_path.join("misspelled_key.json"))
+        .expect_err("Failed to reject the misspelled config");
+    assert!(
+        error.contains("unknown field `dot_ouput_path`, expected one of"),
+        "{error}"
+    );
+    assert!(error.contains("`dot_output_path`"), "{error}");
+    let error = CallGraphConfig::check_file(&config_check_path.join("unknown_backend.json"))
+        .expect_err("Failed to reject the unknown backend");
+    assert!(error.contains("unknown variant `Soufle`"), "{error}");
+}
+
+// Run the test in the tests/watch directory, which simulates an edit of a watched file
+// by analyzing fee.rs and then fee_edited.rs at the same path.
+fn run_watch_tests() {
+    let watch_tests_path = tests_path("watch");
+    let temp_dir = TempDir::new().expect("failed to create a temp dir");
+    let temp_dir_path = temp_dir.path().to_str().unwrap().to_string();
+    let watched_file = temp_dir.path().join("fee.rs");
+    let watched_file_name = watched_file.to_str().unwrap().to_string();
+    let early_error_handler = EarlyDiagCtxt::new(config::ErrorOutputType::default());
+    let analyze = |previous_findings: Option<Vec<Finding>>| {
+        let mut options = build_options(&early_error_handler);
+        options.watch = true;
+        self::invoke_driver_again(
+            &early_error_handler,
+            watched_file_name.clone(),
+            temp_dir_path.clone(),
+            utils::find_sysroot(),
+            vec![],
+            options,
+            previous_findings,
+        )
+    };
+
+    fs::copy(watch_tests_path.join("fee.rs"), &watched_file).expect("Failed to copy fee.rs");
+    let (result, call_backs) = analyze(None);
+    assert_eq!(result, 0);
+    let call_backs = call_backs.unwrap();
+    assert_eq!(call_backs.findings().len(), 1);
+    assert!(call_backs.finding_delta().is_none());
+    assert_eq!(call_backs.source_files(), [watched_file.clone()]);
+
+    fs::copy(watch_tests_path.join("fee_edited.rs"), &watched_file)
+        .expect("Failed to copy fee_edited.rs");
+    let (result, edited_call_backs) = analyze(Some(call_backs.findings().to_vec()));
+    assert_eq!(result, 0);
+    let edited_call_backs = edited_call_backs.unwrap();
+    assert_eq!(edited_call_backs.findings().len(), 2);
+    // The finding in rounded_fee moved down, but only the one in rounded_tip is new
+    let delta = edited_call_backs.finding_delta().unwrap();
+    assert!(delta.resolved.is_empty());
+    assert_eq!(delta.new.len(), 1);
+    assert_eq!(delta.new[0].checker_name, "numerical_precision");
+    assert!(delta.new[0].snippet.contains("tip_rate"));
+}
+
+// Run the test in the tests/analysis_cache directory, which analyzes fee.rs and then fee_edited.rs
+// at the same path with the same analysis cache.
+fn run_analysis_cache_tests() {
+    let analysis_cache_tests_path = tests_path("analysis_cache");
+    let temp_dir = TempDir::new().expect("failed to create a temp dir");
+    let temp_dir_path = temp_dir.path().to_str().unwrap().to_string();
+    let analyzed_file = temp_dir.path().join("fee.rs");
+    let analyzed_file_name = analyzed_file.to_str().unwrap().to_string();
+    let cache_path = temp_dir.path().join("analysis_cache.json");
+    let early_error_handler = EarlyDiagCtxt::new(config::ErrorOutputType::default());
+    let analyze = || {
+        let mut options = build_options(&early_error_handler);
+        options.analysis_cache = Some(cache_path.to_str().unwrap().to_string());
+        self::invoke_driver_again(
+            &early_error_handler,
+            analyzed_file_name.clone(),
+            temp_dir_path.clone(),
+            utils::find_sysroot(),
+            vec![],
+            options,
+            None,
+        )
+    };
+
+    fs::copy(analysis_cache_tests_path.join("fee.rs"), &analyzed_file)
+        .expect("Failed to copy fee.rs");
+    let (result, call_backs) = analyze();
+    assert_eq!(result, 0);
+    assert_eq!(call_backs.unwrap().analyzed_functions().len(), 3);
+    assert!(cache_path.exists());
+
+    fs::copy(
+        analysis_cache_tests_path.join("fee_edited.rs"),
+        &analyzed_file,
+    )
+    .expect("Failed to copy fee_edited.rs");
+    let (result, edited_call_backs) = analyze();
+    assert_eq!(result, 0);
+    // Only flat_fee changed, the finding of rounded_fee is still expected from the cache
+    let analyzed_functions = edited_call_backs.unwrap().analyzed_functions().to_vec();
+    assert_eq!(analyzed_functions.len(), 1);
+    assert!(analyzed_functions[0].ends_with("flat_fee"));
 }
 
 fn find_extern_library(base_name: &str) -> String {
@@ -122,6 +419,38 @@ fn find_extern_library(base_name: &str) -> String {
     unreachable!("could not find the `{}` library", base_name);
 }
 
+// Resolves a path relative to the tests directory, whether the tests run from the checker
+// directory or from the root of the repository.
+fn tests_path(relative_path: &str) -> PathBuf {
+    let path = Path::new("tests").join(relative_path);
+    if path.exists() {
+        path
+    } else {
+        Path::new("checker/tests").join(relative_path)
+    }
+}
+
+// The test case whose findings the tests of --fail-on and of the outputs of the findings share
+const REENTRANT_WITHDRAW: &str = "fail_on/reentrant_withdraw.rs";
+
+// Run the test case in the given file of the tests directory with the given driver.
+fn run_test_file(file: &str, driver: fn(DriverConfig) -> usize) {
+    let temp_dir = TempDir::new().expect("failed to create a temp dir");
+    let result = driver(DriverConfig {
+        file_name: tests_path(file).to_str().unwrap().to_string(),
+        temp_dir_path: temp_dir.path().to_str().unwrap().to_string(),
+        extern_deps: vec![],
+    });
+    assert_eq!(result, 0);
+}
+
+// Run the tests in the given directory of the tests directory with the given driver.
+fn run_tests_in(directory: &str, driver: fn(DriverConfig) -> usize) {
+    let files = run_directory(tests_path(directory));
+    let result = invoke_driver_on_files(files, Vec::<(&str, String)>::new(), &driver);
+    assert_eq!(result, 0);
+}
+
 // Iterates through the files in the directory at the given path and runs each as a separate test
 // case. For each case, a temporary output directory is created. The cases are then iterated in
 // parallel and run via invoke_driver.
@@ -151,8 +480,12 @@ fn run_directory(directory_path: PathBuf) -> Vec<(String, String)> {
 }
 
 fn build_options(early_error_handler: &EarlyDiagCtxt) -> Options {
+    build_options_with_flags(early_error_handler, "")
+}
+
+fn build_options_with_flags(early_error_handler: &EarlyDiagCtxt, flags: &str) -> Options {
     let mut options = Options::default();
-    options.parse_from_str("", early_error_handler, true); // get defaults
+    options.parse_from_str(flags, early_error_handler, true); // get defaults and apply flags
     options.diag_level = DiagLevel::Paranoid; // override default
     options.max_analysis_time_for_body = 20;
     options.max_analysis_time_for_crate = 60;
@@ -165,6 +498,8 @@ fn build_options(early_error_handler: &EarlyDiagCtxt) -> Options {
 struct DatalogTestConfig {
     datalog_backend: DatalogBackend,
     type_relations_path: Option<Box<str>>,
+    #[serde(default)]
+    entry_points: Vec<Box<str>>,
 }
 
 // Partial call graph config to be read from the
@@ -176,8 +511,13 @@ struct CallGraphTestConfig {
     datalog_config: DatalogTestConfig,
 }
 
-// Write a call graph configuration file for the current test case
-fn generate_call_graph_config(file_name: &str, temp_dir_path: &str) -> (CallGraphConfig, String) {
+// Write a call graph configuration file for the current test case. The given datalog backend, if
+// any, is used instead of the one of the test config.
+fn generate_call_graph_config(
+    file_name: &str,
+    temp_dir_path: &str,
+    datalog_backend: Option<DatalogBackend>,
+) -> (CallGraphConfig, String) {
     let test_case_data =
         fs::read_to_string(Path::new(&file_name)).expect("Failed to read test case");
     let config_regex = Regex::new(r"(/\* CONFIG)([\S\s]*?)(\*/)").unwrap();
@@ -188,22 +528,31 @@ fn generate_call_graph_config(file_name: &str, temp_dir_path: &str) -> (CallGrap
         } else {
             unrecoverable!("Could not find a call graph config in test file");
         };
-    let datalog_path = match call_graph_test_config.datalog_config.datalog_backend {
+    let datalog_backend =
+        datalog_backend.unwrap_or(call_graph_test_config.datalog_config.datalog_backend);
+    let datalog_path = match datalog_backend {
         DatalogBackend::DifferentialDatalog => {
             format!("{temp_dir_path}/graph.dat").into_boxed_str()
         }
         DatalogBackend::Souffle => temp_dir_path.to_owned().into_boxed_str(),
     };
+    // Entry points are only output, and annotated in the dot output, for the test cases that
+    // expect them
+    let entry_points_path = test_case_data
+        .contains("/* EXPECTED:ENTRY_POINTS")
+        .then(|| format!("{temp_dir_path}/entry_points.json").into_boxed_str());
     let call_graph_config = CallGraphConfig::new(
         Some(format!("{temp_dir_path}/call_sites.json").into_boxed_str()),
         Some(format!("{temp_dir_path}/graph.dot").into_boxed_str()),
+        entry_points_path,
         call_graph_test_config.reductions,
         call_graph_test_config.included_crates,
         Some(DatalogConfig::new(
             datalog_path,
             format!("{temp_dir_path}/types.json").into_boxed_str(),
             call_graph_test_config.datalog_config.type_relations_path,
-            call_graph_test_config.datalog_config.datalog_backend,
+            datalog_backend,
+            call_graph_test_config.datalog_config.entry_points,
         )),
     );
     let call_graph_config_path = format!("{temp_dir_path}/call_graph_config.json");
@@ -261,8 +610,31 @@ fn invoke_driver(
     temp_dir_path: String,
     sys_root: String,
     extern_deps: Vec<(&str, String)>,
-    mut options: Options,
+    options: Options,
 ) -> usize {
+    self::invoke_driver_again(
+        early_error_handler,
+        file_name,
+        temp_dir_path,
+        sys_root,
+        extern_deps,
+        options,
+        None,
+    )
+    .0
+}
+
+// Invokes the driver like invoke_driver, but compares the findings with those of a previous
+// analysis if they are given, and returns the callbacks along with the result
+fn invoke_driver_again(
+    early_error_handler: &EarlyDiagCtxt,
+    file_name: String,
+    temp_dir_path: String,
+    sys_root: String,
+    extern_deps: Vec<(&str, String)>,
+    mut options: Options,
+    previous_findings: Option<Vec<Finding>>,
+) -> (usize, Option<callbacks::MiraiCallbacks>) {
     let mut rustc_args = vec![]; // any arguments after `--` for rustc
     {
         let file_content = read_to_string(Path::new(&file_name)).unwrap();
@@ -302,15 +674,19 @@ fn invoke_driver(
     }
 
     let mut call_backs = callbacks::MiraiCallbacks::test_runner(options);
+    if let Some(previous_findings) = previous_findings {
+        call_backs = call_backs.with_previous_findings(previous_findings);
+    }
     let result = std::panic::catch_unwind(move || {
         let compiler = rustc_driver::RunCompiler::new(&command_line_arguments, &mut call_backs);
-        compiler.run()
+        let _ = compiler.run();
+        call_backs
     });
     match result {
-        Ok(_) => 0,
+        Ok(call_backs) => (0, Some(call_backs)),
         Err(_) => {
             println!("{file_name} failed");
-            1
+            (1, None)
         }
     }
 }
@@ -342,6 +718,61 @@ fn compare_lines(actual: &str, expected: &str) -> bool {
     actual_counter == expected_counter
 }
 
+// An expected or actual finding, as the checker that reports it, its line and its severity
+type FindingTuple = (String, usize, Severity);
+
+// Parse the findings of an EXPECTED:FINDINGS comment, which lists them as tuples such as
+// `(reentrancy, 46, critical)` within brackets, separated by commas or new lines.
+fn parse_expected_findings(expected: &str) -> Result<Vec<FindingTuple>, String> {
+    let list = expected
+        .trim()
+        .strip_prefix('[')
+        .and_then(|list| list.strip_suffix(']'))
+        .ok_or_else(|| "the expected findings are not within brackets".to_string())?;
+    let tuple_regex = Regex::new(r"\(([^()]*)\)").unwrap();
+    let rest = tuple_regex.replace_all(list, "");
+    if let Some(stray) = rest.split(',').map(str::trim).find(|part| !part.is_empty()) {
+        return Err(format!("{stray} is not a (checker, line, severity) tuple"));
+    }
+    let mut findings = Vec::new();
+    for captures in tuple_regex.captures_iter(list) {
+        let parts: Vec<&str> = captures[1].split(',').map(str::trim).collect();
+        let [checker_name, line, severity] = parts[..] else {
+            return Err(format!("({}) does not have three elements", &captures[1]));
+        };
+        if !CHECKER_NAMES.contains(&checker_name) {
+            return Err(format!("{checker_name} is not the name of a checker"));
+        }
+        let line = line
+            .parse::<usize>()
+            .map_err(|_| format!("{line} is not a line number"))?;
+        let severity = Severity::from_str(severity)?;
+        findings.push((checker_name.to_string(), line, severity));
+    }
+    Ok(findings)
+}
+
+// Compare the actual findings with the expected ones, order-independent, returning the expected
+// findings that were not reported and the reported findings that were not expected.
+fn diff_findings(
+    actual: &[FindingTuple],
+    expected: &[FindingTuple],
+) -> (Vec<FindingTuple>, Vec<FindingTuple>) {
+    let mut unexpected = actual.to_vec();
+    let mut missing = Vec::new();
+    for finding in expected {
+        match unexpected.iter().position(|f| f == finding) {
+            Some(index) => {
+                unexpected.remove(index);
+            }
+            None => missing.push(finding.clone()),
+        }
+    }
+    missing.sort();
+    unexpected.sort();
+    (missing, unexpected)
+}
+
 // Checked call graph output types
 #[derive(Debug, Eq, PartialEq)]
 enum CallGraphOutputType {
@@ -350,15 +781,17 @@ enum CallGraphOutputType {
     Ddlog,
     TypeMap,
     Souffle,
+    EntryPoints,
 }
 
+// The facts files end without a newline, so a newline is pushed after each of them to keep the
+// last tuple of a relation apart from the first tuple of the next one
 fn get_souffle_output(output_path: &Path) -> Result<String, std::io::Error> {
     let mut out = String::new();
-    out.push_str(fs::read_to_string(output_path.join("Dom.facts"))?.as_str());
-    out.push_str(fs::read_to_string(output_path.join("Edge.facts"))?.as_str());
-    out.push_str(fs::read_to_string(output_path.join("EdgeType.facts"))?.as_str());
-    out.push_str(fs::read_to_string(output_path.join("EqType.facts"))?.as_str());
-    out.push_str(fs::read_to_string(output_path.join("Member.facts"))?.as_str());
+    for relation in ["Dom", "Edge", "EdgeType", "EqType", "Member", "SccMember"] {
+        out.push_str(fs::read_to_string(output_path.join(format!("{relation}.facts")))?.as_str());
+        out.push('\n');
+    }
     Ok(out)
 }
 
@@ -384,6 +817,9 @@ fn check_call_graph_output(
         CallGraphOutputType::Souffle => {
             Regex::new(r"(/\* EXPECTED:SOUFFLE)([\S\s]*?)(\*/)").unwrap()
         }
+        CallGraphOutputType::EntryPoints => {
+            Regex::new(r"(/\* EXPECTED:ENTRY_POINTS)([\S\s]*?)(\*/)").unwrap()
+        }
     };
     let expected: String = if let Some(captures) = expected_regex.captures(&test_case_data) {
         assume!(captures.len() == 4);
@@ -405,6 +841,9 @@ fn check_call_graph_output(
         CallGraphOutputType::Souffle => {
             get_souffle_output(Path::new(call_graph_config.get_ddlog_path().unwrap()))
         }
+        CallGraphOutputType::EntryPoints => {
+            fs::read_to_string(call_graph_config.get_entry_points_path().unwrap())
+        }
     };
     if let Ok(actual) = actual {
         if compare_lines(&expected, &actual) {
@@ -428,14 +867,24 @@ fn check_call_graph_output(
 
 // Default test driver
 fn start_driver(config: DriverConfig) -> usize {
+    start_driver_with(config, "", |_| {})
+}
+
+// Runs the test case with the options of the given flags, as changed by set_options. The flags
+// are parsed like those of a MIRAI_FLAGS comment, which the test case can still override.
+fn start_driver_with(
+    config: DriverConfig,
+    flags: &str,
+    set_options: impl FnOnce(&mut Options),
+) -> usize {
     let early_error_handler = EarlyDiagCtxt::new(config::ErrorOutputType::default());
-    let sys_root = utils::find_sysroot();
-    let options = build_options(&early_error_handler);
+    let mut options = build_options_with_flags(&early_error_handler, flags);
+    set_options(&mut options);
     self::invoke_driver(
         &early_error_handler,
         config.file_name,
         config.temp_dir_path,
-        sys_root,
+        utils::find_sysroot(),
         config.extern_deps,
         options,
     )
@@ -444,11 +893,25 @@ fn start_driver(config: DriverConfig) -> usize {
 // Test driver for call graph generation;
 // sets up call graph configuration.
 fn start_driver_call_graph(config: DriverConfig) -> usize {
+    start_driver_call_graph_with_backend(config, None)
+}
+
+// Test driver for call graph generation with the Soufflé datalog backend, whatever the backend of
+// the test config, whose output is checked against the EXPECTED:SOUFFLE block.
+fn start_driver_call_graph_souffle(config: DriverConfig) -> usize {
+    start_driver_call_graph_with_backend(config, Some(DatalogBackend::Souffle))
+}
+
+// Runs the call graph test case with the given datalog backend, or with the one of its config.
+fn start_driver_call_graph_with_backend(
+    config: DriverConfig,
+    datalog_backend: Option<DatalogBackend>,
+) -> usize {
     let early_error_handler = EarlyDiagCtxt::new(config::ErrorOutputType::default());
     let sys_root = utils::find_sysroot();
     let mut options = build_options(&early_error_handler);
     let (call_graph_config, call_graph_config_path) =
-        generate_call_graph_config(&config.file_name, &config.temp_dir_path);
+        generate_call_graph_config(&config.file_name, &config.temp_dir_path, datalog_backend);
     options.call_graph_config = Some(call_graph_config_path);
     let result = self::invoke_driver(
         &early_error_handler,
@@ -482,8 +945,725 @@ fn start_driver_call_graph(config: DriverConfig) -> usize {
                 &call_graph_config,
                 CallGraphOutputType::Souffle,
             ),
+        }) + (if call_graph_config.get_entry_points_path().is_some() {
+            check_call_graph_output(
+                &config.file_name,
+                &call_graph_config,
+                CallGraphOutputType::EntryPoints,
+            )
+        } else {
+            0
         })
     } else {
         result
     }
 }
+
+// Test driver for analyzing only changed files;
+// mocks the list obtained from git status with one that only contains the test file.
+fn start_driver_changed_files(config: DriverConfig) -> usize {
+    let changed_files = vec![config.file_name.clone()];
+    start_driver_with(config, "", |options| {
+        options.only_changed_files = true;
+        options.changed_files = Some(changed_files);
+    })
+}
+
+// Test driver for the reports of the smart contract checkers;
+// reports the findings into the given directory that is shared by all files of the test.
+fn start_driver_report(config: DriverConfig, report_dir: &str, flags: &str) -> usize {
+    start_driver_with(config, flags, |options| {
+        options.report_dir = Some(report_dir.to_string());
+    })
+}
+
+// Reports the findings of the test case, analyzed with the given flags, into a directory of its
+// own, and returns the JSON report, or the result of the driver if the analysis failed.
+fn analyze_into_report(config: DriverConfig, flags: &str) -> Result<WorkspaceReport, usize> {
+    let report_dir = TempDir::new().expect("failed to create a temp dir");
+    let report_dir_path = report_dir.path().to_str().unwrap().to_string();
+    let result = start_driver_report(config, &report_dir_path, flags);
+    if result != 0 {
+        return Err(result);
+    }
+    let report_json = fs::read_to_string(report_dir.path().join("report.json"))
+        .expect("Failed to read the JSON report");
+    Ok(serde_json::from_str(&report_json).expect("Failed to deserialize the JSON report"))
+}
+
+// The findings of the given checker in all crates of the report.
+fn findings_of<'a>(
+    report: &'a WorkspaceReport,
+    checker_name: &'a str,
+) -> impl Iterator<Item = &'a Finding> {
+    report
+        .crates
+        .iter()
+        .flat_map(|c| c.findings.iter())
+        .filter(move |f| f.checker_name == checker_name)
+}
+
+// The text of the EXPECTED:<kind> comment of the test case.
+fn expected_comment(test_case_data: &str, kind: &str) -> String {
+    let expected_regex = Regex::new(&format!(r"(/\* EXPECTED:{kind})([\S\s]*?)(\*/)")).unwrap();
+    match expected_regex.captures(test_case_data) {
+        Some(captures) => captures[2].to_owned(),
+        None => unrecoverable!("Could not find the EXPECTED:{} comment in test file", kind),
+    }
+}
+
+// The line number of the first line of the test case that contains the given text.
+fn line_of(test_case_data: &str, text: &str) -> usize {
+    test_case_data
+        .lines()
+        .position(|l| l.contains(text))
+        .unwrap_or_else(|| panic!("Failed to find {text}"))
+        + 1
+}
+
+// Test driver for suppressed findings;
+// writes a baseline with the finding in rounded_fee and checks that the report shows the
+// suppressed findings along with their suppression sources.
+fn start_driver_suppression(config: DriverConfig) -> usize {
+    let early_error_handler = EarlyDiagCtxt::new(config::ErrorOutputType::default());
+    let sys_root = utils::find_sysroot();
+    let mut options = build_options(&early_error_handler);
+    let test_case_data =
+        fs::read_to_string(Path::new(&config.file_name)).expect("Failed to read test case");
+    let baseline_entry = Finding {
+        crate_name: "suppressed".to_string(),
+        file: config.file_name.clone(),
+        line: line_of(&test_case_data, "fee.round()"),
+        column: 5,
+        end_line: 0,
+        end_column: 0,
+        span_start: 0,
+        span_end: 0,
+        checker_name: "numerical_precision".to_string(),
+        severity: Severity::Low,
+        confidence: Confidence::Low,
+        message: "possible numerical precision error for the smart contract".to_string(),
+        snippet: String::new(),
+        fingerprint: String::new(),
+        help_uri: String::new(),
+        reach_path: Vec::new(),
+        related: Vec::new(),
+        evidence: None,
+    };
+    // An entry for a file that has been removed has drifted from the code
+    let removed_file = format!("{}/removed.rs", config.temp_dir_path);
+    let stale_entry = Finding {
+        file: removed_file.clone(),
+        line: 1,
+        column: 1,
+        ..baseline_entry.clone()
+    };
+    let baseline = vec![baseline_entry.clone(), stale_entry];
+    let baseline_path = format!("{}/baseline.json", config.temp_dir_path);
+    fs::write(
+        &baseline_path,
+        serde_json::to_string(&baseline).expect("Failed to serialize baseline"),
+    )
+    .expect("Failed to write baseline");
+    let report_dir = format!("{}/report", config.temp_dir_path);
+    options.baseline = Some(baseline_path.clone());
+    options.report_dir = Some(report_dir.clone());
+    options.show_suppressed = true;
+    options.prune_baseline = true;
+    // The entries of a checker that does not run cannot be judged, so the baseline is kept
+    let mut only_options = options.clone();
+    only_options.only_checkers = Some(HashSet::from(["reentrancy".to_string()]));
+    let result = self::invoke_driver(
+        &early_error_handler,
+        config.file_name.clone(),
+        config.temp_dir_path.clone(),
+        sys_root.clone(),
+        config.extern_deps.clone(),
+        only_options,
+    );
+    if result != 0 {
+        return result;
+    }
+    let kept_baseline: Vec<Finding> = serde_json::from_str(
+        &fs::read_to_string(&baseline_path).expect("Failed to read the kept baseline"),
+    )
+    .expect("Failed to deserialize the kept baseline");
+    if kept_baseline != baseline {
+        println!(
+            "{} pruned the baseline entries of a checker that did not run",
+            config.file_name
+        );
+        return 1;
+    }
+    let result = self::invoke_driver(
+        &early_error_handler,
+        config.file_name.clone(),
+        config.temp_dir_path,
+        sys_root,
+        config.extern_deps,
+        options,
+    );
+    if result != 0 {
+        return result;
+    }
+    let report_text = fs::read_to_string(Path::new(&report_dir).join("report.txt"))
+        .expect("Failed to read the text report");
+    let expected = [
+        format!(
+            "numerical_precision {}:{}:5 low possible numerical precision error for the smart contract (suppressed by attribute)",
+            config.file_name,
+            line_of(&test_case_data, "amount.round()")
+        ),
+        format!(
+            "numerical_precision {}:{}:5 low possible numerical precision error for the smart contract (suppressed by baseline)",
+            config.file_name,
+            line_of(&test_case_data, "fee.round()")
+        ),
+        format!(
+            "numerical_precision {}:{}:5 low possible numerical precision error for the smart contract (suppressed by comment)",
+            config.file_name,
+            line_of(&test_case_data, "average.round()")
+        ),
+        format!(
+            "numerical_precision {}:{}:5 low possible numerical precision error for the smart contract (suppressed by comment)",
+            config.file_name,
+            line_of(&test_case_data, "tip.round()")
+        ),
+        "stale baseline entries: 1".to_string(),
+        format!(
+            "numerical_precision {removed_file}:1:1 low possible numerical precision error for the smart contract"
+        ),
+    ];
+    let pruned_baseline: Vec<Finding> = serde_json::from_str(
+        &fs::read_to_string(&baseline_path).expect("Failed to read the pruned baseline"),
+    )
+    .expect("Failed to deserialize the pruned baseline");
+    if pruned_baseline != vec![baseline_entry] {
+        println!(
+            "{} failed to prune the baseline, which is now {pruned_baseline:?}",
+            config.file_name
+        );
+        return 1;
+    }
+    if expected.iter().all(|e| report_text.contains(e.as_str())) {
+        0
+    } else {
+        println!("{} failed to list the suppressed findings", config.file_name);
+        println!("Expected:\n{}", expected.join("\n"));
+        println!("Actual:\n{report_text}");
+        1
+    }
+}
+
+// Test driver for --write-baseline;
+// writes a baseline with the findings of the test case, then analyzes a copy of the test case whose
+// lines have moved with that baseline and checks that all of its findings are suppressed by it.
+fn start_driver_baseline(config: DriverConfig) -> usize {
+    let baseline_path = format!("{}/baseline.json", config.temp_dir_path);
+    let file_name = config.file_name.clone();
+    let temp_dir_path = config.temp_dir_path.clone();
+    let extern_deps = config.extern_deps.clone();
+    let result = start_driver_with(config, &format!("--write-baseline {baseline_path}"), |_| {});
+    if result != 0 {
+        return result;
+    }
+    let baseline: Vec<Finding> = serde_json::from_str(
+        &fs::read_to_string(&baseline_path).expect("Failed to read the written baseline"),
+    )
+    .expect("Failed to deserialize the written baseline");
+    if baseline.len() != 2 || baseline.iter().any(|entry| entry.fingerprint.is_empty()) {
+        println!("{file_name} did not write a fingerprinted baseline entry per finding");
+        println!("Actual: {baseline:?}");
+        return 1;
+    }
+
+    // The copy has the same file name, so that it is compiled as a crate of the same name
+    let shifted_dir = Path::new(&temp_dir_path).join("shifted");
+    fs::create_dir_all(&shifted_dir).expect("Failed to create the directory of the copy");
+    let shifted_file = shifted_dir.join(Path::new(&file_name).file_name().unwrap());
+    let test_case_data =
+        fs::read_to_string(Path::new(&file_name)).expect("Failed to read test case");
+    let shifted_data = test_case_data.replacen(
+        "pub fn rounded_fee",
+        "pub const FEE_BASIS: u64 = 10_000;\n\npub fn rounded_fee",
+        1,
+    );
+    fs::write(&shifted_file, shifted_data).expect("Failed to write the copy of the test case");
+    let shifted_config = DriverConfig {
+        file_name: shifted_file.to_str().unwrap().to_string(),
+        temp_dir_path,
+        extern_deps,
+    };
+    let flags = format!("--baseline {baseline_path} --show-suppressed");
+    let report = match analyze_into_report(shifted_config, &flags) {
+        Ok(report) => report,
+        Err(result) => return result,
+    };
+    let crate_report = &report.crates[0];
+    if !crate_report.findings.is_empty() || crate_report.suppressed.len() != 2 {
+        println!(
+            "{file_name} has findings that the baseline did not suppress after their lines moved"
+        );
+        println!("Actual: {crate_report:?}");
+        return 1;
+    }
+    0
+}
+
+// Test driver for the call paths of reentrancy findings;
+// checks that the reported reentrancy finding has the path given by the EXPECTED:REACH_PATH
+// comment of the test file, with one function name per line.
+fn start_driver_reach_path(config: DriverConfig) -> usize {
+    let test_case_data =
+        fs::read_to_string(Path::new(&config.file_name)).expect("Failed to read test case");
+    let expected_path: Vec<String> = expected_comment(&test_case_data, "REACH_PATH")
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+    let file_name = config.file_name.clone();
+    let report = match analyze_into_report(config, "") {
+        Ok(report) => report,
+        Err(result) => return result,
+    };
+    let reach_paths: Vec<&Vec<String>> = findings_of(&report, "reentrancy")
+        .map(|f| &f.reach_path)
+        .collect();
+    if reach_paths != vec![&expected_path] {
+        println!("{file_name} failed to match the reach path");
+        println!("Expected: {expected_path:?}");
+        println!("Actual: {reach_paths:?}");
+        return 1;
+    }
+    0
+}
+
+// Test driver for --guarded-reentrancy;
+// checks that the reentrancy finding of the guarded function, which comes first in the test file,
+// is downgraded to info by default while those of the functions that follow it stay critical.
+fn start_driver_reentrancy_guard(config: DriverConfig) -> usize {
+    let file_name = config.file_name.clone();
+    let report = match analyze_into_report(config, "") {
+        Ok(report) => report,
+        Err(result) => return result,
+    };
+    let mut findings: Vec<&Finding> = findings_of(&report, "reentrancy").collect();
+    findings.sort_by_key(|f| f.line);
+    let severities: Vec<Severity> = findings.iter().map(|f| f.severity).collect();
+    if severities != vec![Severity::Info, Severity::Critical, Severity::Critical] {
+        println!("{file_name} failed to downgrade the guarded reentrancy");
+        println!("Actual: {severities:?}");
+        return 1;
+    }
+    0
+}
+
+// Test driver for annotated sources;
+// checks that the annotated copy of the test file differs from it only by the reentrancy and missing
+// signer markers at the end of the lines of the findings.
+fn start_driver_annotated(config: DriverConfig) -> usize {
+    let test_case_data =
+        fs::read_to_string(Path::new(&config.file_name)).expect("Failed to read test case");
+    let report_dir = TempDir::new().expect("failed to create a temp dir");
+    let report_dir_path = report_dir.path().to_str().unwrap().to_string();
+    let file_name = config.file_name.clone();
+    let result = start_driver_report(config, &report_dir_path, "--emit annotated");
+    if result != 0 {
+        return result;
+    }
+    let annotated_path = report::annotated_path(&report_dir.path().join("annotated"), &file_name);
+    let annotated_data =
+        fs::read_to_string(&annotated_path).expect("Failed to read the annotated source");
+    let expected_markers = [
+        (
+            line_of(&test_case_data, "*balance -= amount;"),
+            "// <<< reentrancy: possible reentrancy for the smart contract",
+        ),
+        (
+            line_of(&test_case_data, "contract_account.try_borrow_mut_lamports()"),
+            "// <<< missing_signer: possible mutation of lamports without a signer check of the account for the smart contract",
+        ),
+    ];
+    let original_lines: Vec<&str> = test_case_data.lines().collect();
+    let annotated_lines: Vec<&str> = annotated_data.lines().collect();
+    if annotated_lines.len() != original_lines.len() {
+        println!("{file_name} has a different number of lines when annotated");
+        return 1;
+    }
+    for (index, (original, annotated)) in original_lines.iter().zip(&annotated_lines).enumerate() {
+        let expected = match expected_markers.iter().find(|(line, _)| *line == index + 1) {
+            Some((_, marker)) => format!("{original} {marker}"),
+            None => original.to_string(),
+        };
+        if *annotated != expected {
+            println!(
+                "{file_name} is not annotated as expected at line {}",
+                index + 1
+            );
+            println!("Expected: {expected}");
+            println!("Actual: {annotated}");
+            return 1;
+        }
+    }
+    0
+}
+
+// Test driver for rule policies;
+// writes a policy that enables the pedantic group and raises the severity of its manual_rent
+// checker, and checks that the findings of the checker that the test case marks are reported
+// with that severity.
+fn start_driver_rules_from(config: DriverConfig) -> usize {
+    let test_case_data =
+        fs::read_to_string(Path::new(&config.file_name)).expect("Failed to read test case");
+    let manual_rent_markers = test_case_data
+        .matches("//~ possible manual rent computation")
+        .count();
+    let policy_path = format!("{}/policy.json", config.temp_dir_path);
+    fs::write(
+        &policy_path,
+        r#"{ "enable": ["pedantic"], "severities": { "manual_rent": "medium" } }"#,
+    )
+    .expect("Failed to write the rule policy");
+    let file_name = config.file_name.clone();
+    let flags = format!("--rules-from {policy_path}");
+    let report = match analyze_into_report(config, &flags) {
+        Ok(report) => report,
+        Err(result) => return result,
+    };
+    let severities: Vec<Severity> = findings_of(&report, "manual_rent")
+        .map(|f| f.severity)
+        .collect();
+    if severities != vec![Severity::Medium; manual_rent_markers] {
+        println!("{file_name} failed to apply the rule policy");
+        println!("Actual: {severities:?}");
+        return 1;
+    }
+    0
+}
+
+// Test driver for --only and --skip;
+// runs the reentrancy and numerical precision checkers, less the skipped numerical precision one,
+// so that only the reentrancy finding is expected.
+fn start_driver_only_checkers(config: DriverConfig) -> usize {
+    let report_dir = TempDir::new().expect("failed to create a temp dir");
+    let report_dir_path = report_dir.path().to_str().unwrap().to_string();
+    start_driver_report(
+        config,
+        &report_dir_path,
+        "--only reentrancy,numerical-precision --skip numerical-precision",
+    )
+}
+
+// Test driver for the related locations of findings;
+// checks that the findings with related locations or evidence are those of the EXPECTED:RELATED
+// comment of the test file, one per line as its checker, its line, the lines of its related
+// locations and the lines of the load, the transfer and the store of its evidence.
+fn start_driver_related_locations(config: DriverConfig) -> usize {
+    let test_case_data =
+        fs::read_to_string(Path::new(&config.file_name)).expect("Failed to read test case");
+    let expected = expected_comment(&test_case_data, "RELATED");
+    let file_name = config.file_name.clone();
+    let report = match analyze_into_report(config, "") {
+        Ok(report) => report,
+        Err(result) => return result,
+    };
+    let actual = report
+        .crates
+        .iter()
+        .flat_map(|c| c.findings.iter())
+        .filter(|f| !f.related.is_empty() || f.evidence.is_some())
+        .map(|f| {
+            let related: Vec<String> = f.related.iter().map(|r| r.line.to_string()).collect();
+            let evidence = match &f.evidence {
+                Some(e) => {
+                    format!(" evidence {} {} {}", e.load.line, e.transfer.line, e.store.line)
+                }
+                None => String::new(),
+            };
+            format!("{} {} related {}{evidence}", f.checker_name, f.line, related.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !compare_lines(&actual, &expected) {
+        println!("{file_name} does not have the expected related locations");
+        println!("Expected: {expected}");
+        println!("Actual: {actual}");
+        return 1;
+    }
+    0
+}
+
+// Test driver for payouts decided by the clock;
+// checks that the finding is high and refers to the line where the clock is read.
+fn start_driver_clock_payout(config: DriverConfig) -> usize {
+    let test_case_data =
+        fs::read_to_string(Path::new(&config.file_name)).expect("Failed to read test case");
+    let clock_line = line_of(&test_case_data, "Clock::get()");
+    let file_name = config.file_name.clone();
+    let report = match analyze_into_report(config, "") {
+        Ok(report) => report,
+        Err(result) => return result,
+    };
+    let payouts: Vec<(Severity, Vec<usize>)> = findings_of(&report, "clock_payout")
+        .map(|f| (f.severity, f.related.iter().map(|r| r.line).collect()))
+        .collect();
+    let expected = vec![(Severity::High, vec![clock_line])];
+    if payouts != expected {
+        println!("{file_name} failed to report the payout decided by the clock");
+        println!("Expected: {expected:?}");
+        println!("Actual: {payouts:?}");
+        return 1;
+    }
+    0
+}
+
+// Test driver for SARIF logs;
+// checks that there is a rule for every checker, and that the results are those of the
+// EXPECTED:SARIF comment of the test file, one per line as its rule, its level, the line of its
+// location and the number of its related locations.
+fn start_driver_sarif(config: DriverConfig) -> usize {
+    let test_case_data =
+        fs::read_to_string(Path::new(&config.file_name)).expect("Failed to read test case");
+    let expected = expected_comment(&test_case_data, "SARIF");
+    let sarif_dir = TempDir::new().expect("failed to create a temp dir");
+    let sarif_path = sarif_dir.path().join("findings.sarif");
+    let file_name = config.file_name.clone();
+    let result = start_driver_with(config, "", |options| {
+        options.sarif_path = Some(sarif_path.to_str().unwrap().to_string());
+    });
+    if result != 0 {
+        return result;
+    }
+    let sarif_json = fs::read_to_string(&sarif_path).expect("Failed to read the SARIF log");
+    let sarif_report: SarifReport =
+        serde_json::from_str(&sarif_json).expect("Failed to deserialize the SARIF log");
+    if sarif_report.version != report::SARIF_VERSION || sarif_report.runs.len() != 1 {
+        println!(
+            "{file_name} has no SARIF {} log with a single run",
+            report::SARIF_VERSION
+        );
+        return 1;
+    }
+    let run = &sarif_report.runs[0];
+    let rule_ids: Vec<&str> = run
+        .tool
+        .driver
+        .rules
+        .iter()
+        .map(|r| r.id.as_str())
+        .collect();
+    if rule_ids != CHECKER_NAMES {
+        println!("{file_name} does not have a rule for every checker");
+        println!("Expected: {CHECKER_NAMES:?}");
+        println!("Actual: {rule_ids:?}");
+        return 1;
+    }
+    if let Some(r) = run
+        .results
+        .iter()
+        .find(|r| rule_ids.get(r.rule_index) != Some(&r.rule_id.as_str()))
+    {
+        println!("{file_name} has a result whose rule index is not that of its rule");
+        println!("Actual: {} at {}", r.rule_id, r.rule_index);
+        return 1;
+    }
+    let actual = run
+        .results
+        .iter()
+        .map(|r| {
+            format!(
+                "{} {} {} {}",
+                r.rule_id,
+                r.level,
+                r.locations[0].physical_location.region.start_line,
+                r.related_locations.len()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !compare_lines(&actual, &expected) {
+        println!("{file_name} does not have the expected SARIF results");
+        println!("Expected: {expected}");
+        println!("Actual: {actual}");
+        return 1;
+    }
+    0
+}
+
+// Test driver for HTML reports;
+// checks that the report has the lines of the EXPECTED:HTML comment of the test file. The path of
+// the test file is shown as its file name, so the comment does not depend on where the tests run.
+fn start_driver_html(config: DriverConfig) -> usize {
+    let test_case_data =
+        fs::read_to_string(Path::new(&config.file_name)).expect("Failed to read test case");
+    let expected = expected_comment(&test_case_data, "HTML");
+    let html_dir = TempDir::new().expect("failed to create a temp dir");
+    let html_path = html_dir.path().join("findings.html");
+    let file_name = config.file_name.clone();
+    let result = start_driver_with(config, "", |options| {
+        options.html_path = Some(html_path.to_str().unwrap().to_string());
+    });
+    if result != 0 {
+        return result;
+    }
+    let test_file_name = Path::new(&file_name).file_name().unwrap().to_str().unwrap();
+    let actual = fs::read_to_string(&html_path)
+        .expect("Failed to read the HTML report")
+        .replace(&file_name, test_file_name);
+    if !compare_lines(&actual, &expected) {
+        println!("{file_name} does not have the expected HTML report");
+        println!("Expected: {expected}");
+        println!("Actual: {actual}");
+        return 1;
+    }
+    0
+}
+
+// Test driver for --callgraph-with-findings;
+// checks the dot file of the call graph against the expected one of the test file.
+fn start_driver_callgraph_findings(config: DriverConfig) -> usize {
+    let test_case_data =
+        fs::read_to_string(Path::new(&config.file_name)).expect("Failed to read test case");
+    let expected = expected_comment(&test_case_data, "DOT");
+    let dot_dir = TempDir::new().expect("failed to create a temp dir");
+    let dot_path = dot_dir.path().join("callgraph.dot");
+    let file_name = config.file_name.clone();
+    let result = start_driver_with(config, "", |options| {
+        options.callgraph_with_findings = Some(dot_path.to_str().unwrap().to_string());
+    });
+    if result != 0 {
+        return result;
+    }
+    let actual = fs::read_to_string(&dot_path).expect("Failed to read the dot file");
+    if !compare_lines(&actual, &expected) {
+        println!("{file_name} does not have the expected call graph");
+        println!("Expected: {expected}");
+        println!("Actual: {actual}");
+        return 1;
+    }
+    0
+}
+
+// Test driver for --format and --output;
+// checks that the text output has the lines of the EXPECTED:TEXT comment of the test file, with the
+// path of the test file shown as its file name.
+fn start_driver_output_format(config: DriverConfig) -> usize {
+    let test_case_data =
+        fs::read_to_string(Path::new(&config.file_name)).expect("Failed to read test case");
+    let expected = expected_comment(&test_case_data, "TEXT");
+    let output_dir = TempDir::new().expect("failed to create a temp dir");
+    let output_path = output_dir.path().join("findings.txt");
+    let file_name = config.file_name.clone();
+    let flags = format!("--format text --output {}", output_path.display());
+    let result = start_driver_with(config, &flags, |_| {});
+    if result != 0 {
+        return result;
+    }
+    let test_file_name = Path::new(&file_name).file_name().unwrap().to_str().unwrap();
+    let actual = fs::read_to_string(&output_path)
+        .expect("Failed to read the text output")
+        .replace(&file_name, test_file_name);
+    if !compare_lines(&actual, &expected) {
+        println!("{file_name} does not have the expected text output");
+        println!("Expected: {expected}");
+        println!("Actual: {actual}");
+        return 1;
+    }
+    0
+}
+
+// Test driver for the findings of the security checkers;
+// checks that the findings of the JSON report are those of the EXPECTED:FINDINGS comment of the
+// test file, as (checker, line, severity) tuples, and shows the difference if they are not. The
+// comment takes the place of the //~ markers of the findings, which the test file leaves out.
+fn start_driver_findings(config: DriverConfig) -> usize {
+    let test_case_data =
+        fs::read_to_string(Path::new(&config.file_name)).expect("Failed to read test case");
+    let expected = match parse_expected_findings(&expected_comment(&test_case_data, "FINDINGS")) {
+        Ok(expected) => expected,
+        Err(e) => {
+            println!("{} has malformed expected findings: {e}", config.file_name);
+            return 1;
+        }
+    };
+    let file_name = config.file_name.clone();
+    let report = match analyze_into_report(config, "") {
+        Ok(report) => report,
+        Err(result) => return result,
+    };
+    let actual: Vec<FindingTuple> = report
+        .crates
+        .iter()
+        .flat_map(|c| c.findings.iter())
+        .map(|f| (f.checker_name.clone(), f.line, f.severity))
+        .collect();
+    let (missing, unexpected) = diff_findings(&actual, &expected);
+    if !missing.is_empty() || !unexpected.is_empty() {
+        println!("{file_name} does not have the expected findings");
+        for (checker_name, line, severity) in missing {
+            println!("- ({checker_name}, {line}, {severity})");
+        }
+        for (checker_name, line, severity) in unexpected {
+            println!("+ ({checker_name}, {line}, {severity})");
+        }
+        return 1;
+    }
+    0
+}
+
+// Test driver for the benchmark of the walk that feeds the checkers of the Checker trait;
+// analyzes the synthetic body with only the self transfer checker, prints how long the analysis
+// took, and checks that none of its transfers, which are between distinct accounts, is reported.
+fn start_driver_benchmark(config: DriverConfig) -> usize {
+    let file_name = config.file_name.clone();
+    let start = Instant::now();
+    let report = match analyze_into_report(config, "--only self_transfer") {
+        Ok(report) => report,
+        Err(result) => return result,
+    };
+    println!("{file_name} analyzed in {:?}", start.elapsed());
+    let self_transfers = findings_of(&report, "self_transfer").count();
+    if self_transfers != 0 {
+        println!("{file_name} has {self_transfers} unexpected self transfers");
+        return 1;
+    }
+    0
+}
+
+// Test driver for --fail-on and --rule-severity;
+// runs the test case with the flags of each line of the EXPECTED:FAIL_ON comment of the test file,
+// and checks that the analysis passes or fails as the line gives after the flags.
+fn start_driver_fail_on(config: DriverConfig) -> usize {
+    let test_case_data =
+        fs::read_to_string(Path::new(&config.file_name)).expect("Failed to read test case");
+    let early_error_handler = EarlyDiagCtxt::new(config::ErrorOutputType::default());
+    let mut failures = 0;
+    for line in expected_comment(&test_case_data, "FAIL_ON").lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (flags, should_fail) = match line.rsplit_once(": ") {
+            Some((flags, "fail")) => (flags, true),
+            Some((flags, "pass")) => (flags, false),
+            _ => {
+                println!("{} has a malformed expected outcome: {line}", config.file_name);
+                return 1;
+            }
+        };
+        let result = self::invoke_driver(
+            &early_error_handler,
+            config.file_name.clone(),
+            config.temp_dir_path.clone(),
+            utils::find_sysroot(),
+            config.extern_deps.clone(),
+            build_options_with_flags(&early_error_handler, flags),
+        );
+        if (result != 0) != should_fail {
+            let outcome = if should_fail { "fail" } else { "pass" };
+            println!("{} did not {outcome} with {flags}", config.file_name);
+            failures += 1;
+        }
+    }
+    failures
+}
@@ -0,0 +1,49 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that only the checkers named by --only run, less the ones named by --skip. The withdraw
+// also rounds the fee and does not check that the accounts differ, which is not reported, since
+// numerical_precision is skipped and distinct_accounts is not named.
+
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn withdraw(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    let fee = (amount as f64 * 0.01).round() as u64;
+    let balance = balances.get_mut(&user_account.key).ok_or(ProgramError)?;
+    if *balance < amount + fee {
+        return Err(ProgramError);
+    }
+    let mut contract_lamports = contract_account.try_borrow_mut_lamports()?;
+    *contract_lamports = contract_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+    *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+    *balance -= amount + fee; //~ possible reentrancy for the smart contract
+    Ok(())
+}
+
+pub fn main() {}
@@ -0,0 +1,80 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that a reentrancy finding records the call path from the entrypoint to the transfer
+
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn withdraw(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if user_account.key == contract_account.key {
+        return Err(ProgramError);
+    }
+    let balance = balances.get_mut(&user_account.key).ok_or(ProgramError)?;
+    if *balance < amount {
+        return Err(ProgramError);
+    }
+    let mut contract_lamports = contract_account.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *contract_lamports = contract_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+    *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+    *balance -= amount; //~ possible reentrancy for the smart contract
+    Ok(())
+}
+
+pub fn process_instruction(
+    balances: &mut HashMap<Pubkey, u64>,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let [user_account, contract_account] = accounts else {
+        return Err(ProgramError);
+    };
+    withdraw(balances, amount, user_account, contract_account)
+}
+
+pub fn entrypoint(
+    balances: &mut HashMap<Pubkey, u64>,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> u64 {
+    let Some(amount) = input.first() else {
+        return 1;
+    };
+    match process_instruction(balances, accounts, *amount as u64) {
+        Ok(()) => 0,
+        Err(_) => 1,
+    }
+}
+
+pub fn main() {}
+
+/* EXPECTED:REACH_PATH
+entrypoint_withdraw::entrypoint
+entrypoint_withdraw::process_instruction
+entrypoint_withdraw::withdraw
+*/
@@ -0,0 +1,285 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+// Tests of the reentrancy checker on hand-built MIR, which run without compiling a contract.
+// The MIR of these tests only uses places and operands without types, so no TyCtxt is needed.
+#![feature(rustc_private)]
+
+extern crate mirai;
+extern crate rustc_driver;
+extern crate rustc_middle;
+extern crate rustc_span;
+
+use rustc_middle::mir;
+use rustc_span::DUMMY_SP;
+
+use mirai::contract_errors::{BlockStatement, ReentrancyChecker};
+use mirai::options::ReentrancyTransfers;
+
+// The locals of the hand-built functions
+const BALANCE: u32 = 1;
+const AMOUNT: u32 = 2;
+const OTHER: u32 = 3;
+
+// The basic blocks of the hand-built functions, a transfer is made in TRANSFER_BB
+const CHECK_BB: u32 = 0;
+const TRANSFER_BB: u32 = 1;
+const UPDATE_BB: u32 = 2;
+
+fn local(index: u32) -> mir::Place<'static> {
+    mir::Place::from(mir::Local::from_u32(index))
+}
+
+fn bb(index: u32) -> mir::BasicBlock {
+    mir::BasicBlock::from_u32(index)
+}
+
+// The terminator that guards `left -= right` against overflow, continuing with target
+fn overflow_assert(
+    bin_op: mir::BinOp,
+    left: mir::Place<'static>,
+    right: mir::Place<'static>,
+    target: mir::BasicBlock,
+) -> mir::TerminatorKind<'static> {
+    mir::TerminatorKind::Assert {
+        cond: mir::Operand::Copy(local(OTHER)),
+        expected: false,
+        msg: Box::new(mir::AssertKind::Overflow(
+            bin_op,
+            mir::Operand::Copy(left),
+            mir::Operand::Copy(right),
+        )),
+        target,
+        unwind: mir::UnwindAction::Continue,
+    }
+}
+
+// The statement `destination = source`
+fn assign(
+    destination: mir::Place<'static>,
+    source: mir::Place<'static>,
+) -> BlockStatement<'static> {
+    BlockStatement::Statement(mir::Statement {
+        source_info: mir::SourceInfo::outermost(DUMMY_SP),
+        kind: mir::StatementKind::Assign(Box::new((
+            destination,
+            mir::Rvalue::Use(mir::Operand::Copy(source)),
+        ))),
+    })
+}
+
+// The terminator that continues with target
+fn goto(target: mir::BasicBlock) -> BlockStatement<'static> {
+    BlockStatement::TerminatorKind(mir::TerminatorKind::Goto { target })
+}
+
+// The function `balance -= amount`, where the balance is decremented in update_bb
+fn withdraw(update_bb: mir::BasicBlock) -> ReentrancyChecker<'static> {
+    let decrement = BlockStatement::TerminatorKind(overflow_assert(
+        mir::BinOp::Sub,
+        local(BALANCE),
+        local(AMOUNT),
+        bb(UPDATE_BB + 1),
+    ));
+    let blocks = [CHECK_BB, TRANSFER_BB, UPDATE_BB]
+        .into_iter()
+        .map(|index| {
+            if bb(index) == update_bb {
+                (bb(index), vec![decrement.clone()])
+            } else {
+                (bb(index), vec![goto(bb(index + 1))])
+            }
+        })
+        .collect();
+    ReentrancyChecker::for_blocks(blocks, vec![bb(TRANSFER_BB)], Some(local(BALANCE)))
+}
+
+#[test]
+fn terminator_decrementing_the_balance() {
+    let checker = ReentrancyChecker::for_blocks(vec![], vec![], Some(local(BALANCE)));
+    let kind = overflow_assert(mir::BinOp::Sub, local(BALANCE), local(AMOUNT), bb(1));
+    assert!(checker.visit_reentrancy_terminator(&kind));
+}
+
+#[test]
+fn terminator_decrementing_another_local() {
+    let checker = ReentrancyChecker::for_blocks(vec![], vec![], Some(local(BALANCE)));
+    let kind = overflow_assert(mir::BinOp::Sub, local(OTHER), local(AMOUNT), bb(1));
+    assert!(!checker.visit_reentrancy_terminator(&kind));
+}
+
+#[test]
+fn terminator_incrementing_the_balance() {
+    let checker = ReentrancyChecker::for_blocks(vec![], vec![], Some(local(BALANCE)));
+    let kind = overflow_assert(mir::BinOp::Add, local(BALANCE), local(AMOUNT), bb(1));
+    assert!(!checker.visit_reentrancy_terminator(&kind));
+}
+
+#[test]
+fn terminator_without_a_balance() {
+    let checker = ReentrancyChecker::for_blocks(vec![], vec![], None);
+    let kind = overflow_assert(mir::BinOp::Sub, local(BALANCE), local(AMOUNT), bb(1));
+    assert!(!checker.visit_reentrancy_terminator(&kind));
+}
+
+#[test]
+fn terminator_that_is_not_an_assert() {
+    let checker = ReentrancyChecker::for_blocks(vec![], vec![], Some(local(BALANCE)));
+    let kind = mir::TerminatorKind::Goto { target: bb(1) };
+    assert!(!checker.visit_reentrancy_terminator(&kind));
+}
+
+#[test]
+fn statement_assigning_the_balance() {
+    let checker = ReentrancyChecker::for_blocks(vec![], vec![], Some(local(BALANCE)));
+    let BlockStatement::Statement(statement) = assign(local(BALANCE), local(OTHER)) else {
+        unreachable!()
+    };
+    assert!(checker.visit_reentrancy_statement(&statement.kind));
+}
+
+#[test]
+fn balance_decremented_after_the_transfer() {
+    assert!(withdraw(bb(UPDATE_BB)).check());
+}
+
+#[test]
+fn balance_decremented_before_the_transfer() {
+    assert!(!withdraw(bb(CHECK_BB)).check());
+}
+
+#[test]
+fn function_without_a_transfer() {
+    let decrement = BlockStatement::TerminatorKind(overflow_assert(
+        mir::BinOp::Sub,
+        local(BALANCE),
+        local(AMOUNT),
+        bb(1),
+    ));
+    let blocks = vec![(bb(0), vec![decrement])];
+    let checker = ReentrancyChecker::for_blocks(blocks, vec![], Some(local(BALANCE)));
+    assert!(!checker.check());
+}
+
+// The function `balance -= amount` with many transfers, where the balance is decremented in
+// update_bb. The last transfer is in the highest of the transfer blocks, whatever order the
+// transfer blocks are given in.
+fn withdraw_with_transfers(update_bb: u32, transfer_bbs: &[u32]) -> ReentrancyChecker<'static> {
+    let decrement = BlockStatement::TerminatorKind(overflow_assert(
+        mir::BinOp::Sub,
+        local(BALANCE),
+        local(AMOUNT),
+        bb(update_bb + 1),
+    ));
+    let blocks = (0..=update_bb.max(transfer_bbs.iter().copied().max().unwrap_or(0)))
+        .map(|index| {
+            if index == update_bb {
+                (bb(index), vec![decrement.clone()])
+            } else {
+                (bb(index), vec![goto(bb(index + 1))])
+            }
+        })
+        .collect();
+    let transfer_blocks = transfer_bbs.iter().map(|index| bb(*index)).collect();
+    ReentrancyChecker::for_blocks(blocks, transfer_blocks, Some(local(BALANCE)))
+}
+
+#[test]
+fn balance_decremented_between_many_transfers() {
+    // The balance is decremented before the last transfer, in block 30, on every run
+    let transfer_bbs: Vec<u32> = (1..=31).step_by(2).rev().collect();
+    for _ in 0..16 {
+        assert!(!withdraw_with_transfers(30, &transfer_bbs).check());
+    }
+}
+
+#[test]
+fn balance_decremented_after_many_transfers() {
+    let transfer_bbs: Vec<u32> = (1..=31).step_by(2).rev().collect();
+    for _ in 0..16 {
+        let checker = withdraw_with_transfers(32, &transfer_bbs);
+        assert!(checker.check());
+        assert_eq!(
+            checker.function_lamport_transfer.keys().next_back(),
+            Some(&bb(31))
+        );
+    }
+}
+
+#[test]
+fn balance_decremented_before_the_transfer_in_a_loop() {
+    // The balance is decremented in block 1 and the lamports are transferred in block 2, which
+    // goes back to block 1 for the next iteration of the loop
+    let decrement = BlockStatement::TerminatorKind(overflow_assert(
+        mir::BinOp::Sub,
+        local(BALANCE),
+        local(AMOUNT),
+        bb(2),
+    ));
+    let blocks = vec![
+        (bb(0), vec![goto(bb(1))]),
+        (bb(1), vec![decrement]),
+        (bb(2), vec![goto(bb(1))]),
+    ];
+    let checker = ReentrancyChecker::for_blocks(blocks, vec![bb(2)], Some(local(BALANCE)));
+    assert!(checker.check());
+    assert!(checker.reachable_after(bb(2)).contains(&bb(2)));
+}
+
+#[test]
+fn balance_decremented_in_a_block_that_is_not_reachable() {
+    // Block 3 has a higher index than the transfer block, but the function returns before it
+    let decrement = BlockStatement::TerminatorKind(overflow_assert(
+        mir::BinOp::Sub,
+        local(BALANCE),
+        local(AMOUNT),
+        bb(4),
+    ));
+    let blocks = vec![
+        (bb(0), vec![goto(bb(1))]),
+        (bb(1), vec![goto(bb(2))]),
+        (
+            bb(2),
+            vec![BlockStatement::TerminatorKind(mir::TerminatorKind::Return)],
+        ),
+        (bb(3), vec![decrement]),
+    ];
+    let checker = ReentrancyChecker::for_blocks(blocks, vec![bb(1)], Some(local(BALANCE)));
+    assert!(!checker.check());
+}
+
+// The paths of the callees that move lamports, as the checker is given them
+const BORROW_LAMPORTS: &str =
+    "solana_program::account_info::AccountInfo::<'a>::try_borrow_mut_lamports";
+const INVOKE: &str = "solana_program::program::invoke";
+const INVOKE_SIGNED: &str = "solana_program::program::invoke_signed";
+
+#[test]
+fn lamports_and_invocations_are_transfers_by_default() {
+    let checker = ReentrancyChecker::new();
+    assert!(checker.is_transfer(BORROW_LAMPORTS));
+    assert!(checker.is_transfer(INVOKE));
+    assert!(checker.is_transfer(INVOKE_SIGNED));
+}
+
+#[test]
+fn only_invocations_are_transfers_for_cpi() {
+    let checker = ReentrancyChecker::with_transfers(ReentrancyTransfers::Cpi, vec![]);
+    assert!(!checker.is_transfer(BORROW_LAMPORTS));
+    assert!(checker.is_transfer(INVOKE));
+    assert!(checker.is_transfer("program::invoke_signed"));
+}
+
+#[test]
+fn safe_transfers_are_not_transfers() {
+    let safe_transfers = vec!["move_lamports".to_string(), "vault::pay".to_string()];
+    let checker = ReentrancyChecker::with_transfers(ReentrancyTransfers::Lamports, safe_transfers);
+    assert!(!checker.is_transfer("vault::move_lamports"));
+    assert!(!checker.is_transfer("vault::pay"));
+    // A safe transfer is named by the end of the path after a separator, not by a suffix of it
+    assert!(checker.is_safe_transfer("move_lamports"));
+    assert!(!checker.is_safe_transfer("vault::remove_lamports"));
+    assert!(checker.is_transfer(INVOKE));
+}
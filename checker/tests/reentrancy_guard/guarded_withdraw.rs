@@ -0,0 +1,107 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that the reentrancy of a function guarded by a reentrancy flag is downgraded by
+// default, while the reentrancy of locked_withdraw, which sets the flag without checking it,
+// and of withdraw, which has no guard, stays critical
+
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct VaultState {
+    pub locked: bool,
+}
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn guarded_withdraw(
+    state: &mut VaultState,
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if state.locked {
+        return Err(ProgramError);
+    }
+    state.locked = true;
+    if user_account.key == contract_account.key {
+        return Err(ProgramError);
+    }
+    let balance = balances.get_mut(&user_account.key).ok_or(ProgramError)?;
+    if *balance < amount {
+        return Err(ProgramError);
+    }
+    let mut contract_lamports = contract_account.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *contract_lamports = contract_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+    *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+    *balance -= amount; //~ possible reentrancy for the smart contract
+    state.locked = false;
+    Ok(())
+}
+
+pub fn locked_withdraw(
+    state: &mut VaultState,
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    // A reentrant call sets the flag again instead of being rejected
+    state.locked = true;
+    if user_account.key == contract_account.key {
+        return Err(ProgramError);
+    }
+    let balance = balances.get_mut(&user_account.key).ok_or(ProgramError)?;
+    if *balance < amount {
+        return Err(ProgramError);
+    }
+    let mut contract_lamports = contract_account.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *contract_lamports = contract_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+    *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+    *balance -= amount; //~ possible reentrancy for the smart contract
+    state.locked = false;
+    Ok(())
+}
+
+pub fn withdraw(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if user_account.key == contract_account.key {
+        return Err(ProgramError);
+    }
+    let balance = balances.get_mut(&user_account.key).ok_or(ProgramError)?;
+    if *balance < amount {
+        return Err(ProgramError);
+    }
+    let mut contract_lamports = contract_account.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *contract_lamports = contract_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+    *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+    *balance -= amount; //~ possible reentrancy for the smart contract
+    Ok(())
+}
+
+pub fn main() {}
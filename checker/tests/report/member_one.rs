@@ -0,0 +1,15 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// The first member crate of a workspace whose findings are reported per crate
+
+pub fn rounded_share(amount: f64) -> f64 {
+    amount.round() //~ possible numerical precision error for the smart contract
+}
+
+pub fn withdraw(balance: u64, amount: u64) -> u64 {
+    balance.saturating_sub(amount) //~ possible saturating arithmetic masking an error for the smart contract
+}
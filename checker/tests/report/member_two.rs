@@ -0,0 +1,11 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// The second member crate of a workspace whose findings are reported per crate
+
+pub fn rounded_fee(fee: f64) -> f64 {
+    fee.round() //~ possible numerical precision error for the smart contract
+}
@@ -0,0 +1,52 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that computing the rent of an account by hand from its data length is flagged,
+// while obtaining it from the Rent sysvar is not. The checker belongs to the pedantic group,
+// so it only runs because the rule policy of the test enables the group
+
+use std::cell::RefCell;
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub data: RefCell<Vec<u8>>,
+}
+
+impl AccountInfo {
+    pub fn data_len(&self) -> usize {
+        self.data.borrow().len()
+    }
+}
+
+pub struct Rent {
+    pub lamports_per_byte_year: u64,
+}
+
+impl Rent {
+    pub fn minimum_balance(&self, data_len: usize) -> u64 {
+        self.lamports_per_byte_year
+            .checked_mul(data_len as u64)
+            .unwrap_or(u64::MAX)
+    }
+}
+
+const ACCOUNT_STORAGE_OVERHEAD: u64 = 128;
+const LAMPORTS_PER_BYTE_YEAR: u64 = 3480;
+
+pub fn required_lamports(account: &AccountInfo) -> Result<u64, ProgramError> {
+    let size = (account.data_len() as u64)
+        .checked_add(ACCOUNT_STORAGE_OVERHEAD)
+        .ok_or(ProgramError)?;
+    let lamports = size.checked_mul(LAMPORTS_PER_BYTE_YEAR).ok_or(ProgramError)?; //~ possible manual rent computation instead of Rent::minimum_balance for the smart contract
+    Ok(lamports)
+}
+
+pub fn required_lamports_from_sysvar(account: &AccountInfo, rent: &Rent) -> u64 {
+    rent.minimum_balance(account.data_len())
+}
+
+pub fn main() {}
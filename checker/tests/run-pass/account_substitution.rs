@@ -0,0 +1,57 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that looking up the balance of a user by the key of the user account is flagged when
+// only the contract account is checked to be a signer, as in the withdraw of the reentrancy
+// sample contract_one. Anyone can pass the key of another user as the user account and withdraw
+// the balance of that user. The lookup is not flagged when the user account itself signs, nor in
+// a function that does not check a signer at all
+
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo<'a> {
+    pub key: &'a Pubkey,
+    pub is_signer: bool,
+}
+
+pub fn withdraw(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if !contract_account.is_signer {
+        return Err(ProgramError);
+    }
+    let balance = balances.get_mut(user_account.key).ok_or(ProgramError)?; //~ possible lookup of state by the key of an account that is not checked to be a signer for the smart contract
+    *balance = balance.checked_sub(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn signed_withdraw(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if !contract_account.is_signer || !user_account.is_signer {
+        return Err(ProgramError);
+    }
+    let balance = balances.get_mut(user_account.key).ok_or(ProgramError)?;
+    *balance = balance.checked_sub(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn get_balance(balances: &HashMap<Pubkey, u64>, user_account: &AccountInfo) -> u64 {
+    balances.get(user_account.key).copied().unwrap_or(0)
+}
+
+pub fn main() {}
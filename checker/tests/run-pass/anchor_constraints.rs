@@ -0,0 +1,163 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that the constraints of the #[account(..)] attributes of the accounts of an Anchor
+// handler are taken as the checks that Anchor makes of the accounts, and only of the accounts
+// they are written on. The signer constraint satisfies the signer check of the account, mut the
+// writable check, and owner, seeds and the has_one of another account the owner check, while the
+// accounts without them are flagged. Anchor is mocked with the parts of its API that the handlers
+// use, and #[account(..)] is written as a tool attribute, which is recognized by its name
+
+#![feature(register_tool)]
+#![register_tool(anchor)]
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub const ID: Pubkey = Pubkey([7; 32]);
+
+pub struct ProgramError;
+
+pub mod anchor_lang {
+    pub mod context {
+        use crate::Pubkey;
+
+        pub struct Context<'a, T> {
+            pub program_id: &'a Pubkey,
+            pub accounts: &'a mut T,
+        }
+    }
+
+    pub mod accounts {
+        use std::ops::Deref;
+
+        use crate::anchor_lang::AccountInfo;
+
+        pub struct Signer<'info> {
+            pub info: &'info AccountInfo,
+        }
+
+        impl Deref for Signer<'_> {
+            type Target = AccountInfo;
+
+            fn deref(&self) -> &AccountInfo {
+                self.info
+            }
+        }
+
+        pub struct UncheckedAccount<'info> {
+            pub info: &'info AccountInfo,
+        }
+
+        impl Deref for UncheckedAccount<'_> {
+            type Target = AccountInfo;
+
+            fn deref(&self) -> &AccountInfo {
+                self.info
+            }
+        }
+    }
+
+    use std::cell::{Ref, RefCell, RefMut};
+
+    use crate::{ProgramError, Pubkey};
+
+    pub struct AccountInfo {
+        pub key: Pubkey,
+        pub owner: Pubkey,
+        pub is_writable: bool,
+        pub lamports: RefCell<u64>,
+        pub data: RefCell<Vec<u8>>,
+    }
+
+    impl AccountInfo {
+        pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+            self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+        }
+
+        pub fn try_borrow_data(&self) -> Result<Ref<'_, Vec<u8>>, ProgramError> {
+            self.data.try_borrow().map_err(|_| ProgramError)
+        }
+    }
+}
+
+use anchor_lang::accounts::{Signer, UncheckedAccount};
+use anchor_lang::context::Context;
+
+pub struct Withdraw<'info> {
+    #[anchor::account(mut)]
+    pub user: Signer<'info>,
+    #[anchor::account(mut)]
+    pub vault: UncheckedAccount<'info>,
+}
+
+pub struct SignedWithdraw<'info> {
+    #[anchor::account(mut)]
+    pub user: Signer<'info>,
+    #[anchor::account(mut, signer)]
+    pub vault: UncheckedAccount<'info>,
+}
+
+pub struct ReadonlyWithdraw<'info> {
+    pub user: Signer<'info>,
+    #[anchor::account(signer)]
+    pub vault: UncheckedAccount<'info>,
+}
+
+pub struct ReadConfig<'info> {
+    #[anchor::account(owner = crate::ID)]
+    pub owned_config: UncheckedAccount<'info>,
+    #[anchor::account(seeds = [b"config", user.key.0.as_ref()], bump)]
+    pub derived_config: UncheckedAccount<'info>,
+    #[anchor::account(has_one = linked_config @ ProgramError)]
+    pub state: UncheckedAccount<'info>,
+    pub linked_config: UncheckedAccount<'info>,
+    pub user: Signer<'info>,
+}
+
+pub mod constrained_program {
+    use super::*;
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<(), ProgramError> {
+        let mut vault_lamports = ctx.accounts.vault.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+        *vault_lamports = vault_lamports.checked_sub(amount).ok_or(ProgramError)?;
+        let mut user_lamports = ctx.accounts.user.try_borrow_mut_lamports()?;
+        *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+        Ok(())
+    }
+
+    pub fn signed_withdraw(ctx: Context<SignedWithdraw>, amount: u64) -> Result<(), ProgramError> {
+        let mut vault_lamports = ctx.accounts.vault.try_borrow_mut_lamports()?;
+        *vault_lamports = vault_lamports.checked_sub(amount).ok_or(ProgramError)?;
+        let mut user_lamports = ctx.accounts.user.try_borrow_mut_lamports()?;
+        *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+        Ok(())
+    }
+
+    pub fn readonly_withdraw(
+        ctx: Context<ReadonlyWithdraw>,
+        amount: u64,
+    ) -> Result<(), ProgramError> {
+        let mut vault_lamports = ctx.accounts.vault.try_borrow_mut_lamports()?; //~ possible mutation of an account without checking that it is writable for the smart contract
+        *vault_lamports = vault_lamports.checked_sub(amount).ok_or(ProgramError)?;
+        let mut user_lamports = ctx.accounts.user.try_borrow_mut_lamports()?; //~ possible mutation of an account without checking that it is writable for the smart contract
+        *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+        Ok(())
+    }
+
+    pub fn read_config(ctx: Context<ReadConfig>) -> Result<u8, ProgramError> {
+        let owned_data = ctx.accounts.owned_config.try_borrow_data()?;
+        let derived_data = ctx.accounts.derived_config.try_borrow_data()?;
+        let linked_data = ctx.accounts.linked_config.try_borrow_data()?;
+        let state_data = ctx.accounts.state.try_borrow_data()?; //~ possible read of account data without an owner check for the smart contract
+        if owned_data.is_empty() || derived_data.is_empty() || linked_data.is_empty() {
+            return Err(ProgramError);
+        }
+        state_data.first().copied().ok_or(ProgramError)
+    }
+}
+
+pub fn main() {}
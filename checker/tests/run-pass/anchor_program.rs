@@ -0,0 +1,178 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that the handlers of an Anchor program are analyzed through the dispatch functions that
+// the #[program] macro generates, even though the module of the program is not public, and that
+// the accounts of their Context are told apart by their field. Mutating the lamports of a Signer
+// is not flagged, while mutating those of an UncheckedAccount is, and reading the data of an
+// UncheckedAccount is flagged unless its owner is compared with the program id. The types of
+// anchor_lang are mocked with the parts of their API that the handlers use
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub mod anchor_lang {
+    pub mod context {
+        use crate::Pubkey;
+
+        pub struct Context<'a, T> {
+            pub program_id: &'a Pubkey,
+            pub accounts: &'a mut T,
+        }
+    }
+
+    pub mod accounts {
+        use std::ops::Deref;
+
+        use crate::anchor_lang::AccountInfo;
+
+        pub struct Signer<'info> {
+            pub info: &'info AccountInfo,
+        }
+
+        impl Deref for Signer<'_> {
+            type Target = AccountInfo;
+
+            fn deref(&self) -> &AccountInfo {
+                self.info
+            }
+        }
+
+        pub struct UncheckedAccount<'info> {
+            pub info: &'info AccountInfo,
+        }
+
+        impl Deref for UncheckedAccount<'_> {
+            type Target = AccountInfo;
+
+            fn deref(&self) -> &AccountInfo {
+                self.info
+            }
+        }
+    }
+
+    use std::cell::{Ref, RefCell, RefMut};
+
+    use crate::{ProgramError, Pubkey};
+
+    pub struct AccountInfo {
+        pub owner: Pubkey,
+        pub lamports: RefCell<u64>,
+        pub data: RefCell<Vec<u8>>,
+    }
+
+    impl AccountInfo {
+        pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+            self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+        }
+
+        pub fn try_borrow_data(&self) -> Result<Ref<'_, Vec<u8>>, ProgramError> {
+            self.data.try_borrow().map_err(|_| ProgramError)
+        }
+    }
+}
+
+use anchor_lang::accounts::{Signer, UncheckedAccount};
+use anchor_lang::context::Context;
+use anchor_lang::AccountInfo;
+
+pub struct Withdraw<'info> {
+    pub user: Signer<'info>,
+    pub vault: UncheckedAccount<'info>,
+}
+
+pub struct ReadConfig<'info> {
+    pub config: UncheckedAccount<'info>,
+}
+
+mod vault_program {
+    use super::*;
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<(), ProgramError> {
+        let mut vault_lamports = ctx.accounts.vault.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+        *vault_lamports = vault_lamports.checked_sub(amount).ok_or(ProgramError)?;
+        let mut user_lamports = ctx.accounts.user.try_borrow_mut_lamports()?;
+        *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+        Ok(())
+    }
+
+    pub fn read_config(ctx: Context<ReadConfig>) -> Result<u8, ProgramError> {
+        let data = ctx.accounts.config.try_borrow_data()?; //~ possible read of account data without an owner check for the smart contract
+        data.first().copied().ok_or(ProgramError)
+    }
+
+    pub fn read_owned_config(ctx: Context<ReadConfig>) -> Result<u8, ProgramError> {
+        if ctx.accounts.config.owner != *ctx.program_id {
+            return Err(ProgramError);
+        }
+        let data = ctx.accounts.config.try_borrow_data()?;
+        data.first().copied().ok_or(ProgramError)
+    }
+}
+
+// The dispatch functions that the #[program] macro generates for the handlers
+pub mod __private {
+    pub mod __global {
+        use crate::*;
+
+        pub fn withdraw(
+            program_id: &Pubkey,
+            accounts: &[AccountInfo],
+            amount: u64,
+        ) -> Result<(), ProgramError> {
+            let [user, vault] = accounts else {
+                return Err(ProgramError);
+            };
+            let mut accounts = Withdraw {
+                user: Signer { info: user },
+                vault: UncheckedAccount { info: vault },
+            };
+            let ctx = Context {
+                program_id,
+                accounts: &mut accounts,
+            };
+            vault_program::withdraw(ctx, amount)
+        }
+
+        pub fn read_config(
+            program_id: &Pubkey,
+            accounts: &[AccountInfo],
+        ) -> Result<u8, ProgramError> {
+            let [config] = accounts else {
+                return Err(ProgramError);
+            };
+            let mut accounts = ReadConfig {
+                config: UncheckedAccount { info: config },
+            };
+            let ctx = Context {
+                program_id,
+                accounts: &mut accounts,
+            };
+            vault_program::read_config(ctx)
+        }
+
+        pub fn read_owned_config(
+            program_id: &Pubkey,
+            accounts: &[AccountInfo],
+        ) -> Result<u8, ProgramError> {
+            let [config] = accounts else {
+                return Err(ProgramError);
+            };
+            let mut accounts = ReadConfig {
+                config: UncheckedAccount { info: config },
+            };
+            let ctx = Context {
+                program_id,
+                accounts: &mut accounts,
+            };
+            vault_program::read_owned_config(ctx)
+        }
+    }
+}
+
+pub fn main() {}
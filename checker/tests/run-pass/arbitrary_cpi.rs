@@ -0,0 +1,103 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that invoking a program whose id is the key of a passed account is flagged, whether the
+// instruction is built in place or by a builder, unless the key is compared with the known id.
+// The signed invocations are also flagged for not re-deriving the address of the vault.
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub const TOKEN_PROGRAM_ID: Pubkey = Pubkey([6; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo<'a> {
+    pub key: &'a Pubkey,
+}
+
+pub mod program {
+    use super::{AccountInfo, Pubkey, ProgramError};
+
+    pub struct Instruction {
+        pub program_id: Pubkey,
+        pub data: Vec<u8>,
+    }
+
+    pub fn invoke(
+        instruction: &Instruction,
+        _accounts: &[&AccountInfo],
+    ) -> Result<(), ProgramError> {
+        if instruction.data.is_empty() {
+            return Err(ProgramError);
+        }
+        Ok(())
+    }
+
+    pub fn invoke_signed(
+        instruction: &Instruction,
+        _accounts: &[&AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<(), ProgramError> {
+        if instruction.data.is_empty() || signers_seeds.is_empty() {
+            return Err(ProgramError);
+        }
+        Ok(())
+    }
+
+    pub fn transfer(token_program_id: &Pubkey, amount: u64) -> Instruction {
+        Instruction {
+            program_id: *token_program_id,
+            data: amount.to_le_bytes().to_vec(),
+        }
+    }
+}
+
+use program::Instruction;
+
+pub fn burn(token_program: &AccountInfo, source: &AccountInfo) -> Result<(), ProgramError> {
+    let instruction = Instruction {
+        program_id: *token_program.key,
+        data: vec![8],
+    };
+    program::invoke(&instruction, &[source]) //~ possible invocation of an arbitrary program whose id is not checked for the smart contract
+}
+
+pub fn checked_burn(token_program: &AccountInfo, source: &AccountInfo) -> Result<(), ProgramError> {
+    if *token_program.key != TOKEN_PROGRAM_ID {
+        return Err(ProgramError);
+    }
+    let instruction = Instruction {
+        program_id: *token_program.key,
+        data: vec![8],
+    };
+    program::invoke(&instruction, &[source])
+}
+
+pub fn pay_out(
+    token_program: &AccountInfo,
+    vault: &AccountInfo,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let instruction = program::transfer(token_program.key, amount);
+    program::invoke_signed(&instruction, &[vault], &[&[b"vault"]]) //~ possible invocation of an arbitrary program whose id is not checked for the smart contract
+    //~ possible use of a program derived address that is not re-derived for the smart contract
+}
+
+pub fn checked_pay_out(
+    token_program: &AccountInfo,
+    vault: &AccountInfo,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    if token_program.key != &TOKEN_PROGRAM_ID {
+        return Err(ProgramError);
+    }
+    let instruction = program::transfer(token_program.key, amount);
+    program::invoke_signed(&instruction, &[vault], &[&[b"vault"]])
+    //~ possible use of a program derived address that is not re-derived for the smart contract
+}
+
+pub fn main() {}
@@ -0,0 +1,48 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that picking a winner by the remainder of the slot of the clock is flagged, while
+// dividing the slot to compute the epoch is not
+
+#[derive(Clone, Copy)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub mod sysvar {
+    use super::ProgramError;
+
+    pub struct Clock {
+        pub slot: u64,
+    }
+
+    impl Clock {
+        pub fn get() -> Result<Clock, ProgramError> {
+            Ok(Clock { slot: 0 })
+        }
+    }
+}
+
+use sysvar::Clock;
+
+pub fn pick_winner(participants: &[Pubkey]) -> Result<Pubkey, ProgramError> {
+    let clock = Clock::get()?;
+    if participants.is_empty() {
+        return Err(ProgramError);
+    }
+    let index = (clock.slot % participants.len() as u64) as usize; //~ possible bad randomness for the smart contract
+    participants.get(index).copied().ok_or(ProgramError)
+}
+
+pub fn current_epoch(slots_per_epoch: u64) -> Result<u64, ProgramError> {
+    let clock = Clock::get()?;
+    if slots_per_epoch == 0 {
+        return Err(ProgramError);
+    }
+    Ok(clock.slot / slots_per_epoch)
+}
+
+pub fn main() {}
@@ -0,0 +1,49 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that reading the clock from a passed account is flagged, while Clock::get() is not
+
+pub mod sysvar {
+    pub struct AccountInfo {
+        pub data: Vec<u8>,
+    }
+
+    pub struct Clock {
+        pub unix_timestamp: i64,
+    }
+
+    impl Clock {
+        pub fn from_account_info(account: &AccountInfo) -> Option<Clock> {
+            if account.data.is_empty() {
+                None
+            } else {
+                Some(Clock { unix_timestamp: 0 })
+            }
+        }
+
+        pub fn get() -> Option<Clock> {
+            Some(Clock { unix_timestamp: 0 })
+        }
+    }
+}
+
+use sysvar::{AccountInfo, Clock};
+
+pub fn manual_timestamp(clock_account: &AccountInfo) -> i64 {
+    match Clock::from_account_info(clock_account) { //~ possible unvalidated clock account read, prefer Clock::get() for the smart contract
+        Some(clock) => clock.unix_timestamp,
+        None => 0,
+    }
+}
+
+pub fn runtime_timestamp() -> i64 {
+    match Clock::get() {
+        Some(clock) => clock.unix_timestamp,
+        None => 0,
+    }
+}
+
+pub fn main() {}
@@ -0,0 +1,69 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that closing an account by moving its lamports to another account is flagged when the
+// data of the account is not zeroed, while zeroing the data with fill is not
+
+use std::cell::{RefCell, RefMut};
+
+#[derive(PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub lamports: RefCell<u64>,
+    pub data: RefCell<Vec<u8>>,
+}
+
+impl AccountInfo {
+    pub fn lamports(&self) -> u64 {
+        *self.lamports.borrow()
+    }
+
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+
+    pub fn try_borrow_mut_data(&self) -> Result<RefMut<'_, Vec<u8>>, ProgramError> {
+        self.data.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn close(account: &AccountInfo, destination: &AccountInfo) -> Result<(), ProgramError> {
+    if account.key == destination.key {
+        return Err(ProgramError);
+    }
+    let balance = account.lamports();
+    let mut destination_lamports = destination.try_borrow_mut_lamports()?;
+    *destination_lamports = destination_lamports
+        .checked_add(balance)
+        .ok_or(ProgramError)?;
+    let mut lamports = account.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *lamports = 0; //~ possible closed account that can be revived with its data, prefer zeroing the data for the smart contract
+    Ok(())
+}
+
+pub fn close_and_zero(
+    account: &AccountInfo,
+    destination: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if account.key == destination.key {
+        return Err(ProgramError);
+    }
+    let balance = account.lamports();
+    let mut destination_lamports = destination.try_borrow_mut_lamports()?;
+    *destination_lamports = destination_lamports
+        .checked_add(balance)
+        .ok_or(ProgramError)?;
+    let mut lamports = account.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *lamports = 0;
+    account.try_borrow_mut_data()?.fill(0);
+    Ok(())
+}
+
+pub fn main() {}
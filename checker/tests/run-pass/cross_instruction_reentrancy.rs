@@ -0,0 +1,78 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that a withdraw, which transfers the lamports before it decreases the balance, is
+// reported as reentered by the deposit that the same dispatch match calls on another arm, since
+// the deposit reads the balances that the withdraw has not stored yet
+
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn process_instruction(
+    balances: &mut HashMap<Pubkey, u64>,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let [user_account, contract_account] = accounts else {
+        return Err(ProgramError);
+    };
+    let amount = instruction_data.get(1).copied().map_or(0, u64::from);
+    match instruction_data.first() {
+        Some(0) => deposit(balances, amount, user_account),
+        Some(1) => withdraw(balances, amount, user_account, contract_account),
+        //~ possible reentrancy across instructions for the smart contract
+        _ => Err(ProgramError),
+    }
+}
+
+fn deposit(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    let balance = balances.entry(user_account.key).or_insert(0);
+    *balance = balance.checked_add(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+fn withdraw(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if user_account.key == contract_account.key {
+        return Err(ProgramError);
+    }
+    let balance = balances.get_mut(&user_account.key).ok_or(ProgramError)?;
+    if *balance < amount {
+        return Err(ProgramError);
+    }
+    let mut contract_lamports = contract_account.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *contract_lamports = contract_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+    *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+    *balance -= amount; //~ possible reentrancy for the smart contract
+    Ok(())
+}
+
+pub fn main() {}
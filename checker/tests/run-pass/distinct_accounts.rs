@@ -0,0 +1,93 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that debiting a vault and crediting a recipient, or mutating the data of two accounts
+// taken from the accounts of the instruction, is flagged unless the accounts are checked to be
+// different
+
+use std::cell::{RefCell, RefMut};
+use std::slice::Iter;
+
+#[derive(PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub lamports: RefCell<u64>,
+    pub data: RefCell<Vec<u8>>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+
+    pub fn try_borrow_mut_data(&self) -> Result<RefMut<'_, Vec<u8>>, ProgramError> {
+        self.data.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn next_account_info<'a>(
+    iter: &mut Iter<'a, AccountInfo>,
+) -> Result<&'a AccountInfo, ProgramError> {
+    iter.next().ok_or(ProgramError)
+}
+
+pub fn withdraw(
+    vault: &AccountInfo,
+    recipient: &AccountInfo,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let mut vault_lamports = vault.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    let mut recipient_lamports = recipient.try_borrow_mut_lamports()?; //~ possible missing check that the debited and credited accounts differ for the smart contract
+    *vault_lamports = vault_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    *recipient_lamports = recipient_lamports.checked_add(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn checked_withdraw(
+    vault: &AccountInfo,
+    recipient: &AccountInfo,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    if vault.key == recipient.key {
+        return Err(ProgramError);
+    }
+    let mut vault_lamports = vault.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    let mut recipient_lamports = recipient.try_borrow_mut_lamports()?;
+    *vault_lamports = vault_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    *recipient_lamports = recipient_lamports.checked_add(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn transfer_points(accounts: &[AccountInfo]) -> Result<(), ProgramError> {
+    let accounts_iter = &mut accounts.iter();
+    let source = next_account_info(accounts_iter)?;
+    let destination = next_account_info(accounts_iter)?;
+    let mut source_data = source.try_borrow_mut_data()?;
+    let mut destination_data = destination.try_borrow_mut_data()?; //~ possible missing check that the mutated accounts differ for the smart contract
+    source_data.clear();
+    destination_data.push(1);
+    Ok(())
+}
+
+pub fn checked_transfer_points(accounts: &[AccountInfo]) -> Result<(), ProgramError> {
+    let accounts_iter = &mut accounts.iter();
+    let source = next_account_info(accounts_iter)?;
+    let destination = next_account_info(accounts_iter)?;
+    if source.key == destination.key {
+        return Err(ProgramError);
+    }
+    let mut source_data = source.try_borrow_mut_data()?;
+    let mut destination_data = destination.try_borrow_mut_data()?;
+    source_data.clear();
+    destination_data.push(1);
+    Ok(())
+}
+
+pub fn main() {}
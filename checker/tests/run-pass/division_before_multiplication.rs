@@ -0,0 +1,32 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that multiplying the quotient of a division of integers is flagged, with the operators
+// as with the checked operations, while dividing the product is not
+
+pub fn reward_share(stake: u64, total_stake: u64, rewards: u64) -> u128 {
+    if total_stake == 0 {
+        return 0;
+    }
+    stake as u128 / total_stake as u128 * rewards as u128 //~ possible precision loss from dividing before multiplying, prefer multiplying first for the smart contract
+}
+
+pub fn exact_reward_share(stake: u64, total_stake: u64, rewards: u64) -> u128 {
+    if total_stake == 0 {
+        return 0;
+    }
+    stake as u128 * rewards as u128 / total_stake as u128
+}
+
+pub fn checked_reward_share(stake: u64, total_stake: u64, rewards: u64) -> Option<u64> {
+    stake.checked_div(total_stake)?.checked_mul(rewards) //~ possible precision loss from dividing before multiplying, prefer multiplying first for the smart contract
+}
+
+pub fn checked_exact_reward_share(stake: u64, total_stake: u64, rewards: u64) -> Option<u64> {
+    stake.checked_mul(rewards)?.checked_div(total_stake)
+}
+
+pub fn main() {}
@@ -0,0 +1,15 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that a pattern that matches in two blocks of a body that map to the same source line is
+// reported once for the line.
+
+pub fn read_amounts(instruction_data: &[u8]) -> (u64, u64) {
+    let (first, second) = (&instruction_data[1..9], &instruction_data[9..17]); //~ possible panicking slice operation on an untrusted length, prefer get or split_at_checked for the smart contract
+    (first.len() as u64, second.len() as u64)
+}
+
+pub fn main() {}
@@ -0,0 +1,47 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that logging a remaining balance is flagged if it is looked up in a map that is created
+// by the instruction, as in the get_balance of the reentrancy contract_one
+
+use std::collections::HashMap;
+
+pub mod log {
+    pub fn sol_log(_message: &str) {}
+}
+
+macro_rules! msg {
+    ($($arg:tt)*) => {
+        crate::log::sol_log(&format!($($arg)*))
+    };
+}
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub fn process_instruction(user: &Pubkey, amount: u64) {
+    let mut balances: HashMap<Pubkey, u64> = HashMap::new(); //~ possible state that is never persisted to account data for the smart contract
+    deposit(&mut balances, user, amount);
+    let balance = get_balance(&mut balances, user);
+    msg!("User has a remaining balance of {} lamports", balance); //~ possible logging of state that is not persisted for the smart contract
+}
+
+pub fn process_stored_instruction(balances: &mut HashMap<Pubkey, u64>, user: &Pubkey, amount: u64) {
+    deposit(balances, user, amount);
+    let balance = get_balance(balances, user);
+    msg!("User has a remaining balance of {} lamports", balance);
+}
+
+pub fn deposit(balances: &mut HashMap<Pubkey, u64>, user: &Pubkey, amount: u64) {
+    let entry = balances.entry(*user).or_insert(0);
+    *entry = entry.wrapping_add(amount);
+}
+
+pub fn get_balance(balances: &mut HashMap<Pubkey, u64>, user: &Pubkey) -> u64 {
+    *balances.get(user).unwrap_or(&0)
+}
+
+pub fn main() {}
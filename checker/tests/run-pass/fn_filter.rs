@@ -0,0 +1,30 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that only the functions whose path matches the function filter are analyzed,
+// so the same rounding is flagged in the withdraw functions and not in the deposit functions
+
+// MIRAI_FLAGS --fn-filter withdraw
+
+pub fn withdraw_share(amount: f64) -> f64 {
+    amount.round() //~ possible numerical precision error for the smart contract
+}
+
+pub fn deposit_share(amount: f64) -> f64 {
+    amount.round()
+}
+
+pub mod vault {
+    pub fn withdraw_all(balance: f64) -> f64 {
+        balance.round() //~ possible numerical precision error for the smart contract
+    }
+
+    pub fn deposit_all(balance: f64) -> f64 {
+        balance.round()
+    }
+}
+
+pub fn main() {}
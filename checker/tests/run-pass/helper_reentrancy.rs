@@ -0,0 +1,76 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that reentrancy is flagged if the lamports are transferred by one helper function and
+// the balance is stored by another helper function that is called after it
+
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn withdraw(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if user_account.key == contract_account.key {
+        return Err(ProgramError);
+    }
+    let balance = balances.get_mut(&user_account.key).ok_or(ProgramError)?;
+    if *balance < amount {
+        return Err(ProgramError);
+    }
+    do_transfer(contract_account, user_account, amount)?;
+    update_balance(balance, amount); //~ possible reentrancy for the smart contract
+    Ok(())
+}
+
+pub fn withdraw_after_update(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if user_account.key == contract_account.key {
+        return Err(ProgramError);
+    }
+    let balance = balances.get_mut(&user_account.key).ok_or(ProgramError)?;
+    if *balance < amount {
+        return Err(ProgramError);
+    }
+    update_balance(balance, amount);
+    do_transfer(contract_account, user_account, amount)
+}
+
+fn do_transfer(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<(), ProgramError> {
+    let mut from_lamports = from.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *from_lamports = from_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut to_lamports = to.try_borrow_mut_lamports()?;
+    *to_lamports = to_lamports.checked_add(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+fn update_balance(balance: &mut u64, amount: u64) {
+    *balance -= amount;
+}
+
+pub fn main() {}
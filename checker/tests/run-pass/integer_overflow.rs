@@ -0,0 +1,64 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that updating a balance in a map by unchecked arithmetic is flagged, while the checked
+// operations, a subtraction after comparing the balance with the amount and the arithmetic on
+// locals and on the fields of a struct are not
+
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub fn deposit(balances: &mut HashMap<Pubkey, u64>, user: &Pubkey, amount: u64) {
+    let entry = balances.entry(*user).or_insert(0);
+    *entry += amount; //~ possible overflow of unchecked addition to a balance, prefer checked_add for the smart contract
+}
+
+pub fn withdraw(balances: &mut HashMap<Pubkey, u64>, user: &Pubkey, amount: u64) {
+    if let Some(balance) = balances.get_mut(user) {
+        *balance -= amount; //~ possible underflow of unchecked subtraction from a balance, prefer checked_sub for the smart contract
+    }
+}
+
+pub fn checked_deposit(
+    balances: &mut HashMap<Pubkey, u64>,
+    user: &Pubkey,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let entry = balances.entry(*user).or_insert(0);
+    *entry = entry.checked_add(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn guarded_withdraw(
+    balances: &mut HashMap<Pubkey, u64>,
+    user: &Pubkey,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let balance = balances.get_mut(user).ok_or(ProgramError)?;
+    if *balance < amount {
+        return Err(ProgramError);
+    }
+    *balance -= amount;
+    Ok(())
+}
+
+pub struct Counter {
+    pub count: u64,
+}
+
+pub fn increment(counter: &mut Counter, step: u64) {
+    counter.count += step;
+}
+
+pub fn fee(amount: u64) -> u64 {
+    amount / 100 + 1
+}
+
+pub fn main() {}
@@ -0,0 +1,69 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that mutating the data of an account whose writable flag has not been checked is flagged,
+// while mutating it after checking the flag is not, and neither is mutating the program derived
+// address of the program, whose key is compared with the address that the program derives
+
+use std::cell::{RefCell, RefMut};
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+impl Pubkey {
+    pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+        let mut key = program_id.0;
+        key[0] = seeds.len() as u8;
+        (Pubkey(key), 255)
+    }
+}
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub is_writable: bool,
+    pub data: RefCell<Vec<u8>>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_data(&self) -> Result<RefMut<'_, Vec<u8>>, ProgramError> {
+        self.data.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn update_config(config: &AccountInfo, value: u8) -> Result<(), ProgramError> {
+    let mut data = config.try_borrow_mut_data()?; //~ possible mutation of an account without checking that it is writable for the smart contract
+    if let Some(first) = data.first_mut() {
+        *first = value;
+    }
+    Ok(())
+}
+
+pub fn update_checked_config(config: &AccountInfo, value: u8) -> Result<(), ProgramError> {
+    if !config.is_writable {
+        return Err(ProgramError);
+    }
+    let mut data = config.try_borrow_mut_data()?;
+    if let Some(first) = data.first_mut() {
+        *first = value;
+    }
+    Ok(())
+}
+
+pub fn update_vault(program_id: &Pubkey, vault: &AccountInfo, value: u8) -> Result<(), ProgramError> {
+    let (expected_vault, _) = Pubkey::find_program_address(&[b"vault"], program_id);
+    if vault.key != expected_vault {
+        return Err(ProgramError);
+    }
+    let mut data = vault.try_borrow_mut_data()?;
+    if let Some(first) = data.first_mut() {
+        *first = value;
+    }
+    Ok(())
+}
+
+pub fn main() {}
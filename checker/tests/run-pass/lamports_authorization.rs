@@ -0,0 +1,64 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that gating a privileged action on the lamports of an account is flagged, while
+// checking for enough lamports before moving them is not
+
+use std::cell::{RefCell, RefMut};
+
+#[derive(PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn lamports(&self) -> u64 {
+        *self.lamports.borrow()
+    }
+
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub struct Config {
+    pub admin_enabled: bool,
+}
+
+const MIN_ADMIN_STAKE: u64 = 1_000_000_000;
+
+pub fn enable_admin(config: &mut Config, admin: &AccountInfo) -> Result<(), ProgramError> {
+    if admin.lamports() > MIN_ADMIN_STAKE { //~ possible authorization by the account balance for the smart contract
+        config.admin_enabled = true;
+        return Ok(());
+    }
+    Err(ProgramError)
+}
+
+pub fn withdraw(
+    amount: u64,
+    vault: &AccountInfo,
+    recipient: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if vault.key == recipient.key {
+        return Err(ProgramError);
+    }
+    if vault.lamports() < amount {
+        return Err(ProgramError);
+    }
+    let mut vault_lamports = vault.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *vault_lamports = vault_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut recipient_lamports = recipient.try_borrow_mut_lamports()?;
+    *recipient_lamports = recipient_lamports.checked_add(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn main() {}
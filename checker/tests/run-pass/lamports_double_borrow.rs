@@ -0,0 +1,38 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that borrowing the lamports of an account twice in one statement is flagged,
+// while borrowing them in two statements, after the first guard has been dropped, is not
+
+use std::cell::{RefCell, RefMut};
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn double_in_one_statement(vault: &AccountInfo) -> Result<(), ProgramError> {
+    *vault.try_borrow_mut_lamports()? = vault.try_borrow_mut_lamports()?.checked_mul(2).ok_or(ProgramError)?; //~ possible double borrow of the lamports of an account for the smart contract
+    Ok(())
+}
+
+pub fn double_in_two_statements(vault: &AccountInfo) -> Result<(), ProgramError> {
+    let doubled = vault
+        .try_borrow_mut_lamports()?
+        .checked_mul(2)
+        .ok_or(ProgramError)?;
+    *vault.try_borrow_mut_lamports()? = doubled;
+    Ok(())
+}
+
+pub fn main() {}
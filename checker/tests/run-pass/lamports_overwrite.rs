@@ -0,0 +1,59 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that assigning a constant to the lamports of an account is flagged, while adjusting
+// the current balance or closing an account after moving its balance is not. Closing the account
+// is flagged by the close_authority checker instead, since its data is not zeroed.
+
+use std::cell::{RefCell, RefMut};
+
+#[derive(PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn lamports(&self) -> u64 {
+        *self.lamports.borrow()
+    }
+
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn top_up(account: &AccountInfo) -> Result<(), ProgramError> {
+    let mut lamports = account.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *lamports = 1_000_000; //~ possible overwrite of the account balance for the smart contract
+    Ok(())
+}
+
+pub fn adjusted_top_up(account: &AccountInfo, amount: u64) -> Result<(), ProgramError> {
+    let mut lamports = account.try_borrow_mut_lamports()?;
+    *lamports = lamports.checked_add(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn close(account: &AccountInfo, destination: &AccountInfo) -> Result<(), ProgramError> {
+    if account.key == destination.key {
+        return Err(ProgramError);
+    }
+    let balance = account.lamports();
+    let mut destination_lamports = destination.try_borrow_mut_lamports()?;
+    *destination_lamports = destination_lamports
+        .checked_add(balance)
+        .ok_or(ProgramError)?;
+    let mut lamports = account.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *lamports = 0; //~ possible closed account that can be revived with its data, prefer zeroing the data for the smart contract
+    Ok(())
+}
+
+pub fn main() {}
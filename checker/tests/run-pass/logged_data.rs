@@ -0,0 +1,28 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that logging the whole instruction data is flagged, while logging a parsed field is not
+
+pub mod log {
+    pub fn sol_log(_message: &str) {}
+}
+
+macro_rules! msg {
+    ($($arg:tt)*) => {
+        crate::log::sol_log(&format!($($arg)*))
+    };
+}
+
+pub fn process_instruction(instruction_data: &[u8]) {
+    msg!("instruction data: {:?}", instruction_data); //~ possible logging of whole account or instruction data for the smart contract
+}
+
+pub fn process_tagged_instruction(instruction_data: &[u8]) {
+    let tag = instruction_data.first().copied().unwrap_or(0);
+    msg!("instruction tag: {}", tag);
+}
+
+pub fn main() {}
@@ -0,0 +1,57 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that reentrancy is flagged if the balance is stored before the transfer in the body of a
+// loop, since the store of the next iteration follows the transfer through the back edge. Each
+// chunk is subtracted without comparing the balance with it, which may underflow
+
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn withdraw_in_chunks(
+    balances: &mut HashMap<Pubkey, u64>,
+    amount: u64,
+    chunk: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if user_account.key == contract_account.key || chunk == 0 {
+        return Err(ProgramError);
+    }
+    let balance = balances.get_mut(&user_account.key).ok_or(ProgramError)?;
+    if *balance < amount {
+        return Err(ProgramError);
+    }
+    let mut withdrawn = 0;
+    while withdrawn < amount {
+        *balance -= chunk; //~ possible reentrancy for the smart contract
+        //~ possible underflow of unchecked subtraction from a balance, prefer checked_sub for the smart contract
+        let mut contract_lamports = contract_account.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+        *contract_lamports = contract_lamports.checked_sub(chunk).ok_or(ProgramError)?;
+        let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+        *user_lamports = user_lamports.checked_add(chunk).ok_or(ProgramError)?;
+        withdrawn += chunk;
+    }
+    Ok(())
+}
+
+pub fn main() {}
@@ -0,0 +1,20 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that the findings below the minimum confidence are not reported, so the rounding, which
+// matches any call of round, is not flagged while the saturating arithmetic is
+
+// MIRAI_FLAGS --min-confidence medium
+
+pub fn rounded_share(amount: f64) -> f64 {
+    amount.round()
+}
+
+pub fn withdraw(balance: u64, amount: u64) -> u64 {
+    balance.saturating_sub(amount) //~ possible saturating arithmetic masking an error for the smart contract
+}
+
+pub fn main() {}
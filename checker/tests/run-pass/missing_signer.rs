@@ -0,0 +1,88 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that mutating the lamports of a debited account whose signer flag has not been checked
+// is flagged, also when the signer flag of the other account has been checked or when the flag is
+// only checked to be set in the other branch of an if, while mutating the lamports of the checked
+// account is not, and neither is crediting an account whose lamports are only increased
+
+use std::cell::{RefCell, RefMut};
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub is_signer: bool,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn assert_signer(account: &AccountInfo) -> Result<(), ProgramError> {
+    if !account.is_signer {
+        return Err(ProgramError);
+    }
+    Ok(())
+}
+
+pub fn deposit(
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if !user_account.is_signer {
+        return Err(ProgramError);
+    }
+    let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+    *user_lamports = user_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut contract_lamports = contract_account.try_borrow_mut_lamports()?;
+    *contract_lamports = contract_lamports.checked_add(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn withdraw(
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    assert_signer(contract_account)?;
+    let mut contract_lamports = contract_account.try_borrow_mut_lamports()?;
+    *contract_lamports = contract_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+    *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn refund(
+    amount: u64,
+    user_account: &AccountInfo,
+    contract_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if !user_account.is_signer {
+        return Err(ProgramError);
+    }
+    let mut contract_lamports = contract_account.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *contract_lamports = contract_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+    *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn settle(amount: u64, user_account: &AccountInfo) -> Result<(), ProgramError> {
+    if user_account.is_signer {
+        let mut user_lamports = user_account.try_borrow_mut_lamports()?;
+        *user_lamports = user_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    } else {
+        let mut user_lamports = user_account.try_borrow_mut_lamports()?; //~ possible mutation of lamports without a signer check of the account for the smart contract
+        *user_lamports = user_lamports.checked_add(amount).ok_or(ProgramError)?;
+    }
+    Ok(())
+}
+
+pub fn main() {}
@@ -0,0 +1,55 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that a map of balances that is created by the instruction and mutated is flagged if it is
+// never written to the data of an account, as in the process_instruction of contracts/overflow,
+// while the same map is not flagged once it is serialized into the data of an account.
+
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo<'a> {
+    pub key: Pubkey,
+    pub data: RefCell<&'a mut [u8]>,
+}
+
+impl<'a> AccountInfo<'a> {
+    pub fn try_borrow_mut_data(&self) -> Result<RefMut<'_, &'a mut [u8]>, ProgramError> {
+        self.data.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn process_instruction(user_account: &AccountInfo, amount: u64) -> Result<(), ProgramError> {
+    let mut balances: HashMap<Pubkey, u64> = HashMap::new(); //~ possible state that is never persisted to account data for the smart contract
+    deposit(&mut balances, &user_account.key, amount);
+    Ok(())
+}
+
+pub fn process_stored_instruction(
+    user_account: &AccountInfo,
+    state_account: &AccountInfo,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let mut balances: HashMap<Pubkey, u64> = HashMap::new();
+    deposit(&mut balances, &user_account.key, amount);
+    let mut data = state_account.try_borrow_mut_data()?;
+    if let Some(count) = data.first_mut() {
+        *count = balances.len() as u8;
+    }
+    Ok(())
+}
+
+pub fn deposit(balances: &mut HashMap<Pubkey, u64>, user: &Pubkey, amount: u64) {
+    let entry = balances.entry(*user).or_insert(0);
+    *entry = entry.wrapping_add(amount);
+}
+
+pub fn main() {}
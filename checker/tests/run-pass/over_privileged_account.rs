@@ -0,0 +1,47 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that requiring an account to be writable is flagged if the account is only read
+
+use std::cell::{Ref, RefCell, RefMut};
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub is_writable: bool,
+    pub data: RefCell<Vec<u8>>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_data(&self) -> Result<Ref<'_, Vec<u8>>, ProgramError> {
+        self.data.try_borrow().map_err(|_| ProgramError)
+    }
+
+    pub fn try_borrow_mut_data(&self) -> Result<RefMut<'_, Vec<u8>>, ProgramError> {
+        self.data.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn read_config(config: &AccountInfo) -> Result<u8, ProgramError> {
+    if !config.is_writable { //~ possible writable account that is only read for the smart contract
+        return Err(ProgramError);
+    }
+    let data = config.try_borrow_data()?;
+    Ok(data.first().copied().unwrap_or(0))
+}
+
+pub fn update_config(config: &AccountInfo, value: u8) -> Result<(), ProgramError> {
+    if !config.is_writable {
+        return Err(ProgramError);
+    }
+    let mut data = config.try_borrow_mut_data()?;
+    if let Some(first) = data.first_mut() {
+        *first = value;
+    }
+    Ok(())
+}
+
+pub fn main() {}
@@ -0,0 +1,64 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that reading the data of a passed account is flagged when the owner of the account is
+// not checked, and flagged differently when the owner is only compared with a hardcoded key,
+// while comparing the owner with the program id is not flagged
+
+use std::cell::{Ref, RefCell};
+
+pub struct ProgramError;
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub const TOKEN_PROGRAM_ID: Pubkey = Pubkey([6; 32]);
+
+pub struct AccountInfo {
+    pub owner: Pubkey,
+    pub data: RefCell<Vec<u8>>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_data(&self) -> Result<Ref<'_, Vec<u8>>, ProgramError> {
+        self.data.try_borrow().map_err(|_| ProgramError)
+    }
+}
+
+pub fn next_account_info<'a, I: Iterator<Item = &'a AccountInfo>>(
+    iter: &mut I,
+) -> Result<&'a AccountInfo, ProgramError> {
+    iter.next().ok_or(ProgramError)
+}
+
+pub fn read_unchecked(accounts: &[AccountInfo]) -> Result<u8, ProgramError> {
+    let accounts_iter = &mut accounts.iter();
+    let state_account = next_account_info(accounts_iter)?;
+    let data = state_account.try_borrow_data()?; //~ possible read of account data without an owner check for the smart contract
+    data.first().copied().ok_or(ProgramError)
+}
+
+pub fn read_with_hardcoded_owner(accounts: &[AccountInfo]) -> Result<u8, ProgramError> {
+    let accounts_iter = &mut accounts.iter();
+    let state_account = next_account_info(accounts_iter)?;
+    if state_account.owner != TOKEN_PROGRAM_ID {
+        return Err(ProgramError);
+    }
+    let data = state_account.try_borrow_data()?; //~ possible owner check against a hardcoded key instead of the program id for the smart contract
+    data.first().copied().ok_or(ProgramError)
+}
+
+pub fn read_owned(program_id: &Pubkey, accounts: &[AccountInfo]) -> Result<u8, ProgramError> {
+    let accounts_iter = &mut accounts.iter();
+    let state_account = next_account_info(accounts_iter)?;
+    if state_account.owner != *program_id {
+        return Err(ProgramError);
+    }
+    let data = state_account.try_borrow_data()?;
+    data.first().copied().ok_or(ProgramError)
+}
+
+pub fn main() {}
@@ -0,0 +1,35 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that unwrapping a value that comes from the instruction data is flagged, and so is a
+// panic that is only reached on an arm of a branch on such a value, since the caller can abort
+// the transaction with the instruction data they choose. Unwrapping a value that does not come
+// from the input of the caller is not flagged
+
+pub struct ProgramError;
+
+fn fee_table() -> Option<u64> {
+    Some(250)
+}
+
+pub fn read_amount(instruction_data: &[u8]) -> u64 {
+    let amount = instruction_data.get(1).copied();
+    u64::from(amount.unwrap()) //~ possible panic that the instruction data can trigger, prefer returning an error for the smart contract
+}
+
+pub fn read_kind(instruction_data: &[u8]) -> Result<u8, ProgramError> {
+    let kind = instruction_data.get(0).copied().ok_or(ProgramError)?;
+    if kind > 3 {
+        panic!("unknown instruction kind"); //~ possible panic that the instruction data can trigger, prefer returning an error for the smart contract
+    }
+    Ok(kind)
+}
+
+pub fn default_fee() -> u64 {
+    fee_table().expect("the fee table is not empty")
+}
+
+pub fn main() {}
@@ -0,0 +1,57 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that creating a program derived address with a bump seed read from the instruction data
+// is flagged, while creating it with the canonical bump of find_program_address or with a bump
+// stored in an account is not
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+impl Pubkey {
+    pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+        let mut key = program_id.0;
+        key[0] = seeds.len() as u8;
+        (Pubkey(key), 255)
+    }
+
+    pub fn create_program_address(
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+    ) -> Result<Pubkey, ProgramError> {
+        let mut key = program_id.0;
+        key[0] = seeds.len() as u8;
+        Ok(Pubkey(key))
+    }
+}
+
+pub struct VaultState {
+    pub bump: u8,
+}
+
+pub fn vault_address(
+    program_id: &Pubkey,
+    instruction_data: &[u8],
+) -> Result<Pubkey, ProgramError> {
+    let bump = *instruction_data.first().ok_or(ProgramError)?;
+    Pubkey::create_program_address(&[b"vault", &[bump]], program_id) //~ possible program derived address with a bump seed of the caller, prefer find_program_address for the smart contract
+}
+
+pub fn canonical_vault_address(program_id: &Pubkey) -> Result<Pubkey, ProgramError> {
+    let (_, bump) = Pubkey::find_program_address(&[b"vault"], program_id);
+    Pubkey::create_program_address(&[b"vault", &[bump]], program_id)
+}
+
+pub fn stored_vault_address(
+    program_id: &Pubkey,
+    state: &VaultState,
+) -> Result<Pubkey, ProgramError> {
+    Pubkey::create_program_address(&[b"vault", &[state.bump]], program_id)
+}
+
+pub fn main() {}
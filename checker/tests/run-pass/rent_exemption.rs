@@ -0,0 +1,76 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that creating an account with hard coded lamports, or with lamports given by the caller,
+// is flagged, while creating it with lamports computed by Rent::minimum_balance is not
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct Rent {
+    pub lamports_per_byte_year: u64,
+}
+
+impl Rent {
+    pub fn minimum_balance(&self, data_len: usize) -> u64 {
+        self.lamports_per_byte_year
+            .checked_mul(data_len as u64)
+            .unwrap_or(u64::MAX)
+    }
+}
+
+pub mod system_instruction {
+    use super::Pubkey;
+
+    pub struct Instruction {
+        pub program_id: Pubkey,
+        pub data: Vec<u8>,
+    }
+
+    pub fn create_account(
+        _from_pubkey: &Pubkey,
+        _to_pubkey: &Pubkey,
+        lamports: u64,
+        space: u64,
+        owner: &Pubkey,
+    ) -> Instruction {
+        let mut data = lamports.to_le_bytes().to_vec();
+        data.extend_from_slice(&space.to_le_bytes());
+        Instruction {
+            program_id: *owner,
+            data,
+        }
+    }
+}
+
+use system_instruction::Instruction;
+
+pub const VAULT_SIZE: usize = 165;
+
+pub fn create_vault(payer: &Pubkey, vault: &Pubkey, program_id: &Pubkey) -> Instruction {
+    system_instruction::create_account(payer, vault, 1_000_000, VAULT_SIZE as u64, program_id) //~ possible account creation with hard coded lamports that may not be rent exempt, prefer Rent::minimum_balance for the smart contract
+}
+
+pub fn create_funded_vault(
+    payer: &Pubkey,
+    vault: &Pubkey,
+    program_id: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    system_instruction::create_account(payer, vault, lamports, VAULT_SIZE as u64, program_id) //~ possible account creation with lamports that are not computed by Rent::minimum_balance for the smart contract
+}
+
+pub fn create_rent_exempt_vault(
+    rent: &Rent,
+    payer: &Pubkey,
+    vault: &Pubkey,
+    program_id: &Pubkey,
+) -> Instruction {
+    let lamports = rent.minimum_balance(VAULT_SIZE);
+    system_instruction::create_account(payer, vault, lamports, VAULT_SIZE as u64, program_id)
+}
+
+pub fn main() {}
@@ -0,0 +1,101 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that the withdraw of the reentrancy contract_twenty_one, which fixes the bugs of
+// contract_one, is not flagged. The balance is kept in the data of a vault account of the
+// program, the user has to sign, the accounts are checked before their lamports are changed and
+// the balance is stored before the lamports are transferred
+
+use std::cell::{RefCell, RefMut};
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub owner: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub lamports: RefCell<u64>,
+    pub data: RefCell<Vec<u8>>,
+}
+
+impl AccountInfo {
+    pub fn lamports(&self) -> u64 {
+        *self.lamports.borrow()
+    }
+
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+
+    pub fn try_borrow_mut_data(&self) -> Result<RefMut<'_, Vec<u8>>, ProgramError> {
+        self.data.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+// The data of a vault account is the key of its user, followed by the balance of the user
+const OWNER_LEN: usize = 32;
+const VAULT_DATA_LEN: usize = OWNER_LEN + 8;
+
+pub fn withdraw(
+    program_id: &Pubkey,
+    amount: u64,
+    user_account: &AccountInfo,
+    vault_account: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if !user_account.is_signer {
+        return Err(ProgramError);
+    }
+    if vault_account.owner != *program_id {
+        return Err(ProgramError);
+    }
+    if !user_account.is_writable || !vault_account.is_writable {
+        return Err(ProgramError);
+    }
+    if user_account.key == vault_account.key {
+        return Err(ProgramError);
+    }
+    let mut data = vault_account.try_borrow_mut_data()?;
+    if data.len() != VAULT_DATA_LEN || data.get(..OWNER_LEN) != Some(&user_account.key.0[..]) {
+        return Err(ProgramError);
+    }
+    let balance = read_balance(&data)?;
+    if balance < amount {
+        return Err(ProgramError);
+    }
+    let vault_lamports = vault_account
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ProgramError)?;
+    let user_lamports = user_account
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError)?;
+    write_balance(&mut data, balance.checked_sub(amount).ok_or(ProgramError)?)?;
+    *vault_account.try_borrow_mut_lamports()? = vault_lamports;
+    *user_account.try_borrow_mut_lamports()? = user_lamports;
+    Ok(())
+}
+
+pub fn read_balance(data: &[u8]) -> Result<u64, ProgramError> {
+    let balance: [u8; 8] = data
+        .get(OWNER_LEN..VAULT_DATA_LEN)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError)?;
+    Ok(u64::from_le_bytes(balance))
+}
+
+pub fn write_balance(data: &mut [u8], balance: u64) -> Result<(), ProgramError> {
+    data.get_mut(OWNER_LEN..VAULT_DATA_LEN)
+        .ok_or(ProgramError)?
+        .copy_from_slice(&balance.to_le_bytes());
+    Ok(())
+}
+
+pub fn main() {}
@@ -0,0 +1,20 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that saturating arithmetic on a balance is flagged unless the amount is compared first
+
+pub fn withdraw(balance: u64, amount: u64) -> u64 {
+    balance.saturating_sub(amount) //~ possible saturating arithmetic masking an error for the smart contract
+}
+
+pub fn checked_withdraw(balance: u64, amount: u64) -> Option<u64> {
+    if balance < amount {
+        return None;
+    }
+    Some(balance.saturating_sub(amount))
+}
+
+pub fn main() {}
@@ -0,0 +1,41 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that deriving a program derived address from 17 seeds, or from a constant seed of 40
+// bytes, is flagged, while deriving it from a few short seeds is not
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+impl Pubkey {
+    pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+        let mut key = program_id.0;
+        key[0] = seeds.len() as u8;
+        (Pubkey(key), 255)
+    }
+}
+
+pub fn too_many_seeds(user: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let seeds: [&[u8]; 17] = [
+        b"s00", b"s01", b"s02", b"s03", b"s04", b"s05", b"s06", b"s07",
+        b"s08", b"s09", b"s10", b"s11", b"s12", b"s13", b"s14", b"s15",
+        &user.0,
+    ];
+    let (key, _bump) = Pubkey::find_program_address(&seeds, program_id); //~ possible program derived address with too many or too long seeds for the smart contract
+    key
+}
+
+pub fn too_long_seed(user: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (key, _bump) = Pubkey::find_program_address(&[b"a_seed_that_is_longer_than_32_bytes_abcd", &user.0], program_id); //~ possible program derived address with too many or too long seeds for the smart contract
+    key
+}
+
+pub fn short_seeds(user: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (key, _bump) = Pubkey::find_program_address(&[b"vault", &user.0], program_id);
+    key
+}
+
+pub fn main() {}
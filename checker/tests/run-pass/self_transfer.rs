@@ -0,0 +1,35 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that a transfer whose source and destination are the same account is flagged
+
+pub mod system_instruction {
+    pub struct Pubkey(pub [u8; 32]);
+
+    pub struct Instruction {
+        pub lamports: u64,
+    }
+
+    pub fn transfer(_from_pubkey: &Pubkey, _to_pubkey: &Pubkey, lamports: u64) -> Instruction {
+        Instruction { lamports }
+    }
+}
+
+use system_instruction::{Instruction, Pubkey};
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+}
+
+pub fn pay_self(account: &AccountInfo, amount: u64) -> Instruction {
+    system_instruction::transfer(&account.key, &account.key, amount) //~ possible self transfer for the smart contract
+}
+
+pub fn pay(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Instruction {
+    system_instruction::transfer(&from.key, &to.key, amount)
+}
+
+pub fn main() {}
@@ -0,0 +1,66 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that indexing the instruction data or the data of an account by a range, and unwrapping
+// the conversion of such a subslice into an array, are flagged when the length of the data has not
+// been compared, while the same operations after comparing the length are not.
+
+use std::cell::{Ref, RefCell};
+
+pub enum ProgramError {
+    AccountBorrowFailed,
+    InvalidInstructionData,
+}
+
+pub struct AccountInfo<'a> {
+    pub data: RefCell<&'a mut [u8]>,
+}
+
+impl<'a> AccountInfo<'a> {
+    pub fn try_borrow_data(&self) -> Result<Ref<'_, &'a mut [u8]>, ProgramError> {
+        self.data
+            .try_borrow()
+            .map_err(|_| ProgramError::AccountBorrowFailed)
+    }
+}
+
+pub fn read_amount(instruction_data: &[u8]) -> u64 {
+    let amount_bytes = &instruction_data[1..9]; //~ possible panicking slice operation on an untrusted length, prefer get or split_at_checked for the smart contract
+    u64::from_le_bytes(amount_bytes.try_into().unwrap()) //~ possible panic from unwrapping the conversion of an untrusted subslice, prefer handling the error of try_into for the smart contract
+}
+
+pub fn read_checked_amount(instruction_data: &[u8]) -> Result<u64, ProgramError> {
+    if instruction_data.len() < 9 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let amount_bytes = &instruction_data[1..9];
+    Ok(u64::from_le_bytes(amount_bytes.try_into().unwrap()))
+}
+
+pub fn read_stored_amount(account: &AccountInfo) -> Result<u64, ProgramError> {
+    let data = account.try_borrow_data()?;
+    let amount_bytes = &data[8..16]; //~ possible panicking slice operation on an untrusted length, prefer get or split_at_checked for the smart contract
+    Ok(u64::from_le_bytes( //~ possible deserialization of account data without a length or discriminator check for the smart contract
+        amount_bytes
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    ))
+}
+
+pub fn read_checked_stored_amount(account: &AccountInfo) -> Result<u64, ProgramError> {
+    let data = account.try_borrow_data()?;
+    if data.len() < 16 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let amount_bytes = &data[8..16];
+    Ok(u64::from_le_bytes(
+        amount_bytes
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    ))
+}
+
+pub fn main() {}
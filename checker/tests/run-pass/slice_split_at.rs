@@ -0,0 +1,22 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that splitting the instruction data is flagged unless its length is validated first
+
+pub fn unchecked_tag(instruction_data: &[u8]) -> usize {
+    let (tag, _rest) = instruction_data.split_at(1); //~ possible panicking slice operation on an untrusted length, prefer get or split_at_checked for the smart contract
+    tag.len()
+}
+
+pub fn checked_tag(instruction_data: &[u8]) -> usize {
+    if instruction_data.len() < 1 {
+        return 0;
+    }
+    let (tag, _rest) = instruction_data.split_at(1);
+    tag.len()
+}
+
+pub fn main() {}
@@ -0,0 +1,22 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that a function with a local buffer larger than the stack frame of a Solana program
+// is flagged, while a function with a small local buffer is not
+
+pub fn large_buffer(seed: u8) -> u8 { //~ possible stack overflow from large locals for the smart contract
+    let mut buffer = [0u8; 8192];
+    buffer[0] = seed;
+    buffer[0] ^ buffer[8191]
+}
+
+pub fn small_buffer(seed: u8) -> u8 {
+    let mut buffer = [0u8; 64];
+    buffer[0] = seed;
+    buffer[0] ^ buffer[63]
+}
+
+pub fn main() {}
@@ -0,0 +1,60 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that matching only the Ok result of an invocation is flagged when the Err arm is
+// empty or only logs, while returning the error is not
+
+pub mod log {
+    pub fn sol_log(_message: &str) {}
+}
+
+pub mod program {
+    pub struct Instruction {
+        pub data: Vec<u8>,
+    }
+
+    pub struct AccountInfo {
+        pub lamports: u64,
+    }
+
+    pub struct ProgramError;
+
+    pub fn invoke(instruction: &Instruction, _accounts: &[AccountInfo]) -> Result<(), ProgramError> {
+        if instruction.data.is_empty() {
+            return Err(ProgramError);
+        }
+        Ok(())
+    }
+}
+
+use program::{AccountInfo, Instruction, ProgramError};
+
+pub fn mint(instruction: &Instruction, accounts: &[AccountInfo], minted: &mut u64) {
+    if let Ok(()) = program::invoke(instruction, accounts) { //~ possible ignored error of a cross-program invocation for the smart contract
+        *minted = 1;
+    }
+}
+
+pub fn logged_mint(instruction: &Instruction, accounts: &[AccountInfo], minted: &mut u64) {
+    match program::invoke(instruction, accounts) { //~ possible ignored error of a cross-program invocation for the smart contract
+        Ok(()) => *minted = 1,
+        Err(_) => log::sol_log("mint failed"),
+    }
+}
+
+pub fn checked_mint(
+    instruction: &Instruction,
+    accounts: &[AccountInfo],
+    minted: &mut u64,
+) -> Result<(), ProgramError> {
+    match program::invoke(instruction, accounts) {
+        Ok(()) => *minted = 1,
+        Err(error) => return Err(error),
+    }
+    Ok(())
+}
+
+pub fn main() {}
@@ -0,0 +1,111 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that values computed from the instruction data through a few assignments, operations and
+// calls are tainted: a bump seed that is computed from the instruction data is flagged, while one
+// read from the data of an account is not, and so is a program id read from the instruction data,
+// unless it is compared with the known id beforehand
+
+use std::cell::{Ref, RefCell};
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub const TOKEN_PROGRAM_ID: Pubkey = Pubkey([6; 32]);
+
+pub struct ProgramError;
+
+impl Pubkey {
+    pub fn create_program_address(
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+    ) -> Result<Pubkey, ProgramError> {
+        let mut key = program_id.0;
+        key[0] = seeds.len() as u8;
+        Ok(Pubkey(key))
+    }
+}
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub data: RefCell<Vec<u8>>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_data(&self) -> Result<Ref<'_, Vec<u8>>, ProgramError> {
+        self.data.try_borrow().map_err(|_| ProgramError)
+    }
+}
+
+pub mod program {
+    use super::{Pubkey, ProgramError};
+
+    pub struct Instruction {
+        pub program_id: Pubkey,
+        pub data: Vec<u8>,
+    }
+
+    pub fn invoke(instruction: &Instruction) -> Result<(), ProgramError> {
+        if instruction.data.is_empty() {
+            return Err(ProgramError);
+        }
+        Ok(())
+    }
+}
+
+use program::Instruction;
+
+pub fn vault_address(
+    program_id: &Pubkey,
+    instruction_data: &[u8],
+) -> Result<Pubkey, ProgramError> {
+    let byte = *instruction_data.first().ok_or(ProgramError)?;
+    let bump = byte ^ 1;
+    Pubkey::create_program_address(&[b"vault", &[bump]], program_id) //~ possible program derived address with a bump seed of the caller, prefer find_program_address for the smart contract
+}
+
+pub fn stored_vault_address(
+    program_id: &Pubkey,
+    state: &AccountInfo,
+) -> Result<Pubkey, ProgramError> {
+    let data = state.try_borrow_data()?;
+    let byte = *data.first().ok_or(ProgramError)?;
+    let bump = byte ^ 1;
+    Pubkey::create_program_address(&[b"vault", &[bump]], program_id)
+}
+
+pub fn burn(instruction_data: &[u8]) -> Result<(), ProgramError> {
+    let key: [u8; 32] = instruction_data
+        .get(1..33)
+        .ok_or(ProgramError)?
+        .try_into()
+        .map_err(|_| ProgramError)?;
+    let program_id = Pubkey(key);
+    let instruction = Instruction {
+        program_id,
+        data: vec![8],
+    };
+    program::invoke(&instruction) //~ possible invocation of an arbitrary program whose id is not checked for the smart contract
+}
+
+pub fn checked_burn(instruction_data: &[u8]) -> Result<(), ProgramError> {
+    let key: [u8; 32] = instruction_data
+        .get(1..33)
+        .ok_or(ProgramError)?
+        .try_into()
+        .map_err(|_| ProgramError)?;
+    let program_id = Pubkey(key);
+    if program_id != TOKEN_PROGRAM_ID {
+        return Err(ProgramError);
+    }
+    let instruction = Instruction {
+        program_id,
+        data: vec![8],
+    };
+    program::invoke(&instruction)
+}
+
+pub fn main() {}
@@ -0,0 +1,67 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that the timestamp of the clock is flagged when it decides a branch or the amount of
+// lamports that are paid, while only logging the timestamp is not
+
+use std::cell::{RefCell, RefMut};
+
+pub struct ProgramError;
+
+pub mod sysvar {
+    use super::ProgramError;
+
+    pub struct Clock {
+        pub slot: u64,
+        pub unix_timestamp: i64,
+    }
+
+    impl Clock {
+        pub fn get() -> Result<Clock, ProgramError> {
+            Ok(Clock {
+                slot: 0,
+                unix_timestamp: 0,
+            })
+        }
+    }
+}
+
+use sysvar::Clock;
+
+pub struct AccountInfo {
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn is_expired(deadline: i64) -> Result<bool, ProgramError> {
+    let clock = Clock::get()?;
+    if clock.unix_timestamp > deadline { //~ possible time manipulation for the smart contract
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+pub fn pay_interest(account: &AccountInfo, start: i64, rate: u64) -> Result<(), ProgramError> {
+    let clock = Clock::get()?;
+    let elapsed = clock.unix_timestamp.checked_sub(start).ok_or(ProgramError)? as u64;
+    let interest = elapsed.checked_mul(rate).ok_or(ProgramError)?;
+    let mut lamports = account.try_borrow_mut_lamports()?;
+    *lamports = lamports.checked_add(interest).ok_or(ProgramError)?; //~ possible time manipulation for the smart contract
+    Ok(())
+}
+
+pub fn log_timestamp() -> Result<(), ProgramError> {
+    let clock = Clock::get()?;
+    println!("{}", clock.unix_timestamp);
+    Ok(())
+}
+
+pub fn main() {}
@@ -0,0 +1,58 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that comparing the raw amounts of two token accounts is flagged, unless the accounts
+// are checked to hold the same mint or mints with the same decimals
+
+#[derive(PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct TokenAccount {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+pub struct Mint {
+    pub decimals: u8,
+}
+
+pub fn has_larger_balance(collateral: &TokenAccount, debt: &TokenAccount) -> bool {
+    collateral.amount > debt.amount //~ possible comparison of token amounts with different decimals for the smart contract
+}
+
+pub fn has_larger_balance_of_same_mint(collateral: &TokenAccount, debt: &TokenAccount) -> bool {
+    collateral.mint == debt.mint && collateral.amount > debt.amount
+}
+
+pub fn has_larger_balance_of_same_decimals(
+    collateral: &TokenAccount,
+    collateral_mint: &Mint,
+    debt: &TokenAccount,
+    debt_mint: &Mint,
+) -> bool {
+    collateral_mint.decimals == debt_mint.decimals && collateral.amount > debt.amount
+}
+
+pub fn has_larger_balance_than_fee(
+    collateral: &TokenAccount,
+    debt: &TokenAccount,
+    fee_mint: &Mint,
+) -> (bool, u8) {
+    // The decimals of an unrelated mint do not make the amounts comparable
+    let fee_decimals = fee_mint.decimals;
+    (collateral.amount > debt.amount, fee_decimals) //~ possible comparison of token amounts with different decimals for the smart contract
+}
+
+pub fn has_larger_balance_of_expected_mint(
+    collateral: &TokenAccount,
+    debt: &TokenAccount,
+    expected_mint: &Pubkey,
+) -> bool {
+    // Only the mint of the collateral is checked, the debt can be of any mint
+    collateral.mint == *expected_mint && collateral.amount > debt.amount //~ possible comparison of token amounts with different decimals for the smart contract
+}
+
+pub fn main() {}
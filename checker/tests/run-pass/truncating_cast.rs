@@ -0,0 +1,55 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that casting an amount read from the instruction data, or the lamports of an account, to
+// a narrower integer type is flagged, while widening the lamports, converting the amount with
+// try_from and casting the discriminant of an instruction are not
+
+use std::cell::RefCell;
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn lamports(&self) -> u64 {
+        *self.lamports.borrow()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Instruction {
+    Deposit,
+    Withdraw,
+}
+
+pub fn unpack_amount(instruction_data: &[u8]) -> Result<u32, ProgramError> {
+    let bytes = instruction_data.get(..8).ok_or(ProgramError)?;
+    let amount = u64::from_le_bytes(bytes.try_into().map_err(|_| ProgramError)?);
+    Ok(amount as u32) //~ possible truncation of an amount by a narrowing cast, prefer try_from for the smart contract
+}
+
+pub fn unpack_checked_amount(instruction_data: &[u8]) -> Result<u32, ProgramError> {
+    let bytes = instruction_data.get(..8).ok_or(ProgramError)?;
+    let amount = u64::from_le_bytes(bytes.try_into().map_err(|_| ProgramError)?);
+    u32::try_from(amount).map_err(|_| ProgramError)
+}
+
+pub fn short_balance(account: &AccountInfo) -> u32 {
+    account.lamports() as u32 //~ possible truncation of an amount by a narrowing cast, prefer try_from for the smart contract
+}
+
+pub fn wide_balance(account: &AccountInfo) -> u128 {
+    account.lamports() as u128
+}
+
+pub fn instruction_tag(instruction: Instruction) -> u8 {
+    instruction as u8
+}
+
+pub fn main() {}
@@ -0,0 +1,41 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that writing a name supplied by the caller into the data of an account is flagged
+// when the length of the name is not capped, while capping it is not
+
+use std::cell::{RefCell, RefMut};
+
+pub struct ProgramError;
+
+pub const MAX_NAME_LEN: usize = 32;
+
+pub struct AccountInfo {
+    pub data: RefCell<Vec<u8>>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_data(&self) -> Result<RefMut<'_, Vec<u8>>, ProgramError> {
+        self.data.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn append_name(account: &AccountInfo, name: Vec<u8>) -> Result<(), ProgramError> {
+    let mut data = account.try_borrow_mut_data()?;
+    data.extend_from_slice(&name); //~ possible write of unbounded user data into account data for the smart contract
+    Ok(())
+}
+
+pub fn append_capped_name(account: &AccountInfo, name: Vec<u8>) -> Result<(), ProgramError> {
+    if name.len() > MAX_NAME_LEN {
+        return Err(ProgramError);
+    }
+    let mut data = account.try_borrow_mut_data()?;
+    data.extend_from_slice(&name);
+    Ok(())
+}
+
+pub fn main() {}
@@ -0,0 +1,78 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that an invocation whose result is dropped is flagged, while one whose error is
+// propagated with ?, matched or returned to the caller is not
+
+pub mod program {
+    pub struct Instruction {
+        pub data: Vec<u8>,
+    }
+
+    pub struct AccountInfo {
+        pub lamports: u64,
+    }
+
+    pub struct ProgramError;
+
+    pub fn invoke(instruction: &Instruction, _accounts: &[AccountInfo]) -> Result<(), ProgramError> {
+        if instruction.data.is_empty() {
+            return Err(ProgramError);
+        }
+        Ok(())
+    }
+
+    pub fn invoke_signed(
+        instruction: &Instruction,
+        accounts: &[AccountInfo],
+        _signer_seeds: &[&[&[u8]]],
+    ) -> Result<(), ProgramError> {
+        invoke(instruction, accounts)
+    }
+}
+
+use program::{AccountInfo, Instruction, ProgramError};
+
+pub fn mint(instruction: &Instruction, accounts: &[AccountInfo], minted: &mut u64) {
+    let _ = program::invoke(instruction, accounts); //~ possible unchecked result of a cross-program invocation, propagate its error with ? for the smart contract
+    *minted = 1;
+}
+
+pub fn signed_mint(
+    instruction: &Instruction,
+    accounts: &[AccountInfo],
+    seeds: &[&[&[u8]]],
+    minted: &mut u64,
+) -> Result<(), ProgramError> {
+    let _result = program::invoke_signed(instruction, accounts, seeds); //~ possible unchecked result of a cross-program invocation, propagate its error with ? for the smart contract
+    *minted = 1;
+    Ok(())
+}
+
+pub fn checked_mint(
+    instruction: &Instruction,
+    accounts: &[AccountInfo],
+    minted: &mut u64,
+) -> Result<(), ProgramError> {
+    program::invoke(instruction, accounts)?;
+    *minted = 1;
+    Ok(())
+}
+
+pub fn matched_mint(instruction: &Instruction, accounts: &[AccountInfo], minted: &mut u64) {
+    if program::invoke(instruction, accounts).is_ok() {
+        *minted = 1;
+    }
+}
+
+pub fn forwarded_mint(
+    instruction: &Instruction,
+    accounts: &[AccountInfo],
+) -> Result<(), ProgramError> {
+    program::invoke(instruction, accounts)
+}
+
+pub fn main() {}
@@ -0,0 +1,78 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that deserializing the data of an account, by try_from_slice or by parsing its bytes, is
+// flagged when neither the length of the data nor its discriminator has been compared, while the
+// same deserializations after such a comparison are not
+
+use std::cell::{Ref, RefCell};
+
+pub struct ProgramError;
+
+pub struct AccountInfo<'a> {
+    pub data: RefCell<&'a mut [u8]>,
+}
+
+impl<'a> AccountInfo<'a> {
+    pub fn try_borrow_data(&self) -> Result<Ref<'_, &'a mut [u8]>, ProgramError> {
+        self.data.try_borrow().map_err(|_| ProgramError)
+    }
+}
+
+pub trait BorshDeserialize: Sized {
+    fn try_from_slice(data: &[u8]) -> Result<Self, ProgramError>;
+}
+
+pub const VAULT_TAG: u8 = 7;
+pub const VAULT_LEN: usize = 9;
+
+pub struct Vault {
+    pub tag: u8,
+    pub amount: u64,
+}
+
+impl BorshDeserialize for Vault {
+    fn try_from_slice(data: &[u8]) -> Result<Vault, ProgramError> {
+        let tag = *data.first().ok_or(ProgramError)?;
+        let amount_bytes = data.get(1..VAULT_LEN).ok_or(ProgramError)?;
+        let amount = u64::from_le_bytes(amount_bytes.try_into().map_err(|_| ProgramError)?);
+        Ok(Vault { tag, amount })
+    }
+}
+
+pub fn load_vault(account: &AccountInfo) -> Result<Vault, ProgramError> {
+    let data = account.try_borrow_data()?;
+    Vault::try_from_slice(&data) //~ possible deserialization of account data without a length or discriminator check for the smart contract
+}
+
+pub fn load_amount(account: &AccountInfo) -> Result<u64, ProgramError> {
+    let data = account.try_borrow_data()?;
+    let amount_bytes = data.get(1..VAULT_LEN).ok_or(ProgramError)?;
+    Ok(u64::from_le_bytes( //~ possible deserialization of account data without a length or discriminator check for the smart contract
+        amount_bytes.try_into().map_err(|_| ProgramError)?,
+    ))
+}
+
+pub fn load_vault_of_length(account: &AccountInfo) -> Result<Vault, ProgramError> {
+    let data = account.try_borrow_data()?;
+    if data.len() != VAULT_LEN {
+        return Err(ProgramError);
+    }
+    Vault::try_from_slice(&data)
+}
+
+pub fn load_tagged_amount(account: &AccountInfo) -> Result<u64, ProgramError> {
+    let data = account.try_borrow_data()?;
+    if data.first() != Some(&VAULT_TAG) {
+        return Err(ProgramError);
+    }
+    let amount_bytes = data.get(1..VAULT_LEN).ok_or(ProgramError)?;
+    Ok(u64::from_le_bytes(
+        amount_bytes.try_into().map_err(|_| ProgramError)?,
+    ))
+}
+
+pub fn main() {}
@@ -0,0 +1,84 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that reading the first byte of the instruction data without checking that it is not empty
+// is flagged, while reading it after comparing the length is not.
+// The handler is modeled after process_instruction of contracts/overflow/contract_one.
+
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub enum ProgramError {
+    InvalidInstructionData,
+    MissingRequiredSignature,
+}
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub is_signer: bool,
+}
+
+pub fn process_instruction(
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let user_account = accounts.first().ok_or(ProgramError::MissingRequiredSignature)?;
+    let mut balances: HashMap<Pubkey, u64> = HashMap::new(); //~ possible state that is never persisted to account data for the smart contract
+    if !user_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let instruction = instruction_data[0]; //~ possible read of the first byte of empty instruction data for the smart contract
+    //~ possible panicking slice operation on an untrusted length, prefer get or split_at_checked for the smart contract
+    let amount = u64::from_le_bytes(
+        instruction_data[1..9] //~ possible panicking slice operation on an untrusted length, prefer get or split_at_checked for the smart contract
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+    match instruction {
+        0 => deposit(&mut balances, user_account.key, amount),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+pub fn process_checked_instruction(
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> Result<(), ProgramError> {
+    let user_account = accounts.first().ok_or(ProgramError::MissingRequiredSignature)?;
+    let mut balances: HashMap<Pubkey, u64> = HashMap::new(); //~ possible state that is never persisted to account data for the smart contract
+    if !user_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if instruction_data.len() < 9 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let instruction = instruction_data[0];
+    let amount = u64::from_le_bytes(
+        instruction_data[1..9]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+    match instruction {
+        0 => deposit(&mut balances, user_account.key, amount),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+pub fn deposit(
+    balances: &mut HashMap<Pubkey, u64>,
+    user: Pubkey,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    let entry = balances.entry(user).or_insert(0);
+    *entry = entry
+        .checked_add(amount)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    Ok(())
+}
+
+pub fn main() {}
@@ -0,0 +1,80 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that a transfer helper which relies on its callers to validate the accounts is flagged
+// when it has several callers, one of which does not validate them, while a helper that checks
+// the signer itself is not
+
+use std::cell::{RefCell, RefMut};
+
+#[derive(PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+    pub is_signer: bool,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn move_lamports(
+    from: &AccountInfo,
+    to: &AccountInfo,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    if from.key == to.key {
+        return Err(ProgramError);
+    }
+    let mut from_lamports = from.try_borrow_mut_lamports()?; //~ possible transfer by a helper that does not validate its accounts for the smart contract
+    //~ possible mutation of lamports without a signer check of the account for the smart contract
+    *from_lamports = from_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut to_lamports = to.try_borrow_mut_lamports()?;
+    *to_lamports = to_lamports.checked_add(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn signed_move_lamports(
+    from: &AccountInfo,
+    to: &AccountInfo,
+    amount: u64,
+) -> Result<(), ProgramError> {
+    if !from.is_signer || from.key == to.key {
+        return Err(ProgramError);
+    }
+    let mut from_lamports = from.try_borrow_mut_lamports()?;
+    *from_lamports = from_lamports.checked_sub(amount).ok_or(ProgramError)?;
+    let mut to_lamports = to.try_borrow_mut_lamports()?;
+    *to_lamports = to_lamports.checked_add(amount).ok_or(ProgramError)?;
+    Ok(())
+}
+
+pub fn withdraw(vault: &AccountInfo, user: &AccountInfo, amount: u64) -> Result<(), ProgramError> {
+    if !user.is_signer {
+        return Err(ProgramError);
+    }
+    move_lamports(vault, user, amount)
+}
+
+pub fn refund(vault: &AccountInfo, user: &AccountInfo, amount: u64) -> Result<(), ProgramError> {
+    move_lamports(vault, user, amount)
+}
+
+pub fn deposit(user: &AccountInfo, vault: &AccountInfo, amount: u64) -> Result<(), ProgramError> {
+    signed_move_lamports(user, vault, amount)
+}
+
+pub fn tip(user: &AccountInfo, vault: &AccountInfo, amount: u64) -> Result<(), ProgramError> {
+    signed_move_lamports(user, vault, amount)
+}
+
+pub fn main() {}
@@ -0,0 +1,33 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that reinterpreting account data by a transmute or a raw pointer cast is flagged,
+// while deserializing it field by field is not
+
+pub struct AccountInfo {
+    pub data: [u8; 8],
+}
+
+#[repr(C)]
+pub struct State {
+    pub amount: u64,
+}
+
+pub fn transmute_state(account: &AccountInfo) -> State {
+    unsafe { std::mem::transmute::<[u8; 8], State>(account.data) } //~ possible unsafe reinterpretation of account data for the smart contract
+}
+
+pub fn state_pointer(account: &AccountInfo) -> *const State {
+    account.data.as_ptr() as *const State //~ possible unsafe reinterpretation of account data for the smart contract
+}
+
+pub fn deserialize_state(account: &AccountInfo) -> State {
+    State {
+        amount: u64::from_le_bytes(account.data),
+    }
+}
+
+pub fn main() {}
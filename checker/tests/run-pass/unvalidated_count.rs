@@ -0,0 +1,41 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that parsing as many items as the first byte of the instruction data says is flagged
+// when the count is not compared with the length of the items, while comparing it is not
+
+pub struct ProgramError;
+
+pub fn total_amount(instruction_data: &[u8]) -> Result<u64, ProgramError> {
+    if instruction_data.is_empty() {
+        return Err(ProgramError);
+    }
+    let count = instruction_data[0] as usize;
+    let items = &instruction_data[1..];
+    let mut total: u64 = 0;
+    for i in 0..count { //~ possible loop over a count from the instruction data that is not validated against its length for the smart contract
+        total = total.checked_add(items[i] as u64).ok_or(ProgramError)?;
+    }
+    Ok(total)
+}
+
+pub fn checked_total_amount(instruction_data: &[u8]) -> Result<u64, ProgramError> {
+    if instruction_data.is_empty() {
+        return Err(ProgramError);
+    }
+    let count = instruction_data[0] as usize;
+    let items = &instruction_data[1..];
+    if items.len() < count {
+        return Err(ProgramError);
+    }
+    let mut total: u64 = 0;
+    for i in 0..count {
+        total = total.checked_add(items[i] as u64).ok_or(ProgramError)?;
+    }
+    Ok(total)
+}
+
+pub fn main() {}
@@ -0,0 +1,66 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that mutating the accounts of a loop is flagged when an iteration validates the account
+// only in an if branch, while validating it on every path of the iteration is not
+
+use std::cell::{RefCell, RefMut};
+
+pub struct ProgramError;
+
+pub struct Pubkey(pub [u8; 32]);
+
+impl PartialEq for Pubkey {
+    fn eq(&self, other: &Pubkey) -> bool {
+        self.0 == other.0
+    }
+}
+
+pub struct AccountInfo {
+    pub owner: Pubkey,
+    pub lamports: RefCell<u64>,
+}
+
+impl AccountInfo {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
+        self.lamports.try_borrow_mut().map_err(|_| ProgramError)
+    }
+}
+
+pub fn credit_all(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    strict: bool,
+) -> Result<(), ProgramError> {
+    for account in accounts.iter() {
+        if strict {
+            if account.owner != *program_id {
+                return Err(ProgramError);
+            }
+        }
+        let mut lamports = account.try_borrow_mut_lamports()?; //~ possible mutation of an unvalidated account in a loop for the smart contract
+        *lamports = lamports.checked_add(amount).ok_or(ProgramError)?;
+    }
+    Ok(())
+}
+
+pub fn credit_owned(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> Result<(), ProgramError> {
+    for account in accounts.iter() {
+        if account.owner != *program_id {
+            return Err(ProgramError);
+        }
+        let mut lamports = account.try_borrow_mut_lamports()?;
+        *lamports = lamports.checked_add(amount).ok_or(ProgramError)?;
+    }
+    Ok(())
+}
+
+pub fn main() {}
@@ -0,0 +1,69 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that an invocation signed for a program derived address that is passed as an account is
+// flagged when the address is not re-derived, while re-deriving and comparing it is not
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pubkey(pub [u8; 32]);
+
+impl Pubkey {
+    pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+        let mut key = program_id.0;
+        key[0] = seeds.len() as u8;
+        (Pubkey(key), 255)
+    }
+}
+
+pub struct ProgramError;
+
+pub struct AccountInfo {
+    pub key: Pubkey,
+}
+
+pub mod program {
+    use super::{AccountInfo, ProgramError};
+
+    pub struct Instruction {
+        pub data: Vec<u8>,
+    }
+
+    pub fn invoke_signed(
+        instruction: &Instruction,
+        _accounts: &[&AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> Result<(), ProgramError> {
+        if instruction.data.is_empty() || signers_seeds.is_empty() {
+            return Err(ProgramError);
+        }
+        Ok(())
+    }
+}
+
+use program::Instruction;
+
+pub fn withdraw_from_vault(
+    _program_id: &Pubkey,
+    vault: &AccountInfo,
+    instruction: &Instruction,
+    bump: u8,
+) -> Result<(), ProgramError> {
+    program::invoke_signed(instruction, &[vault], &[&[b"vault", &[bump]]]) //~ possible use of a program derived address that is not re-derived for the smart contract
+}
+
+pub fn withdraw_from_verified_vault(
+    program_id: &Pubkey,
+    vault: &AccountInfo,
+    instruction: &Instruction,
+) -> Result<(), ProgramError> {
+    let (expected_vault, bump) = Pubkey::find_program_address(&[b"vault"], program_id);
+    if vault.key != expected_vault {
+        return Err(ProgramError);
+    }
+    program::invoke_signed(instruction, &[vault], &[&[b"vault", &[bump]]])
+}
+
+pub fn main() {}
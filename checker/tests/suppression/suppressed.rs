@@ -0,0 +1,30 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that suppressed findings are not reported, but are listed with their suppression source.
+// The test driver writes a baseline that contains the finding in rounded_fee. The findings of
+// rounded_average are allowed by a comment above the function, the finding of rounded_tip by a
+// comment above its line.
+
+#[allow(unknown_lints, numerical_precision)]
+pub fn rounded_share(amount: f64) -> f64 {
+    amount.round()
+}
+
+pub fn rounded_fee(fee: f64) -> f64 {
+    fee.round()
+}
+
+// contract-analysis:allow(all)
+pub fn rounded_average(total: f64, count: f64) -> f64 {
+    let average = total / count;
+    average.round()
+}
+
+pub fn rounded_tip(tip: f64) -> f64 {
+    // contract-analysis:allow(numerical_precision)
+    tip.round()
+}
@@ -0,0 +1,16 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that analyzing a watched file again after an edit only reports the changed findings.
+// This is the file before the edit, fee_edited.rs is the file after it.
+
+pub fn rounded_fee(amount: f64, fee_rate: f64) -> u64 {
+    (amount * fee_rate).round() as u64
+}
+
+pub fn main() {}
+
+//~ possible numerical precision error for the smart contract
@@ -0,0 +1,19 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+//
+
+// A test that analyzing a watched file again after an edit only reports the changed findings.
+// This is the file after the edit, which adds rounded_tip above rounded_fee.
+// When a file is analyzed again, its findings are not emitted as diagnostics.
+
+pub fn rounded_tip(amount: f64, tip_rate: f64) -> u64 {
+    (amount * tip_rate).round() as u64
+}
+
+pub fn rounded_fee(amount: f64, fee_rate: f64) -> u64 {
+    (amount * fee_rate).round() as u64
+}
+
+pub fn main() {}
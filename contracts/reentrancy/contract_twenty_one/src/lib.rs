@@ -0,0 +1,197 @@
+// The deposit and withdraw API of contract_one, implemented without its bugs. The balance of the
+// user is kept in the data of a vault account that the program owns, rather than in a map that
+// is lost when the instruction ends, the user has to sign both a deposit and a withdrawal, and a
+// withdrawal stores the new balance before the lamports are transferred, as
+// checks-effects-interactions has it. The checker reports no finding for this contract.
+use solana_program::{
+    msg,
+    account_info::{next_account_info, AccountInfo},
+    entrypoint,
+    entrypoint::ProgramResult,
+    program::invoke,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_instruction,
+    system_program,
+};
+
+entrypoint!(process_instruction);
+
+// The data of a vault account is the key of the user that it holds the lamports of, followed by
+// the balance of the user in little endian
+const OWNER_LEN: usize = 32;
+const VAULT_DATA_LEN: usize = OWNER_LEN + 8;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let user_account = next_account_info(accounts_iter)?;
+    let vault_account = next_account_info(accounts_iter)?;
+    let system_program_account = next_account_info(accounts_iter)?;
+
+    let (instruction, amount) = parse_instruction(instruction_data)?;
+    match instruction {
+        0 => {
+            msg!("User deposits {} lamports", amount);
+            deposit(program_id, amount, user_account, vault_account, system_program_account)?;
+        }
+        1 => {
+            msg!("User withdraws {} lamports", amount);
+            withdraw(program_id, amount, user_account, vault_account)?;
+        }
+        _ => {
+            msg!("Invalid action");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+
+    let balance = get_balance(program_id, vault_account)?;
+    msg!(
+        "User {} has a remaining balance of {} lamports",
+        user_account.key,
+        balance
+    );
+
+    Ok(())
+}
+
+// Splits the instruction data into the instruction and the amount that follows it, failing
+// rather than panicking if the data is too short
+pub fn parse_instruction(instruction_data: &[u8]) -> Result<(u8, u64), ProgramError> {
+    let (&instruction, rest) = instruction_data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let amount: [u8; 8] = rest
+        .get(..8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    Ok((instruction, u64::from_le_bytes(amount)))
+}
+
+// Checks that the user signed the instruction and that the user and vault accounts are distinct
+// writable accounts
+pub fn check_accounts(user_account: &AccountInfo, vault_account: &AccountInfo) -> ProgramResult {
+    if !user_account.is_signer {
+        msg!("User account must sign the transaction");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !user_account.is_writable || !vault_account.is_writable {
+        msg!("User and vault accounts must be writable");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if user_account.key == vault_account.key {
+        msg!("User and vault accounts must be distinct");
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+// Checks that the data of a vault account of the program holds the balance of the user
+pub fn check_vault_data(user_account: &AccountInfo, data: &[u8]) -> ProgramResult {
+    if data.len() != VAULT_DATA_LEN || data.get(..OWNER_LEN) != Some(user_account.key.as_ref()) {
+        msg!("Vault account does not hold the lamports of the user");
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+pub fn deposit(
+    program_id: &Pubkey,
+    amount: u64,
+    user_account: &AccountInfo,
+    vault_account: &AccountInfo,
+    system_program_account: &AccountInfo
+) -> Result<(), ProgramError>  {
+    check_accounts(user_account, vault_account)?;
+    if vault_account.owner != program_id {
+        msg!("Vault account is not owned by the program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if system_program_account.key != &system_program::ID {
+        msg!("Lamports are only deposited by the system program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    {
+        let mut data = vault_account.try_borrow_mut_data()?;
+        check_vault_data(user_account, &data)?;
+        let balance = read_balance(&data)?
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        write_balance(&mut data, balance)?;
+    }
+
+    // The user account is not owned by the program, so the system program debits it
+    invoke(
+        &system_instruction::transfer(user_account.key, vault_account.key, amount),
+        &[
+            user_account.clone(),
+            vault_account.clone(),
+            system_program_account.clone(),
+        ],
+    )
+}
+
+pub fn withdraw(
+    program_id: &Pubkey,
+    amount: u64,
+    user_account: &AccountInfo,
+    vault_account: &AccountInfo
+) -> Result<(), ProgramError> {
+    check_accounts(user_account, vault_account)?;
+    if vault_account.owner != program_id {
+        msg!("Vault account is not owned by the program");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut data = vault_account.try_borrow_mut_data()?;
+    check_vault_data(user_account, &data)?;
+    let balance = read_balance(&data)?;
+    if balance < amount {
+        msg!("Insufficient balance for withdrawal");
+        return Err(ProgramError::InsufficientFunds);
+    }
+    let vault_lamports = vault_account
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    let user_lamports = user_account
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // The balance is stored before the lamports are transferred, so a reentrant withdrawal finds
+    // it decremented
+    let remaining = balance.checked_sub(amount).ok_or(ProgramError::InsufficientFunds)?;
+    write_balance(&mut data, remaining)?;
+    **vault_account.try_borrow_mut_lamports()? = vault_lamports;
+    **user_account.try_borrow_mut_lamports()? = user_lamports;
+    Ok(())
+}
+
+pub fn get_balance(program_id: &Pubkey, vault_account: &AccountInfo) -> Result<u64, ProgramError> {
+    if vault_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    read_balance(&vault_account.try_borrow_data()?)
+}
+
+// Reads the balance from the data of a vault account
+pub fn read_balance(data: &[u8]) -> Result<u64, ProgramError> {
+    let balance: [u8; 8] = data
+        .get(OWNER_LEN..VAULT_DATA_LEN)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ProgramError::InvalidAccountData)?;
+    Ok(u64::from_le_bytes(balance))
+}
+
+// Writes the balance to the data of a vault account
+pub fn write_balance(data: &mut [u8], balance: u64) -> ProgramResult {
+    data.get_mut(OWNER_LEN..VAULT_DATA_LEN)
+        .ok_or(ProgramError::InvalidAccountData)?
+        .copy_from_slice(&balance.to_le_bytes());
+    Ok(())
+}